@@ -5,3 +5,12 @@ pub mod cell;
 pub mod relationship;
 pub(crate) mod filter;
 pub(crate) mod properties;
+pub(crate) mod comment;
+pub(crate) mod table;
+pub(crate) mod data_validation;
+pub(crate) mod conditional_format;
+pub(crate) mod chart;
+pub(crate) mod dependency;
+pub(crate) mod protection;
+pub(crate) mod page_setup;
+pub(crate) mod header_footer;
@@ -5,6 +5,8 @@ use crate::Format;
 pub mod formula;
 pub mod location;
 pub mod values;
+pub mod date_time;
+pub mod rich_text;
 
 #[derive(Clone, Debug, Default)]
 pub struct Cell<T: CellDisplay + CellValue> {
@@ -14,4 +16,12 @@ pub struct Cell<T: CellDisplay + CellValue> {
     pub(crate) formula: Option<Formula>,
     pub(crate) cell_type: Option<CellType>,
     pub(crate) style: Option<u32>,
-}
\ No newline at end of file
+}
+impl<T: CellDisplay + CellValue> Cell<T> {
+    ///
+    /// The formula of the cell, without the leading `=`
+    ///
+    pub fn formula(&self) -> Option<&str> {
+        self.formula.as_ref().map(|formula| formula.formula.as_str())
+    }
+}
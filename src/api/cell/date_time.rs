@@ -0,0 +1,230 @@
+use std::fmt::{Display, Formatter};
+
+// Days between 1900-01-00 and 1904-01-01 in the 1900 date system
+const DATE1904_OFFSET: f64 = 1462.0;
+
+///
+/// A date and time stored as an Excel serial number.
+///
+/// The serial number counts days in the 1900 date system, the fraction is the time of the day.
+/// Like Excel, 1900-02-29 is treated as a valid date, so serial numbers below 61 are one day off
+/// from the real calendar.
+///
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd, Default)]
+pub struct ExcelDateTime {
+    serial: f64,
+}
+
+impl ExcelDateTime {
+    pub fn from_serial(serial: f64) -> ExcelDateTime {
+        ExcelDateTime { serial }
+    }
+
+    ///
+    /// Create a date time from its parts, returns None if the date is before 1900-01-01,
+    /// the day is not in the month or the time is not in the day
+    ///
+    pub fn from_ymd_hms(year: i32, month: u32, day: u32, hour: u32, minute: u32, second: f64) -> Option<ExcelDateTime> {
+        if !(1..=12).contains(&month) || hour >= 24 || minute >= 60 || !(0.0..60.0).contains(&second) {
+            return None;
+        }
+        // Excel counts the nonexistent 1900-02-29
+        let days = if (year, month, day) == (1900, 2, 29) {
+            60
+        } else {
+            if !(1..=days_in_month(year, month)).contains(&day) {
+                return None;
+            }
+            let days = days_from_civil(year, month, day) - days_from_civil(1899, 12, 31);
+            if days < 1 {
+                return None;
+            }
+            if days >= 60 { days + 1 } else { days }
+        };
+        let time = (hour as f64 * 3600.0 + minute as f64 * 60.0 + second) / 86400.0;
+        Some(ExcelDateTime { serial: days as f64 + time })
+    }
+
+    pub fn serial(&self) -> f64 {
+        self.serial
+    }
+
+    ///
+    /// Split the date time into (year, month, day, hour, minute, second)
+    ///
+    pub fn to_ymd_hms(&self) -> (i32, u32, u32, u32, u32, f64) {
+        let mut days = self.serial.floor() as i64;
+        // round the time to milliseconds, like Excel does
+        let mut millis = ((self.serial - days as f64) * 86_400_000.0).round() as i64;
+        if millis >= 86_400_000 {
+            days += 1;
+            millis -= 86_400_000;
+        }
+        let (year, month, day) = match days {
+            60 => (1900, 2, 29),
+            _ if days < 60 => civil_from_days(days_from_civil(1899, 12, 31) + days),
+            _ => civil_from_days(days_from_civil(1899, 12, 30) + days),
+        };
+        let hour = (millis / 3_600_000) as u32;
+        let minute = (millis % 3_600_000 / 60_000) as u32;
+        let second = (millis % 60_000) as f64 / 1000.0;
+        (year, month, day, hour, minute, second)
+    }
+
+    ///
+    /// The serial number in the date system of the workbook, times of day are the same in both systems.
+    /// Returns None for the dates before 1904-01-01 in the 1904 date system.
+    ///
+    pub(crate) fn to_serial_in(self, date1904: bool) -> Option<f64> {
+        match (date1904, self.serial) {
+            (true, serial) if serial >= 1.0 && serial < DATE1904_OFFSET => None,
+            (true, serial) if serial >= 1.0 => Some(serial - DATE1904_OFFSET),
+            (_, serial) => Some(serial),
+        }
+    }
+
+    pub(crate) fn from_serial_in(serial: f64, date1904: bool) -> ExcelDateTime {
+        if date1904 && serial >= 1.0 {
+            ExcelDateTime::from_serial(serial + DATE1904_OFFSET)
+        } else {
+            ExcelDateTime::from_serial(serial)
+        }
+    }
+
+    ///
+    /// The built-in number format to display the value with:
+    /// `h:mm:ss` for a time of day, `m/d/yyyy` for a date and `m/d/yyyy h:mm` otherwise
+    ///
+    pub(crate) fn default_num_fmt_id(&self) -> u32 {
+        if self.serial < 1.0 {
+            21
+        } else if self.serial.fract() == 0.0 {
+            14
+        } else {
+            22
+        }
+    }
+
+    ///
+    /// Parse an ISO 8601 date time, such as the value of a cell typed `d`
+    ///
+    pub(crate) fn parse_iso(text: &str) -> Option<ExcelDateTime> {
+        let (date, time) = match text.split_once('T') {
+            Some((date, time)) => (date, Some(time)),
+            None => (text, None),
+        };
+        let mut date_parts = date.splitn(3, '-');
+        let year = date_parts.next()?.parse().ok()?;
+        let month = date_parts.next()?.parse().ok()?;
+        let day = date_parts.next()?.parse().ok()?;
+        let (mut hour, mut minute, mut second) = (0, 0, 0.0);
+        if let Some(time) = time {
+            let time = time.trim_end_matches('Z');
+            let mut time_parts = time.splitn(3, ':');
+            hour = time_parts.next()?.parse().ok()?;
+            minute = time_parts.next().unwrap_or("0").parse().ok()?;
+            second = time_parts.next().unwrap_or("0").parse().ok()?;
+        }
+        ExcelDateTime::from_ymd_hms(year, month, day, hour, minute, second)
+    }
+}
+
+impl Display for ExcelDateTime {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let (year, month, day, hour, minute, second) = self.to_ymd_hms();
+        write!(f, "{year:04}-{month:02}-{day:02}T{hour:02}:{minute:02}:{:02}", second as u32)
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl ExcelDateTime {
+    pub fn from_naive_date_time(date_time: &chrono::NaiveDateTime) -> Option<ExcelDateTime> {
+        use chrono::{Datelike, Timelike};
+        let second = date_time.second() as f64 + date_time.nanosecond() as f64 / 1e9;
+        ExcelDateTime::from_ymd_hms(date_time.year(), date_time.month(), date_time.day(), date_time.hour(), date_time.minute(), second)
+    }
+
+    pub fn to_naive_date_time(&self) -> Option<chrono::NaiveDateTime> {
+        let (year, month, day, hour, minute, second) = self.to_ymd_hms();
+        let date = chrono::NaiveDate::from_ymd_opt(year, month, day)?;
+        date.and_hms_milli_opt(hour, minute, second as u32, (second.fract() * 1000.0).round() as u32)
+    }
+}
+
+#[cfg(feature = "time")]
+impl ExcelDateTime {
+    pub fn from_primitive_date_time(date_time: &time::PrimitiveDateTime) -> Option<ExcelDateTime> {
+        let second = date_time.second() as f64 + date_time.nanosecond() as f64 / 1e9;
+        ExcelDateTime::from_ymd_hms(date_time.year(), date_time.month() as u32, date_time.day() as u32, date_time.hour() as u32, date_time.minute() as u32, second)
+    }
+
+    pub fn to_primitive_date_time(&self) -> Option<time::PrimitiveDateTime> {
+        let (year, month, day, hour, minute, second) = self.to_ymd_hms();
+        let month = time::Month::try_from(month as u8).ok()?;
+        let date = time::Date::from_calendar_date(year, month, day as u8).ok()?;
+        let time = time::Time::from_hms_milli(hour as u8, minute as u8, second as u8, (second.fract() * 1000.0).round() as u16).ok()?;
+        Some(time::PrimitiveDateTime::new(date, time))
+    }
+}
+
+// Days since 1970-01-01 of a proleptic Gregorian date
+fn days_from_civil(year: i32, month: u32, day: u32) -> i64 {
+    let year = if month <= 2 { year as i64 - 1 } else { year as i64 };
+    let era = if year >= 0 { year } else { year - 399 } / 400;
+    let year_of_era = year - era * 400;
+    let month = month as i64;
+    let day_of_year = (153 * (if month > 2 { month - 3 } else { month + 9 }) + 2) / 5 + day as i64 - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146097 + day_of_era - 719468
+}
+
+fn days_in_month(year: i32, month: u32) -> u32 {
+    match month {
+        2 if year % 4 == 0 && (year % 100 != 0 || year % 400 == 0) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+fn civil_from_days(days: i64) -> (i32, u32, u32) {
+    let days = days + 719468;
+    let era = if days >= 0 { days } else { days - 146096 } / 146097;
+    let day_of_era = days - era * 146097;
+    let year_of_era = (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let mp = (5 * day_of_year + 2) / 153;
+    let day = (day_of_year - (153 * mp + 2) / 5 + 1) as u32;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+    (year as i32, month, day)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::api::cell::date_time::ExcelDateTime;
+
+    #[test]
+    fn test_serial() {
+        assert_eq!(ExcelDateTime::from_ymd_hms(1900, 1, 1, 0, 0, 0.0).unwrap().serial(), 1.0);
+        assert_eq!(ExcelDateTime::from_ymd_hms(1900, 3, 1, 0, 0, 0.0).unwrap().serial(), 61.0);
+        assert_eq!(ExcelDateTime::from_ymd_hms(2024, 2, 29, 12, 0, 0.0).unwrap().serial(), 45351.5);
+        assert_eq!(ExcelDateTime::from_serial(45351.5).to_ymd_hms(), (2024, 2, 29, 12, 0, 0.0));
+        assert_eq!(ExcelDateTime::from_serial(60.0).to_ymd_hms(), (1900, 2, 29, 0, 0, 0.0));
+        assert_eq!(ExcelDateTime::parse_iso("2024-02-29T12:00:00Z"), ExcelDateTime::from_ymd_hms(2024, 2, 29, 12, 0, 0.0));
+        assert_eq!(ExcelDateTime::from_ymd_hms(1900, 2, 29, 0, 0, 0.0).unwrap().serial(), 60.0);
+    }
+
+    #[test]
+    fn test_invalid_date_time() {
+        assert!(ExcelDateTime::from_ymd_hms(2023, 2, 29, 0, 0, 0.0).is_none());
+        assert!(ExcelDateTime::from_ymd_hms(2100, 2, 29, 0, 0, 0.0).is_none());
+        assert!(ExcelDateTime::from_ymd_hms(2024, 4, 31, 0, 0, 0.0).is_none());
+        assert!(ExcelDateTime::from_ymd_hms(2024, 1, 0, 0, 0, 0.0).is_none());
+        assert!(ExcelDateTime::from_ymd_hms(2024, 1, 1, 24, 0, 0.0).is_none());
+        assert!(ExcelDateTime::from_ymd_hms(2024, 1, 1, 0, 60, 0.0).is_none());
+        assert!(ExcelDateTime::from_ymd_hms(2024, 1, 1, 0, 0, 60.0).is_none());
+        assert!(ExcelDateTime::from_ymd_hms(2024, 1, 1, 0, 0, -1.0).is_none());
+        assert!(ExcelDateTime::from_ymd_hms(2000, 2, 29, 23, 59, 59.5).is_some());
+    }
+}
@@ -1,3 +1,6 @@
+pub(crate) mod token;
+pub(crate) mod expression;
+
 use crate::api::cell::location::{Location, LocationRange};
 
 #[derive(Clone, Debug, Default)]
@@ -0,0 +1,351 @@
+use std::fmt;
+use crate::api::cell::formula::token::{FormulaOperator, FormulaToken, NameReference, ParsedFormula, Reference, StructuredReference};
+use crate::result::FormulaError;
+
+///
+/// The expression tree of a formula
+///
+#[derive(Debug, Clone, PartialEq)]
+pub enum Expression {
+    Number(f64),
+    Text(String),
+    Boolean(bool),
+    Error(String),
+    Reference(Reference),
+    Name(NameReference),
+    StructuredReference(StructuredReference),
+    /// An array constant like `{1,2;3,4}`, by rows
+    Array(Vec<Vec<Expression>>),
+    Function(String, Vec<Expression>),
+    /// An omitted argument of a function, like the second one of `IF(A1,,1)`
+    Missing,
+    Unary(UnaryOperator, Box<Expression>),
+    Binary(Box<Expression>, BinaryOperator, Box<Expression>),
+    Parenthesis(Box<Expression>),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum UnaryOperator {
+    Plus,
+    Minus,
+    /// The postfix `%`
+    Percent,
+    /// The postfix `#` of a spilled range
+    Spill,
+    /// The prefix `@`
+    ImplicitIntersection,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum BinaryOperator {
+    Add,
+    Subtract,
+    Multiply,
+    Divide,
+    Power,
+    Concat,
+    Equal,
+    NotEqual,
+    Less,
+    LessOrEqual,
+    Greater,
+    GreaterOrEqual,
+    /// `:`
+    Range,
+    /// `,` between references
+    Union,
+    /// A space between references
+    Intersection,
+}
+
+impl Expression {
+    pub fn parse(formula: &str) -> Result<Expression, FormulaError> {
+        ParsedFormula::parse(formula).to_expression()
+    }
+
+    ///
+    /// The references of the expression, in the order of the formula
+    ///
+    pub fn references(&self) -> Vec<&Reference> {
+        let mut references = vec![];
+        self.collect_references(&mut references);
+        references
+    }
+
+    fn collect_references<'a>(&'a self, references: &mut Vec<&'a Reference>) {
+        match self {
+            Expression::Reference(reference) => references.push(reference),
+            Expression::Array(rows) => rows.iter().flatten().for_each(|e| e.collect_references(references)),
+            Expression::Function(_, arguments) => arguments.iter().for_each(|e| e.collect_references(references)),
+            Expression::Unary(_, e) | Expression::Parenthesis(e) => e.collect_references(references),
+            Expression::Binary(left, _, right) => {
+                left.collect_references(references);
+                right.collect_references(references);
+            }
+            _ => {}
+        }
+    }
+}
+
+impl BinaryOperator {
+    ///
+    /// The precedence of the operator, the highest first
+    ///
+    fn binding_power(self) -> u8 {
+        match self {
+            BinaryOperator::Range => 10,
+            BinaryOperator::Intersection => 9,
+            BinaryOperator::Union => 8,
+            BinaryOperator::Power => 5,
+            BinaryOperator::Multiply | BinaryOperator::Divide => 4,
+            BinaryOperator::Add | BinaryOperator::Subtract => 3,
+            BinaryOperator::Concat => 2,
+            _ => 1,
+        }
+    }
+
+    fn from_operator(operator: FormulaOperator) -> Option<BinaryOperator> {
+        match operator {
+            FormulaOperator::Plus => Some(BinaryOperator::Add),
+            FormulaOperator::Minus => Some(BinaryOperator::Subtract),
+            FormulaOperator::Multiply => Some(BinaryOperator::Multiply),
+            FormulaOperator::Divide => Some(BinaryOperator::Divide),
+            FormulaOperator::Power => Some(BinaryOperator::Power),
+            FormulaOperator::Concat => Some(BinaryOperator::Concat),
+            FormulaOperator::Equal => Some(BinaryOperator::Equal),
+            FormulaOperator::NotEqual => Some(BinaryOperator::NotEqual),
+            FormulaOperator::Less => Some(BinaryOperator::Less),
+            FormulaOperator::LessOrEqual => Some(BinaryOperator::LessOrEqual),
+            FormulaOperator::Greater => Some(BinaryOperator::Greater),
+            FormulaOperator::GreaterOrEqual => Some(BinaryOperator::GreaterOrEqual),
+            FormulaOperator::Range => Some(BinaryOperator::Range),
+            FormulaOperator::Percent | FormulaOperator::Spill | FormulaOperator::At => None,
+        }
+    }
+}
+
+// The negation binds tighter than the power, -2^2 is 4
+const PREFIX_BINDING_POWER: u8 = 6;
+const PERCENT_BINDING_POWER: u8 = 7;
+const SPILL_BINDING_POWER: u8 = 11;
+
+///
+/// Parse the tokens of a formula, whose spaces are only kept as intersections
+///
+pub(crate) fn parse(tokens: &[FormulaToken]) -> Result<Expression, FormulaError> {
+    let mut spaced = false;
+    let mut parser = Parser { tokens: vec![], position: 0 };
+    for token in tokens {
+        match token {
+            FormulaToken::Whitespace(_) => spaced = true,
+            token => {
+                parser.tokens.push((token, spaced));
+                spaced = false;
+            }
+        }
+    }
+    if let Some((FormulaToken::Operator(FormulaOperator::Equal), _)) = parser.tokens.first() {
+        parser.position = 1;
+    }
+    let expression = parser.expression(0, true)?;
+    match parser.tokens.get(parser.position) {
+        Some((token, _)) => Err(FormulaError::UnexpectedToken(token.to_string())),
+        None => Ok(expression),
+    }
+}
+
+struct Parser<'a> {
+    // The tokens, and whether they follow spaces
+    tokens: Vec<(&'a FormulaToken, bool)>,
+    position: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn peek(&self) -> Option<&'a FormulaToken> {
+        self.tokens.get(self.position).map(|(token, _)| *token)
+    }
+
+    fn next(&mut self) -> Result<&'a FormulaToken, FormulaError> {
+        let token = self.peek().ok_or(FormulaError::UnexpectedEnd)?;
+        self.position += 1;
+        Ok(token)
+    }
+
+    fn expect(&mut self, expected: &FormulaToken) -> Result<(), FormulaError> {
+        match self.next()? {
+            token if token == expected => Ok(()),
+            token => Err(FormulaError::UnexpectedToken(token.to_string())),
+        }
+    }
+
+    ///
+    /// An expression whose operators bind at least as tight as `min_binding_power`.
+    /// Commas are unions of references outside the arguments of functions and arrays.
+    ///
+    fn expression(&mut self, min_binding_power: u8, union: bool) -> Result<Expression, FormulaError> {
+        let mut left = self.prefix(union)?;
+        while let Some(&(token, spaced)) = self.tokens.get(self.position) {
+            let postfix = match token {
+                FormulaToken::Operator(FormulaOperator::Percent) => Some((UnaryOperator::Percent, PERCENT_BINDING_POWER)),
+                FormulaToken::Operator(FormulaOperator::Spill) => Some((UnaryOperator::Spill, SPILL_BINDING_POWER)),
+                _ => None,
+            };
+            if let Some((operator, binding_power)) = postfix {
+                if binding_power < min_binding_power {
+                    break;
+                }
+                self.position += 1;
+                left = Expression::Unary(operator, Box::new(left));
+                continue;
+            }
+            let operator = match token {
+                FormulaToken::Operator(operator) => match BinaryOperator::from_operator(*operator) {
+                    Some(operator) => operator,
+                    None => break,
+                },
+                FormulaToken::Comma if union => BinaryOperator::Union,
+                token if spaced && starts_operand(token) => BinaryOperator::Intersection,
+                _ => break,
+            };
+            let binding_power = operator.binding_power();
+            if binding_power < min_binding_power {
+                break;
+            }
+            if operator != BinaryOperator::Intersection {
+                self.position += 1;
+            }
+            let right = self.expression(binding_power + 1, union)?;
+            left = Expression::Binary(Box::new(left), operator, Box::new(right));
+        }
+        Ok(left)
+    }
+
+    fn prefix(&mut self, union: bool) -> Result<Expression, FormulaError> {
+        let token = self.next()?;
+        let expression = match token {
+            FormulaToken::Number(number) => Expression::Number(number.parse().map_err(|_| FormulaError::UnexpectedToken(number.clone()))?),
+            FormulaToken::Text(text) => Expression::Text(text.clone()),
+            FormulaToken::Boolean(boolean) => Expression::Boolean(*boolean),
+            FormulaToken::Error(error) => Expression::Error(error.clone()),
+            FormulaToken::Reference(reference) => Expression::Reference(reference.clone()),
+            FormulaToken::Name(name) => Expression::Name(name.clone()),
+            FormulaToken::StructuredReference(structured) => Expression::StructuredReference(structured.clone()),
+            FormulaToken::Operator(operator @ (FormulaOperator::Plus | FormulaOperator::Minus | FormulaOperator::At)) => {
+                let operator = match operator {
+                    FormulaOperator::Plus => UnaryOperator::Plus,
+                    FormulaOperator::Minus => UnaryOperator::Minus,
+                    _ => UnaryOperator::ImplicitIntersection,
+                };
+                Expression::Unary(operator, Box::new(self.expression(PREFIX_BINDING_POWER, union)?))
+            }
+            FormulaToken::Function(name) => Expression::Function(name.clone(), self.arguments()?),
+            FormulaToken::OpenParenthesis => {
+                let expression = self.expression(0, true)?;
+                self.expect(&FormulaToken::CloseParenthesis)?;
+                Expression::Parenthesis(Box::new(expression))
+            }
+            FormulaToken::OpenBrace => self.array()?,
+            token => return Err(FormulaError::UnexpectedToken(token.to_string())),
+        };
+        Ok(expression)
+    }
+
+    fn arguments(&mut self) -> Result<Vec<Expression>, FormulaError> {
+        self.expect(&FormulaToken::OpenParenthesis)?;
+        let mut arguments = vec![];
+        if self.peek() == Some(&FormulaToken::CloseParenthesis) {
+            self.position += 1;
+            return Ok(arguments);
+        }
+        loop {
+            match self.peek() {
+                Some(FormulaToken::Comma | FormulaToken::CloseParenthesis) => arguments.push(Expression::Missing),
+                _ => arguments.push(self.expression(0, false)?),
+            }
+            match self.next()? {
+                FormulaToken::Comma => {}
+                FormulaToken::CloseParenthesis => return Ok(arguments),
+                token => return Err(FormulaError::UnexpectedToken(token.to_string())),
+            }
+        }
+    }
+
+    fn array(&mut self) -> Result<Expression, FormulaError> {
+        let mut rows = vec![vec![]];
+        loop {
+            rows.last_mut().unwrap().push(self.expression(0, false)?);
+            match self.next()? {
+                FormulaToken::Comma => {}
+                FormulaToken::Semicolon => rows.push(vec![]),
+                FormulaToken::CloseBrace => return Ok(Expression::Array(rows)),
+                token => return Err(FormulaError::UnexpectedToken(token.to_string())),
+            }
+        }
+    }
+}
+
+///
+/// A token after spaces which starts an operand makes the spaces an intersection
+///
+fn starts_operand(token: &FormulaToken) -> bool {
+    matches!(
+        token,
+        FormulaToken::Reference(_) | FormulaToken::Name(_) | FormulaToken::StructuredReference(_)
+        | FormulaToken::Function(_) | FormulaToken::OpenParenthesis
+    )
+}
+
+impl fmt::Display for Expression {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let join = |expressions: &[Expression], separator: &str| expressions.iter()
+            .map(|expression| expression.to_string())
+            .collect::<Vec<String>>()
+            .join(separator);
+        match self {
+            Expression::Number(number) => write!(f, "{number}"),
+            Expression::Text(text) => write!(f, "\"{}\"", text.replace('"', "\"\"")),
+            Expression::Boolean(true) => write!(f, "TRUE"),
+            Expression::Boolean(false) => write!(f, "FALSE"),
+            Expression::Error(error) => write!(f, "{error}"),
+            Expression::Reference(reference) => write!(f, "{reference}"),
+            Expression::Name(name) => write!(f, "{name}"),
+            Expression::StructuredReference(structured) => write!(f, "{structured}"),
+            Expression::Array(rows) => {
+                let rows = rows.iter().map(|row| join(row, ",")).collect::<Vec<String>>();
+                write!(f, "{{{}}}", rows.join(";"))
+            }
+            Expression::Function(name, arguments) => write!(f, "{name}({})", join(arguments, ",")),
+            Expression::Missing => Ok(()),
+            Expression::Unary(UnaryOperator::Plus, e) => write!(f, "+{e}"),
+            Expression::Unary(UnaryOperator::Minus, e) => write!(f, "-{e}"),
+            Expression::Unary(UnaryOperator::ImplicitIntersection, e) => write!(f, "@{e}"),
+            Expression::Unary(UnaryOperator::Percent, e) => write!(f, "{e}%"),
+            Expression::Unary(UnaryOperator::Spill, e) => write!(f, "{e}#"),
+            Expression::Binary(left, operator, right) => write!(f, "{left}{operator}{right}"),
+            Expression::Parenthesis(e) => write!(f, "({e})"),
+        }
+    }
+}
+
+impl fmt::Display for BinaryOperator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let symbol = match self {
+            BinaryOperator::Add => "+",
+            BinaryOperator::Subtract => "-",
+            BinaryOperator::Multiply => "*",
+            BinaryOperator::Divide => "/",
+            BinaryOperator::Power => "^",
+            BinaryOperator::Concat => "&",
+            BinaryOperator::Equal => "=",
+            BinaryOperator::NotEqual => "<>",
+            BinaryOperator::Less => "<",
+            BinaryOperator::LessOrEqual => "<=",
+            BinaryOperator::Greater => ">",
+            BinaryOperator::GreaterOrEqual => ">=",
+            BinaryOperator::Range => ":",
+            BinaryOperator::Union => ",",
+            BinaryOperator::Intersection => " ",
+        };
+        write!(f, "{symbol}")
+    }
+}
@@ -0,0 +1,799 @@
+use std::fmt;
+use crate::api::cell::formula::expression::{self, Expression};
+use crate::result::FormulaError;
+use crate::utils::col_helper::{to_col, to_col_name};
+use crate::utils::reference_helper::{is_same_sheet, MAX_COL, MAX_ROW, Shift};
+
+///
+/// A formula split into tokens, which are written back as they are read, but for the references in capitals.
+/// The references of the tokens can be changed, for example when the cells of a worksheet move.
+///
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct ParsedFormula {
+    pub tokens: Vec<FormulaToken>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum FormulaToken {
+    /// A number, as it is written
+    Number(String),
+    /// A text, without its quotes
+    Text(String),
+    Boolean(bool),
+    /// An error like `#N/A`
+    Error(String),
+    Reference(Reference),
+    /// A defined name
+    Name(NameReference),
+    /// A reference to a table, like `Table1[[#This Row],[Amount]]`
+    StructuredReference(StructuredReference),
+    /// The name of a function, followed by an open parenthesis
+    Function(String),
+    Operator(FormulaOperator),
+    OpenParenthesis,
+    CloseParenthesis,
+    OpenBrace,
+    CloseBrace,
+    /// The separator of arguments or of the columns of an array, or the union of references
+    Comma,
+    /// The separator of the rows of an array
+    Semicolon,
+    /// Spaces, which are the intersection of references between two references
+    Whitespace(String),
+    /// A text which is not understood
+    Unknown(String),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum FormulaOperator {
+    Plus,
+    Minus,
+    Multiply,
+    Divide,
+    Power,
+    Concat,
+    Equal,
+    NotEqual,
+    Less,
+    LessOrEqual,
+    Greater,
+    GreaterOrEqual,
+    Percent,
+    /// The range between two references, like `A1:INDEX(B1:B9,2)`
+    Range,
+    /// The spilled range of a dynamic array, like `A1#`
+    Spill,
+    /// The implicit intersection, like `@A1:A9`
+    At,
+}
+
+///
+/// A reference to cells, which may be in another worksheet or in another workbook
+///
+#[derive(Debug, Clone, PartialEq)]
+pub struct Reference {
+    /// The external workbook, like `1` in `[1]Sheet1!A1`
+    pub workbook: Option<String>,
+    /// The worksheet, or the first worksheet of a 3D reference
+    pub sheet: Option<String>,
+    /// The last worksheet of a 3D reference like `Sheet1:Sheet3!A1`
+    pub last_sheet: Option<String>,
+    /// The sheet name is quoted even if it doesn't need to be
+    pub quoted: bool,
+    pub area: ReferenceArea,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ReferenceArea {
+    Cell(CellReference),
+    Range(CellReference, CellReference),
+    /// Whole rows like `1:3`
+    Rows(LineReference, LineReference),
+    /// Whole columns like `A:C`
+    Columns(LineReference, LineReference),
+    /// A deleted reference, written `#REF!`
+    Invalid,
+}
+
+///
+/// A cell, whose row and column are 1-based
+///
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CellReference {
+    pub row: u32,
+    pub col: u32,
+    pub row_absolute: bool,
+    pub col_absolute: bool,
+}
+
+///
+/// A side of a range of whole rows or columns, 1-based
+///
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LineReference {
+    pub index: u32,
+    pub absolute: bool,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct NameReference {
+    pub workbook: Option<String>,
+    /// The worksheet of a name local to it
+    pub sheet: Option<String>,
+    pub quoted: bool,
+    pub name: String,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct StructuredReference {
+    /// The table, None for a reference inside the table like `[@Amount]`
+    pub table: Option<String>,
+    /// The columns and the rows, with their brackets
+    pub specifier: String,
+}
+
+impl ParsedFormula {
+    ///
+    /// Split a formula into tokens, a formula with a leading `=` starts with an `Equal` operator
+    ///
+    pub fn parse(formula: &str) -> ParsedFormula {
+        let mut tokenizer = Tokenizer { chars: formula.chars().collect(), position: 0, tokens: vec![] };
+        while tokenizer.position < tokenizer.chars.len() {
+            tokenizer.next_token();
+        }
+        ParsedFormula { tokens: tokenizer.tokens }
+    }
+
+    ///
+    /// The expression tree of the formula
+    ///
+    pub fn to_expression(&self) -> Result<Expression, FormulaError> {
+        expression::parse(&self.tokens)
+    }
+
+    pub fn references(&self) -> Vec<&Reference> {
+        self.tokens.iter()
+            .filter_map(|token| match token {
+                FormulaToken::Reference(reference) => Some(reference),
+                _ => None,
+            })
+            .collect()
+    }
+
+    ///
+    /// Move the relative rows and columns of the references, like a formula copied to another cell.
+    /// The references moved out of the worksheet become `#REF!`.
+    ///
+    pub fn offset(&mut self, rows: i64, cols: i64) {
+        self.references_mut().for_each(|reference| reference.area = reference.area.offset(rows, cols));
+    }
+
+    ///
+    /// Make the references and the names of a worksheet refer to the worksheet renamed
+    ///
+    pub fn rename_sheet(&mut self, old_name: &str, new_name: &str) {
+        // a renamed sheet is quoted only if its new name needs to be
+        let rename = |sheet: &mut Option<String>, quoted: &mut bool| {
+            if sheet.as_deref().is_some_and(|sheet| is_same_sheet(sheet, old_name)) {
+                *sheet = Some(new_name.to_string());
+                *quoted = false;
+            }
+        };
+        for token in &mut self.tokens {
+            match token {
+                FormulaToken::Reference(reference) if reference.workbook.is_none() => {
+                    rename(&mut reference.sheet, &mut reference.quoted);
+                    rename(&mut reference.last_sheet, &mut reference.quoted);
+                }
+                FormulaToken::Name(name) if name.workbook.is_none() => rename(&mut name.sheet, &mut name.quoted),
+                _ => {}
+            }
+        }
+    }
+
+    ///
+    /// Make the references and the names of a removed worksheet `#REF!`
+    ///
+    pub(crate) fn remove_sheet(&mut self, sheet_name: &str) {
+        let removed = |sheet: &Option<String>| sheet.as_deref().is_some_and(|sheet| is_same_sheet(sheet, sheet_name));
+        for token in &mut self.tokens {
+            match token {
+                FormulaToken::Reference(reference) if reference.workbook.is_none()
+                    && (removed(&reference.sheet) || removed(&reference.last_sheet)) => {
+                    *token = FormulaToken::Reference(Reference::new(Prefix::default(), ReferenceArea::Invalid));
+                }
+                FormulaToken::Name(name) if name.workbook.is_none() && removed(&name.sheet) => {
+                    *token = FormulaToken::Error("#REF!".to_string());
+                }
+                _ => {}
+            }
+        }
+    }
+
+    ///
+    /// Make the structured references to tables refer to the tables renamed, given by their old and new names
+    ///
+    pub(crate) fn rename_tables(&mut self, names: &[(String, String)]) {
+        for token in &mut self.tokens {
+            if let FormulaToken::StructuredReference(StructuredReference { table: Some(table), .. }) = token {
+                if let Some((_, new_name)) = names.iter().find(|(old_name, _)| old_name.eq_ignore_ascii_case(table)) {
+                    *table = new_name.to_string();
+                }
+            }
+        }
+    }
+
+    ///
+    /// Shift the references to the rows of a worksheet, after rows inserted before `at` if the count is positive,
+    /// or after rows deleted from `at` if it is negative.
+    /// The references without a sheet name refer to the worksheet if the formula is in it.
+    ///
+    pub fn shift_rows(&mut self, sheet_name: &str, in_sheet: bool, at: u32, count: i32) {
+        match count {
+            0 => {}
+            1.. => self.shift(Shift::InsertRows(at, count.unsigned_abs()), sheet_name, in_sheet),
+            _ => self.shift(Shift::DeleteRows(at, count.unsigned_abs()), sheet_name, in_sheet),
+        }
+    }
+
+    ///
+    /// Shift the references to the columns of a worksheet, like [`ParsedFormula::shift_rows`]
+    ///
+    pub fn shift_columns(&mut self, sheet_name: &str, in_sheet: bool, at: u32, count: i32) {
+        match count {
+            0 => {}
+            1.. => self.shift(Shift::InsertColumns(at, count.unsigned_abs()), sheet_name, in_sheet),
+            _ => self.shift(Shift::DeleteColumns(at, count.unsigned_abs()), sheet_name, in_sheet),
+        }
+    }
+
+    pub(crate) fn shift(&mut self, shift: Shift, sheet_name: &str, in_sheet: bool) {
+        self.references_mut()
+            .filter(|reference| reference.workbook.is_none() && reference.last_sheet.is_none())
+            .filter(|reference| match &reference.sheet {
+                Some(sheet) => is_same_sheet(sheet, sheet_name),
+                None => in_sheet,
+            })
+            .for_each(|reference| reference.area = shift.shift_area(reference.area).unwrap_or(ReferenceArea::Invalid));
+    }
+
+    fn references_mut(&mut self) -> impl Iterator<Item = &mut Reference> {
+        self.tokens.iter_mut()
+            .filter_map(|token| match token {
+                FormulaToken::Reference(reference) => Some(reference),
+                _ => None,
+            })
+    }
+}
+
+impl ReferenceArea {
+    ///
+    /// A reference without a sheet name like `$A$1`, `A1:B2`, `1:3` or `A:C`
+    ///
+    pub(crate) fn parse(text: &str) -> Option<ReferenceArea> {
+        let (first, last) = match text.split_once(':') {
+            Some((first, last)) => (RefPart::parse(first)?, Some(RefPart::parse(last)?)),
+            None => (RefPart::parse(text)?, None),
+        };
+        match (first, last) {
+            (RefPart::Cell(cell), None) => Some(ReferenceArea::Cell(cell)),
+            (RefPart::Cell(first), Some(RefPart::Cell(last))) => Some(ReferenceArea::Range(first, last)),
+            (RefPart::Row(first), Some(RefPart::Row(last))) => Some(ReferenceArea::Rows(first, last)),
+            (RefPart::Column(first), Some(RefPart::Column(last))) => Some(ReferenceArea::Columns(first, last)),
+            _ => None,
+        }
+    }
+
+    fn offset(self, rows: i64, cols: i64) -> ReferenceArea {
+        let line = |line: LineReference, offset: i64, max: u32| match line.absolute {
+            true => Some(line),
+            false => {
+                let index = line.index as i64 + offset;
+                if (1..=max as i64).contains(&index) { Some(LineReference { index: index as u32, ..line }) } else { None }
+            }
+        };
+        let cell = |cell: CellReference| {
+            let row = line(LineReference { index: cell.row, absolute: cell.row_absolute }, rows, MAX_ROW)?;
+            let col = line(LineReference { index: cell.col, absolute: cell.col_absolute }, cols, MAX_COL)?;
+            Some(CellReference { row: row.index, col: col.index, ..cell })
+        };
+        let offset = match self {
+            ReferenceArea::Cell(first) => cell(first).map(ReferenceArea::Cell),
+            ReferenceArea::Range(first, last) => cell(first).zip(cell(last))
+                .map(|(first, last)| ReferenceArea::Range(first, last)),
+            ReferenceArea::Rows(first, last) => line(first, rows, MAX_ROW).zip(line(last, rows, MAX_ROW))
+                .map(|(first, last)| ReferenceArea::Rows(first, last)),
+            ReferenceArea::Columns(first, last) => line(first, cols, MAX_COL).zip(line(last, cols, MAX_COL))
+                .map(|(first, last)| ReferenceArea::Columns(first, last)),
+            ReferenceArea::Invalid => None,
+        };
+        offset.unwrap_or(ReferenceArea::Invalid)
+    }
+}
+
+///
+/// A side of a reference: a cell like $A$1, or a side of a range of whole rows or columns like $1 or A
+///
+#[derive(Debug, Clone, Copy)]
+enum RefPart {
+    Cell(CellReference),
+    Row(LineReference),
+    Column(LineReference),
+}
+
+impl RefPart {
+    fn parse(text: &str) -> Option<RefPart> {
+        let mut rest = text;
+        let col_absolute = rest.starts_with('$');
+        rest = rest.strip_prefix('$').unwrap_or(rest);
+        // Excel accepts the columns in lower case, they are written back in capitals
+        let letters = rest.chars().take_while(|c| c.is_ascii_alphabetic()).count();
+        let col_name = rest[..letters].to_ascii_uppercase();
+        rest = &rest[letters..];
+        let row_absolute = rest.starts_with('$');
+        rest = rest.strip_prefix('$').unwrap_or(rest);
+        if !rest.chars().all(|c| c.is_ascii_digit()) || letters > 3 || (row_absolute && rest.is_empty()) {
+            return None;
+        }
+        let col = to_col(&col_name);
+        let row = rest.parse::<u32>().ok();
+        match (letters, row) {
+            (0, Some(row)) if !row_absolute && (1..=MAX_ROW).contains(&row) => {
+                Some(RefPart::Row(LineReference { index: row, absolute: col_absolute }))
+            }
+            (_, None) if letters > 0 && col <= MAX_COL => Some(RefPart::Column(LineReference { index: col, absolute: col_absolute })),
+            (_, Some(row)) if letters > 0 && col <= MAX_COL && (1..=MAX_ROW).contains(&row) => {
+                Some(RefPart::Cell(CellReference { row, col, row_absolute, col_absolute }))
+            }
+            _ => None,
+        }
+    }
+}
+
+///
+/// The errors of Excel, the longest first
+///
+const ERRORS: [&str; 16] = [
+    "#GETTING_DATA", "#EXTERNAL!", "#BLOCKED!", "#CONNECT!", "#UNKNOWN!", "#PYTHON!", "#SPILL!", "#FIELD!",
+    "#VALUE!", "#DIV/0!", "#CALC!", "#NULL!", "#NAME?", "#BUSY!", "#NUM!", "#N/A",
+];
+
+///
+/// The worksheets before the `!` of a reference
+///
+#[derive(Default)]
+struct Prefix {
+    workbook: Option<String>,
+    sheet: Option<String>,
+    last_sheet: Option<String>,
+    quoted: bool,
+}
+
+struct Tokenizer {
+    chars: Vec<char>,
+    position: usize,
+    tokens: Vec<FormulaToken>,
+}
+
+impl Tokenizer {
+    fn text(&self, start: usize, end: usize) -> String {
+        self.chars[start..end].iter().collect()
+    }
+
+    fn starts_with(&self, start: usize, text: &str) -> bool {
+        text.chars().enumerate().all(|(i, c)| self.chars.get(start + i) == Some(&c))
+    }
+
+    fn push(&mut self, token: FormulaToken, end: usize) {
+        self.tokens.push(token);
+        self.position = end;
+    }
+
+    fn next_token(&mut self) {
+        let start = self.position;
+        let next = self.chars.get(start + 1).copied();
+        let operator = |operator: FormulaOperator| FormulaToken::Operator(operator);
+        match self.chars[start] {
+            c if c.is_whitespace() => {
+                let end = (start..self.chars.len()).find(|&i| !self.chars[i].is_whitespace()).unwrap_or(self.chars.len());
+                self.push(FormulaToken::Whitespace(self.text(start, end)), end);
+            }
+            '"' => {
+                let end = skip_quoted(&self.chars, start, '"');
+                match end > start + 1 && self.chars[end - 1] == '"' {
+                    true => self.push(FormulaToken::Text(self.text(start + 1, end - 1).replace("\"\"", "\"")), end),
+                    false => self.push(FormulaToken::Unknown(self.text(start, end)), end),
+                }
+            }
+            '#' if self.follows_operand() => self.push(operator(FormulaOperator::Spill), start + 1),
+            '#' => {
+                let end = self.error_end(start);
+                self.push(FormulaToken::Error(self.text(start, end)), end);
+            }
+            '\'' => self.quoted(start),
+            '[' => self.bracket(start),
+            '(' => self.push(FormulaToken::OpenParenthesis, start + 1),
+            ')' => self.push(FormulaToken::CloseParenthesis, start + 1),
+            '{' => self.push(FormulaToken::OpenBrace, start + 1),
+            '}' => self.push(FormulaToken::CloseBrace, start + 1),
+            ',' => self.push(FormulaToken::Comma, start + 1),
+            ';' => self.push(FormulaToken::Semicolon, start + 1),
+            '+' => self.push(operator(FormulaOperator::Plus), start + 1),
+            '-' => self.push(operator(FormulaOperator::Minus), start + 1),
+            '*' => self.push(operator(FormulaOperator::Multiply), start + 1),
+            '/' => self.push(operator(FormulaOperator::Divide), start + 1),
+            '^' => self.push(operator(FormulaOperator::Power), start + 1),
+            '&' => self.push(operator(FormulaOperator::Concat), start + 1),
+            '=' => self.push(operator(FormulaOperator::Equal), start + 1),
+            '%' => self.push(operator(FormulaOperator::Percent), start + 1),
+            ':' => self.push(operator(FormulaOperator::Range), start + 1),
+            '@' => self.push(operator(FormulaOperator::At), start + 1),
+            '<' if next == Some('=') => self.push(operator(FormulaOperator::LessOrEqual), start + 2),
+            '<' if next == Some('>') => self.push(operator(FormulaOperator::NotEqual), start + 2),
+            '<' => self.push(operator(FormulaOperator::Less), start + 1),
+            '>' if next == Some('=') => self.push(operator(FormulaOperator::GreaterOrEqual), start + 2),
+            '>' => self.push(operator(FormulaOperator::Greater), start + 1),
+            c if c.is_ascii_digit() || c == '.' => self.number(start),
+            c if is_word_char(c) => self.word(start),
+            c => self.push(FormulaToken::Unknown(c.to_string()), start + 1),
+        }
+    }
+
+    ///
+    /// A `#` right after a reference is the spill operator
+    ///
+    fn follows_operand(&self) -> bool {
+        matches!(
+            self.tokens.last(),
+            Some(FormulaToken::Reference(_) | FormulaToken::Name(_) | FormulaToken::CloseParenthesis)
+        )
+    }
+
+    fn error_end(&self, start: usize) -> usize {
+        if let Some(error) = ERRORS.iter().chain(["#REF!"].iter()).find(|error| self.starts_with(start, error)) {
+            return start + error.chars().count();
+        }
+        let mut end = start + 1;
+        while end < self.chars.len() && (self.chars[end].is_alphanumeric() || self.chars[end] == '/' || self.chars[end] == '_') {
+            end += 1;
+        }
+        if matches!(self.chars.get(end), Some('!' | '?')) {
+            end += 1;
+        }
+        end
+    }
+
+    fn number(&mut self, start: usize) {
+        if let Some((area @ ReferenceArea::Rows(..), end)) = self.area(start) {
+            return self.push(FormulaToken::Reference(Reference::new(Prefix::default(), area)), end);
+        }
+        let digits = |mut end: usize| {
+            while end < self.chars.len() && (self.chars[end].is_ascii_digit() || self.chars[end] == '.') {
+                end += 1;
+            }
+            end
+        };
+        let mut end = digits(start);
+        if matches!(self.chars.get(end), Some('e' | 'E')) {
+            let exponent = if matches!(self.chars.get(end + 1), Some('+' | '-')) { end + 2 } else { end + 1 };
+            if self.chars.get(exponent).is_some_and(char::is_ascii_digit) {
+                end = digits(exponent);
+            }
+        }
+        self.push(FormulaToken::Number(self.text(start, end)), end);
+    }
+
+    fn word(&mut self, start: usize) {
+        let end = word_end(&self.chars, start);
+        let word = self.text(start, end);
+        if self.chars.get(end) == Some(&':') {
+            let last_end = word_end(&self.chars, end + 1);
+            if last_end > end + 1 && self.chars.get(last_end) == Some(&'!') {
+                let prefix = Prefix { sheet: Some(word), last_sheet: Some(self.text(end + 1, last_end)), ..Default::default() };
+                return self.after_prefix(last_end + 1, prefix);
+            }
+        }
+        match self.chars.get(end) {
+            Some('!') => return self.after_prefix(end + 1, Prefix { sheet: Some(word), ..Default::default() }),
+            Some('(') => return self.push(FormulaToken::Function(word), end),
+            Some('[') => {
+                let end_brackets = skip_brackets(&self.chars, end);
+                let structured = StructuredReference { table: Some(word), specifier: self.text(end, end_brackets) };
+                return self.push(FormulaToken::StructuredReference(structured), end_brackets);
+            }
+            _ => {}
+        }
+        match word.to_ascii_uppercase().as_str() {
+            "TRUE" => return self.push(FormulaToken::Boolean(true), end),
+            "FALSE" => return self.push(FormulaToken::Boolean(false), end),
+            _ => {}
+        }
+        match self.area(start) {
+            Some((area, end)) => self.push(FormulaToken::Reference(Reference::new(Prefix::default(), area)), end),
+            None => {
+                let name = NameReference { workbook: None, sheet: None, quoted: false, name: word };
+                self.push(FormulaToken::Name(name), end);
+            }
+        }
+    }
+
+    ///
+    /// A cell, a range or whole rows or columns starting at a position, and the position after it
+    ///
+    fn area(&self, start: usize) -> Option<(ReferenceArea, usize)> {
+        let first_end = word_end(&self.chars, start);
+        if self.chars.get(first_end) == Some(&':') {
+            let last_end = word_end(&self.chars, first_end + 1);
+            if let Some(area) = ReferenceArea::parse(&self.text(start, last_end)) {
+                return Some((area, last_end));
+            }
+        }
+        match ReferenceArea::parse(&self.text(start, first_end))? {
+            area @ ReferenceArea::Cell(_) => Some((area, first_end)),
+            _ => None,
+        }
+    }
+
+    ///
+    /// The reference or the name after the `!` of the worksheets
+    ///
+    fn after_prefix(&mut self, start: usize, prefix: Prefix) {
+        if self.starts_with(start, "#REF!") {
+            return self.push(FormulaToken::Reference(Reference::new(prefix, ReferenceArea::Invalid)), start + 5);
+        }
+        if let Some((area, end)) = self.area(start) {
+            if self.chars.get(end) != Some(&'(') {
+                return self.push(FormulaToken::Reference(Reference::new(prefix, area)), end);
+            }
+        }
+        let end = word_end(&self.chars, start);
+        if end > start && prefix.last_sheet.is_none() {
+            let name = NameReference { workbook: prefix.workbook, sheet: prefix.sheet, quoted: prefix.quoted, name: self.text(start, end) };
+            return self.push(FormulaToken::Name(name), end);
+        }
+        let text = self.text(self.position, end);
+        self.push(FormulaToken::Unknown(text), end);
+    }
+
+    ///
+    /// A quoted sheet name like `'My sheet'!A1` or `'[1]My sheet'!A1`
+    ///
+    fn quoted(&mut self, start: usize) {
+        let end = skip_quoted(&self.chars, start, '\'');
+        if end < start + 2 || self.chars[end - 1] != '\'' || self.chars.get(end) != Some(&'!') {
+            return self.push(FormulaToken::Unknown(self.text(start, end)), end);
+        }
+        let name = self.text(start + 1, end - 1).replace("''", "'");
+        let (workbook, sheets) = match name.strip_prefix('[').and_then(|name| name.split_once(']')) {
+            Some((workbook, sheets)) => (Some(workbook.to_string()), sheets.to_string()),
+            None => (None, name),
+        };
+        let (sheet, last_sheet) = match sheets.split_once(':') {
+            Some((sheet, last_sheet)) => (sheet.to_string(), Some(last_sheet.to_string())),
+            None => (sheets, None),
+        };
+        let sheet = if sheet.is_empty() { None } else { Some(sheet) };
+        self.after_prefix(end + 1, Prefix { workbook, sheet, last_sheet, quoted: true });
+    }
+
+    ///
+    /// An external workbook like `[1]Sheet1!A1` and `[1]!Name`, or a reference inside a table like `[@Amount]`
+    ///
+    fn bracket(&mut self, start: usize) {
+        let end = skip_brackets(&self.chars, start);
+        let workbook = Some(self.text(start + 1, end.saturating_sub(1).max(start + 1)));
+        if self.chars.get(end) == Some(&'!') {
+            return self.after_prefix(end + 1, Prefix { workbook, ..Default::default() });
+        }
+        let sheet_end = word_end(&self.chars, end);
+        if sheet_end > end && self.chars.get(sheet_end) == Some(&'!') {
+            let prefix = Prefix { workbook, sheet: Some(self.text(end, sheet_end)), ..Default::default() };
+            return self.after_prefix(sheet_end + 1, prefix);
+        }
+        let structured = StructuredReference { table: None, specifier: self.text(start, end) };
+        self.push(FormulaToken::StructuredReference(structured), end);
+    }
+}
+
+impl Reference {
+    fn new(prefix: Prefix, area: ReferenceArea) -> Reference {
+        Reference { workbook: prefix.workbook, sheet: prefix.sheet, last_sheet: prefix.last_sheet, quoted: prefix.quoted, area }
+    }
+}
+
+fn is_word_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_' || c == '.' || c == '$' || c == '\\' || c == '?'
+}
+
+fn word_end(chars: &[char], start: usize) -> usize {
+    let mut end = start;
+    while end < chars.len() && is_word_char(chars[end]) {
+        end += 1;
+    }
+    end
+}
+
+///
+/// The position after a text between quotes, whose quotes are escaped by doubling them
+///
+fn skip_quoted(chars: &[char], start: usize, quote: char) -> usize {
+    let mut end = start + 1;
+    while end < chars.len() {
+        if chars[end] == quote {
+            if chars.get(end + 1) == Some(&quote) {
+                end += 2;
+                continue;
+            }
+            return end + 1;
+        }
+        end += 1;
+    }
+    end
+}
+
+///
+/// The position after brackets, which may be nested and whose special characters are escaped by quotes
+///
+fn skip_brackets(chars: &[char], start: usize) -> usize {
+    let mut depth = 0;
+    let mut end = start;
+    while end < chars.len() {
+        match chars[end] {
+            '\'' => end += 1,
+            '[' => depth += 1,
+            ']' => {
+                depth -= 1;
+                if depth == 0 {
+                    return end + 1;
+                }
+            }
+            _ => {}
+        }
+        end += 1;
+    }
+    end
+}
+
+///
+/// A sheet name is quoted if it has other characters than letters, digits, underscores and dots,
+/// if it starts with a digit, or if it could be read as a reference, in the A1 or the R1C1 style
+///
+fn needs_quotes(name: &str) -> bool {
+    name.starts_with(|c: char| c.is_ascii_digit())
+        || name.chars().any(|c| !(c.is_alphanumeric() || c == '_' || c == '.'))
+        || RefPart::parse(name).is_some()
+        || is_r1c1_reference(name)
+        || name.eq_ignore_ascii_case("TRUE")
+        || name.eq_ignore_ascii_case("FALSE")
+}
+
+///
+/// A name like `R`, `C2`, `RC` or `R1C1`
+///
+pub(crate) fn is_r1c1_reference(name: &str) -> bool {
+    let name = name.to_ascii_uppercase();
+    let digits = |text: &str| text.chars().all(|c| c.is_ascii_digit());
+    match name.strip_prefix('R') {
+        Some(rest) => match rest.split_once('C') {
+            Some((row, col)) => digits(row) && digits(col),
+            None => digits(rest),
+        },
+        None => name.strip_prefix('C').is_some_and(digits),
+    }
+}
+
+fn write_prefix(f: &mut fmt::Formatter<'_>, workbook: &Option<String>, sheet: &Option<String>, last_sheet: &Option<String>, quoted: bool) -> fmt::Result {
+    if workbook.is_none() && sheet.is_none() {
+        return Ok(());
+    }
+    let mut prefix = String::new();
+    if let Some(workbook) = workbook {
+        prefix.push_str(&format!("[{workbook}]"));
+    }
+    if let Some(sheet) = sheet {
+        prefix.push_str(sheet);
+    }
+    if let Some(last_sheet) = last_sheet {
+        prefix.push(':');
+        prefix.push_str(last_sheet);
+    }
+    let quoted = quoted || [sheet, last_sheet].into_iter().flatten().any(|sheet| needs_quotes(sheet));
+    match quoted {
+        true => write!(f, "'{}'!", prefix.replace('\'', "''")),
+        false => write!(f, "{prefix}!"),
+    }
+}
+
+impl fmt::Display for ParsedFormula {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.tokens.iter().try_for_each(|token| write!(f, "{token}"))
+    }
+}
+
+impl fmt::Display for FormulaToken {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FormulaToken::Number(text) | FormulaToken::Error(text) | FormulaToken::Function(text)
+            | FormulaToken::Whitespace(text) | FormulaToken::Unknown(text) => write!(f, "{text}"),
+            FormulaToken::Text(text) => write!(f, "\"{}\"", text.replace('"', "\"\"")),
+            FormulaToken::Boolean(true) => write!(f, "TRUE"),
+            FormulaToken::Boolean(false) => write!(f, "FALSE"),
+            FormulaToken::Reference(reference) => write!(f, "{reference}"),
+            FormulaToken::Name(name) => write!(f, "{name}"),
+            FormulaToken::StructuredReference(structured) => write!(f, "{structured}"),
+            FormulaToken::Operator(operator) => write!(f, "{operator}"),
+            FormulaToken::OpenParenthesis => write!(f, "("),
+            FormulaToken::CloseParenthesis => write!(f, ")"),
+            FormulaToken::OpenBrace => write!(f, "{{"),
+            FormulaToken::CloseBrace => write!(f, "}}"),
+            FormulaToken::Comma => write!(f, ","),
+            FormulaToken::Semicolon => write!(f, ";"),
+        }
+    }
+}
+
+impl fmt::Display for FormulaOperator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let symbol = match self {
+            FormulaOperator::Plus => "+",
+            FormulaOperator::Minus => "-",
+            FormulaOperator::Multiply => "*",
+            FormulaOperator::Divide => "/",
+            FormulaOperator::Power => "^",
+            FormulaOperator::Concat => "&",
+            FormulaOperator::Equal => "=",
+            FormulaOperator::NotEqual => "<>",
+            FormulaOperator::Less => "<",
+            FormulaOperator::LessOrEqual => "<=",
+            FormulaOperator::Greater => ">",
+            FormulaOperator::GreaterOrEqual => ">=",
+            FormulaOperator::Percent => "%",
+            FormulaOperator::Range => ":",
+            FormulaOperator::Spill => "#",
+            FormulaOperator::At => "@",
+        };
+        write!(f, "{symbol}")
+    }
+}
+
+impl fmt::Display for Reference {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write_prefix(f, &self.workbook, &self.sheet, &self.last_sheet, self.quoted)?;
+        write!(f, "{}", self.area)
+    }
+}
+
+impl fmt::Display for ReferenceArea {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let abs = |absolute: bool| if absolute { "$" } else { "" };
+        let row = |line: &LineReference| format!("{}{}", abs(line.absolute), line.index);
+        let col = |line: &LineReference| format!("{}{}", abs(line.absolute), to_col_name(line.index));
+        match self {
+            ReferenceArea::Cell(cell) => write!(f, "{cell}"),
+            ReferenceArea::Range(first, last) => write!(f, "{first}:{last}"),
+            ReferenceArea::Rows(first, last) => write!(f, "{}:{}", row(first), row(last)),
+            ReferenceArea::Columns(first, last) => write!(f, "{}:{}", col(first), col(last)),
+            ReferenceArea::Invalid => write!(f, "#REF!"),
+        }
+    }
+}
+
+impl fmt::Display for CellReference {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let abs = |absolute: bool| if absolute { "$" } else { "" };
+        write!(f, "{}{}{}{}", abs(self.col_absolute), to_col_name(self.col), abs(self.row_absolute), self.row)
+    }
+}
+
+impl fmt::Display for NameReference {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write_prefix(f, &self.workbook, &self.sheet, &None, self.quoted)?;
+        write!(f, "{}", self.name)
+    }
+}
+
+impl fmt::Display for StructuredReference {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}{}", self.table.as_deref().unwrap_or_default(), self.specifier)
+    }
+}
@@ -1,4 +1,6 @@
+use crate::api::cell::formula::token::ReferenceArea;
 use crate::utils::col_helper::{to_col, to_col_name, to_loc, to_ref};
+use crate::utils::reference_helper::{MAX_COL, MAX_ROW};
 
 pub(crate) trait Location {
     ///
@@ -23,10 +25,6 @@ pub(crate) trait LocationRange {
     ///
     fn to_range(&self) -> (u32, u32, u32, u32);
     ///
-    /// Convert the row coordinates to a form such as 1:6
-    ///
-    fn to_row_range_ref(&self) -> String;
-    ///
     /// Convert the row coordinates to a form such as (1,6)
     ///
     fn to_row_range(&self) -> (u32, u32);
@@ -39,15 +37,27 @@ pub(crate) trait LocationRange {
     ///
     fn to_col_range(&self) -> (u32, u32);
     ///
-    /// Get the start point of range
-    ///
-    fn start_ref(&self) -> String;
-    ///
     /// Get the end point of range
     ///
     fn end_ref(&self) -> String;
 }
 
+///
+/// A range like A1:B6, or whole columns like A:C or whole rows like 1:3 of the worksheet
+///
+fn str_to_range(range: &str) -> (u32, u32, u32, u32) {
+    match ReferenceArea::parse(range) {
+        Some(ReferenceArea::Columns(first, last)) => (1, first.index, MAX_ROW, last.index),
+        Some(ReferenceArea::Rows(first, last)) => (first.index, 1, last.index, MAX_COL),
+        _ => {
+            let locs = range.split_once(':').unwrap();
+            let start = locs.0.to_location();
+            let end = locs.1.to_location();
+            (start.0, start.1, end.0, end.1)
+        }
+    }
+}
+
 impl Location for &str {
     fn to_ref(&self) -> String {
         self.to_string()
@@ -110,17 +120,7 @@ impl LocationRange for &str {
     }
 
     fn to_range(&self) -> (u32, u32, u32, u32) {
-        let locs = self.split_once(':').unwrap();
-        let start = locs.0.to_location();
-        let end = locs.1.to_location();
-        (start.0, start.1, end.0, end.1)
-    }
-
-    fn to_row_range_ref(&self) -> String {
-        let locs = self.split_once(':').unwrap();
-        let start_row = locs.0.chars().filter(|&c| c >= '0' && c <= '9').collect::<String>();
-        let end_row = locs.1.chars().filter(|&c| c >= '0' && c <= '9').collect::<String>();
-        format!("{}:{}", start_row, end_row)
+        str_to_range(self)
     }
 
     fn to_row_range(&self) -> (u32, u32) {
@@ -144,11 +144,6 @@ impl LocationRange for &str {
         (to_col(&start_col), to_col(&end_col))
     }
 
-    fn start_ref(&self) -> String {
-        let locs = self.split_once(':').unwrap();
-        locs.0.to_string()
-    }
-
     fn end_ref(&self) -> String {
         let locs = self.split_once(':').unwrap();
         locs.1.to_string()
@@ -161,17 +156,7 @@ impl LocationRange for &String {
     }
 
     fn to_range(&self) -> (u32, u32, u32, u32) {
-        let locs = self.split_once(':').unwrap();
-        let start = locs.0.to_location();
-        let end = locs.1.to_location();
-        (start.0, start.1, end.0, end.1)
-    }
-
-    fn to_row_range_ref(&self) -> String {
-        let locs = self.split_once(':').unwrap();
-        let start_row = locs.0.chars().filter(|&c| c >= '0' && c <= '9').collect::<String>();
-        let end_row = locs.1.chars().filter(|&c| c >= '0' && c <= '9').collect::<String>();
-        format!("{}:{}", start_row, end_row)
+        str_to_range(self)
     }
 
     fn to_row_range(&self) -> (u32, u32) {
@@ -195,11 +180,6 @@ impl LocationRange for &String {
         (to_col(&start_col), to_col(&end_col))
     }
 
-    fn start_ref(&self) -> String {
-        let locs = self.split_once(':').unwrap();
-        locs.0.to_string()
-    }
-
     fn end_ref(&self) -> String {
         let locs = self.split_once(':').unwrap();
         locs.1.to_string()
@@ -215,10 +195,6 @@ impl LocationRange for (u32, u32, u32, u32) {
         *self
     }
 
-    fn to_row_range_ref(&self) -> String {
-        format!("{}:{}", self.0, self.2)
-    }
-
     fn to_row_range(&self) -> (u32, u32) {
         (self.0, self.2)
     }
@@ -231,10 +207,6 @@ impl LocationRange for (u32, u32, u32, u32) {
         (self.1, self.3)
     }
 
-    fn start_ref(&self) -> String {
-        format!("{}{}", to_col_name(self.1), self.0)
-    }
-
     fn end_ref(&self) -> String {
         format!("{}{}", to_col_name(self.3), self.2)
     }
@@ -0,0 +1,37 @@
+use crate::Format;
+
+///
+/// A text made of runs, each run may have its own font
+///
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RichText {
+    pub runs: Vec<RichTextRun>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RichTextRun {
+    pub text: String,
+    /// Only the font of the format is used, None means the font of the cell
+    pub format: Option<Format>,
+}
+
+impl RichText {
+    pub fn new() -> RichText {
+        RichText::default()
+    }
+
+    pub fn add_run(mut self, text: &str, format: Option<&Format>) -> RichText {
+        self.runs.push(RichTextRun {
+            text: text.to_string(),
+            format: format.cloned(),
+        });
+        self
+    }
+
+    ///
+    /// The text without formats
+    ///
+    pub fn text(&self) -> String {
+        self.runs.iter().map(|run| run.text.as_str()).collect()
+    }
+}
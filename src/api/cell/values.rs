@@ -1,6 +1,146 @@
 use std::fmt::{Display, Formatter};
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use serde::de::{Error, Visitor};
+use crate::api::cell::date_time::ExcelDateTime;
+use crate::api::cell::rich_text::RichText;
+
+///
+/// The typed value of a cell
+///
+#[derive(Debug, Clone, PartialEq, Default)]
+pub enum Value {
+    #[default]
+    Empty,
+    Number(f64),
+    String(String),
+    Bool(bool),
+    Error(ErrorKind),
+    DateTime(ExcelDateTime),
+    RichText(RichText),
+}
+
+///
+/// The error values of a cell, such as `#DIV/0!`
+///
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ErrorKind {
+    Null,
+    Div0,
+    Value,
+    Ref,
+    Name,
+    Num,
+    NA,
+    GettingData,
+    Spill,
+    Calc,
+    Other(String),
+}
+
+impl ErrorKind {
+    pub(crate) fn from_str(text: &str) -> ErrorKind {
+        match text {
+            "#NULL!" => ErrorKind::Null,
+            "#DIV/0!" => ErrorKind::Div0,
+            "#VALUE!" => ErrorKind::Value,
+            "#REF!" => ErrorKind::Ref,
+            "#NAME?" => ErrorKind::Name,
+            "#NUM!" => ErrorKind::Num,
+            "#N/A" => ErrorKind::NA,
+            "#GETTING_DATA" => ErrorKind::GettingData,
+            "#SPILL!" => ErrorKind::Spill,
+            "#CALC!" => ErrorKind::Calc,
+            _ => ErrorKind::Other(text.to_string()),
+        }
+    }
+
+    pub fn as_str(&self) -> &str {
+        match self {
+            ErrorKind::Null => "#NULL!",
+            ErrorKind::Div0 => "#DIV/0!",
+            ErrorKind::Value => "#VALUE!",
+            ErrorKind::Ref => "#REF!",
+            ErrorKind::Name => "#NAME?",
+            ErrorKind::Num => "#NUM!",
+            ErrorKind::NA => "#N/A",
+            ErrorKind::GettingData => "#GETTING_DATA",
+            ErrorKind::Spill => "#SPILL!",
+            ErrorKind::Calc => "#CALC!",
+            ErrorKind::Other(text) => text,
+        }
+    }
+}
+
+impl Display for ErrorKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl Value {
+    ///
+    /// Build a value from the `t` attribute and the `<v>` text of a cell, shared strings are not resolved
+    ///
+    pub(crate) fn from_cell_text(cell_type: Option<&CellType>, text: Option<&str>) -> Value {
+        let text = match text {
+            Some(text) => text,
+            None => return Value::Empty,
+        };
+        match cell_type {
+            Some(CellType::Boolean) => Value::Bool(text == "1" || text.eq_ignore_ascii_case("true")),
+            Some(CellType::Error) => Value::Error(ErrorKind::from_str(text)),
+            Some(CellType::Date) => match ExcelDateTime::parse_iso(text) {
+                Some(date_time) => Value::DateTime(date_time),
+                None => Value::String(text.to_string()),
+            },
+            Some(CellType::String) | Some(CellType::SharedString) => Value::String(text.to_string()),
+            Some(CellType::Number) | Some(CellType::Undefined) | None => match text.parse() {
+                Ok(number) => Value::Number(number),
+                Err(_) => Value::String(text.to_string()),
+            },
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        *self == Value::Empty
+    }
+
+    pub fn as_f64(&self) -> Option<f64> {
+        match self {
+            Value::Number(number) => Some(*number),
+            Value::DateTime(date_time) => Some(date_time.serial()),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Value::String(text) => Some(text),
+            _ => None,
+        }
+    }
+
+    pub fn as_bool(&self) -> Option<bool> {
+        match self {
+            Value::Bool(bool) => Some(*bool),
+            _ => None,
+        }
+    }
+}
+
+impl Display for Value {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Value::Empty => Ok(()),
+            Value::Number(number) => write!(f, "{number}"),
+            Value::String(text) => write!(f, "{text}"),
+            Value::Bool(bool) => write!(f, "{}", if *bool { "TRUE" } else { "FALSE" }),
+            Value::Error(error) => write!(f, "{error}"),
+            Value::DateTime(date_time) => write!(f, "{date_time}"),
+            Value::RichText(rich_text) => write!(f, "{}", rich_text.text()),
+        }
+    }
+}
 
 #[derive(Debug, Clone, PartialEq)]
 // #[serde(untagged)]
@@ -88,6 +228,11 @@ pub(crate) trait CellValue {
     fn to_cell_type(&self) -> CellType {
         CellType::String
     }
+
+    /// Dates are written as serial numbers with a date format
+    fn to_date_time(&self) -> Option<ExcelDateTime> {
+        None
+    }
 }
 
 impl<T: Display> CellDisplay for T {
@@ -162,4 +307,80 @@ impl CellValue for bool {
     fn to_cell_type(&self) -> CellType {
         CellType::Boolean
     }
-}
\ No newline at end of file
+}
+impl CellValue for ExcelDateTime {
+    fn to_cell_type(&self) -> CellType {
+        CellType::Number
+    }
+
+    fn to_date_time(&self) -> Option<ExcelDateTime> {
+        Some(*self)
+    }
+}
+
+// Dates the 1900 date system cannot hold are written as text
+#[cfg(feature = "chrono")]
+impl CellValue for chrono::NaiveDateTime {
+    fn to_cell_type(&self) -> CellType {
+        if self.to_date_time().is_some() { CellType::Number } else { CellType::String }
+    }
+
+    fn to_date_time(&self) -> Option<ExcelDateTime> {
+        ExcelDateTime::from_naive_date_time(self)
+    }
+}
+#[cfg(feature = "chrono")]
+impl CellValue for chrono::NaiveDate {
+    fn to_cell_type(&self) -> CellType {
+        if self.to_date_time().is_some() { CellType::Number } else { CellType::String }
+    }
+
+    fn to_date_time(&self) -> Option<ExcelDateTime> {
+        ExcelDateTime::from_naive_date_time(&self.and_time(chrono::NaiveTime::MIN))
+    }
+}
+#[cfg(feature = "chrono")]
+impl CellValue for chrono::NaiveTime {
+    fn to_cell_type(&self) -> CellType {
+        CellType::Number
+    }
+
+    fn to_date_time(&self) -> Option<ExcelDateTime> {
+        use chrono::Timelike;
+        let seconds = self.num_seconds_from_midnight() as f64 + self.nanosecond() as f64 / 1e9;
+        Some(ExcelDateTime::from_serial(seconds / 86400.0))
+    }
+}
+
+#[cfg(feature = "time")]
+impl CellValue for time::PrimitiveDateTime {
+    fn to_cell_type(&self) -> CellType {
+        if self.to_date_time().is_some() { CellType::Number } else { CellType::String }
+    }
+
+    fn to_date_time(&self) -> Option<ExcelDateTime> {
+        ExcelDateTime::from_primitive_date_time(self)
+    }
+}
+#[cfg(feature = "time")]
+impl CellValue for time::Date {
+    fn to_cell_type(&self) -> CellType {
+        if self.to_date_time().is_some() { CellType::Number } else { CellType::String }
+    }
+
+    fn to_date_time(&self) -> Option<ExcelDateTime> {
+        ExcelDateTime::from_primitive_date_time(&self.midnight())
+    }
+}
+#[cfg(feature = "time")]
+impl CellValue for time::Time {
+    fn to_cell_type(&self) -> CellType {
+        CellType::Number
+    }
+
+    fn to_date_time(&self) -> Option<ExcelDateTime> {
+        let (hour, minute, second, nanosecond) = self.as_hms_nano();
+        let seconds = hour as f64 * 3600.0 + minute as f64 * 60.0 + second as f64 + nanosecond as f64 / 1e9;
+        Some(ExcelDateTime::from_serial(seconds / 86400.0))
+    }
+}
@@ -0,0 +1,263 @@
+use crate::FormatColor;
+
+///
+/// The kind of a chart
+///
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ChartType {
+    /// Vertical bars
+    Column,
+    /// Horizontal bars
+    Bar,
+    Line,
+    Pie,
+    /// The series plot their values against numeric x values instead of categories
+    Scatter,
+    Area,
+}
+
+impl ChartType {
+    pub(crate) fn has_axes(self) -> bool {
+        self != ChartType::Pie
+    }
+}
+
+///
+/// How the series of column, bar, line and area charts are drawn together
+///
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ChartGrouping {
+    /// The series side by side, or overlapping for lines and areas
+    Standard,
+    /// The series on top of each other
+    Stacked,
+    /// The series on top of each other, scaled to 100%
+    PercentStacked,
+}
+
+impl ChartGrouping {
+    pub(crate) fn to_name(self, chart_type: ChartType) -> &'static str {
+        match (self, chart_type) {
+            (ChartGrouping::Standard, ChartType::Column | ChartType::Bar) => "clustered",
+            (ChartGrouping::Standard, _) => "standard",
+            (ChartGrouping::Stacked, _) => "stacked",
+            (ChartGrouping::PercentStacked, _) => "percentStacked",
+        }
+    }
+
+    pub(crate) fn from_name(name: &str) -> ChartGrouping {
+        match name {
+            "stacked" => ChartGrouping::Stacked,
+            "percentStacked" => ChartGrouping::PercentStacked,
+            _ => ChartGrouping::Standard,
+        }
+    }
+}
+
+///
+/// Where the legend of a chart is drawn
+///
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ChartLegendPosition {
+    Right,
+    Left,
+    Top,
+    Bottom,
+    TopRight,
+    /// No legend
+    None,
+}
+
+impl ChartLegendPosition {
+    pub(crate) fn to_name(self) -> Option<&'static str> {
+        match self {
+            ChartLegendPosition::Right => Some("r"),
+            ChartLegendPosition::Left => Some("l"),
+            ChartLegendPosition::Top => Some("t"),
+            ChartLegendPosition::Bottom => Some("b"),
+            ChartLegendPosition::TopRight => Some("tr"),
+            ChartLegendPosition::None => None,
+        }
+    }
+
+    pub(crate) fn from_name(name: &str) -> ChartLegendPosition {
+        match name {
+            "l" => ChartLegendPosition::Left,
+            "t" => ChartLegendPosition::Top,
+            "b" => ChartLegendPosition::Bottom,
+            "tr" => ChartLegendPosition::TopRight,
+            _ => ChartLegendPosition::Right,
+        }
+    }
+}
+
+///
+/// A series of a chart, referring to the cells of a worksheet like `Sheet1!$B$2:$B$7`.
+/// The name is a text, or a reference to a cell when it starts with `=`.
+///
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChartSeries {
+    pub name: Option<String>,
+    /// The categories, or the x values of a scatter chart
+    pub categories: Option<String>,
+    pub values: String,
+    /// The color of the series, only RGB and theme colors are supported
+    pub color: Option<FormatColor>,
+}
+
+impl ChartSeries {
+    pub fn new(values: &str) -> ChartSeries {
+        ChartSeries {
+            name: None,
+            categories: None,
+            values: values.trim_start_matches('=').to_string(),
+            color: None,
+        }
+    }
+
+    pub fn set_name(mut self, name: &str) -> Self {
+        self.name = Some(name.to_string());
+        self
+    }
+
+    pub fn set_categories(mut self, categories: &str) -> Self {
+        self.categories = Some(categories.trim_start_matches('=').to_string());
+        self
+    }
+
+    pub fn set_color(mut self, color: FormatColor) -> Self {
+        self.color = Some(color);
+        self
+    }
+}
+
+///
+/// The options of an axis of a chart
+///
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct ChartAxis {
+    pub title: Option<String>,
+    pub min: Option<f64>,
+    pub max: Option<f64>,
+    /// The interval between the major tick marks
+    pub major_unit: Option<f64>,
+    /// The number format of the labels, the one of the cells if None
+    pub num_format: Option<String>,
+    pub major_gridlines: bool,
+    /// Draw the values or the categories in reverse order
+    pub reverse: bool,
+    pub hidden: bool,
+}
+
+impl ChartAxis {
+    pub fn set_title(mut self, title: &str) -> Self {
+        self.title = Some(title.to_string());
+        self
+    }
+
+    pub fn set_min(mut self, min: f64) -> Self {
+        self.min = Some(min);
+        self
+    }
+
+    pub fn set_max(mut self, max: f64) -> Self {
+        self.max = Some(max);
+        self
+    }
+
+    pub fn set_major_unit(mut self, major_unit: f64) -> Self {
+        self.major_unit = Some(major_unit);
+        self
+    }
+
+    pub fn set_num_format(mut self, num_format: &str) -> Self {
+        self.num_format = Some(num_format.to_string());
+        self
+    }
+
+    pub fn set_major_gridlines(mut self, major_gridlines: bool) -> Self {
+        self.major_gridlines = major_gridlines;
+        self
+    }
+
+    pub fn set_reverse(mut self, reverse: bool) -> Self {
+        self.reverse = reverse;
+        self
+    }
+
+    pub fn set_hidden(mut self, hidden: bool) -> Self {
+        self.hidden = hidden;
+        self
+    }
+}
+
+///
+/// A chart drawn from the series of a worksheet.
+/// The x axis is the axis of the categories, which is vertical in bar charts.
+///
+#[derive(Debug, Clone, PartialEq)]
+pub struct Chart {
+    pub chart_type: ChartType,
+    pub grouping: ChartGrouping,
+    /// The title of the chart, a text or a reference to a cell when it starts with `=`
+    pub title: Option<String>,
+    pub series: Vec<ChartSeries>,
+    pub x_axis: ChartAxis,
+    pub y_axis: ChartAxis,
+    pub legend_position: ChartLegendPosition,
+    /// One of the 48 styles of Excel
+    pub style: u8,
+}
+
+impl Chart {
+    ///
+    /// A chart without series, with the gridlines of the y axis
+    ///
+    pub fn new(chart_type: ChartType) -> Chart {
+        Chart {
+            chart_type,
+            grouping: ChartGrouping::Standard,
+            title: None,
+            series: vec![],
+            x_axis: ChartAxis::default(),
+            y_axis: ChartAxis::default().set_major_gridlines(chart_type.has_axes()),
+            legend_position: ChartLegendPosition::Right,
+            style: 2,
+        }
+    }
+
+    pub fn add_series(mut self, series: ChartSeries) -> Self {
+        self.series.push(series);
+        self
+    }
+
+    pub fn set_grouping(mut self, grouping: ChartGrouping) -> Self {
+        self.grouping = grouping;
+        self
+    }
+
+    pub fn set_title(mut self, title: &str) -> Self {
+        self.title = Some(title.to_string());
+        self
+    }
+
+    pub fn set_x_axis(mut self, x_axis: ChartAxis) -> Self {
+        self.x_axis = x_axis;
+        self
+    }
+
+    pub fn set_y_axis(mut self, y_axis: ChartAxis) -> Self {
+        self.y_axis = y_axis;
+        self
+    }
+
+    pub fn set_legend_position(mut self, legend_position: ChartLegendPosition) -> Self {
+        self.legend_position = legend_position;
+        self
+    }
+
+    pub fn set_style(mut self, style: u8) -> Self {
+        self.style = style;
+        self
+    }
+}
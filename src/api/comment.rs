@@ -0,0 +1,54 @@
+///
+/// A comment of a cell, also known as a note.
+/// The comment is shown in a box next to the cell when the cell is hovered, or always when it is visible.
+///
+#[derive(Debug, Clone, PartialEq)]
+pub struct Comment {
+    pub text: String,
+    pub author: String,
+    pub visible: bool,
+    /// The width of the box in points
+    pub width: f64,
+    /// The height of the box in points
+    pub height: f64,
+}
+
+impl Comment {
+    pub fn new(text: &str) -> Comment {
+        Comment {
+            text: text.to_string(),
+            ..Default::default()
+        }
+    }
+
+    pub fn set_author(mut self, author: &str) -> Self {
+        self.author = author.to_string();
+        self
+    }
+
+    ///
+    /// Show the comment without hovering the cell
+    ///
+    pub fn set_visible(mut self) -> Self {
+        self.visible = true;
+        self
+    }
+
+    pub fn set_size(mut self, width: f64, height: f64) -> Self {
+        self.width = width;
+        self.height = height;
+        self
+    }
+}
+
+impl Default for Comment {
+    fn default() -> Self {
+        Comment {
+            text: String::new(),
+            author: String::new(),
+            visible: false,
+            width: 96.0,
+            height: 55.5,
+        }
+    }
+}
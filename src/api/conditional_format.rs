@@ -0,0 +1,523 @@
+use crate::{Format, FormatColor};
+
+///
+/// How the value of a cell is compared in a [`ConditionalFormatRule::CellValue`] rule
+///
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ConditionalFormatOperator {
+    Between,
+    NotBetween,
+    Equal,
+    NotEqual,
+    GreaterThan,
+    LessThan,
+    GreaterThanOrEqual,
+    LessThanOrEqual,
+}
+
+impl ConditionalFormatOperator {
+    pub(crate) fn to_name(self) -> &'static str {
+        match self {
+            ConditionalFormatOperator::Between => "between",
+            ConditionalFormatOperator::NotBetween => "notBetween",
+            ConditionalFormatOperator::Equal => "equal",
+            ConditionalFormatOperator::NotEqual => "notEqual",
+            ConditionalFormatOperator::GreaterThan => "greaterThan",
+            ConditionalFormatOperator::LessThan => "lessThan",
+            ConditionalFormatOperator::GreaterThanOrEqual => "greaterThanOrEqual",
+            ConditionalFormatOperator::LessThanOrEqual => "lessThanOrEqual",
+        }
+    }
+
+    pub(crate) fn from_name(name: &str) -> Option<ConditionalFormatOperator> {
+        let operator = match name {
+            "between" => ConditionalFormatOperator::Between,
+            "notBetween" => ConditionalFormatOperator::NotBetween,
+            "equal" => ConditionalFormatOperator::Equal,
+            "notEqual" => ConditionalFormatOperator::NotEqual,
+            "greaterThan" => ConditionalFormatOperator::GreaterThan,
+            "lessThan" => ConditionalFormatOperator::LessThan,
+            "greaterThanOrEqual" => ConditionalFormatOperator::GreaterThanOrEqual,
+            "lessThanOrEqual" => ConditionalFormatOperator::LessThanOrEqual,
+            _ => return None,
+        };
+        Some(operator)
+    }
+}
+
+///
+/// The dates matched by a [`ConditionalFormatRule::TimePeriod`] rule, relative to today
+///
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ConditionalFormatTimePeriod {
+    Yesterday,
+    Today,
+    Tomorrow,
+    Last7Days,
+    LastWeek,
+    ThisWeek,
+    NextWeek,
+    LastMonth,
+    ThisMonth,
+    NextMonth,
+}
+
+impl ConditionalFormatTimePeriod {
+    pub(crate) fn to_name(self) -> &'static str {
+        match self {
+            ConditionalFormatTimePeriod::Yesterday => "yesterday",
+            ConditionalFormatTimePeriod::Today => "today",
+            ConditionalFormatTimePeriod::Tomorrow => "tomorrow",
+            ConditionalFormatTimePeriod::Last7Days => "last7Days",
+            ConditionalFormatTimePeriod::LastWeek => "lastWeek",
+            ConditionalFormatTimePeriod::ThisWeek => "thisWeek",
+            ConditionalFormatTimePeriod::NextWeek => "nextWeek",
+            ConditionalFormatTimePeriod::LastMonth => "lastMonth",
+            ConditionalFormatTimePeriod::ThisMonth => "thisMonth",
+            ConditionalFormatTimePeriod::NextMonth => "nextMonth",
+        }
+    }
+
+    pub(crate) fn from_name(name: &str) -> Option<ConditionalFormatTimePeriod> {
+        let time_period = match name {
+            "yesterday" => ConditionalFormatTimePeriod::Yesterday,
+            "today" => ConditionalFormatTimePeriod::Today,
+            "tomorrow" => ConditionalFormatTimePeriod::Tomorrow,
+            "last7Days" => ConditionalFormatTimePeriod::Last7Days,
+            "lastWeek" => ConditionalFormatTimePeriod::LastWeek,
+            "thisWeek" => ConditionalFormatTimePeriod::ThisWeek,
+            "nextWeek" => ConditionalFormatTimePeriod::NextWeek,
+            "lastMonth" => ConditionalFormatTimePeriod::LastMonth,
+            "thisMonth" => ConditionalFormatTimePeriod::ThisMonth,
+            "nextMonth" => ConditionalFormatTimePeriod::NextMonth,
+            _ => return None,
+        };
+        Some(time_period)
+    }
+
+    ///
+    /// The formula Excel evaluates for the period, `cell` is the top left cell of the range
+    ///
+    pub(crate) fn to_formula(self, cell: &str) -> String {
+        match self {
+            ConditionalFormatTimePeriod::Yesterday => format!("FLOOR({cell},1)=TODAY()-1"),
+            ConditionalFormatTimePeriod::Today => format!("FLOOR({cell},1)=TODAY()"),
+            ConditionalFormatTimePeriod::Tomorrow => format!("FLOOR({cell},1)=TODAY()+1"),
+            ConditionalFormatTimePeriod::Last7Days => format!("AND(TODAY()-FLOOR({cell},1)<=6,FLOOR({cell},1)<=TODAY())"),
+            ConditionalFormatTimePeriod::LastWeek => format!("AND(TODAY()-ROUNDDOWN({cell},0)>=(WEEKDAY(TODAY())),TODAY()-ROUNDDOWN({cell},0)<(WEEKDAY(TODAY())+7))"),
+            ConditionalFormatTimePeriod::ThisWeek => format!("AND(TODAY()-ROUNDDOWN({cell},0)<=WEEKDAY(TODAY())-1,ROUNDDOWN({cell},0)-TODAY()<=7-WEEKDAY(TODAY()))"),
+            ConditionalFormatTimePeriod::NextWeek => format!("AND(ROUNDDOWN({cell},0)-TODAY()>(7-WEEKDAY(TODAY())),ROUNDDOWN({cell},0)-TODAY()<(15-WEEKDAY(TODAY())))"),
+            ConditionalFormatTimePeriod::LastMonth => format!("AND(MONTH({cell})=MONTH(EDATE(TODAY(),0-1)),YEAR({cell})=YEAR(EDATE(TODAY(),0-1)))"),
+            ConditionalFormatTimePeriod::ThisMonth => format!("AND(MONTH({cell})=MONTH(TODAY()),YEAR({cell})=YEAR(TODAY()))"),
+            ConditionalFormatTimePeriod::NextMonth => format!("AND(MONTH({cell})=MONTH(EDATE(TODAY(),0+1)),YEAR({cell})=YEAR(EDATE(TODAY(),0+1)))"),
+        }
+    }
+}
+
+///
+/// The condition of a conditional format.
+/// The formulas are written without the leading `=`, and relative references are relative to the top left cell of the range.
+///
+#[derive(Debug, Clone, PartialEq)]
+pub enum ConditionalFormatRule {
+    /// Compare the value with a formula, or with two formulas for `Between` and `NotBetween`
+    CellValue(ConditionalFormatOperator, String, Option<String>),
+    TextContains(String),
+    TextNotContains(String),
+    TextBeginsWith(String),
+    TextEndsWith(String),
+    /// The n highest values
+    Top(u32),
+    /// The n lowest values
+    Bottom(u32),
+    /// The n percent highest values
+    TopPercent(u32),
+    /// The n percent lowest values
+    BottomPercent(u32),
+    AboveAverage,
+    BelowAverage,
+    Duplicate,
+    Unique,
+    Blanks,
+    NoBlanks,
+    Errors,
+    NoErrors,
+    TimePeriod(ConditionalFormatTimePeriod),
+    /// The cells for which the formula is true, like `$A1>10`
+    Formula(String),
+    /// Color the cells by their values, the format of the conditional format is not used
+    ColorScale(ColorScale),
+    /// Draw bars by the values, the format of the conditional format is not used
+    DataBar(DataBar),
+    /// Show icons by the values, the format of the conditional format is not used
+    IconSet(IconSet),
+}
+
+///
+/// A threshold of a color scale, a data bar or an icon set
+///
+#[derive(Debug, Clone, PartialEq)]
+pub enum ConditionalFormatValue {
+    /// The lowest value of the range
+    Min,
+    /// The highest value of the range
+    Max,
+    /// The lowest value, or zero when all the values are positive. Only used by data bars.
+    AutoMin,
+    /// The highest value, or zero when all the values are negative. Only used by data bars.
+    AutoMax,
+    Number(f64),
+    Percent(f64),
+    Percentile(f64),
+    /// The value of a formula, like `$B$1`
+    Formula(String),
+}
+
+impl ConditionalFormatValue {
+    ///
+    /// The type and the value of the threshold
+    ///
+    pub(crate) fn to_cfvo(&self) -> (&'static str, Option<String>) {
+        match self {
+            ConditionalFormatValue::Min => ("min", None),
+            ConditionalFormatValue::Max => ("max", None),
+            ConditionalFormatValue::AutoMin => ("autoMin", None),
+            ConditionalFormatValue::AutoMax => ("autoMax", None),
+            ConditionalFormatValue::Number(value) => ("num", Some(value.to_string())),
+            ConditionalFormatValue::Percent(value) => ("percent", Some(value.to_string())),
+            ConditionalFormatValue::Percentile(value) => ("percentile", Some(value.to_string())),
+            ConditionalFormatValue::Formula(formula) => ("formula", Some(formula.trim_start_matches('=').to_string())),
+        }
+    }
+
+    pub(crate) fn from_cfvo(tp: &str, value: Option<&str>) -> ConditionalFormatValue {
+        let number = || value.and_then(|value| value.parse().ok()).unwrap_or_default();
+        match tp {
+            "min" => ConditionalFormatValue::Min,
+            "max" => ConditionalFormatValue::Max,
+            "autoMin" => ConditionalFormatValue::AutoMin,
+            "autoMax" => ConditionalFormatValue::AutoMax,
+            "percent" => ConditionalFormatValue::Percent(number()),
+            "percentile" => ConditionalFormatValue::Percentile(number()),
+            "formula" => ConditionalFormatValue::Formula(value.unwrap_or_default().to_string()),
+            _ => ConditionalFormatValue::Number(number()),
+        }
+    }
+}
+
+///
+/// A 2-color or 3-color scale
+///
+#[derive(Debug, Clone, PartialEq)]
+pub struct ColorScale {
+    pub min: (ConditionalFormatValue, FormatColor),
+    pub mid: Option<(ConditionalFormatValue, FormatColor)>,
+    pub max: (ConditionalFormatValue, FormatColor),
+}
+
+impl ColorScale {
+    ///
+    /// A scale from the color of the lowest value to the color of the highest one
+    ///
+    pub fn two_color(min_color: FormatColor, max_color: FormatColor) -> ColorScale {
+        ColorScale {
+            min: (ConditionalFormatValue::Min, min_color),
+            mid: None,
+            max: (ConditionalFormatValue::Max, max_color),
+        }
+    }
+
+    ///
+    /// A scale with a color in the middle, at the 50th percentile
+    ///
+    pub fn three_color(min_color: FormatColor, mid_color: FormatColor, max_color: FormatColor) -> ColorScale {
+        ColorScale {
+            min: (ConditionalFormatValue::Min, min_color),
+            mid: Some((ConditionalFormatValue::Percentile(50.0), mid_color)),
+            max: (ConditionalFormatValue::Max, max_color),
+        }
+    }
+
+    pub fn set_min(mut self, value: ConditionalFormatValue, color: FormatColor) -> Self {
+        self.min = (value, color);
+        self
+    }
+
+    pub fn set_mid(mut self, value: ConditionalFormatValue, color: FormatColor) -> Self {
+        self.mid = Some((value, color));
+        self
+    }
+
+    pub fn set_max(mut self, value: ConditionalFormatValue, color: FormatColor) -> Self {
+        self.max = (value, color);
+        self
+    }
+}
+
+///
+/// Where the axis of a data bar with negative values is drawn
+///
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DataBarAxisPosition {
+    /// At a position depending on the negative values
+    Automatic,
+    /// At the middle of the cells
+    Middle,
+    /// No axis, the negative bars are drawn like the positive ones
+    None,
+}
+
+impl DataBarAxisPosition {
+    pub(crate) fn to_name(self) -> &'static str {
+        match self {
+            DataBarAxisPosition::Automatic => "automatic",
+            DataBarAxisPosition::Middle => "middle",
+            DataBarAxisPosition::None => "none",
+        }
+    }
+
+    pub(crate) fn from_name(name: &str) -> DataBarAxisPosition {
+        match name {
+            "middle" => DataBarAxisPosition::Middle,
+            "none" => DataBarAxisPosition::None,
+            _ => DataBarAxisPosition::Automatic,
+        }
+    }
+}
+
+///
+/// A data bar, filled with a gradient unless a solid fill is set
+///
+#[derive(Debug, Clone, PartialEq)]
+pub struct DataBar {
+    pub min: ConditionalFormatValue,
+    pub max: ConditionalFormatValue,
+    pub color: FormatColor,
+    pub solid_fill: bool,
+    /// The color of the border, no border if None
+    pub border_color: Option<FormatColor>,
+    /// The color of the negative bars, the color of the positive ones if None
+    pub negative_color: Option<FormatColor>,
+    /// The border color of the negative bars, the border color of the positive ones if None
+    pub negative_border_color: Option<FormatColor>,
+    pub axis_position: DataBarAxisPosition,
+    pub axis_color: FormatColor,
+    /// Hide the values of the cells
+    pub bar_only: bool,
+}
+
+impl DataBar {
+    pub fn new(color: FormatColor) -> DataBar {
+        DataBar {
+            min: ConditionalFormatValue::AutoMin,
+            max: ConditionalFormatValue::AutoMax,
+            color,
+            solid_fill: false,
+            border_color: None,
+            negative_color: Some(FormatColor::RGB(255, 0, 0)),
+            negative_border_color: None,
+            axis_position: DataBarAxisPosition::Automatic,
+            axis_color: FormatColor::RGB(0, 0, 0),
+            bar_only: false,
+        }
+    }
+
+    ///
+    /// The values of the shortest and the longest bars
+    ///
+    pub fn set_range(mut self, min: ConditionalFormatValue, max: ConditionalFormatValue) -> Self {
+        self.min = min;
+        self.max = max;
+        self
+    }
+
+    pub fn set_solid_fill(mut self, solid_fill: bool) -> Self {
+        self.solid_fill = solid_fill;
+        self
+    }
+
+    pub fn set_border_color(mut self, border_color: FormatColor) -> Self {
+        self.border_color = Some(border_color);
+        self
+    }
+
+    pub fn set_negative_color(mut self, negative_color: FormatColor) -> Self {
+        self.negative_color = Some(negative_color);
+        self
+    }
+
+    pub fn set_negative_border_color(mut self, negative_border_color: FormatColor) -> Self {
+        self.negative_border_color = Some(negative_border_color);
+        self
+    }
+
+    pub fn set_axis(mut self, axis_position: DataBarAxisPosition, axis_color: FormatColor) -> Self {
+        self.axis_position = axis_position;
+        self.axis_color = axis_color;
+        self
+    }
+
+    pub fn set_bar_only(mut self, bar_only: bool) -> Self {
+        self.bar_only = bar_only;
+        self
+    }
+}
+
+///
+/// The icons of an icon set.
+/// [`Stars3`](IconSetType::Stars3), [`Triangles3`](IconSetType::Triangles3) and [`Boxes5`](IconSetType::Boxes5) need Excel 2010 or later.
+///
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum IconSetType {
+    Arrows3,
+    ArrowsGray3,
+    Flags3,
+    TrafficLights3,
+    TrafficLightsRimmed3,
+    Signs3,
+    Symbols3,
+    SymbolsUncircled3,
+    Stars3,
+    Triangles3,
+    Arrows4,
+    ArrowsGray4,
+    RedToBlack4,
+    Rating4,
+    TrafficLights4,
+    Arrows5,
+    ArrowsGray5,
+    Rating5,
+    Quarters5,
+    Boxes5,
+}
+
+const ICON_SET_NAMES: [(IconSetType, &str); 20] = [
+    (IconSetType::Arrows3, "3Arrows"),
+    (IconSetType::ArrowsGray3, "3ArrowsGray"),
+    (IconSetType::Flags3, "3Flags"),
+    (IconSetType::TrafficLights3, "3TrafficLights1"),
+    (IconSetType::TrafficLightsRimmed3, "3TrafficLights2"),
+    (IconSetType::Signs3, "3Signs"),
+    (IconSetType::Symbols3, "3Symbols"),
+    (IconSetType::SymbolsUncircled3, "3Symbols2"),
+    (IconSetType::Stars3, "3Stars"),
+    (IconSetType::Triangles3, "3Triangles"),
+    (IconSetType::Arrows4, "4Arrows"),
+    (IconSetType::ArrowsGray4, "4ArrowsGray"),
+    (IconSetType::RedToBlack4, "4RedToBlack"),
+    (IconSetType::Rating4, "4Rating"),
+    (IconSetType::TrafficLights4, "4TrafficLights"),
+    (IconSetType::Arrows5, "5Arrows"),
+    (IconSetType::ArrowsGray5, "5ArrowsGray"),
+    (IconSetType::Rating5, "5Rating"),
+    (IconSetType::Quarters5, "5Quarters"),
+    (IconSetType::Boxes5, "5Boxes"),
+];
+
+impl IconSetType {
+    pub(crate) fn to_name(self) -> &'static str {
+        ICON_SET_NAMES.iter().find(|(icon_set_type, _)| *icon_set_type == self).map(|(_, name)| *name).unwrap_or("3TrafficLights1")
+    }
+
+    pub(crate) fn from_name(name: &str) -> IconSetType {
+        ICON_SET_NAMES.iter().find(|(_, n)| *n == name).map(|(icon_set_type, _)| *icon_set_type).unwrap_or(IconSetType::TrafficLights3)
+    }
+
+    ///
+    /// The number of icons
+    ///
+    pub(crate) fn icon_count(self) -> usize {
+        self.to_name()[..1].parse().unwrap_or(3)
+    }
+
+    ///
+    /// Icon sets added in Excel 2010, written in the extension list of the worksheet
+    ///
+    pub(crate) fn is_x14(self) -> bool {
+        matches!(self, IconSetType::Stars3 | IconSetType::Triangles3 | IconSetType::Boxes5)
+    }
+}
+
+///
+/// An icon set, the thresholds start the icons after the first one.
+/// The icons are shown when the values are greater than or equal to the thresholds.
+///
+#[derive(Debug, Clone, PartialEq)]
+pub struct IconSet {
+    pub icon_set_type: IconSetType,
+    pub thresholds: Vec<ConditionalFormatValue>,
+    pub reverse: bool,
+    pub icons_only: bool,
+}
+
+impl IconSet {
+    ///
+    /// An icon set with thresholds of equal percents, like 33% and 67% for 3 icons
+    ///
+    pub fn new(icon_set_type: IconSetType) -> IconSet {
+        let count = icon_set_type.icon_count();
+        let thresholds = (1..count)
+            .map(|i| ConditionalFormatValue::Percent((100.0 * i as f64 / count as f64).round()))
+            .collect();
+        IconSet {
+            icon_set_type,
+            thresholds,
+            reverse: false,
+            icons_only: false,
+        }
+    }
+
+    ///
+    /// The thresholds of the icons after the first one, one less than the number of icons
+    ///
+    pub fn set_thresholds(mut self, thresholds: &[ConditionalFormatValue]) -> Self {
+        self.thresholds = thresholds.to_vec();
+        self
+    }
+
+    pub fn set_reverse(mut self, reverse: bool) -> Self {
+        self.reverse = reverse;
+        self
+    }
+
+    pub fn set_icons_only(mut self, icons_only: bool) -> Self {
+        self.icons_only = icons_only;
+        self
+    }
+}
+
+///
+/// A conditional format of a range, formatting the cells matching a rule
+///
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConditionalFormat {
+    pub rule: ConditionalFormatRule,
+    pub format: Option<Format>,
+    pub stop_if_true: bool,
+}
+
+impl ConditionalFormat {
+    pub fn new(rule: ConditionalFormatRule) -> ConditionalFormat {
+        ConditionalFormat {
+            rule,
+            format: None,
+            stop_if_true: false,
+        }
+    }
+
+    ///
+    /// The format of the matching cells, only the fonts, fills, borders and number formats are applied
+    ///
+    pub fn set_format(mut self, format: &Format) -> Self {
+        self.format = Some(format.clone());
+        self
+    }
+
+    ///
+    /// Don't evaluate the rules of lower priority when this one matches
+    ///
+    pub fn set_stop_if_true(mut self, stop_if_true: bool) -> Self {
+        self.stop_if_true = stop_if_true;
+        self
+    }
+}
@@ -0,0 +1,253 @@
+///
+/// The kind of values allowed by a data validation
+///
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DataValidationType {
+    Any,
+    Whole,
+    Decimal,
+    List,
+    Date,
+    Time,
+    TextLength,
+    Custom,
+}
+
+impl DataValidationType {
+    pub(crate) fn to_name(self) -> &'static str {
+        match self {
+            DataValidationType::Any => "none",
+            DataValidationType::Whole => "whole",
+            DataValidationType::Decimal => "decimal",
+            DataValidationType::List => "list",
+            DataValidationType::Date => "date",
+            DataValidationType::Time => "time",
+            DataValidationType::TextLength => "textLength",
+            DataValidationType::Custom => "custom",
+        }
+    }
+
+    pub(crate) fn from_name(name: &str) -> DataValidationType {
+        match name {
+            "whole" => DataValidationType::Whole,
+            "decimal" => DataValidationType::Decimal,
+            "list" => DataValidationType::List,
+            "date" => DataValidationType::Date,
+            "time" => DataValidationType::Time,
+            "textLength" => DataValidationType::TextLength,
+            "custom" => DataValidationType::Custom,
+            _ => DataValidationType::Any,
+        }
+    }
+}
+
+///
+/// How the value is compared with the formulas of a data validation.
+/// [`Between`](DataValidationOperator::Between) and [`NotBetween`](DataValidationOperator::NotBetween) use both formulas.
+///
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DataValidationOperator {
+    Between,
+    NotBetween,
+    Equal,
+    NotEqual,
+    GreaterThan,
+    LessThan,
+    GreaterThanOrEqual,
+    LessThanOrEqual,
+}
+
+impl DataValidationOperator {
+    pub(crate) fn to_name(self) -> &'static str {
+        match self {
+            DataValidationOperator::Between => "between",
+            DataValidationOperator::NotBetween => "notBetween",
+            DataValidationOperator::Equal => "equal",
+            DataValidationOperator::NotEqual => "notEqual",
+            DataValidationOperator::GreaterThan => "greaterThan",
+            DataValidationOperator::LessThan => "lessThan",
+            DataValidationOperator::GreaterThanOrEqual => "greaterThanOrEqual",
+            DataValidationOperator::LessThanOrEqual => "lessThanOrEqual",
+        }
+    }
+
+    pub(crate) fn from_name(name: &str) -> DataValidationOperator {
+        match name {
+            "notBetween" => DataValidationOperator::NotBetween,
+            "equal" => DataValidationOperator::Equal,
+            "notEqual" => DataValidationOperator::NotEqual,
+            "greaterThan" => DataValidationOperator::GreaterThan,
+            "lessThan" => DataValidationOperator::LessThan,
+            "greaterThanOrEqual" => DataValidationOperator::GreaterThanOrEqual,
+            "lessThanOrEqual" => DataValidationOperator::LessThanOrEqual,
+            _ => DataValidationOperator::Between,
+        }
+    }
+}
+
+///
+/// The style of the alert shown when an invalid value is entered.
+/// Only [`Stop`](DataValidationErrorStyle::Stop) rejects the value.
+///
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DataValidationErrorStyle {
+    Stop,
+    Warning,
+    Information,
+}
+
+impl DataValidationErrorStyle {
+    pub(crate) fn to_name(self) -> &'static str {
+        match self {
+            DataValidationErrorStyle::Stop => "stop",
+            DataValidationErrorStyle::Warning => "warning",
+            DataValidationErrorStyle::Information => "information",
+        }
+    }
+
+    pub(crate) fn from_name(name: &str) -> DataValidationErrorStyle {
+        match name {
+            "warning" => DataValidationErrorStyle::Warning,
+            "information" => DataValidationErrorStyle::Information,
+            _ => DataValidationErrorStyle::Stop,
+        }
+    }
+}
+
+///
+/// A data validation restricting the values of cells.
+/// The formulas are written without the leading `=`, like `10`, `$A$1:$A$5` or `DATE(2024,1,1)`.
+///
+#[derive(Debug, Clone, PartialEq)]
+pub struct DataValidation {
+    pub validation_type: DataValidationType,
+    pub operator: DataValidationOperator,
+    pub formula1: Option<String>,
+    pub formula2: Option<String>,
+    pub ignore_blank: bool,
+    pub dropdown: bool,
+    pub show_input_message: bool,
+    pub input_title: Option<String>,
+    pub input_message: Option<String>,
+    pub show_error_message: bool,
+    pub error_style: DataValidationErrorStyle,
+    pub error_title: Option<String>,
+    pub error_message: Option<String>,
+}
+
+impl Default for DataValidation {
+    fn default() -> Self {
+        DataValidation {
+            validation_type: DataValidationType::Any,
+            operator: DataValidationOperator::Between,
+            formula1: None,
+            formula2: None,
+            ignore_blank: true,
+            dropdown: true,
+            show_input_message: true,
+            input_title: None,
+            input_message: None,
+            show_error_message: true,
+            error_style: DataValidationErrorStyle::Stop,
+            error_title: None,
+            error_message: None,
+        }
+    }
+}
+
+impl DataValidation {
+    pub fn new(validation_type: DataValidationType) -> DataValidation {
+        DataValidation {
+            validation_type,
+            ..Default::default()
+        }
+    }
+
+    ///
+    /// A dropdown of the given values
+    ///
+    pub fn list(values: &[&str]) -> DataValidation {
+        let values = values.join(",").replace('"', "\"\"");
+        DataValidation::new(DataValidationType::List).set_formula1(&format!("\"{values}\""))
+    }
+
+    ///
+    /// A dropdown of the values in a range, like `$A$1:$A$5` or `Sheet2!$A$1:$A$5`
+    ///
+    pub fn list_source(source: &str) -> DataValidation {
+        DataValidation::new(DataValidationType::List).set_formula1(source)
+    }
+
+    ///
+    /// Allow the values for which the formula is true, like `ISNUMBER(A1)`
+    ///
+    pub fn custom(formula: &str) -> DataValidation {
+        DataValidation::new(DataValidationType::Custom).set_formula1(formula)
+    }
+
+    ///
+    /// Compare the values with a formula, or with the two formulas for a range
+    ///
+    pub fn set_criteria(mut self, operator: DataValidationOperator, formula1: &str, formula2: Option<&str>) -> Self {
+        self.operator = operator;
+        self.formula1 = Some(formula1.trim_start_matches('=').to_string());
+        self.formula2 = formula2.map(|formula2| formula2.trim_start_matches('=').to_string());
+        self
+    }
+
+    pub fn set_formula1(mut self, formula1: &str) -> Self {
+        self.formula1 = Some(formula1.trim_start_matches('=').to_string());
+        self
+    }
+
+    ///
+    /// Accept the blank cells, default to true
+    ///
+    pub fn set_ignore_blank(mut self, ignore_blank: bool) -> Self {
+        self.ignore_blank = ignore_blank;
+        self
+    }
+
+    ///
+    /// Show the dropdown of a list, default to true
+    ///
+    pub fn set_dropdown(mut self, dropdown: bool) -> Self {
+        self.dropdown = dropdown;
+        self
+    }
+
+    ///
+    /// The prompt shown when a cell is selected
+    ///
+    pub fn set_input_message(mut self, title: &str, message: &str) -> Self {
+        self.input_title = Some(title.to_string());
+        self.input_message = Some(message.to_string());
+        self.show_input_message = true;
+        self
+    }
+
+    pub fn set_show_input_message(mut self, show_input_message: bool) -> Self {
+        self.show_input_message = show_input_message;
+        self
+    }
+
+    ///
+    /// The alert shown when an invalid value is entered
+    ///
+    pub fn set_error_message(mut self, title: &str, message: &str) -> Self {
+        self.error_title = Some(title.to_string());
+        self.error_message = Some(message.to_string());
+        self.show_error_message = true;
+        self
+    }
+
+    pub fn set_show_error_message(mut self, show_error_message: bool) -> Self {
+        self.show_error_message = show_error_message;
+        self
+    }
+
+    pub fn set_error_style(mut self, error_style: DataValidationErrorStyle) -> Self {
+        self.error_style = error_style;
+        self
+    }
+}
@@ -0,0 +1,278 @@
+use std::collections::{BTreeSet, HashMap};
+use crate::api::cell::formula::token::{FormulaToken, NameReference, ParsedFormula, Reference, ReferenceArea};
+use crate::api::cell::location::Location;
+use crate::utils::reference_helper::{is_same_sheet, MAX_COL, MAX_ROW};
+use crate::xml::workbook::defined_names::DefinedNames;
+use crate::Workbook;
+
+///
+/// A cell of a worksheet, the first row and column are 1
+///
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SheetCell {
+    pub sheet: String,
+    pub row: u32,
+    pub col: u32,
+}
+
+///
+/// A rectangle of cells of a worksheet referred to by a formula, a single cell has the same first and last row and column
+///
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CellArea {
+    pub sheet: String,
+    pub first_row: u32,
+    pub first_col: u32,
+    pub last_row: u32,
+    pub last_col: u32,
+}
+
+impl CellArea {
+    pub fn contains(&self, cell: &SheetCell) -> bool {
+        is_same_sheet(&self.sheet, &cell.sheet)
+            && (self.first_row..=self.last_row).contains(&cell.row)
+            && (self.first_col..=self.last_col).contains(&cell.col)
+    }
+}
+
+///
+/// The dependencies between the formula cells of a workbook and the cells they refer to,
+/// including the cells referred to through defined names.
+/// References to other workbooks and to tables are not followed.
+///
+#[derive(Debug, Clone, Default)]
+pub struct DependencyGraph {
+    sheets: Vec<String>,
+    cells: Vec<SheetCell>,
+    positions: HashMap<(usize, u32, u32), usize>,
+    precedents: Vec<Vec<CellArea>>,
+    // the formula cells among the precedents of each formula cell
+    edges: Vec<Vec<usize>>,
+}
+
+impl DependencyGraph {
+    pub(crate) fn new(workbook: &Workbook) -> DependencyGraph {
+        let sheets = workbook.sheets.iter().map(|sheet| sheet.name.clone()).collect::<Vec<_>>();
+        let xml_workbook = workbook.workbook.borrow();
+        let resolver = Resolver { sheets: &sheets, defined_names: &xml_workbook.defined_names };
+        let mut graph = DependencyGraph { sheets: sheets.clone(), ..Default::default() };
+        let mut formula_cells = vec![BTreeSet::new(); sheets.len()];
+        for (index, sheet) in workbook.sheets.iter().enumerate() {
+            let mut formulas = sheet.formulas().into_iter().collect::<Vec<_>>();
+            formulas.sort();
+            for ((row, col), formula) in formulas {
+                graph.positions.insert((index, row, col), graph.cells.len());
+                graph.cells.push(SheetCell { sheet: sheet.name.clone(), row, col });
+                graph.precedents.push(resolver.formula_areas(&ParsedFormula::parse(&formula), index, &mut vec![]));
+                formula_cells[index].insert((row, col));
+            }
+        }
+        let positions = &graph.positions;
+        let edges = graph.precedents.iter()
+            .map(|areas| areas.iter()
+                .filter_map(|area| Some((resolver.sheet_index(&area.sheet)?, area)))
+                .flat_map(|(sheet, area)| formula_cells[sheet]
+                    .range((area.first_row, area.first_col)..=(area.last_row, area.last_col))
+                    .filter(|(_, col)| (area.first_col..=area.last_col).contains(col))
+                    .map(move |&(row, col)| positions[&(sheet, row, col)]))
+                .collect())
+            .collect();
+        graph.edges = edges;
+        graph
+    }
+
+    fn sheet_index(&self, sheet: &str) -> Option<usize> {
+        self.sheets.iter().position(|name| is_same_sheet(name, sheet))
+    }
+
+    fn position<L: Location>(&self, sheet: &str, loc: &L) -> Option<usize> {
+        let (row, col) = loc.to_location();
+        self.positions.get(&(self.sheet_index(sheet)?, row, col)).copied()
+    }
+
+    ///
+    /// The cells with a formula, sheet by sheet and row by row
+    ///
+    pub fn formula_cells(&self) -> &[SheetCell] {
+        &self.cells
+    }
+
+    ///
+    /// The areas the formula of a cell refers to directly, empty if the cell has no formula
+    ///
+    pub fn precedents<L: Location>(&self, sheet: &str, loc: L) -> &[CellArea] {
+        match self.position(sheet, &loc) {
+            Some(position) => &self.precedents[position],
+            None => &[],
+        }
+    }
+
+    ///
+    /// The formula cells referring directly to a cell
+    ///
+    pub fn dependents<L: Location>(&self, sheet: &str, loc: L) -> Vec<SheetCell> {
+        let (row, col) = loc.to_location();
+        let cell = SheetCell { sheet: sheet.to_string(), row, col };
+        self.cells.iter()
+            .zip(&self.precedents)
+            .filter(|(_, areas)| areas.iter().any(|area| area.contains(&cell)))
+            .map(|(cell, _)| cell.clone())
+            .collect()
+    }
+
+    ///
+    /// The groups of formula cells depending on each other, including the formulas referring to their own cell
+    ///
+    pub fn cycles(&self) -> Vec<Vec<SheetCell>> {
+        self.strongly_connected().into_iter()
+            .filter(|component| component.len() > 1 || self.edges[component[0]].contains(&component[0]))
+            .map(|component| component.into_iter().map(|position| self.cells[position].clone()).collect())
+            .collect()
+    }
+
+    ///
+    /// Whether the formula of a cell depends on itself, directly or through other formulas
+    ///
+    pub fn is_circular<L: Location>(&self, sheet: &str, loc: L) -> bool {
+        let Some(position) = self.position(sheet, &loc) else { return false };
+        let mut visited = vec![false; self.cells.len()];
+        let mut stack = self.edges[position].clone();
+        while let Some(next) = stack.pop() {
+            if next == position {
+                return true;
+            }
+            if !visited[next] {
+                visited[next] = true;
+                stack.extend(&self.edges[next]);
+            }
+        }
+        false
+    }
+
+    ///
+    /// The strongly connected components of the formula cells, by Tarjan's algorithm without recursion
+    ///
+    fn strongly_connected(&self) -> Vec<Vec<usize>> {
+        let count = self.cells.len();
+        let (mut index, mut low_link, mut on_stack) = (vec![usize::MAX; count], vec![0; count], vec![false; count]);
+        let (mut stack, mut components, mut next_index) = (vec![], vec![], 0);
+        for root in 0..count {
+            if index[root] != usize::MAX {
+                continue;
+            }
+            let mut calls = vec![(root, 0)];
+            index[root] = next_index;
+            low_link[root] = next_index;
+            next_index += 1;
+            stack.push(root);
+            on_stack[root] = true;
+            while let Some((node, edge)) = calls.last_mut() {
+                let node = *node;
+                if let Some(&target) = self.edges[node].get(*edge) {
+                    *edge += 1;
+                    if index[target] == usize::MAX {
+                        index[target] = next_index;
+                        low_link[target] = next_index;
+                        next_index += 1;
+                        stack.push(target);
+                        on_stack[target] = true;
+                        calls.push((target, 0));
+                    } else if on_stack[target] {
+                        low_link[node] = low_link[node].min(index[target]);
+                    }
+                    continue;
+                }
+                calls.pop();
+                if let Some(&(parent, _)) = calls.last() {
+                    low_link[parent] = low_link[parent].min(low_link[node]);
+                }
+                if low_link[node] == index[node] {
+                    let mut component = vec![];
+                    while let Some(member) = stack.pop() {
+                        on_stack[member] = false;
+                        component.push(member);
+                        if member == node {
+                            break;
+                        }
+                    }
+                    component.sort();
+                    components.push(component);
+                }
+            }
+        }
+        components.sort();
+        components
+    }
+}
+
+///
+/// Resolve the references of formulas to areas of the worksheets
+///
+struct Resolver<'a> {
+    sheets: &'a [String],
+    defined_names: &'a DefinedNames,
+}
+
+impl Resolver<'_> {
+    fn sheet_index(&self, sheet: &str) -> Option<usize> {
+        self.sheets.iter().position(|name| is_same_sheet(name, sheet))
+    }
+
+    fn formula_areas(&self, formula: &ParsedFormula, sheet: usize, names: &mut Vec<(String, usize)>) -> Vec<CellArea> {
+        formula.tokens.iter()
+            .flat_map(|token| match token {
+                FormulaToken::Reference(reference) => self.reference_areas(reference, sheet),
+                FormulaToken::Name(name) => self.name_areas(name, sheet, names),
+                _ => vec![],
+            })
+            .collect()
+    }
+
+    fn reference_areas(&self, reference: &Reference, sheet: usize) -> Vec<CellArea> {
+        if reference.workbook.is_some() {
+            return vec![];
+        }
+        let first_sheet = match &reference.sheet {
+            Some(name) => self.sheet_index(name),
+            None => Some(sheet),
+        };
+        let last_sheet = match &reference.last_sheet {
+            Some(name) => self.sheet_index(name),
+            None => first_sheet,
+        };
+        let (Some(first_sheet), Some(last_sheet)) = (first_sheet, last_sheet) else { return vec![] };
+        let (first_row, first_col, last_row, last_col) = match reference.area {
+            ReferenceArea::Cell(cell) => (cell.row, cell.col, cell.row, cell.col),
+            ReferenceArea::Range(first, last) => (first.row.min(last.row), first.col.min(last.col), first.row.max(last.row), first.col.max(last.col)),
+            ReferenceArea::Rows(first, last) => (first.index.min(last.index), 1, first.index.max(last.index), MAX_COL),
+            ReferenceArea::Columns(first, last) => (1, first.index.min(last.index), MAX_ROW, first.index.max(last.index)),
+            ReferenceArea::Invalid => return vec![],
+        };
+        (first_sheet.min(last_sheet)..=first_sheet.max(last_sheet))
+            .map(|sheet| CellArea { sheet: self.sheets[sheet].clone(), first_row, first_col, last_row, last_col })
+            .collect()
+    }
+
+    fn name_areas(&self, name: &NameReference, sheet: usize, names: &mut Vec<(String, usize)>) -> Vec<CellArea> {
+        if name.workbook.is_some() {
+            return vec![];
+        }
+        let sheet = match &name.sheet {
+            Some(sheet) => match self.sheet_index(sheet) {
+                Some(sheet) => sheet,
+                None => return vec![],
+            },
+            None => sheet,
+        };
+        // a name may refer to itself through other names
+        let key = (name.name.to_ascii_uppercase(), sheet);
+        if names.contains(&key) {
+            return vec![];
+        }
+        names.push(key);
+        match self.defined_names.get_value(&name.name, sheet as u32) {
+            Some(value) => self.formula_areas(&ParsedFormula::parse(&value), sheet, names),
+            None => vec![],
+        }
+    }
+}
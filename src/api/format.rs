@@ -5,19 +5,25 @@ pub use crate::api::format::border::{FormatBorder, FormatBorderElement, FormatBo
 pub use color::FormatColor;
 pub use crate::api::format::fill::FormatFill;
 pub use font::FormatFont;
+pub use num_format::FormatNumber;
+pub use protection::FormatProtection;
 
 mod align;
 mod color;
 mod fill;
 mod font;
+mod num_format;
+mod protection;
 pub mod border;
 
-#[derive(Default, Clone, Debug)]
+#[derive(Default, Clone, Debug, PartialEq)]
 pub struct Format {
     pub font: FormatFont,
     pub border: FormatBorder,
     pub fill: FormatFill,
     pub align: FormatAlign,
+    pub num_format: FormatNumber,
+    pub protection: FormatProtection,
 }
 
 impl Format {
@@ -44,6 +50,28 @@ impl Format {
     pub fn get_borders(&self) -> &FormatBorder {
         &self.border
     }
+
+    ///
+    /// The number format code, such as `General` or `#,##0.00`
+    ///
+    pub fn get_num_format(&self) -> &str {
+        &self.num_format.format_code
+    }
+
+    ///
+    /// The index of the number format in the workbook, None for a custom format not yet added
+    ///
+    pub fn get_num_format_index(&self) -> Option<u32> {
+        self.num_format.num_fmt_id
+    }
+
+    pub fn is_locked(&self) -> bool {
+        self.protection.locked
+    }
+
+    pub fn is_hidden(&self) -> bool {
+        self.protection.hidden
+    }
 }
 
 impl Format {
@@ -146,4 +174,37 @@ impl Format {
         self.align.indent = Some(indent);
         self
     }
+
+    ///
+    /// Set the number format by its code, such as `#,##0.00`, `0%` or `yyyy-mm-dd`.
+    /// Codes of built-in formats use the built-in index.
+    ///
+    pub fn set_num_format(mut self, format_code: &str) -> Self {
+        self.num_format = FormatNumber::from_format_code(format_code);
+        self
+    }
+
+    ///
+    /// Set a built-in number format by its index, such as 4 for `#,##0.00` or 14 for dates
+    ///
+    pub fn set_num_format_index(mut self, index: u32) -> Self {
+        self.num_format = FormatNumber::from_num_fmt_id(index, None);
+        self
+    }
+
+    ///
+    /// Whether the cell can't be edited once its worksheet is protected, cells are locked by default
+    ///
+    pub fn set_locked(mut self, locked: bool) -> Self {
+        self.protection.locked = locked;
+        self
+    }
+
+    ///
+    /// Whether the formula of the cell is hidden once its worksheet is protected
+    ///
+    pub fn set_hidden(mut self, hidden: bool) -> Self {
+        self.protection.hidden = hidden;
+        self
+    }
 }
\ No newline at end of file
@@ -1,9 +1,10 @@
 use crate::FormatColor;
 use crate::xml::common::{Element, FromFormat};
 use crate::xml::style::color::Color;
+use crate::xml::shared_string::{RunProperties, ToggleElement};
 use crate::xml::style::font::{Bold, Font, Italic, Underline};
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq)]
 pub struct FormatFont {
     pub(crate) bold: bool,
     pub(crate) italic: bool,
@@ -49,3 +50,28 @@ impl FromFormat<FormatFont> for Font {
         format.color = self.color.as_ref().get_format();
     }
 }
+
+impl FromFormat<FormatFont> for RunProperties {
+    fn set_attrs_by_format(&mut self, format: &FormatFont) {
+        let color = Color::from_format(&format.color);
+        self.color = if color.is_empty() { None } else { Some(color) };
+        self.font = Some(Element::from_val(format.name.to_string()));
+        self.size = Some(Element::from_val(format.size));
+        self.bold = if format.bold { Some(ToggleElement::default()) } else { None };
+        self.underline = if format.underline { Some(ToggleElement::default()) } else { None };
+        self.italic = if format.italic { Some(ToggleElement::default()) } else { None };
+    }
+
+    fn set_format(&self, format: &mut FormatFont) {
+        format.bold = self.bold.as_ref().is_some_and(ToggleElement::is_on);
+        format.italic = self.italic.as_ref().is_some_and(ToggleElement::is_on);
+        format.underline = self.underline.as_ref().is_some_and(|u| u.val.as_deref() != Some("none"));
+        if let Some(size) = &self.size {
+            format.size = size.val;
+        }
+        if let Some(font) = &self.font {
+            format.name = font.val.to_string();
+        }
+        format.color = self.color.as_ref().get_format();
+    }
+}
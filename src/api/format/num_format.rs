@@ -0,0 +1,34 @@
+use crate::xml::style::num_fmt::{builtin_format_code, builtin_num_fmt_id};
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct FormatNumber {
+    // None for a custom format code, its id is given when the format is added to the workbook
+    pub(crate) num_fmt_id: Option<u32>,
+    pub(crate) format_code: String,
+}
+
+impl Default for FormatNumber {
+    fn default() -> Self {
+        FormatNumber {
+            num_fmt_id: Some(0),
+            format_code: "General".to_string(),
+        }
+    }
+}
+
+impl FormatNumber {
+    pub(crate) fn from_format_code(format_code: &str) -> FormatNumber {
+        FormatNumber {
+            num_fmt_id: builtin_num_fmt_id(format_code),
+            format_code: format_code.to_string(),
+        }
+    }
+
+    pub(crate) fn from_num_fmt_id(num_fmt_id: u32, format_code: Option<&str>) -> FormatNumber {
+        let format_code = format_code.or(builtin_format_code(num_fmt_id)).unwrap_or("General");
+        FormatNumber {
+            num_fmt_id: Some(num_fmt_id),
+            format_code: format_code.to_string(),
+        }
+    }
+}
@@ -0,0 +1,32 @@
+use crate::xml::common::FromFormat;
+use crate::xml::style::protection::Protection;
+
+///
+/// Whether a cell can be edited and its formula seen, once its worksheet is protected
+///
+#[derive(Clone, Debug, PartialEq)]
+pub struct FormatProtection {
+    pub(crate) locked: bool,
+    pub(crate) hidden: bool,
+}
+
+impl Default for FormatProtection {
+    fn default() -> Self {
+        FormatProtection {
+            locked: true,
+            hidden: false,
+        }
+    }
+}
+
+impl FromFormat<FormatProtection> for Protection {
+    fn set_attrs_by_format(&mut self, format: &FormatProtection) {
+        self.locked = if format.locked { None } else { Some(false) };
+        self.hidden = if format.hidden { Some(true) } else { None };
+    }
+
+    fn set_format(&self, format: &mut FormatProtection) {
+        format.locked = self.locked != Some(false);
+        format.hidden = self.hidden == Some(true);
+    }
+}
@@ -0,0 +1,163 @@
+use std::path::{Path, PathBuf};
+use crate::FormatColor;
+
+///
+/// A header or a footer of the printed pages, built from its left, center and right sections.
+/// The text and the fields are added to the section selected last, the left one by default,
+/// like `HeaderFooter::new().center().text("Page ").page_number()` for `&CPage &P`.
+///
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct HeaderFooter {
+    pub(crate) sections: [String; 3],
+    pub(crate) images: [Vec<PathBuf>; 3],
+    codes: Option<String>,
+    section: usize,
+    // a digit written after a font size would be read as a part of the size
+    after_font_size: bool,
+}
+
+impl HeaderFooter {
+    pub fn new() -> HeaderFooter {
+        HeaderFooter::default()
+    }
+
+    ///
+    /// A header or a footer already written with the control codes of Excel, like `&CPage &P`
+    ///
+    pub fn from_codes(codes: &str) -> HeaderFooter {
+        HeaderFooter {
+            codes: Some(codes.to_string()),
+            ..Default::default()
+        }
+    }
+
+    pub fn left(mut self) -> Self {
+        self.section = 0;
+        self.after_font_size = false;
+        self
+    }
+
+    pub fn center(mut self) -> Self {
+        self.section = 1;
+        self.after_font_size = false;
+        self
+    }
+
+    pub fn right(mut self) -> Self {
+        self.section = 2;
+        self.after_font_size = false;
+        self
+    }
+
+    pub fn text(mut self, text: &str) -> Self {
+        if self.after_font_size && text.starts_with(|c: char| c.is_ascii_digit()) {
+            self.sections[self.section].push(' ');
+        }
+        self.push_code(&text.replace('&', "&&"))
+    }
+
+    pub fn page_number(self) -> Self {
+        self.push_code("&P")
+    }
+
+    ///
+    /// The number of pages
+    ///
+    pub fn page_count(self) -> Self {
+        self.push_code("&N")
+    }
+
+    pub fn date(self) -> Self {
+        self.push_code("&D")
+    }
+
+    pub fn time(self) -> Self {
+        self.push_code("&T")
+    }
+
+    pub fn file_name(self) -> Self {
+        self.push_code("&F")
+    }
+
+    pub fn file_path(self) -> Self {
+        self.push_code("&Z")
+    }
+
+    pub fn sheet_name(self) -> Self {
+        self.push_code("&A")
+    }
+
+    ///
+    /// The font of the following text, like `Arial` and `Bold Italic`
+    ///
+    pub fn font(self, name: &str, style: &str) -> Self {
+        self.push_code(&format!("&\"{name},{style}\""))
+    }
+
+    pub fn font_size(mut self, size: u32) -> Self {
+        self = self.push_code(&format!("&{size}"));
+        self.after_font_size = true;
+        self
+    }
+
+    ///
+    /// The color of the following text, given by its RGB value or by its theme color
+    ///
+    pub fn font_color(self, color: &FormatColor) -> Self {
+        match color {
+            FormatColor::RGB(r, g, b) => self.push_code(&format!("&K{r:02X}{g:02X}{b:02X}")),
+            FormatColor::Theme(theme, tint) => {
+                let sign = if *tint < 0.0 { '-' } else { '+' };
+                self.push_code(&format!("&K{theme:02}{sign}{:03}", (tint.abs() * 100.0).round() as u32))
+            }
+            FormatColor::Default | FormatColor::Index(_) => self,
+        }
+    }
+
+    ///
+    /// Turn the bold of the following text on or off
+    ///
+    pub fn bold(self) -> Self {
+        self.push_code("&B")
+    }
+
+    pub fn italic(self) -> Self {
+        self.push_code("&I")
+    }
+
+    pub fn underline(self) -> Self {
+        self.push_code("&U")
+    }
+
+    pub fn strikethrough(self) -> Self {
+        self.push_code("&S")
+    }
+
+    ///
+    /// Show an image in the section, at most one image by section
+    ///
+    pub fn image<P: AsRef<Path>>(mut self, filename: P) -> Self {
+        self.images[self.section].push(filename.as_ref().to_path_buf());
+        self.push_code("&G")
+    }
+
+    fn push_code(mut self, code: &str) -> Self {
+        self.sections[self.section].push_str(code);
+        self.after_font_size = false;
+        self
+    }
+
+    ///
+    /// The header or the footer written with the control codes of Excel
+    ///
+    pub fn to_codes(&self) -> String {
+        if let Some(codes) = &self.codes {
+            return codes.clone();
+        }
+        ["&L", "&C", "&R"].iter()
+            .zip(&self.sections)
+            .filter(|(_, section)| !section.is_empty())
+            .map(|(code, section)| format!("{code}{section}"))
+            .collect()
+    }
+}
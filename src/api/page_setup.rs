@@ -0,0 +1,82 @@
+///
+/// The size of the paper a worksheet is printed on
+///
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PaperSize {
+    /// 8.5 x 11 in
+    Letter,
+    /// 11 x 17 in
+    Tabloid,
+    /// 17 x 11 in
+    Ledger,
+    /// 8.5 x 14 in
+    Legal,
+    /// 5.5 x 8.5 in
+    Statement,
+    /// 7.25 x 10.5 in
+    Executive,
+    /// 297 x 420 mm
+    A3,
+    /// 210 x 297 mm
+    A4,
+    /// 148 x 210 mm
+    A5,
+    /// 250 x 353 mm
+    B4,
+    /// 176 x 250 mm
+    B5,
+    /// 8.5 x 13 in
+    Folio,
+    /// 4.125 x 9.5 in
+    Envelope10,
+    /// 110 x 220 mm
+    EnvelopeDL,
+    /// 162 x 229 mm
+    EnvelopeC5,
+    /// Another size, by its number in the SpreadsheetML specification
+    Other(u32),
+}
+
+impl PaperSize {
+    pub(crate) fn to_code(self) -> u32 {
+        match self {
+            PaperSize::Letter => 1,
+            PaperSize::Tabloid => 3,
+            PaperSize::Ledger => 4,
+            PaperSize::Legal => 5,
+            PaperSize::Statement => 6,
+            PaperSize::Executive => 7,
+            PaperSize::A3 => 8,
+            PaperSize::A4 => 9,
+            PaperSize::A5 => 11,
+            PaperSize::B4 => 12,
+            PaperSize::B5 => 13,
+            PaperSize::Folio => 14,
+            PaperSize::Envelope10 => 20,
+            PaperSize::EnvelopeDL => 27,
+            PaperSize::EnvelopeC5 => 28,
+            PaperSize::Other(code) => code,
+        }
+    }
+
+    pub(crate) fn from_code(code: u32) -> PaperSize {
+        match code {
+            1 => PaperSize::Letter,
+            3 => PaperSize::Tabloid,
+            4 => PaperSize::Ledger,
+            5 => PaperSize::Legal,
+            6 => PaperSize::Statement,
+            7 => PaperSize::Executive,
+            8 => PaperSize::A3,
+            9 => PaperSize::A4,
+            11 => PaperSize::A5,
+            12 => PaperSize::B4,
+            13 => PaperSize::B5,
+            14 => PaperSize::Folio,
+            20 => PaperSize::Envelope10,
+            27 => PaperSize::EnvelopeDL,
+            28 => PaperSize::EnvelopeC5,
+            code => PaperSize::Other(code),
+        }
+    }
+}
@@ -0,0 +1,156 @@
+///
+/// The options of a protected worksheet: the password asked to unprotect it, and the actions left to the users.
+/// By default the cells can be selected, and nothing else can be changed but the unlocked cells.
+///
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProtectionOptions {
+    pub(crate) password: Option<String>,
+    pub(crate) legacy_password: bool,
+    pub(crate) edit_objects: bool,
+    pub(crate) edit_scenarios: bool,
+    pub(crate) format_cells: bool,
+    pub(crate) format_columns: bool,
+    pub(crate) format_rows: bool,
+    pub(crate) insert_columns: bool,
+    pub(crate) insert_rows: bool,
+    pub(crate) insert_hyperlinks: bool,
+    pub(crate) delete_columns: bool,
+    pub(crate) delete_rows: bool,
+    pub(crate) select_locked_cells: bool,
+    pub(crate) select_unlocked_cells: bool,
+    pub(crate) sort: bool,
+    pub(crate) autofilter: bool,
+    pub(crate) pivot_tables: bool,
+}
+
+impl Default for ProtectionOptions {
+    fn default() -> Self {
+        ProtectionOptions {
+            password: None,
+            legacy_password: false,
+            edit_objects: false,
+            edit_scenarios: false,
+            format_cells: false,
+            format_columns: false,
+            format_rows: false,
+            insert_columns: false,
+            insert_rows: false,
+            insert_hyperlinks: false,
+            delete_columns: false,
+            delete_rows: false,
+            select_locked_cells: true,
+            select_unlocked_cells: true,
+            sort: false,
+            autofilter: false,
+            pivot_tables: false,
+        }
+    }
+}
+
+impl ProtectionOptions {
+    pub fn new() -> ProtectionOptions {
+        ProtectionOptions::default()
+    }
+
+    ///
+    /// The password, hashed with SHA-512 and a random salt
+    ///
+    pub fn set_password(mut self, password: &str) -> Self {
+        self.password = Some(password.to_string());
+        self.legacy_password = false;
+        self
+    }
+
+    ///
+    /// The password, with the weak 16 bits hash read by the old versions of the spreadsheet applications
+    ///
+    pub fn set_legacy_password(mut self, password: &str) -> Self {
+        self.password = Some(password.to_string());
+        self.legacy_password = true;
+        self
+    }
+
+    ///
+    /// Allow to edit the drawings, like the charts, the images and the comments
+    ///
+    pub fn allow_edit_objects(mut self, allow: bool) -> Self {
+        self.edit_objects = allow;
+        self
+    }
+
+    pub fn allow_edit_scenarios(mut self, allow: bool) -> Self {
+        self.edit_scenarios = allow;
+        self
+    }
+
+    pub fn allow_format_cells(mut self, allow: bool) -> Self {
+        self.format_cells = allow;
+        self
+    }
+
+    pub fn allow_format_columns(mut self, allow: bool) -> Self {
+        self.format_columns = allow;
+        self
+    }
+
+    pub fn allow_format_rows(mut self, allow: bool) -> Self {
+        self.format_rows = allow;
+        self
+    }
+
+    pub fn allow_insert_columns(mut self, allow: bool) -> Self {
+        self.insert_columns = allow;
+        self
+    }
+
+    pub fn allow_insert_rows(mut self, allow: bool) -> Self {
+        self.insert_rows = allow;
+        self
+    }
+
+    pub fn allow_insert_hyperlinks(mut self, allow: bool) -> Self {
+        self.insert_hyperlinks = allow;
+        self
+    }
+
+    pub fn allow_delete_columns(mut self, allow: bool) -> Self {
+        self.delete_columns = allow;
+        self
+    }
+
+    pub fn allow_delete_rows(mut self, allow: bool) -> Self {
+        self.delete_rows = allow;
+        self
+    }
+
+    pub fn allow_select_locked_cells(mut self, allow: bool) -> Self {
+        self.select_locked_cells = allow;
+        self
+    }
+
+    pub fn allow_select_unlocked_cells(mut self, allow: bool) -> Self {
+        self.select_unlocked_cells = allow;
+        self
+    }
+
+    ///
+    /// Allow to sort the unlocked cells
+    ///
+    pub fn allow_sort(mut self, allow: bool) -> Self {
+        self.sort = allow;
+        self
+    }
+
+    ///
+    /// Allow to use the existing autofilters
+    ///
+    pub fn allow_autofilter(mut self, allow: bool) -> Self {
+        self.autofilter = allow;
+        self
+    }
+
+    pub fn allow_pivot_tables(mut self, allow: bool) -> Self {
+        self.pivot_tables = allow;
+        self
+    }
+}
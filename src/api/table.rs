@@ -0,0 +1,210 @@
+///
+/// The function calculating a column in the totals row of a table
+///
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TableFunction {
+    Average,
+    Count,
+    CountNums,
+    Max,
+    Min,
+    StdDev,
+    Sum,
+    Var,
+}
+
+impl TableFunction {
+    pub(crate) fn to_name(self) -> &'static str {
+        match self {
+            TableFunction::Average => "average",
+            TableFunction::Count => "count",
+            TableFunction::CountNums => "countNums",
+            TableFunction::Max => "max",
+            TableFunction::Min => "min",
+            TableFunction::StdDev => "stdDev",
+            TableFunction::Sum => "sum",
+            TableFunction::Var => "var",
+        }
+    }
+
+    pub(crate) fn from_name(name: &str) -> Option<TableFunction> {
+        let function = match name {
+            "average" => TableFunction::Average,
+            "count" => TableFunction::Count,
+            "countNums" => TableFunction::CountNums,
+            "max" => TableFunction::Max,
+            "min" => TableFunction::Min,
+            "stdDev" => TableFunction::StdDev,
+            "sum" => TableFunction::Sum,
+            "var" => TableFunction::Var,
+            _ => return None,
+        };
+        Some(function)
+    }
+
+    ///
+    /// The number of the function in `SUBTOTAL`, which ignores the hidden rows
+    ///
+    pub(crate) fn to_subtotal(self) -> u32 {
+        match self {
+            TableFunction::Average => 101,
+            TableFunction::Count => 103,
+            TableFunction::CountNums => 102,
+            TableFunction::Max => 104,
+            TableFunction::Min => 105,
+            TableFunction::StdDev => 107,
+            TableFunction::Sum => 109,
+            TableFunction::Var => 110,
+        }
+    }
+}
+
+///
+/// A column of a table
+///
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TableColumn {
+    pub header: String,
+    pub total_function: Option<TableFunction>,
+    pub total_label: Option<String>,
+}
+
+impl TableColumn {
+    pub fn new(header: &str) -> TableColumn {
+        TableColumn {
+            header: header.to_string(),
+            ..Default::default()
+        }
+    }
+
+    ///
+    /// Calculate the column in the totals row
+    ///
+    pub fn set_total_function(mut self, function: TableFunction) -> Self {
+        self.total_function = Some(function);
+        self
+    }
+
+    ///
+    /// Show a text in the totals row, like `Total`
+    ///
+    pub fn set_total_label(mut self, label: &str) -> Self {
+        self.total_label = Some(label.to_string());
+        self
+    }
+}
+
+///
+/// The options of a new table.
+/// The headers of the columns not given are taken from the first row of the table,
+/// or named like `Column1` when the cell is empty.
+///
+#[derive(Debug, Clone, PartialEq)]
+pub struct TableOptions {
+    pub(crate) name: Option<String>,
+    pub(crate) style: String,
+    pub(crate) columns: Vec<TableColumn>,
+    pub(crate) header_row: bool,
+    pub(crate) total_row: bool,
+    pub(crate) autofilter: bool,
+    pub(crate) show_first_column: bool,
+    pub(crate) show_last_column: bool,
+    pub(crate) show_row_stripes: bool,
+    pub(crate) show_column_stripes: bool,
+}
+
+impl Default for TableOptions {
+    fn default() -> Self {
+        TableOptions {
+            name: None,
+            style: "TableStyleMedium2".to_string(),
+            columns: vec![],
+            header_row: true,
+            total_row: false,
+            autofilter: true,
+            show_first_column: false,
+            show_last_column: false,
+            show_row_stripes: true,
+            show_column_stripes: false,
+        }
+    }
+}
+
+impl TableOptions {
+    pub fn new() -> TableOptions {
+        TableOptions::default()
+    }
+
+    ///
+    /// The name of the table used in formulas, like `Table1`
+    ///
+    pub fn set_name(mut self, name: &str) -> Self {
+        self.name = Some(name.to_string());
+        self
+    }
+
+    ///
+    /// A built-in table style, like `TableStyleLight9`
+    ///
+    pub fn set_style(mut self, style: &str) -> Self {
+        self.style = style.to_string();
+        self
+    }
+
+    pub fn set_columns(mut self, columns: &[TableColumn]) -> Self {
+        self.columns = columns.to_vec();
+        self
+    }
+
+    pub fn set_header_row(mut self, header_row: bool) -> Self {
+        self.header_row = header_row;
+        self
+    }
+
+    ///
+    /// Use the last row of the table as the totals row
+    ///
+    pub fn set_total_row(mut self, total_row: bool) -> Self {
+        self.total_row = total_row;
+        self
+    }
+
+    pub fn set_autofilter(mut self, autofilter: bool) -> Self {
+        self.autofilter = autofilter;
+        self
+    }
+
+    pub fn set_show_first_column(mut self, show_first_column: bool) -> Self {
+        self.show_first_column = show_first_column;
+        self
+    }
+
+    pub fn set_show_last_column(mut self, show_last_column: bool) -> Self {
+        self.show_last_column = show_last_column;
+        self
+    }
+
+    pub fn set_show_row_stripes(mut self, show_row_stripes: bool) -> Self {
+        self.show_row_stripes = show_row_stripes;
+        self
+    }
+
+    pub fn set_show_column_stripes(mut self, show_column_stripes: bool) -> Self {
+        self.show_column_stripes = show_column_stripes;
+        self
+    }
+}
+
+///
+/// A table of a worksheet
+///
+#[derive(Debug, Clone, PartialEq)]
+pub struct Table {
+    pub name: String,
+    /// The range of the table as (first_row, first_col, last_row, last_col)
+    pub range: (u32, u32, u32, u32),
+    pub columns: Vec<TableColumn>,
+    pub style: Option<String>,
+    pub header_row: bool,
+    pub total_row: bool,
+}
@@ -1,33 +1,37 @@
+mod import;
+
 use std::{fs, slice};
 use std::cell::RefCell;
-use std::collections::hash_map::DefaultHasher;
-use std::fs::File;
-use std::hash::{Hash, Hasher};
+use std::collections::HashSet;
+use std::io::{Cursor, Read, Seek, Write};
 use std::path::Path;
 use std::rc::Rc;
-use std::time::{SystemTime, UNIX_EPOCH};
-use futures::executor::block_on;
-use futures::join;
-use zip::result::ZipError;
-use crate::api::worksheet::WorkSheet;
-use crate::file::XlsxFileType;
-use crate::utils::{id_util, zip_util};
+use zip::ZipArchive;
+use crate::api::dependency::DependencyGraph;
+use crate::api::worksheet::{WorkbookParts, WorkSheet};
+#[cfg(feature = "calc")]
+use crate::core::calc::Calculator;
+use crate::file::{XlsxFileType, XlsxFileWriter};
+use crate::utils::zip_util;
+use crate::utils::reference_helper::Shift;
 use crate::result::{WorkSheetError, WorkbookError, WorkbookResult};
 use crate::{Properties, xml};
 use crate::xml::content_types::ContentTypes;
 use crate::xml::core_properties::CoreProperties;
 use crate::xml::app_properties::AppProperties;
+use crate::xml::calc_chain::CalcChain;
 use crate::xml::io::{Io, IoV2};
 use crate::xml::medias::Medias;
 use crate::xml::metadata::Metadata;
 use crate::xml::style::StyleSheet;
 use crate::xml::relationships::Relationships;
 use crate::xml::shared_string::SharedString;
+use crate::xml::workbook::{check_sheet_name, duplicated_sheet_name};
 
 #[derive(Debug)]
 pub struct Workbook {
     pub sheets: Vec<WorkSheet>,
-    pub(crate) tmp_path: String,
+    pub(crate) source: Vec<u8>,
     pub(crate) file_path: String,
     closed: bool,
     pub(crate) workbook: Rc<RefCell<xml::workbook::Workbook>>,
@@ -36,30 +40,113 @@ pub struct Workbook {
     pub(crate) content_types: Rc<RefCell<ContentTypes>>,
     pub(crate) medias: Rc<RefCell<Medias>>,
     pub(crate) metadata: Rc<RefCell<Metadata>>,
+    pub(crate) calc_chain: Option<CalcChain>,
     pub(crate) core_properties: Option<CoreProperties>,
     pub(crate) app_properties: Option<AppProperties>,
-    pub(crate) shared_string: Rc<SharedString>,
+    pub(crate) shared_string: Rc<RefCell<SharedString>>,
+    // Parts of the source package left out when saving, like the ones of the removed worksheets
+    removed_parts: Vec<String>,
 }
 
 ///
 /// Private methods
 ///
 impl Workbook {
-    fn get_core_properties(&mut self) -> &mut CoreProperties {
-        self.core_properties.get_or_insert(CoreProperties::from_path(&self.file_path).unwrap())
+    fn get_core_properties(&mut self) -> WorkbookResult<&mut CoreProperties> {
+        if self.core_properties.is_none() {
+            let mut archive = ZipArchive::new(Cursor::new(self.source.as_slice()))?;
+            let core_properties = CoreProperties::from_zip_file(&mut archive, "docProps/core.xml");
+            self.core_properties = Some(core_properties.ok_or(WorkbookError::FileNotFound)?);
+        }
+        Ok(self.core_properties.as_mut().unwrap())
+    }
+
+    ///
+    /// Shift the cells of a worksheet, and the formulas of the other worksheets referring to them
+    ///
+    fn shift_cells(&mut self, id: u32, shift: Shift) -> WorkbookResult<()> {
+        let sheet = self.get_worksheet_mut(id)?;
+        // no row or column to insert or delete
+        if shift.at_count().1 == 0 {
+            return Ok(());
+        }
+        sheet.shift_cells(shift)?;
+        let name = sheet.name.clone();
+        self.sheets.iter_mut()
+            .filter(|sheet| sheet.id != id)
+            .for_each(|sheet| sheet.shift_formulas(shift, &name));
+        Ok(())
+    }
+
+    ///
+    /// Save the calculation chain of the workbook, if it has one, with the formula cells present.
+    /// The chain is removed when no formula is left.
+    ///
+    fn save_calc_chain(&self, writer: &mut XlsxFileWriter) {
+        let Some(calc_chain) = &self.calc_chain else { return };
+        let formula_cells = self.sheets.iter()
+            .map(|sheet| (sheet.id, sheet.formula_cells()))
+            .collect::<Vec<_>>();
+        let calc_chain = calc_chain.rebuild(&formula_cells);
+        if calc_chain.is_empty() {
+            writer.remove(XlsxFileType::CalcChain);
+            self.workbook_rel.borrow_mut().remove_calc_chain();
+            self.content_types.borrow_mut().remove_calc_chain();
+        } else {
+            calc_chain.save(writer);
+            self.workbook_rel.borrow_mut().get_or_add_calc_chain();
+            self.content_types.borrow_mut().add_calc_chain();
+        }
+    }
+
+    ///
+    /// The parts of the source package belonging to a worksheet: the worksheet, its relationships
+    /// and the parts they lead to, like its drawings, charts, comments and tables.
+    /// The media and the parts of the workbook, like the pivot caches, are kept as they may be shared.
+    ///
+    fn worksheet_parts(&self, sheet: &WorkSheet) -> WorkbookResult<Vec<String>> {
+        let mut archive = ZipArchive::new(Cursor::new(self.source.as_slice()))?;
+        let sheet_part = format!("xl/{}", sheet.target);
+        let workbook_parts = self.workbook_rel.borrow().get_internal_targets().iter()
+            .map(|target| zip_util::resolve_target("xl/workbook.xml", target))
+            .filter(|part_name| *part_name != sheet_part)
+            .collect::<Vec<String>>();
+        let mut parts = vec![];
+        let mut pending = vec![sheet_part];
+        while let Some(part_name) = pending.pop() {
+            if parts.contains(&part_name) || workbook_parts.contains(&part_name)
+                || part_name.starts_with("xl/media/") || archive.by_name(&part_name).is_err() {
+                continue;
+            }
+            let rels_name = zip_util::rels_part_name(&part_name);
+            if let Some(rels) = Relationships::from_zip_file(&mut archive, &rels_name) {
+                pending.extend(rels.get_internal_targets().iter().map(|target| zip_util::resolve_target(&part_name, target)));
+                parts.push(rels_name);
+            }
+            parts.push(part_name);
+        }
+        Ok(parts)
     }
 
-    fn get_app_properties(&mut self) -> &mut AppProperties {
-        self.app_properties.get_or_insert(AppProperties::from_path(&self.file_path).unwrap())
+    fn get_app_properties(&mut self) -> WorkbookResult<&mut AppProperties> {
+        if self.app_properties.is_none() {
+            let mut archive = ZipArchive::new(Cursor::new(self.source.as_slice()))?;
+            let app_properties = AppProperties::from_zip_file(&mut archive, "docProps/app.xml");
+            self.app_properties = Some(app_properties.ok_or(WorkbookError::FileNotFound)?);
+        }
+        Ok(self.app_properties.as_mut().unwrap())
     }
 }
 
+///
+/// The blank workbook template, built into the library so that [`Workbook::new`]
+/// does not depend on any file at runtime.
+///
+const NEW_WORKBOOK: &[u8] = include_bytes!("../../resources/new.xlsx");
+
 impl Workbook {
     pub fn new() -> Workbook {
-        let file_path = concat!(env!("CARGO_MANIFEST_DIR"), "/resources/new.xlsx");
-        let mut wb = Self::from_path(file_path).unwrap();
-        wb.file_path = String::from(file_path);
-        wb
+        Self::from_bytes(NEW_WORKBOOK).unwrap()
     }
 
     pub fn get_worksheet_mut(&mut self, id: u32) -> WorkbookResult<&mut WorkSheet> {
@@ -98,15 +185,19 @@ impl Workbook {
 
     pub fn add_worksheet(&mut self) -> WorkbookResult<&mut WorkSheet> {
         let (r_id, target_id) = self.workbook_rel.borrow_mut().add_worksheet_v2();
-        let (sheet_id, name) = self.workbook.borrow_mut().add_worksheet_v2(r_id, None)?;
+        let (sheet_id, name) = self.workbook.borrow_mut().add_worksheet_v2(r_id, None)
+            // the relationship of a sheet whose name is taken is dropped
+            .inspect_err(|_| self.workbook_rel.borrow_mut().remove(r_id))?;
         let worksheet = WorkSheet::add_worksheet(sheet_id, &name, target_id, self);
         self.sheets.push(worksheet);
         self.get_worksheet_mut(sheet_id)
     }
 
     pub fn add_worksheet_by_name(&mut self, name: &str) -> WorkbookResult<&mut WorkSheet> {
+        check_sheet_name(name)?;
         let (r_id, target_id) = self.workbook_rel.borrow_mut().add_worksheet_v2();
-        let (sheet_id, name) = self.workbook.borrow_mut().add_worksheet_v2(r_id, Some(name))?;
+        let (sheet_id, name) = self.workbook.borrow_mut().add_worksheet_v2(r_id, Some(name))
+            .inspect_err(|_| self.workbook_rel.borrow_mut().remove(r_id))?;
         let worksheet = WorkSheet::add_worksheet(sheet_id, &name, target_id, self);
         self.sheets.push(worksheet);
         self.get_worksheet_mut(sheet_id)
@@ -117,7 +208,8 @@ impl Workbook {
             .iter()
             .find(|sheet| sheet.id == id).ok_or(WorkSheetError::FileNotFound)?;
         let (r_id, target_id) = self.workbook_rel.borrow_mut().add_worksheet_v2();
-        let (sheet_id, new_name) = self.workbook.borrow_mut().add_worksheet_v2(r_id, None)?;
+        let (sheet_id, new_name) = self.workbook.borrow_mut().add_worksheet_v2(r_id, None)
+            .inspect_err(|_| self.workbook_rel.borrow_mut().remove(r_id))?;
         let worksheet = WorkSheet::from_worksheet_v2(sheet_id, &new_name, target_id, copy_worksheet);
         self.sheets.push(worksheet);
         self.get_worksheet_mut(sheet_id)
@@ -127,14 +219,104 @@ impl Workbook {
         let copy_worksheet = self.sheets
             .iter()
             .find(|sheet| sheet.name == name).ok_or(WorkSheetError::FileNotFound)?;
-        let new_name = format!("{} Duplicated", name);
+        let new_name = duplicated_sheet_name(name);
+        check_sheet_name(&new_name)?;
         let (r_id, target_id) = self.workbook_rel.borrow_mut().add_worksheet_v2();
-        let (sheet_id, _) = self.workbook.borrow_mut().add_worksheet_v2(r_id, Some(&new_name))?;
+        let (sheet_id, _) = self.workbook.borrow_mut().add_worksheet_v2(r_id, Some(&new_name))
+            .inspect_err(|_| self.workbook_rel.borrow_mut().remove(r_id))?;
         let worksheet = WorkSheet::from_worksheet_v2(sheet_id, &new_name, target_id, copy_worksheet);
         self.sheets.push(worksheet);
         self.get_worksheet_mut(sheet_id)
     }
 
+    ///
+    /// Rename a worksheet, the references to it in the formulas, the defined names, the links and the charts
+    /// of all the worksheets follow it
+    ///
+    pub fn rename_worksheet(&mut self, id: u32, name: &str) -> WorkbookResult<()> {
+        let sheet = self.get_worksheet_mut(id)?;
+        let old_name = sheet.name.clone();
+        sheet.set_name(name)?;
+        self.sheets.iter_mut()
+            .filter(|sheet| sheet.id != id)
+            .for_each(|sheet| sheet.rename_sheet(&old_name, name));
+        Ok(())
+    }
+
+    pub fn rename_worksheet_by_name(&mut self, old_name: &str, name: &str) -> WorkbookResult<()> {
+        let id = self.get_worksheet_by_name(old_name)?.id;
+        self.rename_worksheet(id, name)
+    }
+
+    ///
+    /// Remove a worksheet with its drawings, comments and tables, and the names local to it.
+    /// The references of the other worksheets and of the defined names to it become `#REF!`.
+    ///
+    pub fn remove_worksheet(&mut self, id: u32) -> WorkbookResult<()> {
+        let position = self.sheets.iter().position(|sheet| sheet.id == id).ok_or(WorkSheetError::FileNotFound)?;
+        self.remove_worksheet_at(position)
+    }
+
+    pub fn remove_worksheet_by_name(&mut self, name: &str) -> WorkbookResult<()> {
+        let position = self.sheets.iter().position(|sheet| sheet.name == name).ok_or(WorkSheetError::FileNotFound)?;
+        self.remove_worksheet_at(position)
+    }
+
+    fn remove_worksheet_at(&mut self, position: usize) -> WorkbookResult<()> {
+        if self.sheets.len() == 1 {
+            return Err(WorkbookError::LastWorksheet);
+        }
+        let mut parts = self.worksheet_parts(&self.sheets[position])?;
+        parts.extend(self.sheets[position].imported_part_names());
+        parts.extend(self.sheets[position].part_names());
+        // the parts another worksheet leads to, like a copy of the worksheet, are kept
+        let kept = self.sheets.iter().enumerate()
+            .filter(|(i, _)| *i != position)
+            .flat_map(|(_, sheet)| sheet.part_names().into_iter().chain(sheet.imported_part_names()))
+            .collect::<HashSet<String>>();
+        parts.retain(|part_name| !kept.contains(part_name));
+        parts.sort();
+        parts.dedup();
+        let was_active = self.workbook.borrow().book_views.get_active_tab() == position as u32;
+        // the parts of the worksheet which are not in the source yet are simply not saved
+        let sheet = self.sheets.remove(position);
+        sheet.table_ids().into_iter().for_each(|id| self.workbook.borrow_mut().remove_table_name(id));
+        let r_id = self.workbook.borrow_mut().remove_sheet(position);
+        self.workbook.borrow_mut().defined_names.remove_sheet(&sheet.name);
+        self.sheets.iter_mut().for_each(|other| other.remove_sheet(&sheet.name));
+        self.workbook_rel.borrow_mut().remove(r_id);
+        parts.iter().for_each(|part_name| self.content_types.borrow_mut().remove_part(part_name));
+        self.removed_parts.extend(parts);
+        if was_active {
+            let active_tab = self.workbook.borrow().book_views.get_active_tab() as usize;
+            self.sheets[active_tab].select();
+        }
+        Ok(())
+    }
+
+    ///
+    /// Move a worksheet to a position among the tabs, from 0 for the first one.
+    /// The positions past the last tab move the worksheet to the end.
+    ///
+    pub fn move_worksheet(&mut self, id: u32, position: usize) -> WorkbookResult<()> {
+        let from = self.sheets.iter().position(|sheet| sheet.id == id).ok_or(WorkSheetError::FileNotFound)?;
+        self.move_worksheet_at(from, position);
+        Ok(())
+    }
+
+    pub fn move_worksheet_by_name(&mut self, name: &str, position: usize) -> WorkbookResult<()> {
+        let from = self.sheets.iter().position(|sheet| sheet.name == name).ok_or(WorkSheetError::FileNotFound)?;
+        self.move_worksheet_at(from, position);
+        Ok(())
+    }
+
+    fn move_worksheet_at(&mut self, from: usize, to: usize) {
+        let to = to.min(self.sheets.len() - 1);
+        let sheet = self.sheets.remove(from);
+        self.sheets.insert(to, sheet);
+        self.workbook.borrow_mut().move_sheet(from, to);
+    }
+
     pub fn set_size(&mut self, width: u32, height: u32) -> WorkbookResult<()> {
         let workbook = &mut self.workbook.borrow_mut();
         let book_view = workbook.book_views.book_views.get_mut(0).unwrap();
@@ -156,19 +338,68 @@ impl Workbook {
         Ok(())
     }
 
+    ///
+    /// Prevent the users from adding, deleting, moving, renaming or hiding the worksheets
+    ///
+    pub fn protect_structure(&mut self, password: Option<&str>) -> WorkbookResult<()> {
+        self.workbook.borrow_mut().protect(true, false, password);
+        Ok(())
+    }
+
+    ///
+    /// Prevent the users from moving, resizing or closing the windows of the workbook
+    ///
+    pub fn protect_windows(&mut self, password: Option<&str>) -> WorkbookResult<()> {
+        self.workbook.borrow_mut().protect(false, true, password);
+        Ok(())
+    }
+
+    ///
+    /// Define a name local to a worksheet, which is scoped by the position of the worksheet among the tabs
+    ///
     pub fn define_local_name(&mut self, name: &str, value: &str, sheet_id: u32) -> WorkbookResult<()> {
-        if sheet_id > self.sheets.len() as u32 {
-            return Err(WorkbookError::SheetError(WorkSheetError::FileNotFound));
-        }
-        self.workbook.borrow_mut().defined_names.add_define_name(name, value, Some(sheet_id - 1));
+        let position = self.sheets.iter().position(|sheet| sheet.id == sheet_id).ok_or(WorkSheetError::FileNotFound)?;
+        self.workbook.borrow_mut().defined_names.add_define_name(name, value, Some(position as u32));
         Ok(())
     }
 
-    pub fn worksheets_mut(&mut self) -> slice::IterMut<WorkSheet> {
+    ///
+    /// Insert rows in a worksheet before a row, the cells below move down.
+    /// The ranges of the worksheet, the defined names and the formulas of all the worksheets follow the moved cells.
+    /// The rows are not inserted if cells with values, merged cells or tables would be pushed past the last row,
+    /// and the references pushed past it become `#REF!`. Inserting no row does nothing.
+    ///
+    pub fn insert_rows(&mut self, sheet_id: u32, at: u32, count: u32) -> WorkbookResult<()> {
+        self.shift_cells(sheet_id, Shift::InsertRows(at, count))
+    }
+
+    ///
+    /// Delete rows of a worksheet, the cells below move up.
+    /// The references of all the worksheets to the deleted cells become `#REF!`.
+    ///
+    pub fn delete_rows(&mut self, sheet_id: u32, at: u32, count: u32) -> WorkbookResult<()> {
+        self.shift_cells(sheet_id, Shift::DeleteRows(at, count))
+    }
+
+    ///
+    /// Insert columns in a worksheet before a column, the cells on the right move right, like [`Workbook::insert_rows`]
+    ///
+    pub fn insert_columns(&mut self, sheet_id: u32, at: u32, count: u32) -> WorkbookResult<()> {
+        self.shift_cells(sheet_id, Shift::InsertColumns(at, count))
+    }
+
+    ///
+    /// Delete columns of a worksheet, the cells on the right move left, like [`Workbook::delete_rows`]
+    ///
+    pub fn delete_columns(&mut self, sheet_id: u32, at: u32, count: u32) -> WorkbookResult<()> {
+        self.shift_cells(sheet_id, Shift::DeleteColumns(at, count))
+    }
+
+    pub fn worksheets_mut(&mut self) -> slice::IterMut<'_, WorkSheet> {
         self.sheets.iter_mut()
     }
 
-    pub fn worksheets(&self) -> slice::Iter<WorkSheet> {
+    pub fn worksheets(&self) -> slice::Iter<'_, WorkSheet> {
         self.sheets.iter()
     }
 
@@ -180,140 +411,219 @@ impl Workbook {
         Ok(())
     }
 
+    ///
+    /// Use the 1904 date system, in which serial numbers count days from 1904-01-01.
+    /// Dates written after this call are converted with the new system.
+    ///
+    pub fn set_date1904(&mut self, date1904: bool) -> WorkbookResult<()> {
+        self.workbook.borrow_mut().set_date1904(date1904);
+        Ok(())
+    }
+
+    pub fn is_date1904(&self) -> bool {
+        self.workbook.borrow().is_date1904()
+    }
+
+    ///
+    /// The dependencies between the formulas of the workbook and the cells they refer to, as they are now
+    ///
+    pub fn dependency_graph(&self) -> DependencyGraph {
+        DependencyGraph::new(self)
+    }
+
+    ///
+    /// Drop the cached values of the formulas in all the worksheets, see [`WorkSheet::clear_formula_values`].
+    /// Cached values are otherwise kept as they were read.
+    ///
+    pub fn clear_formula_values(&mut self) -> WorkbookResult<()> {
+        self.sheets.iter_mut().for_each(WorkSheet::clear_formula_values);
+        Ok(())
+    }
+
+    ///
+    /// Evaluate the formulas of all the worksheets, and store their results as the cached values of the cells,
+    /// which are then read by [`Read::read_value`](crate::Read::read_value).
+    /// The formulas which cannot be evaluated, like those calling a function the calculator does not know,
+    /// and the ones in a circular reference keep their values.
+    /// Saving the workbook does not calculate it, the values are saved as they are.
+    ///
+    #[cfg(feature = "calc")]
+    pub fn calculate(&mut self) -> WorkbookResult<()> {
+        let values = Calculator::new(self).calculate();
+        self.sheets.iter_mut()
+            .zip(values)
+            .for_each(|(sheet, values)| sheet.set_formula_values(&values));
+        Ok(())
+    }
+
     pub fn set_properties(&mut self, properties: &Properties) -> WorkbookResult<()> {
-        let core_properties = self.get_core_properties();
+        let core_properties = self.get_core_properties()?;
         core_properties.update_by_properties(properties);
-        let app_properties = self.get_app_properties();
+        let app_properties = self.get_app_properties()?;
         app_properties.update_by_properties(properties);
         Ok(())
     }
 }
 
 impl Workbook {
-    fn from_path_v2<P: AsRef<Path>>(file_path: P) -> WorkbookResult<Workbook> {
-        let file_name = file_path.as_ref().file_name().ok_or(ZipError::FileNotFound)?;
-        let tmp_path = format!("./~${}_{:X}", file_name.to_str().ok_or(ZipError::FileNotFound)?, id_util::new_id());
-        let file = File::open(&file_path)?;
-        let mut archive = zip::ZipArchive::new(file)?;
+    fn from_source(source: Vec<u8>, file_path: String) -> WorkbookResult<Workbook> {
+        let mut archive = ZipArchive::new(Cursor::new(source.as_slice()))?;
         let mut medias = Medias::default();
-        let mut workbook_xml = xml::workbook::Workbook::from_zip_file(&mut archive, "xl/workbook.xml");
-        let mut workbook_rel = Relationships::from_zip_file(&mut archive, "xl/_rels/workbook.xml.rels");
-        let mut content_types = ContentTypes::from_zip_file(&mut archive, "[Content_Types].xml");
-        let mut style_sheet = StyleSheet::from_zip_file(&mut archive, "xl/styles.xml");
-        let mut metadata = Metadata::from_zip_file(&mut archive, "xl/metadata.xml");
-        let mut shared_string = SharedString::from_zip_file(&mut archive, "xl/sharedStrings.xml");
-        for i in 0..archive.len() {
-            let mut file = archive.by_index(i)?;
-            let file_name = file.name();
+        let workbook_xml = xml::workbook::Workbook::from_zip_file(&mut archive, "xl/workbook.xml");
+        let workbook_rel = Relationships::from_zip_file(&mut archive, "xl/_rels/workbook.xml.rels");
+        let mut content_types = ContentTypes::from_zip_file(&mut archive, "[Content_Types].xml").unwrap_or_default();
+        let style_sheet = StyleSheet::from_zip_file(&mut archive, "xl/styles.xml");
+        let metadata = Metadata::from_zip_file(&mut archive, "xl/metadata.xml");
+        let calc_chain = CalcChain::from_zip_file(&mut archive, "xl/calcChain.xml");
+        let shared_string = SharedString::from_archive(&mut archive, "xl/sharedStrings.xml")?;
+        for file_name in archive.file_names() {
             if file_name.starts_with("xl/media/") {
-                medias.add_existed_media(&file_name);
+                medias.add_existed_media(file_name);
+            } else if file_name.starts_with("xl/drawings/vmlDrawing") {
+                content_types.add_existed_vml_drawing(file_name);
             }
         }
-        let workbook = Rc::new(RefCell::new(workbook_xml.unwrap_or_default()));
-        let workbook_rel = Rc::new(RefCell::new(workbook_rel.unwrap_or_default()));
-        let content_types = Rc::new(RefCell::new(content_types.unwrap_or_default()));
-        let style_sheet = Rc::new(RefCell::new(style_sheet.unwrap_or_default()));
-        let metadata = Rc::new(RefCell::new(metadata.unwrap_or_default()));
-        let shared_string = Rc::new(shared_string.unwrap_or_default());
-        let medias = Rc::new(RefCell::new(medias));
-        let sheets = workbook.borrow().sheets.sheets.iter().map(
-            |sheet_xml| {
-                let binding = workbook_rel.borrow();
-                let (target, target_id) = binding.get_target(&sheet_xml.r_id);
-                WorkSheet::from_archive(
-                    sheet_xml.sheet_id,
-                    &sheet_xml.name,
-                    target,
-                    target_id,
-                    &file_path,
-                    &mut archive,
-                    Rc::clone(&workbook),
-                    Rc::clone(&workbook_rel),
-                    Rc::clone(&style_sheet),
-                    Rc::clone(&content_types),
-                    Rc::clone(&medias),
-                    Rc::clone(&metadata),
-                    Rc::clone(&shared_string),
-                )
+        let parts = WorkbookParts {
+            workbook: Rc::new(RefCell::new(workbook_xml.unwrap_or_default())),
+            workbook_rel: Rc::new(RefCell::new(workbook_rel.unwrap_or_default())),
+            style_sheet: Rc::new(RefCell::new(style_sheet.unwrap_or_default())),
+            content_types: Rc::new(RefCell::new(content_types)),
+            medias: Rc::new(RefCell::new(medias)),
+            metadata: Rc::new(RefCell::new(metadata.unwrap_or_default())),
+            shared_string: Rc::new(RefCell::new(shared_string)),
+        };
+        // the worksheets register their tables in the workbook while they are loaded
+        let sheet_list = parts.workbook.borrow().sheets.sheets.iter()
+            .map(|sheet_xml| (sheet_xml.sheet_id, sheet_xml.name.clone(), sheet_xml.r_id.clone()))
+            .collect::<Vec<_>>();
+        let sheets = sheet_list.into_iter().map(
+            |(sheet_id, name, r_id)| {
+                let binding = parts.workbook_rel.borrow();
+                let (target, target_id) = binding.get_target(&r_id);
+                WorkSheet::from_archive(sheet_id, &name, target, target_id, &mut archive, &parts)
             }).collect::<Vec<WorkSheet>>();
         let api_workbook = Workbook {
             sheets,
-            tmp_path,
-            file_path: file_path.as_ref().to_str().unwrap().to_string(),
+            source,
+            file_path,
             closed: false,
-            workbook: Rc::clone(&workbook),
-            workbook_rel: Rc::clone(&workbook_rel),
-            style_sheet: Rc::clone(&style_sheet),
-            content_types: Rc::clone(&content_types),
-            medias: Rc::clone(&medias),
-            metadata,
+            workbook: parts.workbook,
+            workbook_rel: parts.workbook_rel,
+            style_sheet: parts.style_sheet,
+            content_types: parts.content_types,
+            medias: parts.medias,
+            metadata: parts.metadata,
+            calc_chain,
             core_properties: None,
             app_properties: None,
-            shared_string,
+            shared_string: parts.shared_string,
+            removed_parts: vec![],
         };
         Ok(api_workbook)
     }
 
+    ///
+    /// The parts shared with the worksheets
+    ///
+    fn shared_parts(&self) -> WorkbookParts {
+        WorkbookParts {
+            workbook: Rc::clone(&self.workbook),
+            workbook_rel: Rc::clone(&self.workbook_rel),
+            style_sheet: Rc::clone(&self.style_sheet),
+            content_types: Rc::clone(&self.content_types),
+            medias: Rc::clone(&self.medias),
+            metadata: Rc::clone(&self.metadata),
+            shared_string: Rc::clone(&self.shared_string),
+        }
+    }
+
     pub fn from_path<P: AsRef<Path>>(file_path: P) -> WorkbookResult<Workbook> {
-        let workbook = Self::from_path_v2(file_path);
-        // let workbook = block_on(workbook);
-        workbook
-    }
-
-    async fn save_async(&self) -> WorkbookResult<()> {
-        let workbook = self.workbook.borrow();
-        let workbook = workbook.save_async(&self.tmp_path);
-        let style_sheet = self.style_sheet.borrow();
-        let style_sheet = style_sheet.save_async(&self.tmp_path);
-        let workbook_rel = self.workbook_rel.borrow();
-        let workbook_rel = workbook_rel.save_async(&self.tmp_path, XlsxFileType::WorkbookRels);
-        let content_types = self.content_types.borrow();
-        let content_types = content_types.save_async(&self.tmp_path);
-        let medias = self.medias.borrow();
-        let medias = medias.save_async(&self.tmp_path);
-        let metadata = self.metadata.borrow();
-        let metadata = metadata.save_async(&self.tmp_path);
-        join!(workbook, style_sheet, workbook_rel, content_types, medias, metadata);
-        Ok(())
+        let source = fs::read(&file_path)?;
+        let file_path = file_path.as_ref().to_str().ok_or(WorkbookError::FileNotFound)?;
+        Self::from_source(source, file_path.to_string())
     }
 
-    pub fn save_as<P: AsRef<Path>>(&self, file_path: P) -> WorkbookResult<()> {
+    ///
+    /// Load a workbook from any reader, such as a file, a network stream or a [`Cursor`].
+    /// The whole reader is read from its start.
+    /// The workbook has no file path, so use [`Workbook::save_as`] or [`Workbook::save_to_writer`] to save it.
+    ///
+    pub fn from_reader<R: Read + Seek>(mut reader: R) -> WorkbookResult<Workbook> {
+        reader.rewind()?;
+        let mut source = Vec::new();
+        reader.read_to_end(&mut source)?;
+        Self::from_source(source, String::new())
+    }
+
+    ///
+    /// Load a workbook from the bytes of an xlsx file.
+    ///
+    pub fn from_bytes(bytes: &[u8]) -> WorkbookResult<Workbook> {
+        Self::from_source(bytes.to_vec(), String::new())
+    }
+
+    ///
+    /// Write the workbook as an xlsx package to any writer.
+    ///
+    pub fn save_to_writer<W: Write + Seek>(&self, writer: W) -> WorkbookResult<()> {
         if self.closed {
             return Err(WorkbookError::FileNotFound);
         }
-        // Extract xlsx to tmp dir
-        zip_util::extract_dir(&self.file_path, &self.tmp_path)?;
+        let mut file_writer = XlsxFileWriter::default();
+        // the parts rewritten below, like the ones of a worksheet added in place of a removed one, are kept
+        self.removed_parts.iter().for_each(|part_name| file_writer.remove_part(part_name));
         // save sheets
-        self.sheets.iter().for_each(|s| s.save_as(&self.tmp_path).unwrap());
-        block_on(self.save_async()).unwrap();
+        for sheet in &self.sheets {
+            sheet.save_as(&mut file_writer)?;
+        }
+        self.save_calc_chain(&mut file_writer);
+        self.workbook.borrow().save(&mut file_writer);
+        self.style_sheet.borrow().save(&mut file_writer);
+        self.workbook_rel.borrow().save(&mut file_writer, XlsxFileType::WorkbookRels);
+        self.content_types.borrow().save(&mut file_writer);
+        self.medias.borrow().save(&mut file_writer);
+        self.metadata.borrow().save(&mut file_writer);
+        let mut shared_string = self.shared_string.borrow_mut();
+        if !shared_string.is_empty() {
+            shared_string.set_count(self.sheets.iter().map(|sheet| sheet.shared_string_count()).sum());
+            shared_string.save(&mut file_writer);
+        }
         // save if modified
-        if let Some(core_propertises) = &self.core_properties {
-            core_propertises.save(&self.tmp_path);
+        if let Some(core_properties) = &self.core_properties {
+            core_properties.save(&mut file_writer);
         }
         if let Some(app_properties) = &self.app_properties {
-            app_properties.save(&self.tmp_path);
+            app_properties.save(&mut file_writer);
         }
         // package files
-        zip_util::zip_dir(&self.tmp_path, file_path)?;
-        // clean cache
-        fs::remove_dir_all(&self.tmp_path).unwrap();
+        let mut archive = ZipArchive::new(Cursor::new(self.source.as_slice()))?;
+        zip_util::write_package(&mut archive, &file_writer, writer)?;
         Ok(())
     }
 
-    pub fn save(&mut self) -> WorkbookResult<()> {
-        self.save_as(&self.file_path.clone())
+    ///
+    /// Save the workbook as the bytes of an xlsx file.
+    ///
+    pub fn to_bytes(&self) -> WorkbookResult<Vec<u8>> {
+        let mut cursor = Cursor::new(Vec::new());
+        self.save_to_writer(&mut cursor)?;
+        Ok(cursor.into_inner())
     }
 
-    pub fn finish(&mut self) {
-        if !self.closed {
-            fs::remove_dir_all(&self.tmp_path);
-            self.closed = true;
+    pub fn save_as<P: AsRef<Path>>(&self, file_path: P) -> WorkbookResult<()> {
+        let bytes = self.to_bytes()?;
+        fs::write(file_path, bytes)?;
+        Ok(())
+    }
+
+    pub fn save(&mut self) -> WorkbookResult<()> {
+        if self.file_path.is_empty() {
+            return Err(WorkbookError::FileNotFound);
         }
+        self.save_as(&self.file_path)
     }
-}
 
-impl Drop for Workbook {
-    fn drop(&mut self) {
-        self.finish();
+    pub fn finish(&mut self) {
+        self.closed = true;
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,178 @@
+use std::collections::HashMap;
+use std::io::{Cursor, Read, Seek};
+use zip::ZipArchive;
+use crate::api::workbook::Workbook;
+use crate::api::worksheet::WorkSheet;
+use crate::result::{WorkSheetError, WorkbookResult};
+use crate::utils::reference_helper::rename_sheet_in_formula;
+use crate::utils::zip_util;
+use crate::xml::content_types::ContentTypes;
+use crate::xml::io::IoV2;
+use crate::xml::relationships::Relationships;
+
+impl Workbook {
+    ///
+    /// Copy a worksheet of another workbook after the last tab, with the styles, the shared strings, the media,
+    /// the drawings, the charts, the comments and the tables it uses, which are numbered anew here.
+    /// The worksheet keeps its name unless a worksheet already has it here, a number is then added like `Data (2)`.
+    /// The formulas referring to the other worksheets of its workbook are kept as they are,
+    /// and the parts depending on its workbook, like the pivot tables, are left out.
+    ///
+    pub fn import_worksheet(&mut self, other: &Workbook, sheet_id: u32) -> WorkbookResult<&mut WorkSheet> {
+        let position = other.sheets.iter().position(|sheet| sheet.id == sheet_id).ok_or(WorkSheetError::FileNotFound)?;
+        let source_sheet = &other.sheets[position];
+        let package = other.to_bytes()?;
+        let mut archive = ZipArchive::new(Cursor::new(package.as_slice()))?;
+        let name = self.free_sheet_name(&source_sheet.name);
+        let target_id = self.workbook_rel.borrow().next_sheet_target_id();
+        let target = format!("worksheets/sheet{target_id}.xml");
+        // the content types of the parts copied are registered as they are named, they are dropped if the copy fails
+        let content_types = self.content_types.borrow().clone();
+        let (parts, mut copied) = self.copy_parts(&mut archive, other, &format!("xl/{}", source_sheet.target), &format!("xl/{target}"))
+            .and_then(|parts| {
+                let copied = ZipArchive::new(zip_util::write_parts(&parts, Cursor::new(Vec::new()))?)?;
+                Ok((parts, copied))
+            })
+            .inspect_err(|_| *self.content_types.borrow_mut() = content_types.clone())?;
+        // the worksheet is added to the workbook once its parts are copied
+        let (r_id, _) = self.workbook_rel.borrow_mut().add_worksheet_v2();
+        let (id, name) = self.workbook.borrow_mut().add_worksheet_v2(r_id, Some(&name))
+            .inspect_err(|_| {
+                self.workbook_rel.borrow_mut().remove(r_id);
+                *self.content_types.borrow_mut() = content_types;
+            })?;
+        let mut worksheet = WorkSheet::from_archive(
+            id,
+            &name,
+            &target,
+            target_id,
+            &mut copied,
+            &self.shared_parts(),
+        );
+        worksheet.import(other, &source_sheet.name, parts);
+        // the names local to the worksheet, like its print area
+        let local_sheet_id = self.sheets.len() as u32;
+        for (defined_name, value) in other.workbook.borrow().defined_names.get_local_names(position as u32) {
+            let value = rename_sheet_in_formula(value, &source_sheet.name, &name);
+            self.workbook.borrow_mut().defined_names.add_define_name(defined_name, &value, Some(local_sheet_id));
+        }
+        self.sheets.push(worksheet);
+        self.get_worksheet_mut(id)
+    }
+
+    pub fn import_worksheet_by_name(&mut self, other: &Workbook, name: &str) -> WorkbookResult<&mut WorkSheet> {
+        let id = other.get_worksheet_by_name(name)?.id;
+        self.import_worksheet(other, id)
+    }
+
+    ///
+    /// The name of a worksheet copied here, followed by a number like `Data (2)` if a worksheet already has it
+    ///
+    fn free_sheet_name(&self, name: &str) -> String {
+        let taken = |name: &str| self.sheets.iter().any(|sheet| sheet.name.to_lowercase() == name.to_lowercase());
+        if !taken(name) {
+            return name.to_string();
+        }
+        (2..).map(|number| {
+            let suffix = format!(" ({number})");
+            let name = name.chars().take(31 - suffix.len()).collect::<String>();
+            format!("{name}{suffix}")
+        })
+            .find(|name| !taken(name))
+            .unwrap()
+    }
+
+    ///
+    /// Copy the parts of a worksheet of another package, named after the parts of this workbook:
+    /// the worksheet, the parts its relationships lead to and their relationships pointing to the new names.
+    /// The parts of the other workbook, like its pivot caches, and the parts leading to them are left out.
+    ///
+    fn copy_parts<R: Read + Seek>(&mut self, archive: &mut ZipArchive<R>, other: &Workbook, sheet_part: &str, new_sheet_part: &str) -> WorkbookResult<Vec<(String, Vec<u8>)>> {
+        let source_types = ContentTypes::from_zip_file(archive, "[Content_Types].xml").unwrap_or_default();
+        let workbook_parts = other.workbook_rel.borrow().get_internal_targets().iter()
+            .map(|target| zip_util::resolve_target("xl/workbook.xml", target))
+            .filter(|part_name| part_name != sheet_part)
+            .collect::<Vec<String>>();
+        let mut part_names = vec![];
+        let mut part_rels = vec![];
+        let mut pending = vec![sheet_part.to_string()];
+        while let Some(part_name) = pending.pop() {
+            if part_names.contains(&part_name) || workbook_parts.contains(&part_name) || archive.by_name(&part_name).is_err() {
+                continue;
+            }
+            let rels = Relationships::from_zip_file(archive, &zip_util::rels_part_name(&part_name));
+            let targets = rels.as_ref().map_or(vec![], |rels| rels.get_internal_targets().iter()
+                .map(|target| zip_util::resolve_target(&part_name, target))
+                .collect::<Vec<String>>());
+            if part_name != sheet_part && targets.iter().any(|target| workbook_parts.contains(target)) {
+                continue;
+            }
+            pending.extend(targets);
+            part_names.push(part_name);
+            part_rels.push(rels);
+        }
+        let mut taken = self.part_names()?;
+        let mut new_names = HashMap::new();
+        for part_name in &part_names {
+            let new_name = match part_name == sheet_part {
+                true => new_sheet_part.to_string(),
+                false => self.new_part_name(part_name, &taken),
+            };
+            self.content_types.borrow_mut().add_part_like(&source_types, part_name, &new_name);
+            taken.push(new_name.clone());
+            new_names.insert(part_name.clone(), new_name);
+        }
+        let mut parts = vec![];
+        for (part_name, rels) in part_names.iter().zip(part_rels) {
+            let new_name = &new_names[part_name];
+            let mut content = vec![];
+            archive.by_name(part_name)?.read_to_end(&mut content)?;
+            parts.push((new_name.clone(), content));
+            if let Some(mut rels) = rels {
+                rels.retarget(|target| {
+                    let new_name = new_names.get(&zip_util::resolve_target(part_name, target))?;
+                    let file_name = new_name.rsplit('/').next().unwrap_or(new_name);
+                    Some(match target.rsplit_once('/') {
+                        Some((dir, _)) => format!("{dir}/{file_name}"),
+                        None => file_name.to_string(),
+                    })
+                });
+                parts.push((zip_util::rels_part_name(new_name), rels.to_xml().into_bytes()));
+            }
+        }
+        Ok(parts)
+    }
+
+    ///
+    /// The names of the parts of the package, with the ones not saved yet
+    ///
+    fn part_names(&self) -> WorkbookResult<Vec<String>> {
+        let archive = ZipArchive::new(Cursor::new(self.source.as_slice()))?;
+        let mut part_names = archive.file_names().map(String::from).collect::<Vec<String>>();
+        part_names.extend(self.content_types.borrow().get_part_names());
+        self.sheets.iter().for_each(|sheet| part_names.extend(sheet.imported_part_names()));
+        Ok(part_names)
+    }
+
+    ///
+    /// A free name for a part copied here, numbered like `xl/drawings/drawing3.xml` after the parts of its kind
+    ///
+    fn new_part_name(&mut self, part_name: &str, taken: &[String]) -> String {
+        let (dir, file_name) = part_name.rsplit_once('/').unwrap_or(("", part_name));
+        let (stem, extension) = file_name.rsplit_once('.').unwrap_or((file_name, ""));
+        let prefix = format!("{dir}/{}", stem.trim_end_matches(|c: char| c.is_ascii_digit()));
+        let id = if dir == "xl/media" {
+            self.medias.borrow_mut().add_copied_media()
+        } else if dir == "xl/tables" {
+            self.content_types.borrow_mut().add_table()
+        } else if extension == "vml" {
+            self.content_types.borrow_mut().add_vml_drawing()
+        } else {
+            1 + taken.iter()
+                .filter_map(|name| name.strip_prefix(&prefix)?.split_once('.')?.0.parse::<u32>().ok())
+                .max()
+                .unwrap_or_default()
+        };
+        format!("{prefix}{id}.{extension}")
+    }
+}
@@ -6,13 +6,25 @@ pub(crate) mod column;
 mod format;
 mod hyperlink;
 mod image;
+mod comment;
+mod table;
+mod data_validation;
+mod conditional_format;
+mod chart;
+mod shift;
+mod protection;
+mod page_setup;
+mod header_footer;
+mod import;
+mod duplicate;
+#[cfg(feature = "calc")]
+mod calc;
 
 use std::cell::RefCell;
 use std::collections::HashMap;
-use std::fs::File;
+use std::io;
 use std::path::Path;
 use std::rc::Rc;
-use zip::read::ZipFile;
 use zip::ZipArchive;
 use crate::{Filters, FormatColor, xml};
 use crate::api::cell::location::{Location, LocationRange};
@@ -22,16 +34,21 @@ use crate::api::worksheet::read::Read;
 use crate::api::worksheet::row::WorkSheetRow;
 use crate::api::worksheet::write::Write;
 use crate::api::workbook::Workbook as ApiWorkbook;
-use crate::file::XlsxFileType;
-use crate::result::{WorkSheetError, WorkSheetResult};
+use crate::file::{XlsxFileType, XlsxFileWriter};
+use crate::result::WorkSheetResult;
+use crate::utils::zip_util;
+use crate::xml::chart::ChartSpace;
+use crate::xml::comments::Comments;
 use crate::xml::drawings::Drawings;
+use crate::xml::drawings::header_footer_drawing::HeaderFooterDrawing;
 use crate::xml::drawings::vml_drawing::VmlDrawing;
 use crate::xml::io::IoV2;
 use crate::xml::metadata::Metadata;
 use crate::xml::relationships::Relationships;
 use crate::xml::shared_string::SharedString;
+use crate::xml::table::Table;
 use crate::xml::worksheet::WorkSheet as XmlWorkSheet;
-use crate::xml::workbook::Workbook;
+use crate::xml::workbook::{check_sheet_name, Workbook};
 use crate::xml::style::StyleSheet;
 
 #[derive(Debug)]
@@ -47,11 +64,32 @@ pub struct WorkSheet {
     style_sheet: Rc<RefCell<StyleSheet>>,
     content_types: Rc<RefCell<xml::content_types::ContentTypes>>,
     medias: Rc<RefCell<xml::medias::Medias>>,
+    comments: Option<Comments>,
     vml_drawing: Option<VmlDrawing>,
+    header_footer_drawing: Option<HeaderFooterDrawing>,
+    // Tables with the ids of their files
+    tables: Vec<(u32, Table)>,
     drawings: Option<Drawings>,
     drawings_rel: Option<Relationships>,
+    // Charts of the drawing with the ids of their files
+    charts: Vec<(u32, ChartSpace)>,
     metadata: Rc<RefCell<Metadata>>,
-    shared_string: Rc<SharedString>,
+    shared_string: Rc<RefCell<SharedString>>,
+    // Parts copied from another workbook with their new names, saved unless they are rewritten
+    imported_parts: Vec<(String, Vec<u8>)>,
+}
+
+///
+/// The parts of the workbook shared by the worksheets loaded from it
+///
+pub(crate) struct WorkbookParts {
+    pub(crate) workbook: Rc<RefCell<Workbook>>,
+    pub(crate) workbook_rel: Rc<RefCell<Relationships>>,
+    pub(crate) style_sheet: Rc<RefCell<StyleSheet>>,
+    pub(crate) content_types: Rc<RefCell<xml::content_types::ContentTypes>>,
+    pub(crate) medias: Rc<RefCell<xml::medias::Medias>>,
+    pub(crate) metadata: Rc<RefCell<Metadata>>,
+    pub(crate) shared_string: Rc<RefCell<SharedString>>,
 }
 
 impl Write for WorkSheet {}
@@ -60,20 +98,69 @@ impl WorkSheetCol for WorkSheet {}
 impl Read for WorkSheet {}
 
 impl WorkSheet {
-    pub(crate) fn save_as<P: AsRef<Path>>(&self, file_path: P) -> WorkSheetResult<()> {
-        self.worksheet.save(&file_path, &self.target);
-        self.worksheet_rel.save(&file_path, XlsxFileType::WorksheetRels(self.target_id));
+    pub(crate) fn shared_string_count(&self) -> usize {
+        self.worksheet.sheet_data.shared_string_count()
+    }
+
+    pub(crate) fn formula_cells(&self) -> Vec<(u32, u32)> {
+        self.worksheet.sheet_data.formula_cells()
+    }
+
+    ///
+    /// The parts the worksheet leads to now with their relationships, the media left out as other parts may show them
+    ///
+    pub(crate) fn part_names(&self) -> Vec<String> {
+        let sheet_part = format!("xl/{}", self.target);
+        let mut parts = vec![zip_util::rels_part_name(&sheet_part), sheet_part.clone()];
+        let add_targets = |part_name: &str, rels: &Relationships, parts: &mut Vec<String>| {
+            for target in rels.get_internal_targets() {
+                let target = zip_util::resolve_target(part_name, target);
+                if !target.starts_with("xl/media/") {
+                    parts.push(target);
+                }
+            }
+        };
+        add_targets(&sheet_part, &self.worksheet_rel, &mut parts);
+        if let (Some(drawings_rel), Some(id)) = (&self.drawings_rel, self.worksheet_rel.get_drawings_rid()) {
+            let drawing_part = format!("xl/drawings/drawing{id}.xml");
+            parts.push(zip_util::rels_part_name(&drawing_part));
+            add_targets(&drawing_part, drawings_rel, &mut parts);
+        }
+        if let Some(header_footer_drawing) = &self.header_footer_drawing {
+            parts.push(format!("xl/drawings/_rels/vmlDrawing{}.vml.rels", header_footer_drawing.id));
+        }
+        parts
+    }
+
+    ///
+    /// Save the files of the sheet, the formulas with the cached values they have
+    ///
+    pub(crate) fn save_as(&self, writer: &mut XlsxFileWriter) -> WorkSheetResult<()> {
+        self.imported_parts.iter().for_each(|(part_name, content)| writer.write_part(part_name, content));
+        self.worksheet.save(writer, &self.target);
+        self.worksheet_rel.save(writer, XlsxFileType::WorksheetRels(self.target_id));
         if let Some(id) = self.worksheet_rel.get_drawings_rid() {
             if let Some(drawings) = &self.drawings {
-                drawings.save(&file_path, id);
+                drawings.save(writer, id);
             }
             if let Some(drawings_rel) = &self.drawings_rel {
-                drawings_rel.save(&file_path, XlsxFileType::DrawingRels(id));
+                drawings_rel.save(writer, XlsxFileType::DrawingRels(id));
+            }
+            for (id, chart) in &self.charts {
+                chart.save(writer, *id);
             }
         }
-        if let Some(id) = self.worksheet_rel.get_vml_drawing_rid() {
-            // self.vml_drawing.as_ref().unwrap().save(&file_path, id);
-            // self.vml_drawing.as_ref().unwrap().save(&file_path, id);
+        if let (Some(comments), Some(id)) = (&self.comments, self.worksheet_rel.get_comments_id()) {
+            comments.save(writer, id);
+        }
+        if let Some(vml_drawing) = &self.vml_drawing {
+            vml_drawing.save(writer);
+        }
+        if let Some(header_footer_drawing) = &self.header_footer_drawing {
+            header_footer_drawing.save(writer);
+        }
+        for (id, table) in &self.tables {
+            table.save(writer, *id);
         }
         Ok(())
     }
@@ -113,22 +200,34 @@ impl WorkSheet {
         &self.name
     }
 
+    ///
+    /// Rename the worksheet, the references to it in its own formulas and in the defined names follow it.
+    /// [`Workbook::rename_worksheet`](crate::Workbook::rename_worksheet) also rewrites the other worksheets.
+    ///
     pub fn set_name(&mut self, name: &str) -> WorkSheetResult<()> {
-        let workbook = &mut self.workbook.borrow_mut();
-        let name_existed = workbook.sheets.sheets
-            .iter()
-            .filter(|sheet| sheet.name == name)
-            .count() > 0;
-        if name_existed {
-            Err(WorkSheetError::DuplicatedSheets)
-        } else {
-            workbook.sheets.sheets
-                .iter_mut()
-                .filter(|sheet| sheet.name == self.name)
-                .for_each(|sheet| sheet.name = name.to_string());
-            self.name = name.to_string();
-            Ok(())
-        }
+        check_sheet_name(name)?;
+        let old_name = self.name.clone();
+        self.workbook.borrow_mut().rename_sheet(&old_name, name)?;
+        self.name = name.to_string();
+        self.worksheet.rename_sheet(&old_name, name);
+        self.charts.iter_mut().for_each(|(_, chart)| chart.rename_sheet(&old_name, name));
+        Ok(())
+    }
+
+    ///
+    /// Rewrite the formulas, the links and the charts referring to another worksheet renamed
+    ///
+    pub(crate) fn rename_sheet(&mut self, old_name: &str, new_name: &str) {
+        self.worksheet.rename_sheet(old_name, new_name);
+        self.charts.iter_mut().for_each(|(_, chart)| chart.rename_sheet(old_name, new_name));
+    }
+
+    ///
+    /// Make the formulas, the links and the charts referring to a removed worksheet `#REF!`
+    ///
+    pub(crate) fn remove_sheet(&mut self, sheet_name: &str) {
+        self.worksheet.remove_sheet(sheet_name);
+        self.charts.iter_mut().for_each(|(_, chart)| chart.remove_sheet(sheet_name));
     }
 
     pub fn activate(&mut self) {
@@ -234,6 +333,16 @@ impl WorkSheet {
         sheet.state = Some(String::from("hidden"));
     }
 
+    ///
+    /// Drop the cached values of the formulas in the worksheet, so that they are not read as stale results.
+    /// If any value was dropped, the workbook is flagged to be fully recalculated when it is opened.
+    ///
+    pub fn clear_formula_values(&mut self) {
+        if self.worksheet.sheet_data.clear_formula_values() {
+            self.workbook.borrow_mut().set_full_calc_on_load();
+        }
+    }
+
     pub fn set_tab_color(&mut self, tab_color: &FormatColor) {
         self.worksheet.set_tab_color(tab_color);
     }
@@ -246,9 +355,19 @@ impl WorkSheet {
 
     pub fn insert_image<L: LocationRange, P: AsRef<Path>>(&mut self, loc_range: L, filename: &P) -> WorkSheetResult<()> {
         let (from_row, from_col, to_row, to_col) = loc_range.to_range();
-        let r_id = self.add_drawing((from_row, from_col, to_row, to_col), filename)?;
-        self.worksheet.insert_image(r_id);
-        Ok(())
+        self.add_drawing((from_row, from_col, to_row, to_col), filename)
+    }
+
+    ///
+    /// The drawing of the worksheet with its relationships, added if the worksheet has none
+    ///
+    pub(crate) fn get_or_add_drawings(&mut self) -> (&mut Drawings, &mut Relationships) {
+        if self.worksheet_rel.get_drawings_rid().is_none() {
+            let id = self.content_types.borrow_mut().add_drawing();
+            let r_id = self.worksheet_rel.add_drawings(id);
+            self.worksheet.insert_image(r_id);
+        }
+        (self.drawings.get_or_insert(Drawings::default()), self.drawings_rel.get_or_insert(Relationships::default()))
     }
 
     pub fn id(&self) -> u32 {
@@ -275,11 +394,16 @@ impl WorkSheet {
             style_sheet: workbook.style_sheet.clone(),
             content_types: workbook.content_types.clone(),
             medias: workbook.medias.clone(),
+            comments: None,
             vml_drawing: None,
+            header_footer_drawing: None,
+            tables: vec![],
             drawings: None,
             drawings_rel: None,
+            charts: vec![],
             metadata: workbook.metadata.clone(),
             shared_string: workbook.shared_string.clone(),
+            imported_parts: vec![],
         }
     }
 
@@ -289,7 +413,7 @@ impl WorkSheet {
         target_id: u32,
         worksheet: &WorkSheet,
     ) -> WorkSheet {
-        Self {
+        let mut copy = Self {
             id: sheet_id,
             name: name.to_string(),
             target: format!("worksheets/sheet{target_id}.xml"),
@@ -301,61 +425,89 @@ impl WorkSheet {
             style_sheet: worksheet.style_sheet.clone(),
             content_types: worksheet.content_types.clone(),
             medias: worksheet.medias.clone(),
-            vml_drawing: None,
+            comments: worksheet.comments.clone(),
+            vml_drawing: worksheet.vml_drawing.clone(),
+            header_footer_drawing: worksheet.header_footer_drawing.clone(),
+            tables: worksheet.tables.clone(),
             drawings: worksheet.drawings.clone(),
             drawings_rel: worksheet.drawings_rel.clone(),
+            charts: worksheet.charts.clone(),
             metadata: worksheet.metadata.clone(),
             shared_string: worksheet.shared_string.clone(),
-        }
+            // the parts copied with the worksheet are saved by it
+            imported_parts: vec![],
+        };
+        copy.copy_tables();
+        copy.copy_comments();
+        copy.copy_drawings(&worksheet.name);
+        copy
     }
 
-    pub(crate) fn from_archive<P: AsRef<Path>>(
+    pub(crate) fn from_archive<R: io::Read + io::Seek>(
         sheet_id: u32,
         name: &str,
         target: &str,
         target_id: u32,
-        file_path: P,
-        archive: &mut ZipArchive<File>,
-        workbook: Rc<RefCell<Workbook>>,
-        workbook_rel: Rc<RefCell<Relationships>>,
-        style_sheet: Rc<RefCell<StyleSheet>>,
-        content_types: Rc<RefCell<xml::content_types::ContentTypes>>,
-        medias: Rc<RefCell<xml::medias::Medias>>,
-        metadata: Rc<RefCell<Metadata>>,
-        shared_string: Rc<SharedString>,
+        archive: &mut ZipArchive<R>,
+        parts: &WorkbookParts,
     ) -> WorkSheet {
         // Read worksheet from zip dir
         let mut worksheet = XmlWorkSheet::from_zip_file(archive, &format!("xl/{target}")).unwrap_or_default();
-        worksheet.sheet_data.clean_formula_value();
+        worksheet.retain_known_extensions();
         let worksheet_rel_id: String = target.chars().filter(|&c| c >= '0' && c <= '9').collect();
         let worksheet_rel = Relationships::from_zip_file(archive, &format!("xl/worksheets/_rels/sheet{worksheet_rel_id}.xml.rels")).unwrap_or_default();
         // load drawings
         let (mut drawings, mut drawings_rel) = (None, None);
         if let Some(drawings_id) = worksheet_rel.get_drawings_rid() {
-            drawings = Drawings::from_zip_file(archive, &format!("xl/drawings/drawing{drawings_id}.xml"));
+            drawings = Drawings::from_archive(archive, drawings_id);
             drawings_rel = Relationships::from_zip_file(archive, &format!("xl/drawings/_rels/drawing{drawings_id}.xml.rels"));
         };
-        let vml_drawing = match worksheet_rel.get_vml_drawing_rid() {
-            Some(vml_drawing_id) => VmlDrawing::from_path(&file_path, vml_drawing_id).ok(),
-            None => None
-        };
+        // load the charts of the drawings
+        let charts = drawings_rel.as_ref().map_or(vec![], |drawings_rel| drawings_rel.get_chart_ids())
+            .into_iter()
+            .filter_map(|chart_id| Some((chart_id, ChartSpace::from_archive(archive, chart_id)?)))
+            .collect();
+        // load comments and the legacy drawing showing them
+        let comments = worksheet_rel.get_comments_id()
+            .and_then(|comments_id| Comments::from_zip_file(archive, &format!("xl/comments{comments_id}.xml")));
+        let vml_drawing = worksheet.get_legacy_drawing_rid()
+            .and_then(|r_id| worksheet_rel.get_target_id(r_id))
+            .and_then(|vml_drawing_id| VmlDrawing::from_archive(archive, vml_drawing_id));
+        // load the legacy drawing of the images of the headers and the footers
+        let header_footer_drawing = worksheet.get_legacy_drawing_hf_rid()
+            .and_then(|r_id| worksheet_rel.get_target_id(r_id))
+            .and_then(|vml_drawing_id| HeaderFooterDrawing::from_archive(archive, vml_drawing_id));
+        // load tables
+        let mut tables = vec![];
+        for table_id in worksheet.get_table_rids().into_iter().filter_map(|r_id| worksheet_rel.get_target_id(r_id)) {
+            if let Some(table) = Table::from_zip_file(archive, &format!("xl/tables/table{table_id}.xml")) {
+                parts.content_types.borrow_mut().add_existed_table(table.get_id());
+                parts.workbook.borrow_mut().set_table_name(table_id, &table.to_api_table().name);
+                tables.push((table_id, table));
+            }
+        }
         WorkSheet {
             id: sheet_id,
             name: String::from(name),
             target: format!("{target}"),
             target_id,
-            workbook,
-            workbook_rel,
+            workbook: Rc::clone(&parts.workbook),
+            workbook_rel: Rc::clone(&parts.workbook_rel),
             worksheet,
             worksheet_rel,
-            style_sheet,
-            content_types,
-            medias,
+            style_sheet: Rc::clone(&parts.style_sheet),
+            content_types: Rc::clone(&parts.content_types),
+            medias: Rc::clone(&parts.medias),
+            comments,
             vml_drawing,
+            header_footer_drawing,
+            tables,
             drawings,
             drawings_rel,
-            metadata,
-            shared_string,
+            charts,
+            metadata: Rc::clone(&parts.metadata),
+            shared_string: Rc::clone(&parts.shared_string),
+            imported_parts: vec![],
         }
     }
 }
\ No newline at end of file
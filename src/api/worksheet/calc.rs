@@ -0,0 +1,10 @@
+use crate::{Value, WorkSheet};
+
+impl WorkSheet {
+    ///
+    /// Store the results of the formulas as the cached values of their cells
+    ///
+    pub(crate) fn set_formula_values(&mut self, values: &[((u32, u32), Value)]) {
+        self.worksheet.sheet_data.set_formula_values(values);
+    }
+}
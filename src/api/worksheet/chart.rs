@@ -0,0 +1,34 @@
+use crate::api::cell::location::LocationRange;
+use crate::api::chart::Chart;
+use crate::api::worksheet::WorkSheet;
+use crate::result::{ChartError, WorkSheetResult};
+use crate::xml::chart::ChartSpace;
+
+impl WorkSheet {
+    ///
+    /// Insert a chart over a range of cells, the chart is moved and sized with them
+    ///
+    pub fn insert_chart<L: LocationRange>(&mut self, loc_range: L, chart: &Chart) -> WorkSheetResult<()> {
+        if chart.series.is_empty() {
+            return Err(ChartError::NoSeries.into());
+        }
+        if !(1..=48).contains(&chart.style) {
+            return Err(ChartError::InvalidStyle.into());
+        }
+        let id = self.content_types.borrow_mut().add_chart();
+        let (drawings, drawings_rel) = self.get_or_add_drawings();
+        let r_id = drawings_rel.add_chart(id);
+        drawings.add_chart(loc_range, r_id);
+        self.charts.push((id, ChartSpace::new(chart)));
+        Ok(())
+    }
+
+    ///
+    /// The charts of the worksheet, except the kinds of charts not supported
+    ///
+    pub fn charts(&self) -> Vec<Chart> {
+        self.charts.iter()
+            .filter_map(|(_, chart)| chart.to_api_chart())
+            .collect()
+    }
+}
@@ -0,0 +1,65 @@
+use crate::api::cell::location::Location;
+use crate::api::comment::Comment;
+use crate::api::worksheet::WorkSheet;
+use crate::result::WorkSheetResult;
+use crate::xml::comments::Comments;
+use crate::xml::drawings::vml_drawing::VmlDrawing;
+
+impl WorkSheet {
+    ///
+    /// Add a comment to a cell, an existing comment of the cell is replaced
+    ///
+    pub fn write_comment<L: Location>(&mut self, loc: L, text: &str, author: &str) -> WorkSheetResult<()> {
+        self.write_comment_with_options(loc, &Comment::new(text).set_author(author))
+    }
+
+    ///
+    /// Add a comment to a cell with its visibility and size
+    ///
+    pub fn write_comment_with_options<L: Location>(&mut self, loc: L, comment: &Comment) -> WorkSheetResult<()> {
+        let comments = match self.comments.as_mut() {
+            Some(comments) => comments,
+            None => {
+                if self.worksheet_rel.get_comments_id().is_none() {
+                    let id = self.content_types.borrow_mut().add_comments();
+                    self.worksheet_rel.add_comments(id);
+                }
+                self.comments.insert(Comments::default())
+            }
+        };
+        comments.set_comment(&loc, &comment.text, &comment.author);
+        let vml_drawing = match self.vml_drawing.as_mut() {
+            Some(vml_drawing) => vml_drawing,
+            None => {
+                let legacy_drawing_id = self.worksheet.get_legacy_drawing_rid()
+                    .and_then(|r_id| self.worksheet_rel.get_target_id(r_id));
+                let id = match legacy_drawing_id {
+                    Some(id) => id,
+                    None => {
+                        let id = self.content_types.borrow_mut().add_vml_drawing();
+                        let r_id = self.worksheet_rel.add_vml_drawing(id);
+                        self.worksheet.set_legacy_drawing(r_id);
+                        id
+                    }
+                };
+                self.vml_drawing.insert(VmlDrawing::new(id))
+            }
+        };
+        vml_drawing.set_note(&loc, comment.visible, comment.width, comment.height);
+        Ok(())
+    }
+
+    ///
+    /// Read the comment of a cell, [`None`] if the cell has no comment
+    ///
+    pub fn read_comment<L: Location>(&self, loc: L) -> Option<Comment> {
+        let (text, author) = self.comments.as_ref()?.get_comment(&loc)?;
+        let mut comment = Comment::new(&text).set_author(&author.unwrap_or_default());
+        if let Some((visible, width, height)) = self.vml_drawing.as_ref().and_then(|vml_drawing| vml_drawing.get_note(&loc)) {
+            comment.visible = visible;
+            comment.width = width;
+            comment.height = height;
+        }
+        Some(comment)
+    }
+}
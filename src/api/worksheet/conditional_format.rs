@@ -0,0 +1,64 @@
+use crate::api::cell::location::{Location, LocationRange};
+use crate::api::conditional_format::{ConditionalFormat, ConditionalFormatRule};
+use crate::api::worksheet::WorkSheet;
+use crate::result::WorkSheetResult;
+use crate::xml::extension::x14_conditional_formattings::X14CfRule;
+use crate::xml::worksheet::conditional_formatting::CfRule;
+
+impl WorkSheet {
+    ///
+    /// Format the cells of a range matching a rule.
+    /// The rules added later have lower priorities than the existing ones.
+    /// Data bars and the icon sets added in Excel 2010 are also written in the extension list of the worksheet.
+    ///
+    pub fn add_conditional_format<L: LocationRange>(&mut self, loc_range: L, conditional_format: &ConditionalFormat) -> WorkSheetResult<()> {
+        let (first_row, first_col, _, _) = loc_range.to_range();
+        let mut cf_rule = CfRule::from_api_rule(&conditional_format.rule, &(first_row, first_col).to_ref());
+        cf_rule.dxf_id = conditional_format.format.as_ref()
+            .map(|format| self.style_sheet.borrow_mut().add_dxf(format));
+        cf_rule.set_stop_if_true(conditional_format.stop_if_true);
+        let x14_cf_rule = X14CfRule::from_api_rule(&conditional_format.rule);
+        self.worksheet.add_conditional_formatting(&loc_range, cf_rule, x14_cf_rule);
+        Ok(())
+    }
+
+    ///
+    /// The conditional formats of the worksheet with the ranges they apply to, in priority order
+    ///
+    pub fn conditional_formats(&self) -> Vec<(String, ConditionalFormat)> {
+        let style_sheet = self.style_sheet.borrow();
+        let x14_conditional_formatting = self.worksheet.get_x14_conditional_formatting();
+        let x14_cf_rules = x14_conditional_formatting.iter()
+            .flat_map(|conditional_formatting| conditional_formatting.cf_rule.iter()
+                .map(|x14_cf_rule| (conditional_formatting.sqref.as_str(), x14_cf_rule)))
+            .collect::<Vec<_>>();
+        let mut conditional_formats = self.worksheet.get_conditional_formatting().iter()
+            .flat_map(|conditional_formatting| conditional_formatting.get_rules().iter()
+                .map(|cf_rule| (conditional_formatting.get_sqref(), cf_rule)))
+            .filter_map(|(sqref, cf_rule)| {
+                let mut rule = cf_rule.to_api_rule()?;
+                let x14_cf_rule = cf_rule.get_x14_id()
+                    .and_then(|id| x14_cf_rules.iter().find(|(_, x14_cf_rule)| x14_cf_rule.id.as_deref() == Some(id)));
+                if let (ConditionalFormatRule::DataBar(data_bar), Some((_, x14_cf_rule))) = (&mut rule, x14_cf_rule) {
+                    x14_cf_rule.update_data_bar(data_bar);
+                }
+                let conditional_format = ConditionalFormat {
+                    rule,
+                    format: cf_rule.dxf_id.and_then(|dxf_id| style_sheet.get_dxf_format(dxf_id)),
+                    stop_if_true: cf_rule.is_stop_if_true(),
+                };
+                Some((cf_rule.priority, sqref.to_string(), conditional_format))
+            })
+            .collect::<Vec<_>>();
+        // the rules only known by Excel 2010 and later have their own priorities
+        conditional_formats.extend(x14_cf_rules.iter()
+            .filter_map(|(sqref, x14_cf_rule)| {
+                let priority = x14_cf_rule.priority?;
+                Some((priority, sqref.to_string(), ConditionalFormat::new(x14_cf_rule.to_api_rule()?)))
+            }));
+        conditional_formats.sort_by_key(|(priority, _, _)| *priority);
+        conditional_formats.into_iter()
+            .map(|(_, sqref, conditional_format)| (sqref, conditional_format))
+            .collect()
+    }
+}
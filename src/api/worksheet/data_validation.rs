@@ -0,0 +1,30 @@
+use crate::api::cell::location::LocationRange;
+use crate::api::data_validation::{DataValidation, DataValidationType};
+use crate::api::worksheet::WorkSheet;
+use crate::result::{DataValidationError, WorkSheetResult};
+
+impl WorkSheet {
+    ///
+    /// Restrict the values of the cells in a range
+    ///
+    pub fn add_data_validation<L: LocationRange>(&mut self, loc_range: L, data_validation: &DataValidation) -> WorkSheetResult<()> {
+        match (data_validation.validation_type, &data_validation.formula1) {
+            (DataValidationType::Any, _) => {}
+            (_, None) => return Err(DataValidationError::MissingFormula.into()),
+            // the literal values of a list are quoted, and limited to 255 characters
+            (DataValidationType::List, Some(formula)) if formula.starts_with('"') && formula.chars().count() > 257 => {
+                return Err(DataValidationError::ListTooLong.into());
+            }
+            _ => {}
+        }
+        self.worksheet.add_data_validation(&loc_range, data_validation);
+        Ok(())
+    }
+
+    ///
+    /// The data validations of the worksheet with the ranges they apply to, like `A1:A10 C1:C10`
+    ///
+    pub fn data_validations(&self) -> Vec<(String, DataValidation)> {
+        self.worksheet.get_data_validations()
+    }
+}
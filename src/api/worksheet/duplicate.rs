@@ -0,0 +1,84 @@
+use std::collections::HashMap;
+use crate::api::worksheet::WorkSheet;
+use crate::utils::reference_helper::rename_tables_in_formula;
+
+impl WorkSheet {
+    ///
+    /// Give the tables of a copy of a worksheet files of their own, named like `Table1_2` after the tables copied.
+    /// The structured references of the copy follow its tables.
+    ///
+    pub(crate) fn copy_tables(&mut self) {
+        let mut targets = HashMap::new();
+        let mut renamed = vec![];
+        for (id, table) in &mut self.tables {
+            let new_id = self.content_types.borrow_mut().add_table();
+            let name = table.to_api_table().name;
+            let new_name = self.workbook.borrow().free_copied_table_name(&name);
+            table.import(new_id, &new_name, &mut |dxf_id| dxf_id);
+            self.workbook.borrow_mut().set_table_name(new_id, &new_name);
+            targets.insert(format!("../tables/table{id}.xml"), format!("../tables/table{new_id}.xml"));
+            *id = new_id;
+            renamed.push((name, new_name));
+        }
+        self.retarget_parts(&targets);
+        if !renamed.is_empty() {
+            let mut rename = |formula: &str| rename_tables_in_formula(formula, &renamed);
+            self.worksheet.map_formulas(&mut rename);
+            self.tables.iter_mut().for_each(|(_, table)| table.map_formulas(&mut rename));
+        }
+    }
+
+    ///
+    /// Give the comments and the legacy drawings of a copy of a worksheet files of their own
+    ///
+    pub(crate) fn copy_comments(&mut self) {
+        let mut targets = HashMap::new();
+        if let (Some(comments), Some(id)) = (&self.comments, self.worksheet_rel.get_comments_id()) {
+            let new_id = self.content_types.borrow_mut().add_comments();
+            self.comments = Some(comments.copy());
+            targets.insert(format!("../comments{id}.xml"), format!("../comments{new_id}.xml"));
+        }
+        if let Some(vml_drawing) = &self.vml_drawing {
+            let new_id = self.content_types.borrow_mut().add_vml_drawing();
+            targets.insert(format!("../drawings/vmlDrawing{}.vml", vml_drawing.id), format!("../drawings/vmlDrawing{new_id}.vml"));
+            self.vml_drawing = Some(vml_drawing.copy(new_id));
+        }
+        if let Some(header_footer_drawing) = &self.header_footer_drawing {
+            let new_id = self.content_types.borrow_mut().add_vml_drawing();
+            targets.insert(format!("../drawings/vmlDrawing{}.vml", header_footer_drawing.id), format!("../drawings/vmlDrawing{new_id}.vml"));
+            self.header_footer_drawing = Some(header_footer_drawing.copy(new_id));
+        }
+        self.retarget_parts(&targets);
+    }
+
+    ///
+    /// Give the drawing of a copy of a worksheet and its charts files of their own,
+    /// the charts refer to the cells of the copy instead of the worksheet copied
+    ///
+    pub(crate) fn copy_drawings(&mut self, source_name: &str) {
+        let (Some(_), Some(id)) = (&self.drawings, self.worksheet_rel.get_drawings_rid()) else {
+            return;
+        };
+        let new_id = self.content_types.borrow_mut().add_drawing();
+        let mut chart_targets = HashMap::new();
+        for (chart_id, chart) in &mut self.charts {
+            let new_chart_id = self.content_types.borrow_mut().add_chart();
+            *chart = chart.copy();
+            chart.rename_sheet(source_name, &self.name);
+            chart_targets.insert(format!("../charts/chart{chart_id}.xml"), format!("../charts/chart{new_chart_id}.xml"));
+            *chart_id = new_chart_id;
+        }
+        if let Some(drawings_rel) = &mut self.drawings_rel {
+            drawings_rel.retarget(|target| Some(chart_targets.get(target).cloned().unwrap_or(target.to_string())));
+        }
+        let targets = HashMap::from([(format!("../drawings/drawing{id}.xml"), format!("../drawings/drawing{new_id}.xml"))]);
+        self.retarget_parts(&targets);
+    }
+
+    ///
+    /// Point the relationships of the worksheet to the parts copied
+    ///
+    fn retarget_parts(&mut self, targets: &HashMap<String, String>) {
+        self.worksheet_rel.retarget(|target| Some(targets.get(target).cloned().unwrap_or(target.to_string())));
+    }
+}
@@ -1,9 +1,11 @@
 use crate::api::worksheet::WorkSheet;
-use crate::Format;
+use crate::{ExcelDateTime, Format};
 
 pub(crate) trait _Format {
     fn add_format(&mut self, format: &Format) -> u32;
     fn get_format(&self, style_id: u32) -> Format;
+    fn add_date_format(&mut self, style_id: Option<u32>, date_time: &ExcelDateTime) -> u32;
+    fn is_date_style(&self, style_id: u32) -> bool;
 }
 
 impl _Format for WorkSheet {
@@ -16,4 +18,12 @@ impl _Format for WorkSheet {
         self.style_sheet.borrow().update_format(&mut format, style_id);
         format
     }
-}
\ No newline at end of file
+
+    fn add_date_format(&mut self, style_id: Option<u32>, date_time: &ExcelDateTime) -> u32 {
+        self.style_sheet.borrow_mut().add_date_format(style_id, date_time.default_num_fmt_id())
+    }
+
+    fn is_date_style(&self, style_id: u32) -> bool {
+        self.style_sheet.borrow().is_date_style(style_id)
+    }
+}
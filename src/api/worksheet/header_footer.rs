@@ -0,0 +1,161 @@
+use crate::api::header_footer::HeaderFooter;
+use crate::api::worksheet::WorkSheet;
+use crate::result::{HeaderFooterError, WorkSheetResult};
+use crate::utils::image_util::image_size;
+use crate::xml::drawings::header_footer_drawing::HeaderFooterDrawing;
+
+///
+/// The headers and the footers of a worksheet, by their names in the legacy drawing of their images
+///
+#[derive(Clone, Copy)]
+enum Kind {
+    OddHeader,
+    OddFooter,
+    EvenHeader,
+    EvenFooter,
+    FirstHeader,
+    FirstFooter,
+}
+
+impl Kind {
+    ///
+    /// The names of the pictures in the left, center and right sections, like `LH` or `CFFIRST`
+    ///
+    fn positions(self) -> [String; 3] {
+        let (part, page) = match self {
+            Kind::OddHeader => ("H", ""),
+            Kind::OddFooter => ("F", ""),
+            Kind::EvenHeader => ("H", "EVEN"),
+            Kind::EvenFooter => ("F", "EVEN"),
+            Kind::FirstHeader => ("H", "FIRST"),
+            Kind::FirstFooter => ("F", "FIRST"),
+        };
+        ["L", "C", "R"].map(|section| format!("{section}{part}{page}"))
+    }
+}
+
+impl WorkSheet {
+    ///
+    /// The header of the printed pages, or of the odd pages when the even pages have their own header
+    ///
+    pub fn set_header(&mut self, header: &HeaderFooter) -> WorkSheetResult<()> {
+        self.set_header_footer(Kind::OddHeader, header)
+    }
+
+    ///
+    /// The footer of the printed pages, or of the odd pages when the even pages have their own footer
+    ///
+    pub fn set_footer(&mut self, footer: &HeaderFooter) -> WorkSheetResult<()> {
+        self.set_header_footer(Kind::OddFooter, footer)
+    }
+
+    ///
+    /// The header of the even pages, which are then different from the odd pages
+    ///
+    pub fn set_even_header(&mut self, header: &HeaderFooter) -> WorkSheetResult<()> {
+        self.set_header_footer(Kind::EvenHeader, header)?;
+        self.different_odd_even(true);
+        Ok(())
+    }
+
+    pub fn set_even_footer(&mut self, footer: &HeaderFooter) -> WorkSheetResult<()> {
+        self.set_header_footer(Kind::EvenFooter, footer)?;
+        self.different_odd_even(true);
+        Ok(())
+    }
+
+    ///
+    /// The header of the first page, which is then different from the other pages
+    ///
+    pub fn set_first_header(&mut self, header: &HeaderFooter) -> WorkSheetResult<()> {
+        self.set_header_footer(Kind::FirstHeader, header)?;
+        self.different_first(true);
+        Ok(())
+    }
+
+    pub fn set_first_footer(&mut self, footer: &HeaderFooter) -> WorkSheetResult<()> {
+        self.set_header_footer(Kind::FirstFooter, footer)?;
+        self.different_first(true);
+        Ok(())
+    }
+
+    ///
+    /// Whether the first page has its own header and footer
+    ///
+    pub fn different_first(&mut self, different: bool) {
+        let header_footer = self.worksheet.header_footer.get_or_insert_with(Default::default);
+        header_footer.different_first = different.then_some(1);
+    }
+
+    ///
+    /// Whether the even pages have their own header and footer
+    ///
+    pub fn different_odd_even(&mut self, different: bool) {
+        let header_footer = self.worksheet.header_footer.get_or_insert_with(Default::default);
+        header_footer.different_odd_even = different.then_some(1);
+    }
+
+    ///
+    /// The header of the printed pages with the control codes of Excel, like `&CPage &P`
+    ///
+    pub fn get_header(&self) -> Option<String> {
+        self.worksheet.header_footer.as_ref().and_then(|header_footer| header_footer.odd_header.clone())
+    }
+
+    pub fn get_footer(&self) -> Option<String> {
+        self.worksheet.header_footer.as_ref().and_then(|header_footer| header_footer.odd_footer.clone())
+    }
+
+    fn set_header_footer(&mut self, kind: Kind, header_footer: &HeaderFooter) -> WorkSheetResult<()> {
+        let codes = header_footer.to_codes();
+        if codes.chars().count() > 255 {
+            return Err(HeaderFooterError::TooLong.into());
+        }
+        if header_footer.images.iter().any(|images| images.len() > 1) {
+            return Err(HeaderFooterError::TooManyImages.into());
+        }
+        let mut images = vec![];
+        for (position, image) in kind.positions().into_iter().zip(&header_footer.images) {
+            if let Some(filename) = image.first() {
+                let (width, height) = image_size(&std::fs::read(filename)?).ok_or(HeaderFooterError::UnknownImage)?;
+                images.push((position, filename, width, height));
+            }
+        }
+        // the images previously shown in the header or the footer are replaced
+        if let Some(drawing) = &mut self.header_footer_drawing {
+            kind.positions().iter().for_each(|position| drawing.remove_picture(position));
+        }
+        for (position, filename, width, height) in images {
+            let extension = filename.extension().unwrap_or_default().to_string_lossy().to_lowercase();
+            self.content_types.borrow_mut().add_bin(&extension);
+            let image_id = self.medias.borrow_mut().add_media(filename);
+            let title = filename.file_stem().unwrap_or_default().to_string_lossy().to_string();
+            // the images are shown at their size at 96 dpi
+            self.header_footer_drawing().set_picture(&position, image_id, &extension, &title, width as f64 * 0.75, height as f64 * 0.75);
+        }
+        let xml_header_footer = self.worksheet.header_footer.get_or_insert_with(Default::default);
+        let text = match kind {
+            Kind::OddHeader => &mut xml_header_footer.odd_header,
+            Kind::OddFooter => &mut xml_header_footer.odd_footer,
+            Kind::EvenHeader => &mut xml_header_footer.even_header,
+            Kind::EvenFooter => &mut xml_header_footer.even_footer,
+            Kind::FirstHeader => &mut xml_header_footer.first_header,
+            Kind::FirstFooter => &mut xml_header_footer.first_footer,
+        };
+        *text = Some(codes);
+        Ok(())
+    }
+
+    ///
+    /// The legacy drawing of the images of the headers and the footers, added if the worksheet has none
+    ///
+    fn header_footer_drawing(&mut self) -> &mut HeaderFooterDrawing {
+        if self.header_footer_drawing.is_none() {
+            let id = self.content_types.borrow_mut().add_vml_drawing();
+            let r_id = self.worksheet_rel.add_vml_drawing(id);
+            self.worksheet.set_legacy_drawing_hf(r_id);
+            self.header_footer_drawing = Some(HeaderFooterDrawing::new(id));
+        }
+        self.header_footer_drawing.as_mut().unwrap()
+    }
+}
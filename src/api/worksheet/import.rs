@@ -0,0 +1,64 @@
+use std::collections::HashMap;
+use std::rc::Rc;
+use crate::api::workbook::Workbook;
+use crate::api::worksheet::WorkSheet;
+use crate::utils::reference_helper::rename_tables_in_formula;
+
+impl WorkSheet {
+    ///
+    /// Take a worksheet loaded from parts copied from another workbook: its cells, columns, conditional formats
+    /// and tables are pointed to the styles and the shared strings copied here, and the copied parts are kept to be saved.
+    ///
+    pub(crate) fn import(&mut self, source: &Workbook, source_name: &str, parts: Vec<(String, Vec<u8>)>) {
+        let (style_sheet, shared_string) = (Rc::clone(&self.style_sheet), Rc::clone(&self.shared_string));
+        let (source_style_sheet, source_shared_string) = (source.style_sheet.borrow(), source.shared_string.borrow());
+        let (mut styles, mut dxfs) = (HashMap::new(), HashMap::new());
+        let mut style = |id| *styles.entry(id)
+            .or_insert_with(|| style_sheet.borrow_mut().import_xf(&source_style_sheet, id));
+        let mut dxf = |id| *dxfs.entry(id)
+            .or_insert_with(|| style_sheet.borrow_mut().import_dxf(&source_style_sheet, id));
+        let mut text = |id| shared_string.borrow_mut().import_item(&source_shared_string, id);
+        self.worksheet.import_ids(&mut style, &mut dxf, &mut text);
+        self.number_tables(&mut dxf);
+        if source_name != self.name {
+            let name = self.name.clone();
+            self.rename_sheet(source_name, &name);
+        }
+        self.imported_parts = parts;
+        // the tab selected in the other workbook is not selected here
+        self.deselect();
+    }
+
+    ///
+    /// Take the tables copied with their new ids. The tables named like another table or a defined name
+    /// are renamed after their ids, with the structured references of the worksheet to them.
+    ///
+    pub(crate) fn number_tables<D: FnMut(u32) -> u32>(&mut self, dxf: &mut D) {
+        self.tables.iter().for_each(|(id, _)| self.workbook.borrow_mut().remove_table_name(*id));
+        let mut renamed = vec![];
+        for (id, table) in &mut self.tables {
+            let name = table.to_api_table().name;
+            let new_name = match self.workbook.borrow().is_name_taken(&name) {
+                true => self.workbook.borrow().free_table_name(*id),
+                false => name.clone(),
+            };
+            table.import(*id, &new_name, dxf);
+            self.workbook.borrow_mut().set_table_name(*id, &new_name);
+            if new_name != name {
+                renamed.push((name, new_name));
+            }
+        }
+        if !renamed.is_empty() {
+            let mut rename = |formula: &str| rename_tables_in_formula(formula, &renamed);
+            self.worksheet.map_formulas(&mut rename);
+            self.tables.iter_mut().for_each(|(_, table)| table.map_formulas(&mut rename));
+        }
+    }
+
+    ///
+    /// The names of the parts copied from another workbook
+    ///
+    pub(crate) fn imported_part_names(&self) -> Vec<String> {
+        self.imported_parts.iter().map(|(part_name, _)| part_name.clone()).collect()
+    }
+}
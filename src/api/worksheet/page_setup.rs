@@ -0,0 +1,165 @@
+use crate::api::cell::formula::token::{CellReference, FormulaToken, LineReference, ParsedFormula, Reference, ReferenceArea};
+use crate::api::cell::location::LocationRange;
+use crate::api::page_setup::PaperSize;
+use crate::api::worksheet::WorkSheet;
+
+impl WorkSheet {
+    pub fn set_landscape(&mut self) {
+        self.worksheet.set_orientation("landscape");
+    }
+
+    pub fn set_portrait(&mut self) {
+        self.worksheet.set_orientation("portrait");
+    }
+
+    pub fn set_paper(&mut self, paper_size: PaperSize) {
+        self.worksheet.set_paper_size(paper_size.to_code());
+    }
+
+    pub fn get_paper(&self) -> Option<PaperSize> {
+        self.worksheet.get_paper_size().map(PaperSize::from_code)
+    }
+
+    ///
+    /// Print the worksheet at a percentage of its size, from 10 to 400
+    ///
+    pub fn set_print_scale(&mut self, scale: u32) {
+        self.worksheet.set_print_scale(scale.clamp(10, 400));
+    }
+
+    ///
+    /// Shrink the worksheet to print it on a number of pages across and down, 0 leaves a direction unlimited
+    ///
+    pub fn fit_to_pages(&mut self, width: u32, height: u32) {
+        self.worksheet.fit_to_pages(width, height);
+    }
+
+    ///
+    /// The margins of the printed pages in inches, the header and the footer are measured from the edges
+    ///
+    pub fn set_margins(&mut self, left: f64, right: f64, top: f64, bottom: f64, header: f64, footer: f64) {
+        self.worksheet.set_margins(left, right, top, bottom, header, footer);
+    }
+
+    pub fn center_horizontally(&mut self, center: bool) {
+        self.worksheet.set_print_options(|print_options| print_options.horizontal_centered = center.then_some(1));
+    }
+
+    pub fn center_vertically(&mut self, center: bool) {
+        self.worksheet.set_print_options(|print_options| print_options.vertical_centered = center.then_some(1));
+    }
+
+    pub fn print_gridlines(&mut self, print: bool) {
+        self.worksheet.set_print_options(|print_options| print_options.grid_lines = print.then_some(1));
+    }
+
+    ///
+    /// Print the row numbers and the column letters
+    ///
+    pub fn print_headings(&mut self, print: bool) {
+        self.worksheet.set_print_options(|print_options| print_options.headings = print.then_some(1));
+    }
+
+    ///
+    /// Start a new printed page after each of the rows, like `&[20, 40]` for the pages 1-20, 21-40 and 41-.
+    /// The previous breaks are replaced, and an empty slice removes them.
+    ///
+    pub fn set_row_breaks(&mut self, rows: &[u32]) {
+        self.worksheet.set_row_breaks(rows);
+    }
+
+    ///
+    /// The rows followed by a page break, in ascending order
+    ///
+    pub fn get_row_breaks(&self) -> Vec<u32> {
+        self.worksheet.get_row_breaks()
+    }
+
+    ///
+    /// Start a new printed page after each of the columns, given by their numbers like 3 for the column C
+    ///
+    pub fn set_column_breaks(&mut self, cols: &[u32]) {
+        self.worksheet.set_col_breaks(cols);
+    }
+
+    pub fn get_column_breaks(&self) -> Vec<u32> {
+        self.worksheet.get_col_breaks()
+    }
+
+    ///
+    /// Print only a range of the worksheet, like `A1:F40`
+    ///
+    pub fn set_print_area<L: LocationRange>(&mut self, loc_range: L) {
+        let (first_row, first_col, last_row, last_col) = loc_range.to_range();
+        let first = CellReference { row: first_row, col: first_col, row_absolute: true, col_absolute: true };
+        let last = CellReference { row: last_row, col: last_col, row_absolute: true, col_absolute: true };
+        let print_area = self.sheet_reference(ReferenceArea::Range(first, last)).to_string();
+        self.set_local_name("_xlnm.Print_Area", &print_area);
+    }
+
+    ///
+    /// Print a range of rows at the top of every page, like `1:2`
+    ///
+    pub fn repeat_rows<R: LocationRange>(&mut self, row_range: R) {
+        let (first, last) = row_range.to_row_range();
+        self.set_print_titles(ReferenceArea::Rows(LineReference { index: first, absolute: true }, LineReference { index: last, absolute: true }));
+    }
+
+    ///
+    /// Print a range of columns at the left of every page, like `A:B`
+    ///
+    pub fn repeat_columns<R: LocationRange>(&mut self, col_range: R) {
+        let (first, last) = col_range.to_col_range();
+        self.set_print_titles(ReferenceArea::Columns(LineReference { index: first, absolute: true }, LineReference { index: last, absolute: true }));
+    }
+
+    ///
+    /// Set the titles of the rows or of the columns, keeping the other ones:
+    /// the titles are written as the columns, then the rows, like `Sheet1!$A:$A,Sheet1!$1:$2`
+    ///
+    fn set_print_titles(&mut self, area: ReferenceArea) {
+        let rows = matches!(area, ReferenceArea::Rows(..));
+        let mut titles = self.local_name("_xlnm.Print_Titles")
+            .map(|value| ParsedFormula::parse(&value).references().into_iter()
+                .filter(|reference| match reference.area {
+                    ReferenceArea::Rows(..) => !rows,
+                    ReferenceArea::Columns(..) => rows,
+                    _ => false,
+                })
+                .cloned()
+                .collect::<Vec<_>>())
+            .unwrap_or_default();
+        titles.push(self.sheet_reference(area));
+        titles.sort_by_key(|reference| matches!(reference.area, ReferenceArea::Rows(..)));
+        let value = ParsedFormula {
+            tokens: titles.into_iter()
+                .map(FormulaToken::Reference)
+                .flat_map(|token| [FormulaToken::Comma, token])
+                .skip(1)
+                .collect(),
+        };
+        self.set_local_name("_xlnm.Print_Titles", &value.to_string());
+    }
+
+    fn sheet_reference(&self, area: ReferenceArea) -> Reference {
+        Reference { workbook: None, sheet: Some(self.name.clone()), last_sheet: None, quoted: false, area }
+    }
+
+    ///
+    /// The index of the worksheet in the workbook, which scopes its local names
+    ///
+    fn local_sheet_id(&self) -> u32 {
+        let workbook = self.workbook.borrow();
+        workbook.sheets.sheets.iter().position(|sheet| sheet.sheet_id == self.id).unwrap_or_default() as u32
+    }
+
+    fn local_name(&self, name: &str) -> Option<String> {
+        let local_sheet_id = self.local_sheet_id();
+        self.workbook.borrow().defined_names.get_scoped_value(name, Some(local_sheet_id)).map(str::to_string)
+    }
+
+    fn set_local_name(&mut self, name: &str, value: &str) {
+        let local_sheet_id = self.local_sheet_id();
+        self.workbook.borrow_mut().defined_names.set_define_name(name, value, Some(local_sheet_id));
+    }
+}
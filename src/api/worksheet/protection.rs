@@ -0,0 +1,34 @@
+use crate::api::cell::location::LocationRange;
+use crate::api::protection::ProtectionOptions;
+use crate::api::worksheet::WorkSheet;
+
+impl WorkSheet {
+    ///
+    /// Protect the worksheet: only the unlocked cells and the actions allowed by the options can be changed
+    ///
+    pub fn protect(&mut self, options: &ProtectionOptions) {
+        self.worksheet.protect(options);
+    }
+
+    pub fn unprotect(&mut self) {
+        self.worksheet.unprotect();
+    }
+
+    pub fn is_protected(&self) -> bool {
+        self.worksheet.is_protected()
+    }
+
+    ///
+    /// Let the users edit a range of the protected worksheet, after typing the password if any
+    ///
+    pub fn add_protected_range<L: LocationRange>(&mut self, name: &str, loc_range: L, password: Option<&str>) {
+        self.worksheet.add_protected_range(name, &loc_range, password);
+    }
+
+    ///
+    /// The names of the ranges editable in the protected worksheet, with the ranges like `A1:A10 C1:C10`
+    ///
+    pub fn protected_ranges(&self) -> Vec<(String, String)> {
+        self.worksheet.get_protected_ranges()
+    }
+}
@@ -1,6 +1,8 @@
-use crate::api::cell::location::Location;
-use crate::{Cell, Format, WorkSheet, WorkSheetResult};
-use crate::api::cell::values::{CellDisplay, CellType, CellValue};
+use std::collections::HashMap;
+use crate::api::cell::formula::token::ParsedFormula;
+use crate::api::cell::location::{Location, LocationRange};
+use crate::{Cell, ExcelDateTime, Value, WorkSheet, WorkSheetResult};
+use crate::api::cell::values::CellType;
 use crate::api::worksheet::format::_Format;
 use crate::api::worksheet::hyperlink::_Hyperlink;
 use crate::result::{CellError, RowError, WorkSheetError};
@@ -9,6 +11,21 @@ pub trait Read: _Read {
     fn read_cell<L: Location>(&self, loc: L) -> WorkSheetResult<Cell<String>> {
         self.read_api_cell(&loc)
     }
+
+    ///
+    /// Read the typed value of a cell, a cell that does not exist is [`Value::Empty`]
+    ///
+    fn read_value<L: Location>(&self, loc: L) -> WorkSheetResult<Value> {
+        self.read_api_value(&loc)
+    }
+
+    ///
+    /// Read the formula of a cell with its last computed value, as stored in the file.
+    /// Returns None if the cell does not exist or has no formula.
+    ///
+    fn read_formula<L: Location>(&self, loc: L) -> WorkSheetResult<Option<(String, Value)>> {
+        self.read_api_formula(&loc)
+    }
     // fn read_text<L: Location>(&self, loc: L) -> WorkSheetResult<&str> { self.read_value(loc) }
     // fn read_string<L: Location>(&self, loc: L) -> WorkSheetResult<&str> { self.read_value(loc) }
     // fn read_shared_string<L: Location>(&self, loc: L) -> WorkSheetResult<&str> { self.read_value(loc) }
@@ -18,29 +35,168 @@ pub trait Read: _Read {
 
 trait _Read {
     fn read_api_cell<L: Location>(&self, loc: &L) -> WorkSheetResult<Cell<String>>;
+    fn read_api_value<L: Location>(&self, loc: &L) -> WorkSheetResult<Value>;
+    fn read_api_formula<L: Location>(&self, loc: &L) -> WorkSheetResult<Option<(String, Value)>>;
     // fn get_cell_type<L: Location>(&self, loc: L) -> WorkSheetResult<&CellType>;
     // fn read_value<L: Location>(&self, loc: L) -> WorkSheetResult<&str>;
     // fn read_text<L: Location>(&self, loc: L) -> WorkSheetResult<&str>;
     // fn read_format_all<L: Location>(&self, loc: L) -> WorkSheetResult<Format>;
 }
 
-impl _Read for WorkSheet {
-    fn read_api_cell<L: Location>(&self, loc: &L) -> WorkSheetResult<Cell<String>> {
-        let mut cell = self.worksheet.sheet_data.read_api_cell(loc)?;
-        if let Some(style) = cell.style {
-            cell.format = Some(self.get_format(style));
+impl WorkSheet {
+    ///
+    /// Iterate over the rows of the sheet, each row with an iterator over its cells.
+    /// Only rows and cells stored in the sheet are visited, the numbers of rows and columns start from 1.
+    /// Shared strings are resolved, formats are not read, see [`WorkSheet::rows_with_format`].
+    ///
+    pub fn rows(&self) -> impl Iterator<Item = (u32, impl Iterator<Item = (u32, Cell<String>)> + '_)> + '_ {
+        self.iter_rows(false)
+    }
+
+    ///
+    /// Same as [`WorkSheet::rows`], with the format of every cell read
+    ///
+    pub fn rows_with_format(&self) -> impl Iterator<Item = (u32, impl Iterator<Item = (u32, Cell<String>)> + '_)> + '_ {
+        self.iter_rows(true)
+    }
+
+    ///
+    /// The smallest range containing every cell of the sheet, as (first_row, first_col, last_row, last_col).
+    /// Returns None if the sheet has no cells.
+    ///
+    pub fn used_range(&self) -> Option<(u32, u32, u32, u32)> {
+        self.worksheet.sheet_data.used_range()
+    }
+
+    ///
+    /// Read the values of a range, row by row. Missing cells are [`Value::Empty`].
+    /// The range is cut at the last row and column used, so that whole columns like `A:C` can be read.
+    ///
+    pub fn read_range<L: LocationRange>(&self, loc_range: L) -> Vec<Vec<Value>> {
+        let (first_row, first_col, last_row, last_col) = loc_range.to_range();
+        let Some((_, _, used_last_row, used_last_col)) = self.used_range() else {
+            return vec![];
+        };
+        let (last_row, last_col) = (last_row.min(used_last_row), last_col.min(used_last_col));
+        let width = (last_col + 1).saturating_sub(first_col) as usize;
+        let height = (last_row + 1).saturating_sub(first_row) as usize;
+        let mut values = vec![vec![Value::Empty; width]; height];
+        for row in self.worksheet.sheet_data.rows_between(first_row, last_row) {
+            let values = &mut values[(row.row - first_row) as usize];
+            for cell in row.cells_between(first_col, last_col) {
+                values[(cell.loc.col - first_col) as usize] = self.cell_value(cell.to_api_cell());
+            }
         }
+        values
+    }
+
+    fn iter_rows(&self, with_format: bool) -> impl Iterator<Item = (u32, impl Iterator<Item = (u32, Cell<String>)> + '_)> + '_ {
+        self.worksheet.sheet_data.rows_between(1, u32::MAX).map(move |row| {
+            let cells = row.cells.iter().map(move |cell| {
+                let mut api_cell = cell.to_api_cell();
+                if with_format {
+                    api_cell.format = api_cell.style.map(|style| self.get_format(style));
+                }
+                self.resolve_shared_string(&mut api_cell);
+                (cell.loc.col, api_cell)
+            });
+            (row.row, cells)
+        })
+    }
+
+    fn resolve_shared_string(&self, cell: &mut Cell<String>) {
         if let Some(CellType::SharedString) = cell.cell_type {
-            let id: usize = if let Some(s) = cell.text {
+            let id: usize = if let Some(s) = &cell.text {
                 s.parse().unwrap_or_default()
             } else { 0 };
             cell.cell_type = Some(CellType::String);
-            cell.text = Some(self.shared_string.get_text(id).unwrap().to_string());
+            cell.text = self.shared_string.borrow().get_text(id);
         };
+    }
+
+    ///
+    /// The formulas of the sheet by location.
+    /// The cells of a shared formula get the formula of its first cell, moved like a formula copied to them.
+    ///
+    pub(crate) fn formulas(&self) -> HashMap<(u32, u32), String> {
+        let shared = self.shared_formulas();
+        self.worksheet.sheet_data.rows_between(1, u32::MAX)
+            .flat_map(|row| row.cells.iter())
+            .filter_map(|cell| {
+                let formula = cell.formula.as_ref()?.to_api_formula();
+                let loc = (cell.loc.row, cell.loc.col);
+                if !formula.formula.is_empty() {
+                    return Some((loc, formula.formula));
+                }
+                Some((loc, shared_formula(&shared, formula.si?, loc)?))
+            })
+            .collect()
+    }
+
+    ///
+    /// The first cell and the formula of the shared formulas, by their shared index
+    ///
+    fn shared_formulas(&self) -> HashMap<i32, ((u32, u32), String)> {
+        self.worksheet.sheet_data.rows_between(1, u32::MAX)
+            .flat_map(|row| row.cells.iter())
+            .filter_map(|cell| {
+                let formula = cell.formula.as_ref()?.to_api_formula();
+                if formula.formula_type.as_deref() != Some("shared") || formula.formula.is_empty() {
+                    return None;
+                }
+                Some((formula.si?, ((cell.loc.row, cell.loc.col), formula.formula)))
+            })
+            .collect()
+    }
+
+    fn cell_value(&self, cell: Cell<String>) -> Value {
+        match cell.cell_type {
+            Some(CellType::SharedString) => {
+                let id: usize = cell.text.unwrap_or_default().parse().unwrap_or_default();
+                self.shared_string.borrow().get_value(id).unwrap_or_default()
+            }
+            cell_type => match Value::from_cell_text(cell_type.as_ref(), cell.text.as_deref()) {
+                Value::Number(serial) if cell.style.is_some_and(|style| self.is_date_style(style)) => {
+                    let date1904 = self.workbook.borrow().is_date1904();
+                    Value::DateTime(ExcelDateTime::from_serial_in(serial, date1904))
+                }
+                value => value,
+            },
+        }
+    }
+}
+
+impl _Read for WorkSheet {
+    fn read_api_cell<L: Location>(&self, loc: &L) -> WorkSheetResult<Cell<String>> {
+        let mut cell = self.worksheet.sheet_data.read_api_cell(loc)?;
+        if let Some(style) = cell.style {
+            cell.format = Some(self.get_format(style));
+        }
+        self.resolve_shared_string(&mut cell);
         cell.hyperlink = self.worksheet.get_hyperlink(loc);
         Ok(cell)
     }
 
+    fn read_api_value<L: Location>(&self, loc: &L) -> WorkSheetResult<Value> {
+        match self.worksheet.sheet_data.read_api_cell(loc) {
+            Ok(cell) => Ok(self.cell_value(cell)),
+            Err(_) => Ok(Value::Empty),
+        }
+    }
+
+    fn read_api_formula<L: Location>(&self, loc: &L) -> WorkSheetResult<Option<(String, Value)>> {
+        match self.worksheet.sheet_data.read_api_cell(loc) {
+            Ok(cell) => {
+                let formula = cell.formula.as_ref().map(|formula| match formula.si.filter(|_| formula.formula.is_empty()) {
+                    Some(si) => shared_formula(&self.shared_formulas(), si, loc.to_location()).unwrap_or_default(),
+                    None => formula.formula.clone(),
+                });
+                Ok(formula.map(|formula| (formula, self.cell_value(cell))))
+            }
+            Err(_) => Ok(None),
+        }
+    }
+
     // fn get_cell_type<L: Location>(&self, loc: L) -> WorkSheetResult<&CellType> {
     //     let worksheet = &self.worksheet;
     //     let sheet_data = &worksheet.sheet_data;
@@ -83,4 +239,14 @@ impl _Read for WorkSheet {
     //         None => Err(WorkSheetError::FileNotFound)
     //     }
     // }
-}
\ No newline at end of file
+}
+
+///
+/// The formula of a cell sharing the formula of another cell, moved like a formula copied to it
+///
+fn shared_formula(shared: &HashMap<i32, ((u32, u32), String)>, si: i32, (row, col): (u32, u32)) -> Option<String> {
+    let ((first_row, first_col), formula) = shared.get(&si)?;
+    let mut parsed = ParsedFormula::parse(formula);
+    parsed.offset(row as i64 - *first_row as i64, col as i64 - *first_col as i64);
+    Some(parsed.to_string())
+}
@@ -0,0 +1,57 @@
+use crate::api::worksheet::WorkSheet;
+use crate::result::{ColError, RowError, TableError, WorkSheetResult};
+use crate::utils::reference_helper::Shift;
+
+impl WorkSheet {
+    ///
+    /// Move the cells, the ranges of the worksheet and the formulas of the worksheet and of the defined names.
+    /// The cells with values, merged cells and tables are not pushed past the last row or column,
+    /// and the references pushed past it become `#REF!`.
+    ///
+    pub(crate) fn shift_cells(&mut self, shift: Shift) -> WorkSheetResult<()> {
+        if !shift.is_valid() {
+            return match shift.is_row() {
+                true => Err(RowError::RowNotFound.into()),
+                false => Err(ColError::ColNotFound.into()),
+            };
+        }
+        // the cells are not pushed past the last row or column of the worksheet
+        let last_table_index = self.tables.iter()
+            .map(|(_, table)| table.get_range())
+            .map(|(_, _, last_row, last_col)| if shift.is_row() { last_row } else { last_col })
+            .max()
+            .unwrap_or_default();
+        let last_index = self.worksheet.last_used_index(shift.is_row()).max(last_table_index);
+        if shift.is_insert() && last_index > 0 && shift.shift_index(last_index).is_none() {
+            return match shift.is_row() {
+                true => Err(RowError::OutOfSheet.into()),
+                false => Err(ColError::OutOfSheet.into()),
+            };
+        }
+        if !self.tables.iter().all(|(_, table)| table.can_shift(shift)) {
+            return Err(TableError::InvalidShift.into());
+        }
+        self.worksheet.shift(shift, &self.name);
+        if let Some(comments) = &mut self.comments {
+            comments.shift(shift);
+        }
+        if let Some(vml_drawing) = &mut self.vml_drawing {
+            vml_drawing.shift(shift);
+        }
+        if let Some(drawings) = &mut self.drawings {
+            drawings.shift(shift);
+        }
+        self.charts.iter_mut().for_each(|(_, chart)| chart.shift(shift, &self.name));
+        self.tables.iter_mut().for_each(|(_, table)| table.shift(shift));
+        self.workbook.borrow_mut().defined_names.shift(shift, &self.name);
+        Ok(())
+    }
+
+    ///
+    /// Rewrite the formulas, the links and the charts referring to the cells of another worksheet moved by a shift
+    ///
+    pub(crate) fn shift_formulas(&mut self, shift: Shift, sheet_name: &str) {
+        self.worksheet.shift_formulas(shift, sheet_name);
+        self.charts.iter_mut().for_each(|(_, chart)| chart.shift(shift, sheet_name));
+    }
+}
@@ -0,0 +1,117 @@
+use crate::api::cell::location::LocationRange;
+use crate::api::cell::values::Value;
+use crate::api::table::{Table as ApiTable, TableOptions};
+use crate::api::worksheet::read::Read;
+use crate::api::worksheet::write::Write;
+use crate::api::worksheet::WorkSheet;
+use crate::result::{TableError, WorkSheetResult};
+use crate::utils::reference_helper::is_valid_name;
+use crate::xml::table::Table;
+
+impl WorkSheet {
+    ///
+    /// Add a table over a range of cells, with a name unique among the tables and the defined names of the workbook.
+    /// The headers are written to the first row of the range, and the totals to the last one if there is a totals row.
+    ///
+    pub fn add_table<L: LocationRange>(&mut self, loc_range: L, options: &TableOptions) -> WorkSheetResult<()> {
+        let (first_row, first_col, last_row, last_col) = loc_range.to_range();
+        let header_row = options.header_row as u32;
+        let total_row = options.total_row as u32;
+        let width = (last_col + 1).saturating_sub(first_col) as usize;
+        if last_row < first_row + header_row + total_row || width == 0 || options.columns.len() > width {
+            return Err(TableError::InvalidRange.into());
+        }
+        let overlap = self.tables.iter().any(|(_, table)| {
+            let (r0, c0, r1, c1) = table.get_range();
+            first_row <= r1 && r0 <= last_row && first_col <= c1 && c0 <= last_col
+        });
+        if overlap {
+            return Err(TableError::Overlap.into());
+        }
+        if let Some(name) = &options.name {
+            if !is_valid_name(name) {
+                return Err(TableError::InvalidName.into());
+            }
+            if self.workbook.borrow().is_name_taken(name) {
+                return Err(TableError::DuplicatedName.into());
+            }
+        }
+        let headers = self.table_headers(first_row, first_col, width, options);
+        if options.header_row {
+            for (i, header) in headers.iter().enumerate() {
+                self.write((first_row, first_col + i as u32), header.as_str())?;
+            }
+        }
+        if options.total_row {
+            for (i, column) in options.columns.iter().enumerate() {
+                let loc = (last_row, first_col + i as u32);
+                if let Some(label) = &column.total_label {
+                    self.write(loc, label.as_str())?;
+                }
+                if let Some(function) = column.total_function {
+                    let formula = format!("SUBTOTAL({},[{}])", function.to_subtotal(), escape_column_name(&headers[i]));
+                    self.write_old_formula(loc, &formula)?;
+                }
+            }
+        }
+        let id = self.content_types.borrow_mut().add_table();
+        let r_id = self.worksheet_rel.add_table(id);
+        self.worksheet.add_table_part(r_id);
+        let name = options.name.clone().unwrap_or(self.workbook.borrow().free_table_name(id));
+        self.workbook.borrow_mut().set_table_name(id, &name);
+        self.tables.push((id, Table::new(id, &name, &loc_range, &headers, options)));
+        Ok(())
+    }
+
+    ///
+    /// The tables of the worksheet
+    ///
+    pub fn tables(&self) -> Vec<ApiTable> {
+        self.tables.iter().map(|(_, table)| table.to_api_table()).collect()
+    }
+
+    ///
+    /// The ids of the parts of the tables
+    ///
+    pub(crate) fn table_ids(&self) -> Vec<u32> {
+        self.tables.iter().map(|(id, _)| *id).collect()
+    }
+
+    ///
+    /// The headers of the columns, which are unique in the table
+    ///
+    fn table_headers(&self, first_row: u32, first_col: u32, width: usize, options: &TableOptions) -> Vec<String> {
+        let mut headers: Vec<String> = vec![];
+        for i in 0..width {
+            let mut header = match options.columns.get(i) {
+                Some(column) if !column.header.is_empty() => column.header.clone(),
+                _ => match self.read_value((first_row, first_col + i as u32)) {
+                    Ok(value) if options.header_row && value != Value::Empty => value.to_string(),
+                    _ => format!("Column{}", i + 1),
+                },
+            };
+            let name = header.clone();
+            let mut n = 2;
+            while headers.iter().any(|h| h.eq_ignore_ascii_case(&header)) {
+                header = format!("{name}{n}");
+                n += 1;
+            }
+            headers.push(header);
+        }
+        headers
+    }
+}
+
+///
+/// Escape the special characters of a column name in a structured reference
+///
+fn escape_column_name(name: &str) -> String {
+    let mut escaped = String::with_capacity(name.len());
+    for c in name.chars() {
+        if matches!(c, '[' | ']' | '#' | '\'') {
+            escaped.push('\'');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
@@ -1,11 +1,11 @@
 use std::slice::Iter;
 use crate::api::cell::Cell;
 use crate::api::cell::formula::Formula;
-use crate::api::cell::values::{CellDisplay, CellValue};
+use crate::api::cell::values::{CellDisplay, CellType, CellValue};
 use crate::api::cell::location::{Location, LocationRange};
 use crate::api::worksheet::format::_Format;
 use crate::api::worksheet::hyperlink::_Hyperlink;
-use crate::Format;
+use crate::{ExcelDateTime, Format};
 use crate::api::worksheet::WorkSheet;
 use crate::result::WorkSheetResult;
 use crate::xml::extension::{AddExtension, ExtensionType};
@@ -215,6 +215,8 @@ trait _Write: _Format + _Hyperlink {
     // fn write_formula_all<L: Location>(&mut self, loc: &L, formula: &str, formula_type: FormulaType, format: Option<&Format>) -> WorkSheetResult<()>;
     // fn write_hyperlink<L: Location>(&mut self, loc: &L, url: &str, data: &str, format: Option<&Format>) -> WorkSheetResult<()>;
     fn merge_range_all<L: LocationRange, T: CellDisplay + CellValue>(&mut self, loc: L, data: T, format: Option<&Format>) -> WorkSheetResult<()>;
+    fn write_date_time<L: Location>(&mut self, loc: &L, date_time: &ExcelDateTime, style: Option<u32>) -> WorkSheetResult<()>;
+    fn add_shared_string(&mut self, text: &str) -> usize;
 }
 
 impl _Write for WorkSheet {
@@ -239,6 +241,15 @@ impl _Write for WorkSheet {
             self.content_types.borrow_mut().add_metadata();
         }
         self.worksheet.sheet_data.write_by_api_cell(loc, &cell)?;
+        if let (Some(text), None) = (&cell.text, &cell.formula) {
+            let cell_type = cell.cell_type.clone().unwrap_or(text.to_cell_type());
+            if let Some(date_time) = text.to_date_time() {
+                self.write_date_time(loc, &date_time, cell.style)?;
+            } else if cell_type == CellType::String {
+                let id = self.add_shared_string(&text.to_display());
+                self.worksheet.sheet_data.write_shared_string(loc, id, None)?;
+            }
+        }
         Ok(())
     }
 
@@ -247,12 +258,35 @@ impl _Write for WorkSheet {
         if let Some(format) = format {
             style = Some(self.add_format(format));
         }
-        let worksheet = &mut self.worksheet;
-        let sheet_data = &mut worksheet.sheet_data;
-        sheet_data.write_display(loc, data, style)?;
+        if let Some(date_time) = data.to_date_time() {
+            self.write_date_time(loc, &date_time, style)?;
+        } else if data.to_cell_type() == CellType::String {
+            let id = self.add_shared_string(&data.to_display());
+            self.worksheet.sheet_data.write_shared_string(loc, id, style)?;
+        } else {
+            self.worksheet.sheet_data.write_display(loc, data, style)?;
+        }
         Ok(())
     }
 
+    fn write_date_time<L: Location>(&mut self, loc: &L, date_time: &ExcelDateTime, style: Option<u32>) -> WorkSheetResult<()> {
+        // the dates the 1904 date system cannot hold are written as text
+        let Some(serial) = date_time.to_serial_in(self.workbook.borrow().is_date1904()) else {
+            let id = self.add_shared_string(&date_time.to_string());
+            self.worksheet.sheet_data.write_shared_string(loc, id, style)?;
+            return Ok(());
+        };
+        let style = self.add_date_format(style, date_time);
+        self.worksheet.sheet_data.write_display(loc, &serial, Some(style))?;
+        Ok(())
+    }
+
+    fn add_shared_string(&mut self, text: &str) -> usize {
+        self.workbook_rel.borrow_mut().get_or_add_shared_strings();
+        self.content_types.borrow_mut().add_shared_strings();
+        self.shared_string.borrow_mut().add_text(text)
+    }
+
     // fn write_formula_all<L: Location>(&mut self, loc: &L, formula: &str, formula_type: FormulaType, format: Option<&Format>) -> WorkSheetResult<()> {
     //     let mut style = None;
     //     if let Some(format) = format {
@@ -1 +1,3 @@
-pub(crate) mod internal_tree;
\ No newline at end of file
+pub(crate) mod internal_tree;
+#[cfg(feature = "calc")]
+pub(crate) mod calc;
\ No newline at end of file
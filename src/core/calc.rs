@@ -0,0 +1,686 @@
+//! Evaluate the formulas of a workbook, to store their results as the cached values of the cells.
+//! - Values are computed lazily and memoized, a cell is evaluated once, after the formulas it refers to.
+//! - Formulas in a circular reference keep their cached values.
+//! - Formulas which cannot be evaluated, like those calling an unknown function, keep their cached values.
+mod functions;
+mod number_format;
+
+use std::cmp::Ordering;
+use std::collections::{HashMap, HashSet};
+use crate::api::cell::formula::expression::{BinaryOperator, Expression, UnaryOperator};
+use crate::api::cell::formula::token::{NameReference, Reference, ReferenceArea};
+use crate::api::workbook::Workbook;
+use crate::utils::reference_helper::{is_same_sheet, MAX_COL, MAX_ROW};
+use crate::{ErrorKind, Read, Value};
+
+///
+/// Why a value could not be computed: an error value, or a formula the calculator does not support
+///
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum Failure {
+    Error(ErrorKind),
+    Unsupported,
+}
+
+impl From<ErrorKind> for Failure { fn from(err: ErrorKind) -> Failure { Failure::Error(err) } }
+
+pub(crate) type CalcResult<T = CalcValue> = Result<T, Failure>;
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum CalcValue {
+    Empty,
+    Number(f64),
+    Text(String),
+    Bool(bool),
+    Error(ErrorKind),
+    /// A reference to the cells of a worksheet
+    Area(Area),
+    /// An array of values by rows, such as an array constant or the result of an operation on areas
+    Array(Vec<Vec<CalcValue>>),
+}
+
+///
+/// A range of cells of a worksheet, the sheet is its index in the workbook
+///
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct Area {
+    pub(crate) sheet: usize,
+    pub(crate) first_row: u32,
+    pub(crate) first_col: u32,
+    pub(crate) last_row: u32,
+    pub(crate) last_col: u32,
+}
+
+impl Area {
+    pub(crate) fn height(&self) -> u32 {
+        self.last_row - self.first_row + 1
+    }
+
+    pub(crate) fn width(&self) -> u32 {
+        self.last_col - self.first_col + 1
+    }
+}
+
+///
+/// Where a formula is evaluated, the references without a sheet name refer to its sheet
+///
+#[derive(Debug, Clone, Copy)]
+struct Position {
+    sheet: usize,
+}
+
+pub(crate) struct Calculator<'a> {
+    workbook: &'a Workbook,
+    pub(crate) date1904: bool,
+    // The formulas of every sheet by location
+    formulas: Vec<HashMap<(u32, u32), String>>,
+    // The results of the formulas evaluated, None if they are not supported
+    results: HashMap<(usize, u32, u32), Option<CalcValue>>,
+    evaluating: HashSet<(usize, u32, u32)>,
+    evaluating_names: HashSet<String>,
+}
+
+impl<'a> Calculator<'a> {
+    pub(crate) fn new(workbook: &'a Workbook) -> Calculator<'a> {
+        Calculator {
+            workbook,
+            date1904: workbook.is_date1904(),
+            formulas: workbook.sheets.iter().map(|sheet| sheet.formulas()).collect(),
+            results: HashMap::new(),
+            evaluating: HashSet::new(),
+            evaluating_names: HashSet::new(),
+        }
+    }
+
+    ///
+    /// Evaluate the formulas of all the sheets, by rows.
+    /// Returns the results of the formulas supported for each sheet.
+    ///
+    pub(crate) fn calculate(mut self) -> Vec<Vec<((u32, u32), Value)>> {
+        (0..self.formulas.len()).map(|sheet| {
+            let mut locations = self.formulas[sheet].keys().copied().collect::<Vec<(u32, u32)>>();
+            locations.sort();
+            locations.into_iter()
+                .filter_map(|(row, col)| Some(((row, col), self.evaluate_cell(sheet, row, col)?.to_value())))
+                .collect()
+        }).collect()
+    }
+
+    ///
+    /// The value of a cell, computed if it has a supported formula
+    ///
+    pub(crate) fn cell_value(&mut self, sheet: usize, row: u32, col: u32) -> CalcValue {
+        if self.formulas[sheet].contains_key(&(row, col)) {
+            if let Some(value) = self.evaluate_cell(sheet, row, col) {
+                return value;
+            }
+        }
+        let value = self.workbook.sheets[sheet].read_value((row, col)).unwrap_or_default();
+        CalcValue::from_value(value, self.date1904)
+    }
+
+    fn evaluate_cell(&mut self, sheet: usize, row: u32, col: u32) -> Option<CalcValue> {
+        let key = (sheet, row, col);
+        if let Some(result) = self.results.get(&key) {
+            return result.clone();
+        }
+        // a circular reference found while evaluating, through a function like INDIRECT, keeps the cached value
+        if self.evaluating.contains(&key) {
+            return None;
+        }
+        self.evaluate_in_order(key);
+        self.results.get(&key).cloned().flatten()
+    }
+
+    ///
+    /// Evaluate a formula after the formulas it refers to, with a stack instead of recursion
+    /// for the long chains of formulas depending on each other.
+    ///
+    fn evaluate_in_order(&mut self, key: (usize, u32, u32)) {
+        // the cells being evaluated, each one refers to the next one
+        let mut path = vec![];
+        let mut circular = HashSet::new();
+        let mut stack = vec![(key, false)];
+        while let Some((key, expanded)) = stack.pop() {
+            if self.results.contains_key(&key) {
+                continue;
+            }
+            if expanded {
+                path.pop();
+                let result = if circular.contains(&key) { None } else { self.compute(key) };
+                self.evaluating.remove(&key);
+                self.results.insert(key, result);
+                continue;
+            }
+            if !self.evaluating.insert(key) {
+                continue;
+            }
+            path.push(key);
+            stack.push((key, true));
+            for precedent in self.precedents(key) {
+                if self.results.contains_key(&precedent) {
+                    continue;
+                }
+                if self.evaluating.contains(&precedent) {
+                    let start = path.iter().position(|cell| *cell == precedent).unwrap_or(path.len() - 1);
+                    circular.extend(path[start..].iter().copied());
+                } else {
+                    stack.push((precedent, false));
+                }
+            }
+        }
+    }
+
+    fn compute(&mut self, (sheet, row, col): (usize, u32, u32)) -> Option<CalcValue> {
+        let formula = self.formulas[sheet][&(row, col)].clone();
+        let result = match Expression::parse(&formula) {
+            Ok(expression) => self.eval(&expression, Position { sheet }).ok().map(|value| self.scalar(value)),
+            Err(_) => None,
+        };
+        result.map(|value| if value == CalcValue::Empty { CalcValue::Number(0.0) } else { value })
+    }
+
+    ///
+    /// The cells with a formula which a formula refers to, directly or through the defined names
+    ///
+    fn precedents(&self, (sheet, row, col): (usize, u32, u32)) -> Vec<(usize, u32, u32)> {
+        let mut precedents = vec![];
+        if let Ok(expression) = Expression::parse(&self.formulas[sheet][&(row, col)]) {
+            self.collect_precedents(&expression, sheet, &mut precedents, &mut HashSet::new());
+        }
+        precedents
+    }
+
+    fn collect_precedents(&self, expression: &Expression, sheet: usize, precedents: &mut Vec<(usize, u32, u32)>, names: &mut HashSet<String>) {
+        match expression {
+            Expression::Reference(reference) => {
+                if let Ok(CalcValue::Area(area)) = self.area(reference, Position { sheet }) {
+                    let formulas = &self.formulas[area.sheet];
+                    let cells = area.height() as u64 * area.width() as u64;
+                    if cells <= formulas.len() as u64 {
+                        let locations = (area.first_row..=area.last_row)
+                            .flat_map(|row| (area.first_col..=area.last_col).map(move |col| (row, col)));
+                        precedents.extend(locations.filter(|location| formulas.contains_key(location))
+                            .map(|(row, col)| (area.sheet, row, col)));
+                    } else {
+                        let mut locations = formulas.keys()
+                            .filter(|(row, col)| (area.first_row..=area.last_row).contains(row) && (area.first_col..=area.last_col).contains(col))
+                            .map(|(row, col)| (area.sheet, *row, *col))
+                            .collect::<Vec<_>>();
+                        locations.sort();
+                        precedents.extend(locations);
+                    }
+                }
+            }
+            Expression::Name(name) if name.workbook.is_none() => {
+                let sheet = match &name.sheet {
+                    Some(sheet_name) => match self.sheet_index(sheet_name) {
+                        Some(sheet) => sheet,
+                        None => return,
+                    },
+                    None => sheet,
+                };
+                let value = self.workbook.workbook.borrow().defined_names.get_value(&name.name, sheet as u32);
+                if let Some(expression) = value.and_then(|value| Expression::parse(&value).ok()) {
+                    if names.insert(name.name.to_ascii_uppercase()) {
+                        self.collect_precedents(&expression, sheet, precedents, names);
+                    }
+                }
+            }
+            Expression::Array(rows) => rows.iter().flatten()
+                .for_each(|expression| self.collect_precedents(expression, sheet, precedents, names)),
+            Expression::Function(_, arguments) => arguments.iter()
+                .for_each(|expression| self.collect_precedents(expression, sheet, precedents, names)),
+            Expression::Unary(_, expression) | Expression::Parenthesis(expression) => self.collect_precedents(expression, sheet, precedents, names),
+            Expression::Binary(left, _, right) => {
+                self.collect_precedents(left, sheet, precedents, names);
+                self.collect_precedents(right, sheet, precedents, names);
+            }
+            _ => {}
+        }
+    }
+
+    ///
+    /// Evaluate an expression, whose errors are values. Fails only if it is not supported.
+    ///
+    fn eval(&mut self, expression: &Expression, at: Position) -> CalcResult {
+        let value = match expression {
+            Expression::Number(number) => CalcValue::Number(*number),
+            Expression::Text(text) => CalcValue::Text(text.to_string()),
+            Expression::Boolean(bool) => CalcValue::Bool(*bool),
+            Expression::Error(error) => CalcValue::Error(ErrorKind::from_str(error)),
+            Expression::Reference(reference) => self.area(reference, at)?,
+            Expression::Name(name) => self.name(name, at)?,
+            Expression::StructuredReference(_) => return Err(Failure::Unsupported),
+            Expression::Array(rows) => {
+                let mut array = vec![];
+                for row in rows {
+                    let mut values = vec![];
+                    for expression in row {
+                        let value = self.eval(expression, at)?;
+                        values.push(self.scalar(value));
+                    }
+                    array.push(values);
+                }
+                CalcValue::Array(array)
+            }
+            Expression::Function(name, arguments) => self.function(name, arguments, at)?,
+            Expression::Missing => CalcValue::Empty,
+            Expression::Unary(operator, expression) => {
+                let value = self.eval(expression, at)?;
+                match operator {
+                    UnaryOperator::Plus | UnaryOperator::Spill => value,
+                    UnaryOperator::Minus => self.map(value, |value| Ok(CalcValue::Number(-value.number()?))),
+                    UnaryOperator::Percent => self.map(value, |value| Ok(CalcValue::Number(value.number()? / 100.0))),
+                    UnaryOperator::ImplicitIntersection => self.scalar(value),
+                }
+            }
+            Expression::Binary(left, operator, right) => {
+                let left = self.eval(left, at)?;
+                let right = self.eval(right, at)?;
+                match operator {
+                    BinaryOperator::Range => match (left, right) {
+                        (CalcValue::Area(left), CalcValue::Area(right)) if left.sheet == right.sheet => CalcValue::Area(Area {
+                            sheet: left.sheet,
+                            first_row: left.first_row.min(right.first_row),
+                            first_col: left.first_col.min(right.first_col),
+                            last_row: left.last_row.max(right.last_row),
+                            last_col: left.last_col.max(right.last_col),
+                        }),
+                        (CalcValue::Error(error), _) | (_, CalcValue::Error(error)) => CalcValue::Error(error),
+                        _ => CalcValue::Error(ErrorKind::Value),
+                    },
+                    BinaryOperator::Intersection => match (left, right) {
+                        (CalcValue::Area(left), CalcValue::Area(right)) if left.sheet == right.sheet => {
+                            let area = Area {
+                                sheet: left.sheet,
+                                first_row: left.first_row.max(right.first_row),
+                                first_col: left.first_col.max(right.first_col),
+                                last_row: left.last_row.min(right.last_row),
+                                last_col: left.last_col.min(right.last_col),
+                            };
+                            if area.first_row > area.last_row || area.first_col > area.last_col {
+                                CalcValue::Error(ErrorKind::Null)
+                            } else {
+                                CalcValue::Area(area)
+                            }
+                        }
+                        (CalcValue::Error(error), _) | (_, CalcValue::Error(error)) => CalcValue::Error(error),
+                        _ => CalcValue::Error(ErrorKind::Value),
+                    },
+                    // the functions only aggregate the values of a union
+                    BinaryOperator::Union => {
+                        let mut values = self.values(left);
+                        values.extend(self.values(right));
+                        CalcValue::Array(vec![values])
+                    }
+                    operator => self.binary(left, *operator, right),
+                }
+            }
+            Expression::Parenthesis(expression) => self.eval(expression, at)?,
+        };
+        Ok(value)
+    }
+
+    ///
+    /// The area of a reference, `#REF!` if its sheet does not exist
+    ///
+    fn area(&self, reference: &Reference, at: Position) -> CalcResult {
+        if reference.workbook.is_some() || reference.last_sheet.is_some() {
+            return Err(Failure::Unsupported);
+        }
+        let sheet = match &reference.sheet {
+            Some(name) => match self.sheet_index(name) {
+                Some(sheet) => sheet,
+                None => return Ok(CalcValue::Error(ErrorKind::Ref)),
+            },
+            None => at.sheet,
+        };
+        let (first_row, first_col, last_row, last_col) = match reference.area {
+            ReferenceArea::Cell(cell) => (cell.row, cell.col, cell.row, cell.col),
+            ReferenceArea::Range(first, last) => (
+                first.row.min(last.row), first.col.min(last.col),
+                first.row.max(last.row), first.col.max(last.col),
+            ),
+            ReferenceArea::Rows(first, last) => (first.index.min(last.index), 1, first.index.max(last.index), MAX_COL),
+            ReferenceArea::Columns(first, last) => (1, first.index.min(last.index), MAX_ROW, first.index.max(last.index)),
+            ReferenceArea::Invalid => return Ok(CalcValue::Error(ErrorKind::Ref)),
+        };
+        Ok(CalcValue::Area(Area { sheet, first_row, first_col, last_row, last_col }))
+    }
+
+    fn name(&mut self, name: &NameReference, at: Position) -> CalcResult {
+        if name.workbook.is_some() {
+            return Err(Failure::Unsupported);
+        }
+        let sheet = match &name.sheet {
+            Some(sheet_name) => self.sheet_index(sheet_name).ok_or(Failure::Unsupported)?,
+            None => at.sheet,
+        };
+        let value = self.workbook.workbook.borrow().defined_names
+            .get_value(&name.name, sheet as u32)
+            .ok_or(Failure::Unsupported)?;
+        let key = name.name.to_ascii_uppercase();
+        if !self.evaluating_names.insert(key.clone()) {
+            return Err(Failure::Unsupported);
+        }
+        let result = match Expression::parse(&value) {
+            Ok(expression) => self.eval(&expression, Position { sheet }),
+            Err(_) => Err(Failure::Unsupported),
+        };
+        self.evaluating_names.remove(&key);
+        result
+    }
+
+    fn function(&mut self, name: &str, arguments: &[Expression], at: Position) -> CalcResult {
+        let name = name.trim_start_matches("_xlfn.").trim_start_matches("_xlws.").to_ascii_uppercase();
+        // the functions choosing between their arguments only evaluate the chosen one
+        match name.as_str() {
+            "IF" if (1..=3).contains(&arguments.len()) => {
+                let condition = self.eval(&arguments[0], at)?;
+                let condition = match self.scalar(condition).boolean() {
+                    Ok(condition) => condition,
+                    Err(Failure::Error(error)) => return Ok(CalcValue::Error(error)),
+                    Err(failure) => return Err(failure),
+                };
+                match (condition, arguments.get(1), arguments.get(2)) {
+                    (true, Some(value), _) | (false, _, Some(value)) => self.eval(value, at),
+                    (true, None, _) => Ok(CalcValue::Bool(true)),
+                    (false, _, None) => Ok(CalcValue::Bool(false)),
+                }
+            }
+            "IFERROR" | "IFNA" if arguments.len() == 2 => {
+                let value = self.eval(&arguments[0], at)?;
+                match self.scalar(value.clone()) {
+                    CalcValue::Error(ErrorKind::NA) => self.eval(&arguments[1], at),
+                    CalcValue::Error(_) if name == "IFERROR" => self.eval(&arguments[1], at),
+                    _ => Ok(value),
+                }
+            }
+            _ => {
+                let mut values = vec![];
+                for argument in arguments {
+                    values.push(self.eval(argument, at)?);
+                }
+                match functions::call(self, &name, values) {
+                    Err(Failure::Error(error)) => Ok(CalcValue::Error(error)),
+                    result => result,
+                }
+            }
+        }
+    }
+
+    fn sheet_index(&self, name: &str) -> Option<usize> {
+        self.workbook.sheets.iter().position(|sheet| is_same_sheet(&sheet.name, name))
+    }
+
+    ///
+    /// The area without the rows and the columns after the last cell of its sheet
+    ///
+    fn clamp(&self, area: Area) -> Area {
+        let sheet = &self.workbook.sheets[area.sheet];
+        Area {
+            last_row: area.last_row.min(sheet.max_row().max(area.first_row)),
+            last_col: area.last_col.min(sheet.max_column().max(area.first_col)),
+            ..area
+        }
+    }
+
+    ///
+    /// A single value: the first cell of an area, or the first value of an array
+    ///
+    pub(crate) fn scalar(&mut self, value: CalcValue) -> CalcValue {
+        match value {
+            CalcValue::Area(area) => self.cell_value(area.sheet, area.first_row, area.first_col),
+            CalcValue::Array(array) => array.into_iter().next()
+                .and_then(|row| row.into_iter().next())
+                .unwrap_or(CalcValue::Empty),
+            value => value,
+        }
+    }
+
+    ///
+    /// The values of an area or an array by rows, a single value is an array of one value
+    ///
+    pub(crate) fn grid(&mut self, value: CalcValue) -> Vec<Vec<CalcValue>> {
+        match value {
+            CalcValue::Area(area) => {
+                let area = self.clamp(area);
+                (area.first_row..=area.last_row)
+                    .map(|row| (area.first_col..=area.last_col).map(|col| self.cell_value(area.sheet, row, col)).collect())
+                    .collect()
+            }
+            CalcValue::Array(array) => array,
+            value => vec![vec![value]],
+        }
+    }
+
+    ///
+    /// The values of an area or an array, by rows
+    ///
+    pub(crate) fn values(&mut self, value: CalcValue) -> Vec<CalcValue> {
+        self.grid(value).into_iter().flatten().collect()
+    }
+
+    ///
+    /// Apply an operation to a value, or to every value of an area or an array
+    ///
+    fn map<F: Fn(&CalcValue) -> CalcResult>(&mut self, value: CalcValue, operation: F) -> CalcValue {
+        let apply = |value: &CalcValue| match value {
+            CalcValue::Error(error) => CalcValue::Error(error.clone()),
+            value => operation(value).unwrap_or_else(|failure| failure.into_value()),
+        };
+        match value {
+            value @ (CalcValue::Area(_) | CalcValue::Array(_)) if !self.is_single(&value) => {
+                let grid = self.grid(value);
+                CalcValue::Array(grid.iter().map(|row| row.iter().map(apply).collect()).collect())
+            }
+            value => {
+                let value = self.scalar(value);
+                apply(&value)
+            }
+        }
+    }
+
+    fn is_single(&self, value: &CalcValue) -> bool {
+        match value {
+            CalcValue::Area(area) => area.height() == 1 && area.width() == 1,
+            CalcValue::Array(array) => array.len() == 1 && array[0].len() == 1,
+            _ => true,
+        }
+    }
+
+    ///
+    /// A binary operation on values, or on every pair of values of areas or arrays
+    ///
+    fn binary(&mut self, left: CalcValue, operator: BinaryOperator, right: CalcValue) -> CalcValue {
+        if self.is_single(&left) && self.is_single(&right) {
+            let left = self.scalar(left);
+            let right = self.scalar(right);
+            return binary_scalar(&left, operator, &right);
+        }
+        let left = self.grid(left);
+        let right = self.grid(right);
+        let height = left.len().max(right.len());
+        let width = left.iter().chain(right.iter()).map(Vec::len).max().unwrap_or(0);
+        let element = |grid: &Vec<Vec<CalcValue>>, row: usize, col: usize| {
+            let row = if grid.len() == 1 { 0 } else { row };
+            let values = grid.get(row)?;
+            let col = if values.len() == 1 { 0 } else { col };
+            values.get(col).cloned()
+        };
+        let array = (0..height).map(|row| (0..width).map(|col| {
+            match (element(&left, row, col), element(&right, row, col)) {
+                (Some(left), Some(right)) => binary_scalar(&left, operator, &right),
+                _ => CalcValue::Error(ErrorKind::NA),
+            }
+        }).collect()).collect();
+        CalcValue::Array(array)
+    }
+}
+
+fn binary_scalar(left: &CalcValue, operator: BinaryOperator, right: &CalcValue) -> CalcValue {
+    let result = match operator {
+        BinaryOperator::Concat => left.text().and_then(|left| Ok(CalcValue::Text(left + &right.text()?))),
+        BinaryOperator::Equal | BinaryOperator::NotEqual | BinaryOperator::Less
+        | BinaryOperator::LessOrEqual | BinaryOperator::Greater | BinaryOperator::GreaterOrEqual => {
+            compare(left, right).map(|ordering| CalcValue::Bool(match operator {
+                BinaryOperator::Equal => ordering == Ordering::Equal,
+                BinaryOperator::NotEqual => ordering != Ordering::Equal,
+                BinaryOperator::Less => ordering == Ordering::Less,
+                BinaryOperator::LessOrEqual => ordering != Ordering::Greater,
+                BinaryOperator::Greater => ordering == Ordering::Greater,
+                _ => ordering != Ordering::Less,
+            }))
+        }
+        operator => arithmetic(left, operator, right),
+    };
+    result.unwrap_or_else(|failure| failure.into_value())
+}
+
+fn arithmetic(left: &CalcValue, operator: BinaryOperator, right: &CalcValue) -> CalcResult {
+    let left = left.number()?;
+    let right = right.number()?;
+    let result = match operator {
+        BinaryOperator::Add => left + right,
+        BinaryOperator::Subtract => left - right,
+        BinaryOperator::Multiply => left * right,
+        BinaryOperator::Divide if right == 0.0 => return Err(ErrorKind::Div0.into()),
+        BinaryOperator::Divide => left / right,
+        BinaryOperator::Power if left == 0.0 && right < 0.0 => return Err(ErrorKind::Div0.into()),
+        BinaryOperator::Power => left.powf(right),
+        _ => return Err(ErrorKind::Value.into()),
+    };
+    number(result)
+}
+
+///
+/// A number result, `#NUM!` if it is not finite
+///
+pub(crate) fn number(number: f64) -> CalcResult {
+    if number.is_finite() { Ok(CalcValue::Number(number)) } else { Err(ErrorKind::Num.into()) }
+}
+
+///
+/// Compare two values like the comparison operators: numbers are before texts, which are before booleans.
+/// Texts are compared ignoring the case, and an empty value is compared as the default value of the other type.
+///
+pub(crate) fn compare(left: &CalcValue, right: &CalcValue) -> CalcResult<Ordering> {
+    let rank = |value: &CalcValue| match value {
+        CalcValue::Text(_) => 1,
+        CalcValue::Bool(_) => 2,
+        _ => 0,
+    };
+    let ordering = match (left, right) {
+        (CalcValue::Error(error), _) | (_, CalcValue::Error(error)) => return Err(error.clone().into()),
+        (CalcValue::Empty, CalcValue::Empty) => Ordering::Equal,
+        (CalcValue::Empty, value) => compare(&value.default_value(), value)?,
+        (value, CalcValue::Empty) => compare(value, &value.default_value())?,
+        (CalcValue::Number(left), CalcValue::Number(right)) => left.partial_cmp(right).unwrap_or(Ordering::Equal),
+        (CalcValue::Text(left), CalcValue::Text(right)) => left.to_lowercase().cmp(&right.to_lowercase()),
+        (CalcValue::Bool(left), CalcValue::Bool(right)) => left.cmp(right),
+        (left, right) => rank(left).cmp(&rank(right)),
+    };
+    Ok(ordering)
+}
+
+impl Failure {
+    fn into_value(self) -> CalcValue {
+        match self {
+            Failure::Error(error) => CalcValue::Error(error),
+            Failure::Unsupported => CalcValue::Error(ErrorKind::Value),
+        }
+    }
+}
+
+impl CalcValue {
+    fn from_value(value: Value, date1904: bool) -> CalcValue {
+        match value {
+            Value::Empty => CalcValue::Empty,
+            Value::Number(number) => CalcValue::Number(number),
+            Value::String(text) => CalcValue::Text(text),
+            Value::Bool(bool) => CalcValue::Bool(bool),
+            Value::Error(error) => CalcValue::Error(error),
+            Value::DateTime(date_time) => date_time.to_serial_in(date1904).map_or(CalcValue::Error(ErrorKind::Num), CalcValue::Number),
+            Value::RichText(rich_text) => CalcValue::Text(rich_text.text()),
+        }
+    }
+
+    fn to_value(&self) -> Value {
+        match self {
+            CalcValue::Number(number) => Value::Number(*number),
+            CalcValue::Text(text) => Value::String(text.to_string()),
+            CalcValue::Bool(bool) => Value::Bool(*bool),
+            CalcValue::Error(error) => Value::Error(error.clone()),
+            _ => Value::Number(0.0),
+        }
+    }
+
+    fn default_value(&self) -> CalcValue {
+        match self {
+            CalcValue::Text(_) => CalcValue::Text(String::new()),
+            CalcValue::Bool(_) => CalcValue::Bool(false),
+            _ => CalcValue::Number(0.0),
+        }
+    }
+
+    ///
+    /// The value as a number, texts are parsed and booleans are 1 or 0
+    ///
+    pub(crate) fn number(&self) -> CalcResult<f64> {
+        match self {
+            CalcValue::Empty => Ok(0.0),
+            CalcValue::Number(number) => Ok(*number),
+            CalcValue::Bool(bool) => Ok(if *bool { 1.0 } else { 0.0 }),
+            CalcValue::Text(text) => parse_number(text).ok_or(ErrorKind::Value.into()),
+            CalcValue::Error(error) => Err(error.clone().into()),
+            CalcValue::Area(_) | CalcValue::Array(_) => Err(ErrorKind::Value.into()),
+        }
+    }
+
+    pub(crate) fn text(&self) -> CalcResult<String> {
+        match self {
+            CalcValue::Empty => Ok(String::new()),
+            CalcValue::Number(number) => Ok(number_text(*number)),
+            CalcValue::Bool(bool) => Ok(if *bool { "TRUE" } else { "FALSE" }.to_string()),
+            CalcValue::Text(text) => Ok(text.to_string()),
+            CalcValue::Error(error) => Err(error.clone().into()),
+            CalcValue::Area(_) | CalcValue::Array(_) => Err(ErrorKind::Value.into()),
+        }
+    }
+
+    pub(crate) fn boolean(&self) -> CalcResult<bool> {
+        match self {
+            CalcValue::Empty => Ok(false),
+            CalcValue::Number(number) => Ok(*number != 0.0),
+            CalcValue::Bool(bool) => Ok(*bool),
+            CalcValue::Text(text) if text.eq_ignore_ascii_case("TRUE") => Ok(true),
+            CalcValue::Text(text) if text.eq_ignore_ascii_case("FALSE") => Ok(false),
+            CalcValue::Error(error) => Err(error.clone().into()),
+            _ => Err(ErrorKind::Value.into()),
+        }
+    }
+}
+
+pub(crate) fn parse_number(text: &str) -> Option<f64> {
+    let text = text.trim();
+    match text.strip_suffix('%') {
+        Some(percent) => percent.trim().parse::<f64>().ok().map(|number| number / 100.0),
+        None => text.parse::<f64>().ok().filter(|number| number.is_finite()),
+    }
+}
+
+///
+/// A number as text, with the 15 significant digits Excel keeps
+///
+pub(crate) fn number_text(number: f64) -> String {
+    let number = round_significant(number);
+    if number == 0.0 { "0".to_string() } else { number.to_string() }
+}
+
+///
+/// Round a number to 15 significant digits, to drop the errors of binary floating point like 0.1+0.2
+///
+pub(crate) fn round_significant(number: f64) -> f64 {
+    format!("{number:.14e}").parse().unwrap_or(number)
+}
@@ -0,0 +1,828 @@
+//! The functions of the calculator, by name without the `_xlfn.` prefix.
+//! The functions choosing between their arguments, like IF, are evaluated by the calculator itself.
+use std::cmp::Ordering;
+use std::time::{SystemTime, UNIX_EPOCH};
+use crate::api::cell::date_time::ExcelDateTime;
+use crate::core::calc::number_format::{format_number, format_text};
+use crate::core::calc::{compare, number, parse_number, round_significant, Area, CalcResult, CalcValue, Calculator, Failure};
+use crate::ErrorKind;
+
+// The serial number of 1970-01-01 in the 1900 date system
+const UNIX_EPOCH_SERIAL: f64 = 25569.0;
+
+pub(super) fn call(calc: &mut Calculator, name: &str, values: Vec<CalcValue>) -> CalcResult {
+    let mut arguments = Arguments { calc, values };
+    match name {
+        "SUM" => number(arguments.numbers()?.iter().sum()),
+        "PRODUCT" => number(arguments.numbers()?.iter().product()),
+        "AVERAGE" => average(&arguments.numbers()?),
+        "MIN" => Ok(CalcValue::Number(arguments.numbers()?.into_iter().reduce(f64::min).unwrap_or_default())),
+        "MAX" => Ok(CalcValue::Number(arguments.numbers()?.into_iter().reduce(f64::max).unwrap_or_default())),
+        "COUNT" => Ok(count(arguments.all_values(), |value| matches!(value, CalcValue::Number(_)))),
+        "COUNTA" => Ok(count(arguments.all_values(), |value| *value != CalcValue::Empty)),
+        "COUNTBLANK" => {
+            arguments.arity(1, 1)?;
+            Ok(count(arguments.all_values(), |value| matches!(value, CalcValue::Empty) || *value == CalcValue::Text(String::new())))
+        }
+        "ABS" => arguments.math(f64::abs),
+        "INT" => arguments.math(f64::floor),
+        "SQRT" => {
+            arguments.arity(1, 1)?;
+            match arguments.number(0)? {
+                number if number < 0.0 => Err(ErrorKind::Num.into()),
+                number => Ok(CalcValue::Number(number.sqrt())),
+            }
+        }
+        "MOD" => {
+            arguments.arity(2, 2)?;
+            let (number, divisor) = (arguments.number(0)?, arguments.number(1)?);
+            if divisor == 0.0 {
+                return Err(ErrorKind::Div0.into());
+            }
+            self::number(number - divisor * (number / divisor).floor())
+        }
+        "POWER" => {
+            arguments.arity(2, 2)?;
+            self::number(arguments.number(0)?.powf(arguments.number(1)?))
+        }
+        "ROUND" => arguments.round(Rounding::Nearest),
+        "ROUNDUP" => arguments.round(Rounding::Up),
+        "ROUNDDOWN" => arguments.round(Rounding::Down),
+        "SUMPRODUCT" => sum_product(&mut arguments),
+        "AND" => arguments.logical(|values| values.iter().all(|&value| value)),
+        "OR" => arguments.logical(|values| values.iter().any(|&value| value)),
+        "NOT" => {
+            arguments.arity(1, 1)?;
+            Ok(CalcValue::Bool(!arguments.boolean(0)?))
+        }
+        "TRUE" => Ok(CalcValue::Bool(true)),
+        "FALSE" => Ok(CalcValue::Bool(false)),
+        "CHOOSE" => {
+            arguments.arity(2, 255)?;
+            let index = arguments.number(0)?.trunc();
+            if index < 1.0 || index as usize >= arguments.values.len() {
+                return Err(ErrorKind::Value.into());
+            }
+            Ok(arguments.values.swap_remove(index as usize))
+        }
+        "VLOOKUP" => lookup(&mut arguments, false),
+        "HLOOKUP" => lookup(&mut arguments, true),
+        "XLOOKUP" => x_lookup(&mut arguments),
+        "INDEX" => index(&mut arguments),
+        "MATCH" => match_position(&mut arguments),
+        "COUNTIF" => {
+            arguments.arity(2, 2)?;
+            conditional(&mut arguments, None, Aggregate::Count)
+        }
+        "COUNTIFS" => conditional(&mut arguments, None, Aggregate::Count),
+        "SUMIF" | "AVERAGEIF" => {
+            arguments.arity(2, 3)?;
+            let aggregate = if name == "SUMIF" { Aggregate::Sum } else { Aggregate::Average };
+            // the values are the cells from the first one of the last argument, in an area of the size of the range
+            let values = match arguments.values.get(2) {
+                Some(CalcValue::Area(area)) => {
+                    let (height, width) = size(&arguments.values[0]);
+                    CalcValue::Area(Area { last_row: area.first_row + height - 1, last_col: area.first_col + width - 1, ..*area })
+                }
+                Some(CalcValue::Empty) | None => arguments.values[0].clone(),
+                Some(values) => values.clone(),
+            };
+            arguments.values.truncate(2);
+            conditional(&mut arguments, Some(values), aggregate)
+        }
+        "SUMIFS" | "AVERAGEIFS" => {
+            arguments.arity(3, 255)?;
+            let aggregate = if name == "SUMIFS" { Aggregate::Sum } else { Aggregate::Average };
+            let values = arguments.values.remove(0);
+            conditional(&mut arguments, Some(values), aggregate)
+        }
+        "LEN" => {
+            arguments.arity(1, 1)?;
+            Ok(CalcValue::Number(arguments.text(0)?.chars().count() as f64))
+        }
+        "LEFT" | "RIGHT" => {
+            arguments.arity(1, 2)?;
+            let text = arguments.text(0)?.chars().collect::<Vec<char>>();
+            let count = arguments.number_or(1, 1.0)?;
+            if count < 0.0 {
+                return Err(ErrorKind::Value.into());
+            }
+            let count = (count as usize).min(text.len());
+            let text = if name == "LEFT" { &text[..count] } else { &text[text.len() - count..] };
+            Ok(CalcValue::Text(text.iter().collect()))
+        }
+        "MID" => {
+            arguments.arity(3, 3)?;
+            let text = arguments.text(0)?;
+            let (start, count) = (arguments.number(1)?, arguments.number(2)?);
+            if start < 1.0 || count < 0.0 {
+                return Err(ErrorKind::Value.into());
+            }
+            Ok(CalcValue::Text(text.chars().skip(start as usize - 1).take(count as usize).collect()))
+        }
+        "UPPER" => arguments.map_text(|text| text.to_uppercase()),
+        "LOWER" => arguments.map_text(|text| text.to_lowercase()),
+        "TRIM" => arguments.map_text(|text| text.split(' ').filter(|word| !word.is_empty()).collect::<Vec<&str>>().join(" ")),
+        "CONCATENATE" => {
+            let mut text = String::new();
+            for index in 0..arguments.values.len() {
+                text += &arguments.text(index)?;
+            }
+            Ok(CalcValue::Text(text))
+        }
+        "CONCAT" => {
+            let mut text = String::new();
+            for value in arguments.all_values() {
+                text += &value.text()?;
+            }
+            Ok(CalcValue::Text(text))
+        }
+        "TEXTJOIN" => {
+            arguments.arity(3, 255)?;
+            let delimiter = arguments.text(0)?;
+            let ignore_empty = arguments.boolean(1)?;
+            let values = arguments.values.split_off(2);
+            let mut texts = vec![];
+            for value in values.into_iter().flat_map(|value| arguments.calc.values(value)) {
+                let text = value.text()?;
+                if !(ignore_empty && text.is_empty()) {
+                    texts.push(text);
+                }
+            }
+            Ok(CalcValue::Text(texts.join(&delimiter)))
+        }
+        "SUBSTITUTE" => {
+            arguments.arity(3, 4)?;
+            let (text, old, new) = (arguments.text(0)?, arguments.text(1)?, arguments.text(2)?);
+            if old.is_empty() {
+                return Ok(CalcValue::Text(text));
+            }
+            match arguments.values.get(3) {
+                Some(_) => {
+                    let instance = arguments.number(3)?;
+                    if instance < 1.0 {
+                        return Err(ErrorKind::Value.into());
+                    }
+                    match text.match_indices(&old).nth(instance as usize - 1) {
+                        Some((index, _)) => Ok(CalcValue::Text(format!("{}{new}{}", &text[..index], &text[index + old.len()..]))),
+                        None => Ok(CalcValue::Text(text)),
+                    }
+                }
+                None => Ok(CalcValue::Text(text.replace(&old, &new))),
+            }
+        }
+        "VALUE" => {
+            arguments.arity(1, 1)?;
+            Ok(CalcValue::Number(arguments.number(0)?))
+        }
+        "TEXT" => {
+            arguments.arity(2, 2)?;
+            let format = arguments.text(1)?;
+            match arguments.scalar(0) {
+                CalcValue::Text(text) if parse_number(&text).is_none() => Ok(CalcValue::Text(format_text(&text, &format))),
+                value => Ok(CalcValue::Text(format_number(value.number()?, &format, arguments.calc.date1904))),
+            }
+        }
+        "DATE" => {
+            arguments.arity(3, 3)?;
+            let year = arguments.number(0)?.trunc() as i64;
+            let year = match year {
+                0..=1899 => year + 1900,
+                1900..=9999 => year,
+                _ => return Err(ErrorKind::Num.into()),
+            };
+            let serial = date_serial(year, arguments.number(1)?.trunc() as i64, arguments.number(2)?.trunc() as i64)?;
+            ExcelDateTime::from_serial(serial).to_serial_in(arguments.calc.date1904).map(CalcValue::Number).ok_or(ErrorKind::Num.into())
+        }
+        "TIME" => {
+            arguments.arity(3, 3)?;
+            let seconds = arguments.number(0)?.trunc() * 3600.0 + arguments.number(1)?.trunc() * 60.0 + arguments.number(2)?.trunc();
+            if seconds < 0.0 {
+                return Err(ErrorKind::Num.into());
+            }
+            Ok(CalcValue::Number((seconds / 86400.0).fract()))
+        }
+        "YEAR" => arguments.date_part(|(year, ..)| year as f64),
+        "MONTH" => arguments.date_part(|(_, month, ..)| month as f64),
+        "DAY" => arguments.date_part(|(_, _, day, ..)| day as f64),
+        "HOUR" => arguments.date_part(|(_, _, _, hour, ..)| hour as f64),
+        "MINUTE" => arguments.date_part(|(.., minute, _)| minute as f64),
+        "SECOND" => arguments.date_part(|(.., second)| second.round()),
+        "WEEKDAY" => {
+            arguments.arity(1, 2)?;
+            let serial = arguments.date_time(0)?.serial().floor() as i64;
+            // the serial number 1 is a Sunday
+            let day = (serial - 1).rem_euclid(7) as f64;
+            match arguments.number_or(1, 1.0)? as i64 {
+                1 | 17 => Ok(CalcValue::Number(day + 1.0)),
+                2 | 11 => Ok(CalcValue::Number((day + 6.0) % 7.0 + 1.0)),
+                3 => Ok(CalcValue::Number((day + 6.0) % 7.0)),
+                _ => Err(ErrorKind::Num.into()),
+            }
+        }
+        "TODAY" | "NOW" => {
+            arguments.arity(0, 0)?;
+            let seconds = SystemTime::now().duration_since(UNIX_EPOCH).map_or(0.0, |duration| duration.as_secs_f64());
+            let serial = UNIX_EPOCH_SERIAL + seconds / 86400.0;
+            let serial = if name == "TODAY" { serial.floor() } else { serial };
+            ExcelDateTime::from_serial(serial).to_serial_in(arguments.calc.date1904).map(CalcValue::Number).ok_or(ErrorKind::Num.into())
+        }
+        "EDATE" | "EOMONTH" => {
+            arguments.arity(2, 2)?;
+            let (year, month, day, ..) = arguments.date_time(0)?.to_ymd_hms();
+            let month = month as i64 + arguments.number(1)?.trunc() as i64;
+            let serial = match name {
+                // the day of a shorter month is its last day
+                "EDATE" => date_serial(year as i64, month, 1)? + (day as f64 - 1.0).min(days_in_month(year as i64, month)? - 1.0),
+                _ => date_serial(year as i64, month + 1, 0)?,
+            };
+            ExcelDateTime::from_serial(serial).to_serial_in(arguments.calc.date1904).map(CalcValue::Number).ok_or(ErrorKind::Num.into())
+        }
+        "DAYS" => {
+            arguments.arity(2, 2)?;
+            Ok(CalcValue::Number(arguments.number(0)?.floor() - arguments.number(1)?.floor()))
+        }
+        "ISBLANK" => arguments.is(|value| *value == CalcValue::Empty),
+        "ISNUMBER" => arguments.is(|value| matches!(value, CalcValue::Number(_))),
+        "ISTEXT" => arguments.is(|value| matches!(value, CalcValue::Text(_))),
+        "ISLOGICAL" => arguments.is(|value| matches!(value, CalcValue::Bool(_))),
+        "ISERROR" => arguments.is(|value| matches!(value, CalcValue::Error(_))),
+        "ISNA" => arguments.is(|value| *value == CalcValue::Error(ErrorKind::NA)),
+        "NA" => Err(ErrorKind::NA.into()),
+        _ => Err(Failure::Unsupported),
+    }
+}
+
+///
+/// The evaluated arguments of a function
+///
+struct Arguments<'c, 'a> {
+    calc: &'c mut Calculator<'a>,
+    values: Vec<CalcValue>,
+}
+
+impl Arguments<'_, '_> {
+    ///
+    /// A formula with a wrong number of arguments cannot be written in Excel, it is not evaluated
+    ///
+    fn arity(&self, min: usize, max: usize) -> CalcResult<()> {
+        if (min..=max).contains(&self.values.len()) { Ok(()) } else { Err(Failure::Unsupported) }
+    }
+
+    fn scalar(&mut self, index: usize) -> CalcValue {
+        let value = self.values.get(index).cloned().unwrap_or(CalcValue::Empty);
+        self.calc.scalar(value)
+    }
+
+    fn number(&mut self, index: usize) -> CalcResult<f64> {
+        self.scalar(index).number()
+    }
+
+    ///
+    /// A number, or the default value of an argument omitted
+    ///
+    fn number_or(&mut self, index: usize, default: f64) -> CalcResult<f64> {
+        match self.values.get(index) {
+            None | Some(CalcValue::Empty) => Ok(default),
+            Some(_) => self.number(index),
+        }
+    }
+
+    fn text(&mut self, index: usize) -> CalcResult<String> {
+        self.scalar(index).text()
+    }
+
+    fn boolean(&mut self, index: usize) -> CalcResult<bool> {
+        self.scalar(index).boolean()
+    }
+
+    fn date_time(&mut self, index: usize) -> CalcResult<ExcelDateTime> {
+        match self.number(index)? {
+            serial if serial < 0.0 => Err(ErrorKind::Num.into()),
+            serial => Ok(ExcelDateTime::from_serial_in(serial, self.calc.date1904)),
+        }
+    }
+
+    ///
+    /// The values of all the arguments, with the values of their areas and arrays
+    ///
+    fn all_values(&mut self) -> Vec<CalcValue> {
+        std::mem::take(&mut self.values).into_iter()
+            .flat_map(|value| self.calc.values(value))
+            .collect()
+    }
+
+    ///
+    /// The numbers of the arguments to aggregate.
+    /// Only the numbers of areas and arrays are kept, the other arguments are converted to numbers.
+    ///
+    fn numbers(&mut self) -> CalcResult<Vec<f64>> {
+        let mut numbers = vec![];
+        for value in std::mem::take(&mut self.values) {
+            match value {
+                CalcValue::Area(_) | CalcValue::Array(_) => for value in self.calc.values(value) {
+                    match value {
+                        CalcValue::Number(number) => numbers.push(number),
+                        CalcValue::Error(error) => return Err(error.into()),
+                        _ => {}
+                    }
+                },
+                CalcValue::Empty => {}
+                value => numbers.push(value.number()?),
+            }
+        }
+        Ok(numbers)
+    }
+
+    fn math<F: Fn(f64) -> f64>(&mut self, function: F) -> CalcResult {
+        self.arity(1, 1)?;
+        number(function(self.number(0)?))
+    }
+
+    fn round(&mut self, rounding: Rounding) -> CalcResult {
+        self.arity(1, 2)?;
+        let value = self.number(0)?;
+        let digits = self.number_or(1, 0.0)?.trunc() as i32;
+        let factor = 10f64.powi(digits.abs());
+        let scaled = round_significant(if digits >= 0 { value * factor } else { value / factor });
+        let rounded = match rounding {
+            Rounding::Nearest => scaled.round(),
+            Rounding::Up => if scaled >= 0.0 { scaled.ceil() } else { scaled.floor() },
+            Rounding::Down => scaled.trunc(),
+        };
+        number(if digits >= 0 { rounded / factor } else { rounded * factor })
+    }
+
+    fn logical<F: Fn(&[bool]) -> bool>(&mut self, function: F) -> CalcResult {
+        self.arity(1, 255)?;
+        let mut values = vec![];
+        for value in std::mem::take(&mut self.values) {
+            match value {
+                CalcValue::Area(_) | CalcValue::Array(_) => for value in self.calc.values(value) {
+                    match value {
+                        CalcValue::Number(_) | CalcValue::Bool(_) | CalcValue::Error(_) => values.push(value.boolean()?),
+                        _ => {}
+                    }
+                },
+                value => values.push(value.boolean()?),
+            }
+        }
+        if values.is_empty() {
+            return Err(ErrorKind::Value.into());
+        }
+        Ok(CalcValue::Bool(function(&values)))
+    }
+
+    fn map_text<F: Fn(&str) -> String>(&mut self, function: F) -> CalcResult {
+        self.arity(1, 1)?;
+        Ok(CalcValue::Text(function(&self.text(0)?)))
+    }
+
+    fn date_part<F: Fn((i32, u32, u32, u32, u32, f64)) -> f64>(&mut self, function: F) -> CalcResult {
+        self.arity(1, 1)?;
+        Ok(CalcValue::Number(function(self.date_time(0)?.to_ymd_hms())))
+    }
+
+    fn is<F: Fn(&CalcValue) -> bool>(&mut self, function: F) -> CalcResult {
+        self.arity(1, 1)?;
+        Ok(CalcValue::Bool(function(&self.scalar(0))))
+    }
+}
+
+enum Rounding {
+    Nearest,
+    Up,
+    Down,
+}
+
+enum Aggregate {
+    Count,
+    Sum,
+    Average,
+}
+
+fn average(numbers: &[f64]) -> CalcResult {
+    if numbers.is_empty() {
+        return Err(ErrorKind::Div0.into());
+    }
+    number(numbers.iter().sum::<f64>() / numbers.len() as f64)
+}
+
+fn count<F: Fn(&CalcValue) -> bool>(values: Vec<CalcValue>, function: F) -> CalcValue {
+    CalcValue::Number(values.iter().filter(|value| function(value)).count() as f64)
+}
+
+fn sum_product(arguments: &mut Arguments) -> CalcResult {
+    arguments.arity(1, 255)?;
+    let sizes = arguments.values.iter().map(size).collect::<Vec<(u32, u32)>>();
+    if sizes.iter().any(|size| *size != sizes[0]) {
+        return Err(ErrorKind::Value.into());
+    }
+    let grids = std::mem::take(&mut arguments.values).into_iter()
+        .map(|value| arguments.calc.grid(value))
+        .collect::<Vec<_>>();
+    let mut sum = 0.0;
+    for (row, values) in grids[0].iter().enumerate() {
+        for col in 0..values.len() {
+            let mut product = 1.0;
+            for grid in &grids {
+                match grid.get(row).and_then(|values| values.get(col)) {
+                    Some(CalcValue::Number(number)) => product *= number,
+                    Some(CalcValue::Error(error)) => return Err(error.clone().into()),
+                    _ => product = 0.0,
+                }
+            }
+            sum += product;
+        }
+    }
+    number(sum)
+}
+
+///
+/// The number of rows and columns of an area or an array, a single value is one cell
+///
+fn size(value: &CalcValue) -> (u32, u32) {
+    match value {
+        CalcValue::Area(area) => (area.height(), area.width()),
+        CalcValue::Array(array) => (array.len() as u32, array.first().map_or(0, Vec::len) as u32),
+        _ => (1, 1),
+    }
+}
+
+///
+/// The value at a row and a column of an area or an array, from 0
+///
+fn element(calc: &mut Calculator, value: &CalcValue, row: u32, col: u32) -> CalcValue {
+    match value {
+        CalcValue::Area(area) => calc.cell_value(area.sheet, area.first_row + row, area.first_col + col),
+        CalcValue::Array(array) => array.get(row as usize)
+            .and_then(|values| values.get(col as usize))
+            .cloned()
+            .unwrap_or(CalcValue::Empty),
+        value if row == 0 && col == 0 => value.clone(),
+        _ => CalcValue::Empty,
+    }
+}
+
+///
+/// VLOOKUP, or HLOOKUP looking up the first row
+///
+fn lookup(arguments: &mut Arguments, horizontal: bool) -> CalcResult {
+    arguments.arity(3, 4)?;
+    let value = arguments.scalar(0);
+    if let CalcValue::Error(error) = value {
+        return Err(error.into());
+    }
+    let table = arguments.values[1].clone();
+    let (height, width) = size(&table);
+    let index = arguments.number(2)?.trunc();
+    let approximate = match arguments.values.get(3) {
+        None | Some(CalcValue::Empty) => true,
+        Some(_) => arguments.boolean(3)?,
+    };
+    if index < 1.0 {
+        return Err(ErrorKind::Value.into());
+    }
+    let index = index as u32 - 1;
+    if index >= if horizontal { height } else { width } {
+        return Err(ErrorKind::Ref.into());
+    }
+    let grid = arguments.calc.grid(table.clone());
+    let keys = match horizontal {
+        true => grid.first().cloned().unwrap_or_default(),
+        false => grid.iter().filter_map(|values| values.first().cloned()).collect(),
+    };
+    let position = match approximate {
+        true => approximate_match(&keys, &value, Ordering::Less),
+        false => exact_match(&keys, &value, true),
+    };
+    let position = position.ok_or(ErrorKind::NA)? as u32;
+    match horizontal {
+        true => Ok(element(arguments.calc, &table, index, position)),
+        false => Ok(element(arguments.calc, &table, position, index)),
+    }
+}
+
+fn x_lookup(arguments: &mut Arguments) -> CalcResult {
+    arguments.arity(3, 6)?;
+    let value = arguments.scalar(0);
+    if let CalcValue::Error(error) = value {
+        return Err(error.into());
+    }
+    let (height, width) = size(&arguments.values[1]);
+    if height != 1 && width != 1 {
+        return Err(ErrorKind::Value.into());
+    }
+    let match_mode = arguments.number_or(4, 0.0)? as i64;
+    let search_mode = arguments.number_or(5, 1.0)? as i64;
+    let keys = arguments.calc.values(arguments.values[1].clone());
+    let mut positions = (0..keys.len()).collect::<Vec<usize>>();
+    if search_mode < 0 {
+        positions.reverse();
+    }
+    let position = match match_mode {
+        0 => positions.into_iter().find(|&position| equals(&keys[position], &value, false)),
+        2 => positions.into_iter().find(|&position| equals(&keys[position], &value, true)),
+        -1 | 1 => {
+            // the exact match, or else the closest value smaller or larger
+            let wanted = if match_mode < 0 { Ordering::Less } else { Ordering::Greater };
+            let mut best: Option<usize> = None;
+            for position in positions {
+                let key = &keys[position];
+                if !same_type(key, &value) {
+                    continue;
+                }
+                match compare(key, &value)? {
+                    Ordering::Equal => {
+                        best = Some(position);
+                        break;
+                    }
+                    ordering if ordering == wanted => {
+                        let closer = best.map_or(Ok(true), |best| compare(key, &keys[best]).map(|ordering| ordering == wanted.reverse()))?;
+                        if closer {
+                            best = Some(position);
+                        }
+                    }
+                    _ => {}
+                }
+            }
+            best
+        }
+        _ => return Err(ErrorKind::Value.into()),
+    };
+    let position = match position {
+        Some(position) => position as u32,
+        None => return match arguments.values.get(3) {
+            None | Some(CalcValue::Empty) => Err(ErrorKind::NA.into()),
+            Some(_) => Ok(arguments.scalar(3)),
+        },
+    };
+    let results = arguments.values[2].clone();
+    let (result_height, result_width) = size(&results);
+    // the row or the column of the results at the position found
+    let values = match height == 1 && width > 1 {
+        true => (0..result_height).map(|row| vec![element(arguments.calc, &results, row, position)]).collect::<Vec<_>>(),
+        false => vec![(0..result_width).map(|col| element(arguments.calc, &results, position, col)).collect()],
+    };
+    match values.len() * values[0].len() {
+        1 => Ok(values[0][0].clone()),
+        _ => Ok(CalcValue::Array(values)),
+    }
+}
+
+fn index(arguments: &mut Arguments) -> CalcResult {
+    arguments.arity(2, 3)?;
+    let (height, width) = size(&arguments.values[0]);
+    let mut row = arguments.number(1)?.trunc();
+    let mut col = arguments.number_or(2, 0.0)?.trunc();
+    // a single row is indexed by its columns
+    if height == 1 && arguments.values.len() == 2 {
+        (row, col) = (0.0, row);
+    }
+    if row < 0.0 || col < 0.0 || row > height as f64 || col > width as f64 {
+        return Err(ErrorKind::Ref.into());
+    }
+    let (row, col) = (row as u32, col as u32);
+    match &arguments.values[0] {
+        CalcValue::Area(area) => {
+            let mut area = *area;
+            if row > 0 {
+                area.first_row += row - 1;
+                area.last_row = area.first_row;
+            }
+            if col > 0 {
+                area.first_col += col - 1;
+                area.last_col = area.first_col;
+            }
+            Ok(CalcValue::Area(area))
+        }
+        CalcValue::Array(array) => match (row, col) {
+            (0, 0) => Ok(CalcValue::Array(array.clone())),
+            (0, col) => Ok(CalcValue::Array(array.iter().map(|values| vec![values[col as usize - 1].clone()]).collect())),
+            (row, 0) => Ok(CalcValue::Array(vec![array[row as usize - 1].clone()])),
+            (row, col) => Ok(array[row as usize - 1][col as usize - 1].clone()),
+        },
+        value => Ok(value.clone()),
+    }
+}
+
+fn match_position(arguments: &mut Arguments) -> CalcResult {
+    arguments.arity(2, 3)?;
+    let value = arguments.scalar(0);
+    if let CalcValue::Error(error) = value {
+        return Err(error.into());
+    }
+    let (height, width) = size(&arguments.values[1]);
+    if height != 1 && width != 1 {
+        return Err(ErrorKind::NA.into());
+    }
+    let match_type = arguments.number_or(2, 1.0)?;
+    let keys = arguments.calc.values(arguments.values[1].clone());
+    let position = match match_type {
+        0.0 => exact_match(&keys, &value, true),
+        _ if match_type > 0.0 => approximate_match(&keys, &value, Ordering::Less),
+        _ => approximate_match(&keys, &value, Ordering::Greater),
+    };
+    Ok(CalcValue::Number(position.ok_or(ErrorKind::NA)? as f64 + 1.0))
+}
+
+fn same_type(left: &CalcValue, right: &CalcValue) -> bool {
+    std::mem::discriminant(left) == std::mem::discriminant(right)
+}
+
+///
+/// The values are equal ignoring the case, with the wildcards of the value looked up if they are allowed
+///
+fn equals(key: &CalcValue, value: &CalcValue, wildcards: bool) -> bool {
+    match (key, value) {
+        (CalcValue::Text(key), CalcValue::Text(value)) if wildcards => wildcard_match(value, key),
+        (key, value) => same_type(key, value) && compare(key, value) == Ok(Ordering::Equal),
+    }
+}
+
+fn exact_match(keys: &[CalcValue], value: &CalcValue, wildcards: bool) -> Option<usize> {
+    keys.iter().position(|key| equals(key, value, wildcards))
+}
+
+///
+/// The last position of the keys before the first one after the value, for keys sorted in an order.
+/// The keys of another type than the value are skipped.
+///
+fn approximate_match(keys: &[CalcValue], value: &CalcValue, order: Ordering) -> Option<usize> {
+    let mut position = None;
+    for (index, key) in keys.iter().enumerate() {
+        if !same_type(key, value) {
+            continue;
+        }
+        match compare(key, value) {
+            Ok(Ordering::Equal) => return Some(index),
+            Ok(ordering) if ordering == order => position = Some(index),
+            _ => break,
+        }
+    }
+    position
+}
+
+///
+/// Match a text with a pattern ignoring the case, `*` matches any characters, `?` any character, and `~` escapes them
+///
+fn wildcard_match(pattern: &str, text: &str) -> bool {
+    #[derive(Clone, Copy, PartialEq)]
+    enum Token {
+        Any,
+        One,
+        Char(char),
+    }
+    let mut tokens = vec![];
+    let mut chars = pattern.chars().flat_map(char::to_lowercase);
+    while let Some(c) = chars.next() {
+        tokens.push(match c {
+            '*' => Token::Any,
+            '?' => Token::One,
+            '~' => chars.next().map_or(Token::Char('~'), Token::Char),
+            c => Token::Char(c),
+        });
+    }
+    let text = text.chars().flat_map(char::to_lowercase).collect::<Vec<char>>();
+    // matched[j]: the tokens so far match the first j characters
+    let mut matched = vec![false; text.len() + 1];
+    matched[0] = true;
+    for token in tokens {
+        let mut next = vec![false; text.len() + 1];
+        for j in 0..=text.len() {
+            next[j] = match token {
+                Token::Any => matched[j] || j > 0 && next[j - 1],
+                Token::One => j > 0 && matched[j - 1],
+                Token::Char(c) => j > 0 && matched[j - 1] && text[j - 1] == c,
+            };
+        }
+        matched = next;
+    }
+    matched[text.len()]
+}
+
+///
+/// A criteria of COUNTIF and SUMIF, like `5`, `">=10"`, `"<>"` or `"a*"`
+///
+struct Criteria {
+    operator: Ordering,
+    negated: bool,
+    value: CalcValue,
+}
+
+impl Criteria {
+    fn new(value: CalcValue) -> Criteria {
+        let text = match value {
+            CalcValue::Text(text) => text,
+            // an empty cell is the criteria 0
+            CalcValue::Empty => return Criteria { operator: Ordering::Equal, negated: false, value: CalcValue::Number(0.0) },
+            value => return Criteria { operator: Ordering::Equal, negated: false, value },
+        };
+        let (operator, negated, rest) = [("<=", Ordering::Greater, true), (">=", Ordering::Less, true), ("<>", Ordering::Equal, true),
+            ("<", Ordering::Less, false), (">", Ordering::Greater, false), ("=", Ordering::Equal, false)]
+            .into_iter()
+            .find_map(|(prefix, operator, negated)| Some((operator, negated, text.strip_prefix(prefix)?)))
+            .unwrap_or((Ordering::Equal, false, text.as_str()));
+        let value = if let Some(number) = parse_number(rest) {
+            CalcValue::Number(number)
+        } else if rest.eq_ignore_ascii_case("TRUE") || rest.eq_ignore_ascii_case("FALSE") {
+            CalcValue::Bool(rest.eq_ignore_ascii_case("TRUE"))
+        } else if rest.starts_with('#') {
+            CalcValue::Error(ErrorKind::from_str(&rest.to_ascii_uppercase()))
+        } else {
+            CalcValue::Text(rest.to_string())
+        };
+        Criteria { operator, negated, value }
+    }
+
+    fn matches(&self, value: &CalcValue) -> bool {
+        let equality = self.operator == Ordering::Equal;
+        let ordering = match (&self.value, value) {
+            // `=` matches the empty cells, and `<>` the others
+            (CalcValue::Text(criteria), value) if criteria.is_empty() && equality => {
+                return (matches!(value, CalcValue::Empty) || *value == CalcValue::Text(String::new())) != self.negated;
+            }
+            (CalcValue::Text(criteria), CalcValue::Text(text)) if equality => {
+                return wildcard_match(criteria, text) != self.negated;
+            }
+            (CalcValue::Error(criteria), CalcValue::Error(error)) => Some(if criteria == error { Ordering::Equal } else { Ordering::Less }),
+            (CalcValue::Number(criteria), CalcValue::Text(text)) if equality => parse_number(text).and_then(|number| number.partial_cmp(criteria)),
+            (criteria, value) if same_type(criteria, value) => compare(value, criteria).ok(),
+            _ => None,
+        };
+        match ordering {
+            Some(ordering) => (ordering == self.operator) != self.negated,
+            None => equality && self.negated,
+        }
+    }
+}
+
+///
+/// COUNTIFS, SUMIFS and AVERAGEIFS, with the ranges and criteria as arguments
+///
+fn conditional(arguments: &mut Arguments, values: Option<CalcValue>, aggregate: Aggregate) -> CalcResult {
+    if !arguments.values.len().is_multiple_of(2) || arguments.values.is_empty() {
+        return Err(Failure::Unsupported);
+    }
+    let ranges = arguments.values.iter().step_by(2).cloned().collect::<Vec<CalcValue>>();
+    let mut criteria = vec![];
+    for index in (1..arguments.values.len()).step_by(2) {
+        criteria.push(Criteria::new(arguments.scalar(index)));
+    }
+    let size = size(&ranges[0]);
+    if ranges.iter().chain(values.iter()).any(|range| self::size(range) != size) {
+        return Err(ErrorKind::Value.into());
+    }
+    // the cells after the last cell of the sheet are empty
+    let grid = arguments.calc.grid(ranges[0].clone());
+    let mut numbers = vec![];
+    let mut matches = 0;
+    for (row, cells) in grid.iter().enumerate() {
+        'cells: for col in 0..cells.len() {
+            let (row, col) = (row as u32, col as u32);
+            for (range, criteria) in ranges.iter().zip(&criteria) {
+                if !criteria.matches(&element(arguments.calc, range, row, col)) {
+                    continue 'cells;
+                }
+            }
+            matches += 1;
+            if let Some(values) = &values {
+                match element(arguments.calc, values, row, col) {
+                    CalcValue::Number(number) => numbers.push(number),
+                    CalcValue::Error(error) => return Err(error.into()),
+                    _ => {}
+                }
+            }
+        }
+    }
+    match aggregate {
+        Aggregate::Count => Ok(CalcValue::Number(matches as f64)),
+        Aggregate::Sum => number(numbers.iter().sum()),
+        Aggregate::Average => average(&numbers),
+    }
+}
+
+///
+/// The serial number of a date in the 1900 date system, the months and the days overflow to the next ones
+///
+fn date_serial(year: i64, month: i64, day: i64) -> CalcResult<f64> {
+    let months = year * 12 + month - 1;
+    let first_day = ExcelDateTime::from_ymd_hms(months.div_euclid(12) as i32, months.rem_euclid(12) as u32 + 1, 1, 0, 0, 0.0)
+        .ok_or(ErrorKind::Num)?;
+    let serial = first_day.serial() + day as f64 - 1.0;
+    if serial < 0.0 { Err(ErrorKind::Num.into()) } else { Ok(serial) }
+}
+
+fn days_in_month(year: i64, month: i64) -> CalcResult<f64> {
+    Ok(date_serial(year, month + 1, 1)? - date_serial(year, month, 1)?)
+}
+
+#[test]
+fn test_wildcard_match() {
+    assert!(wildcard_match("a*c", "ABC"));
+    assert!(wildcard_match("a?c", "abc"));
+    assert!(!wildcard_match("a?c", "ac"));
+    assert!(wildcard_match("*~*", "a*"));
+    assert!(!wildcard_match("*~*", "ab"));
+    assert!(wildcard_match("*", ""));
+}
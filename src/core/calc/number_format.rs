@@ -0,0 +1,316 @@
+//! Format numbers with the number format codes of the cells, like the TEXT function does.
+//! - Numbers: `0`, `#` and `?` placeholders, thousands separators, scaling commas, percents and exponents.
+//! - Dates and times: years, months, days, hours, minutes, seconds and AM/PM.
+//! - Colors and conditions in brackets are ignored.
+use crate::api::cell::date_time::ExcelDateTime;
+use crate::core::calc::{number_text, round_significant};
+
+const MONTHS: [&str; 12] = ["January", "February", "March", "April", "May", "June", "July", "August", "September", "October", "November", "December"];
+const DAYS: [&str; 7] = ["Sunday", "Monday", "Tuesday", "Wednesday", "Thursday", "Friday", "Saturday"];
+
+///
+/// Format a number, with the section of the format for its sign
+///
+pub(crate) fn format_number(number: f64, format: &str, date1904: bool) -> String {
+    let sections = split_sections(format);
+    // the negative section is written without the sign
+    let (section, number, sign) = match sections.len() {
+        2.. if number < 0.0 => (sections[1], -number, ""),
+        3.. if number == 0.0 => (sections[2], number, ""),
+        _ if number < 0.0 => (sections[0], -number, "-"),
+        _ => (sections[0], number, ""),
+    };
+    let parts = parts(section);
+    if parts.iter().any(|part| matches!(part, Part::Date(..) | Part::AmPm(_))) {
+        return format_date(number, &parts, date1904);
+    }
+    if parts.iter().any(|part| matches!(part, Part::General)) {
+        let number = number_text(number);
+        return parts.iter().map(|part| match part {
+            Part::Literal(text) => text.as_str(),
+            _ => number.as_str(),
+        }).collect();
+    }
+    format!("{sign}{}", format_digits(number, &parts))
+}
+
+///
+/// Format a text with the fourth section of a format, a text is kept if there is none
+///
+pub(crate) fn format_text(text: &str, format: &str) -> String {
+    match split_sections(format).get(3) {
+        Some(section) => parts(section).iter().map(|part| match part {
+            Part::Literal(literal) => literal.as_str(),
+            Part::Text => text,
+            _ => "",
+        }).collect(),
+        None => text.to_string(),
+    }
+}
+
+#[derive(Debug, PartialEq)]
+enum Part {
+    Literal(String),
+    /// Digit placeholders, separators and exponents of a number
+    Digits(String),
+    /// A run of the same letter of a date, such as `yyyy` or `mm`
+    Date(char, usize),
+    /// `AM/PM`, or `A/P` if it is false
+    AmPm(bool),
+    General,
+    /// The `@` of a text
+    Text,
+}
+
+fn split_sections(format: &str) -> Vec<&str> {
+    let mut sections = vec![];
+    let mut start = 0;
+    let mut quoted = false;
+    let mut escaped = false;
+    for (index, c) in format.char_indices() {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' => escaped = true,
+            '"' => quoted = !quoted,
+            ';' if !quoted => {
+                sections.push(&format[start..index]);
+                start = index + 1;
+            }
+            _ => {}
+        }
+    }
+    sections.push(&format[start..]);
+    sections
+}
+
+fn parts(section: &str) -> Vec<Part> {
+    let chars = section.chars().collect::<Vec<char>>();
+    let mut parts = vec![];
+    let mut literal = String::new();
+    let push = |parts: &mut Vec<Part>, literal: &mut String, part: Part| {
+        if !literal.is_empty() {
+            parts.push(Part::Literal(std::mem::take(literal)));
+        }
+        parts.push(part);
+    };
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        let rest = chars[i..].iter().collect::<String>();
+        if c == '"' {
+            let end = chars[i + 1..].iter().position(|&c| c == '"').map_or(chars.len(), |end| i + 1 + end);
+            literal.extend(&chars[i + 1..end]);
+            i = end + 1;
+        } else if c == '\\' {
+            literal.extend(chars.get(i + 1));
+            i += 2;
+        } else if c == '_' {
+            // the width of the next character
+            literal.push(' ');
+            i += 2;
+        } else if c == '*' {
+            // the fill character
+            i += 2;
+        } else if c == '[' {
+            i = chars[i..].iter().position(|&c| c == ']').map_or(chars.len(), |end| i + end + 1);
+        } else if rest.to_ascii_lowercase().starts_with("general") {
+            push(&mut parts, &mut literal, Part::General);
+            i += "general".len();
+        } else if rest.to_ascii_uppercase().starts_with("AM/PM") {
+            push(&mut parts, &mut literal, Part::AmPm(true));
+            i += "AM/PM".len();
+        } else if rest.to_ascii_uppercase().starts_with("A/P") {
+            push(&mut parts, &mut literal, Part::AmPm(false));
+            i += "A/P".len();
+        } else if "yYmMdDhHsS".contains(c) {
+            let letter = c.to_ascii_lowercase();
+            let count = chars[i..].iter().take_while(|c| c.to_ascii_lowercase() == letter).count();
+            push(&mut parts, &mut literal, Part::Date(letter, count));
+            i += count;
+        } else if c == '@' {
+            push(&mut parts, &mut literal, Part::Text);
+            i += 1;
+        } else if "0#?.,".contains(c) || (c == 'E' || c == 'e') && matches!(chars.get(i + 1), Some('+' | '-')) {
+            let mut digits = String::new();
+            while i < chars.len() {
+                match chars[i] {
+                    c @ ('0' | '#' | '?' | '.' | ',') => digits.push(c),
+                    'E' | 'e' if matches!(chars.get(i + 1), Some('+' | '-')) => {
+                        digits.push('E');
+                        digits.push(chars[i + 1]);
+                        i += 1;
+                    }
+                    _ => break,
+                }
+                i += 1;
+            }
+            push(&mut parts, &mut literal, Part::Digits(digits));
+        } else {
+            literal.push(c);
+            i += 1;
+        }
+    }
+    if !literal.is_empty() {
+        parts.push(Part::Literal(literal));
+    }
+    parts
+}
+
+///
+/// Format a positive number with the first placeholders of a format, the others are written as they are
+///
+fn format_digits(number: f64, parts: &[Part]) -> String {
+    let percents = parts.iter()
+        .filter_map(|part| match part {
+            Part::Literal(text) => Some(text.matches('%').count()),
+            _ => None,
+        })
+        .sum::<usize>();
+    let number = number * 100f64.powi(percents as i32);
+    let mut formatted = false;
+    parts.iter().map(|part| match part {
+        Part::Literal(text) => text.to_string(),
+        Part::Digits(pattern) if !formatted => {
+            formatted = true;
+            format_pattern(number, pattern)
+        }
+        Part::Digits(pattern) => pattern.to_string(),
+        Part::Text => String::new(),
+        _ => number_text(number),
+    }).collect()
+}
+
+fn format_pattern(number: f64, pattern: &str) -> String {
+    if let Some(index) = pattern.find('E') {
+        let exponent_digits = pattern[index + 2..].chars().filter(|&c| c == '0').count();
+        let mantissa_pattern = &pattern[..index];
+        let decimals = decimals(mantissa_pattern.split_once('.').map_or("", |(_, frac_pattern)| frac_pattern)).0;
+        let mut exponent = if number == 0.0 { 0 } else { number.log10().floor() as i32 };
+        let mut mantissa = round(number / 10f64.powi(exponent), decimals);
+        if mantissa >= 10.0 {
+            mantissa /= 10.0;
+            exponent += 1;
+        }
+        let sign = if exponent < 0 { "-" } else if &pattern[index + 1..index + 2] == "+" { "+" } else { "" };
+        return format!("{}E{sign}{:0width$}", format_pattern(mantissa, mantissa_pattern), exponent.abs(), width = exponent_digits);
+    }
+    // each comma after the digits divides by a thousand
+    let scaling = pattern.len() - pattern.trim_end_matches(',').len();
+    let number = number / 1000f64.powi(scaling as i32);
+    let (int_pattern, frac_pattern) = match pattern.trim_end_matches(',').split_once('.') {
+        Some((int_pattern, frac_pattern)) => (int_pattern, Some(frac_pattern)),
+        None => (pattern.trim_end_matches(','), None),
+    };
+    let grouping = int_pattern.contains(',');
+    let (decimals, min_decimals) = decimals(frac_pattern.unwrap_or(""));
+    let rounded = format!("{:.*}", decimals, round(number, decimals));
+    let (int_digits, frac_digits) = rounded.split_once('.').unwrap_or((&rounded, ""));
+    let min_int = int_pattern.chars().filter(|&c| c == '0').count();
+    let int_digits = int_digits.trim_start_matches('0');
+    let mut int_text = format!("{:0>min_int$}", int_digits);
+    if grouping {
+        int_text = group_thousands(&int_text.chars().collect::<Vec<char>>());
+    }
+    let mut frac_text = frac_digits.to_string();
+    while frac_text.len() > min_decimals && frac_text.ends_with('0') {
+        frac_text.pop();
+    }
+    match frac_pattern {
+        Some(_) => format!("{int_text}.{frac_text}"),
+        None => int_text,
+    }
+}
+
+fn group_thousands(digits: &[char]) -> String {
+    let mut text = String::new();
+    for (index, digit) in digits.iter().enumerate() {
+        if index > 0 && (digits.len() - index).is_multiple_of(3) {
+            text.push(',');
+        }
+        text.push(*digit);
+    }
+    text
+}
+
+///
+/// The number of decimals of the fraction of a pattern, and the number of them always written
+///
+fn decimals(frac_pattern: &str) -> (usize, usize) {
+    let decimals = frac_pattern.chars().filter(|c| matches!(c, '0' | '#' | '?')).count();
+    let min_decimals = frac_pattern.chars().filter(|&c| c == '0').count();
+    (decimals, min_decimals)
+}
+
+///
+/// Round half away from zero, like Excel does
+///
+pub(crate) fn round(number: f64, decimals: usize) -> f64 {
+    let factor = 10f64.powi(decimals as i32);
+    round_significant(number * factor).round() / factor
+}
+
+fn format_date(serial: f64, parts: &[Part], date1904: bool) -> String {
+    let date_time = ExcelDateTime::from_serial_in(serial, date1904);
+    let (year, month, day, hour, minute, second) = date_time.to_ymd_hms();
+    let weekday = (date_time.serial().floor() as i64 - 1).rem_euclid(7) as usize;
+    let twelve_hours = parts.iter().any(|part| matches!(part, Part::AmPm(_)));
+    let codes = parts.iter()
+        .enumerate()
+        .filter_map(|(index, part)| match part {
+            Part::Date(letter, _) => Some((index, *letter)),
+            _ => None,
+        })
+        .collect::<Vec<(usize, char)>>();
+    // `m` is a minute after an hour or before a second
+    let is_minute = |index: usize| {
+        let position = codes.iter().position(|&(code_index, _)| code_index == index).unwrap_or_default();
+        position > 0 && codes[position - 1].1 == 'h' || codes.get(position + 1).is_some_and(|&(_, letter)| letter == 's')
+    };
+    let second = second.round() as u32;
+    parts.iter().enumerate().map(|(index, part)| match part {
+        Part::Literal(text) => text.to_string(),
+        Part::Date('y', count) if *count <= 2 => format!("{:02}", year % 100),
+        Part::Date('y', _) => format!("{year:04}"),
+        Part::Date('m', count) if is_minute(index) => if *count == 1 { minute.to_string() } else { format!("{minute:02}") },
+        Part::Date('m', 1) => month.to_string(),
+        Part::Date('m', 2) => format!("{month:02}"),
+        Part::Date('m', 3) => MONTHS[month as usize - 1][..3].to_string(),
+        Part::Date('m', 5) => MONTHS[month as usize - 1][..1].to_string(),
+        Part::Date('m', _) => MONTHS[month as usize - 1].to_string(),
+        Part::Date('d', 1) => day.to_string(),
+        Part::Date('d', 2) => format!("{day:02}"),
+        Part::Date('d', 3) => DAYS[weekday][..3].to_string(),
+        Part::Date('d', _) => DAYS[weekday].to_string(),
+        Part::Date('h', count) => {
+            let hour = if twelve_hours { (hour + 11) % 12 + 1 } else { hour };
+            if *count == 1 { hour.to_string() } else { format!("{hour:02}") }
+        }
+        Part::Date(_, count) => if *count == 1 { second.to_string() } else { format!("{second:02}") },
+        Part::AmPm(true) => if hour < 12 { "AM" } else { "PM" }.to_string(),
+        Part::AmPm(false) => if hour < 12 { "A" } else { "P" }.to_string(),
+        Part::Digits(digits) => digits.to_string(),
+        Part::General => number_text(serial),
+        Part::Text => String::new(),
+    }).collect()
+}
+
+#[test]
+fn test_format_number() {
+    assert_eq!(format_number(1234.567, "#,##0.00", false), "1,234.57");
+    assert_eq!(format_number(-1234.5, "0.0;(0.0)", false), "(1234.5)");
+    assert_eq!(format_number(0.256, "0.0%", false), "25.6%");
+    assert_eq!(format_number(5.0, "#.##", false), "5.");
+    assert_eq!(format_number(12345.0, "0.00E+00", false), "1.23E+04");
+    assert_eq!(format_number(7.0, "\"Total: \"000", false), "Total: 007");
+    assert_eq!(format_number(1500000.0, "#,##0.0,,\"M\"", false), "1.5M");
+    assert_eq!(format_number(45351.75, "yyyy-mm-dd hh:mm", false), "2024-02-29 18:00");
+    assert_eq!(format_number(45351.75, "dddd, mmmm d, yyyy h:mm AM/PM", false), "Thursday, February 29, 2024 6:00 PM");
+    assert_eq!(format_number(2.5, "General", false), "2.5");
+    assert_eq!(format_text("abc", "0;-0;0;\"[\"@\"]\""), "[abc]");
+}
+
+#[test]
+fn test_round() {
+    assert_eq!(round(2.675, 2), 2.68);
+    assert_eq!(round(-2.5, 0), -3.0);
+}
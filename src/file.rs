@@ -1,7 +1,5 @@
-use std::fs::File;
 use std::{fs, io};
-use std::io::{Read, Write};
-use std::path::{Path, PathBuf};
+use std::path::Path;
 
 pub enum XlsxFileType {
     WorkbookFile,
@@ -15,76 +13,64 @@ pub enum XlsxFileType {
     Drawings(u32),
     DrawingRels(u32),
     VmlDrawings(u32),
+    VmlDrawingRels(u32),
     Comments(u32),
+    Tables(u32),
+    Charts(u32),
     MetaData,
+    CalcChain,
     CoreProperties,
     AppProperties,
 }
 
-pub struct XlsxFileReader {
-    file_type: XlsxFileType,
-    pub(crate) file_path: PathBuf,
-    file: File,
+/// Parts rewritten while saving a workbook, keyed by their name inside the xlsx package.
+/// Parts of the source package that are not rewritten are copied unchanged, unless they are removed.
+#[derive(Debug, Default)]
+pub(crate) struct XlsxFileWriter {
+    parts: Vec<(String, Vec<u8>)>,
+    removed: Vec<String>,
 }
 
-pub struct XlsxFileWriter {
-    file_type: XlsxFileType,
-    file_path: PathBuf,
-    file: File,
-}
+impl XlsxFileWriter {
+    pub(crate) fn write_all(&mut self, file_type: XlsxFileType, buf: &[u8]) {
+        self.write_part(&file_type.get_part_name(), buf);
+    }
 
-impl XlsxFileReader {
-    pub(crate) fn from_path<P: AsRef<Path>>(base_path: P, file_type: XlsxFileType) -> io::Result<XlsxFileReader> {
-        // let file_path = get_path(base_path, &file_type);
-        let file_path = file_type.get_path(base_path);
-        Ok(XlsxFileReader {
-            file: File::open(&file_path)?,
-            file_type,
-            file_path,
-        })
+    ///
+    /// Write a part by its name inside the package, like `xl/printerSettings/printerSettings1.bin`
+    ///
+    pub(crate) fn write_part(&mut self, part_name: &str, buf: &[u8]) {
+        self.removed.retain(|name| name != part_name);
+        match self.parts.iter_mut().find(|(name, _)| name == part_name) {
+            Some((_, content)) => *content = buf.to_vec(),
+            None => self.parts.push((part_name.to_string(), buf.to_vec())),
+        }
     }
 
-    pub(crate) fn read_to_string(&mut self, target_string: &mut String) -> io::Result<usize> {
-        self.file.read_to_string(target_string)
+    pub(crate) fn copy_from<P: AsRef<Path>>(&mut self, file_type: XlsxFileType, from: P) -> io::Result<()> {
+        let buf = fs::read(from)?;
+        self.write_all(file_type, &buf);
+        Ok(())
     }
-}
 
-impl XlsxFileWriter {
-    pub(crate) fn from_path<P: AsRef<Path>>(base_path: P, file_type: XlsxFileType) -> io::Result<XlsxFileWriter> {
-        let file_path = file_type.get_path(&base_path);
-        Ok(XlsxFileWriter {
-            file: {
-                Self::mkdir(&base_path, &file_type)?;
-                File::create(&file_path)?
-            },
-            file_type,
-            file_path,
-        })
+    ///
+    /// Leave a part of the source package out of the saved package
+    ///
+    pub(crate) fn remove(&mut self, file_type: XlsxFileType) {
+        self.remove_part(&file_type.get_part_name());
     }
 
-    fn mkdir<P: AsRef<Path>>(base_path: P, file_type: &XlsxFileType) -> io::Result<()> {
-        let file_path = file_type.get_path(base_path);
-        let mut dirs = file_path.clone();
-        dirs.pop();
-        fs::create_dir_all(&dirs).unwrap_or_else(|_| {});
-        Ok(())
+    pub(crate) fn remove_part(&mut self, part_name: &str) {
+        self.parts.retain(|(name, _)| name != part_name);
+        self.removed.push(part_name.to_string());
     }
 
-    pub(crate) fn write_all(&mut self, buf: &[u8]) -> io::Result<()> {
-        self.file.write_all(buf)
+    pub(crate) fn parts(&self) -> &[(String, Vec<u8>)] {
+        &self.parts
     }
 
-    pub(crate) fn copy_from<P, Q>(base_path: P, file_type: XlsxFileType, from: Q) -> io::Result<()>
-        where P: AsRef<Path>,
-              Q: AsRef<Path>
-    {
-        let file_path = file_type.get_path(&base_path);
-        Self::mkdir(&base_path, &file_type)?;
-        if from.as_ref().to_path_buf() == file_path.to_path_buf() {
-            return Ok(())
-        }
-        fs::copy(from, file_path)?;
-        Ok(())
+    pub(crate) fn is_removed(&self, part_name: &str) -> bool {
+        self.removed.iter().any(|name| name == part_name)
     }
 }
 
@@ -93,6 +79,7 @@ impl XlsxFileType {
         match self {
             XlsxFileType::WorkbookFile | XlsxFileType::SharedStringFile
             | XlsxFileType::StylesFile | XlsxFileType::MetaData
+            | XlsxFileType::CalcChain
             | XlsxFileType::Comments(_) => "./xl",
 
             XlsxFileType::SheetFile(_) => "./xl",
@@ -101,7 +88,9 @@ impl XlsxFileType {
             XlsxFileType::ContentTypes => ".",
             XlsxFileType::Medias(_) => "./xl/media",
             XlsxFileType::Drawings(_) | XlsxFileType::VmlDrawings(_) => "./xl/drawings",
-            XlsxFileType::DrawingRels(_) => "./xl/drawings/_rels",
+            XlsxFileType::DrawingRels(_) | XlsxFileType::VmlDrawingRels(_) => "./xl/drawings/_rels",
+            XlsxFileType::Tables(_) => "./xl/tables",
+            XlsxFileType::Charts(_) => "./xl/charts",
             XlsxFileType::CoreProperties | XlsxFileType::AppProperties => "./docProps",
         }
     }
@@ -119,17 +108,25 @@ impl XlsxFileType {
             XlsxFileType::Drawings(id) => format!("drawing{id}.xml"),
             XlsxFileType::VmlDrawings(id) => format!("vmlDrawing{id}.vml"),
             XlsxFileType::DrawingRels(id) => format!("drawing{id}.xml.rels"),
+            XlsxFileType::VmlDrawingRels(id) => format!("vmlDrawing{id}.vml.rels"),
             XlsxFileType::MetaData => "metadata.xml".to_string(),
+            XlsxFileType::CalcChain => "calcChain.xml".to_string(),
             XlsxFileType::CoreProperties => "core.xml".to_string(),
             XlsxFileType::AppProperties => "app.xml".to_string(),
             XlsxFileType::Comments(id) => format!("comments{id}.xml"),
+            XlsxFileType::Tables(id) => format!("table{id}.xml"),
+            XlsxFileType::Charts(id) => format!("chart{id}.xml"),
         }
     }
-    pub(crate) fn get_path<P: AsRef<Path>>(&self, base_path: P) -> PathBuf {
-        base_path.as_ref().join(self.get_dir()).join(self.get_filename())
-    }
-
-    pub(crate) fn get_relative_path(&self) -> String {
-        format!("{}/{}", self.get_dir(), self.get_filename())
+    ///
+    /// Get the name of the part inside the zip package, such as xl/workbook.xml
+    ///
+    pub(crate) fn get_part_name(&self) -> String {
+        let dir = self.get_dir().trim_start_matches('.').trim_start_matches('/');
+        if dir.is_empty() {
+            self.get_filename()
+        } else {
+            format!("{dir}/{}", self.get_filename())
+        }
     }
 }
\ No newline at end of file
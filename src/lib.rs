@@ -12,14 +12,29 @@ pub use api::format::Format;
 pub use api::format::FormatBorderType;
 pub use api::format::FormatAlignType;
 pub use api::format::FormatColor;
+pub use api::format::FormatProtection;
 pub use api::worksheet::write::Write;
 pub use api::worksheet::read::Read;
 pub use api::cell::Cell;
+pub use api::cell::values::{Value, ErrorKind};
+pub use api::cell::formula::token::{CellReference, FormulaOperator, FormulaToken, LineReference, NameReference, ParsedFormula, Reference, ReferenceArea, StructuredReference};
+pub use api::cell::formula::expression::{BinaryOperator, Expression, UnaryOperator};
+pub use api::cell::date_time::ExcelDateTime;
+pub use api::cell::rich_text::{RichText, RichTextRun};
 pub use api::worksheet::row::Row;
 pub use api::worksheet::row::WorkSheetRow;
 pub use api::worksheet::col::Column;
 pub use api::worksheet::col::WorkSheetCol;
 pub use api::properties::Properties;
+pub use api::comment::Comment;
+pub use api::table::{Table, TableColumn, TableFunction, TableOptions};
+pub use api::data_validation::{DataValidation, DataValidationErrorStyle, DataValidationOperator, DataValidationType};
+pub use api::conditional_format::{ColorScale, ConditionalFormat, ConditionalFormatOperator, ConditionalFormatRule, ConditionalFormatTimePeriod, ConditionalFormatValue, DataBar, DataBarAxisPosition, IconSet, IconSetType};
+pub use api::chart::{Chart, ChartAxis, ChartGrouping, ChartLegendPosition, ChartSeries, ChartType};
+pub use api::protection::ProtectionOptions;
+pub use api::page_setup::PaperSize;
+pub use api::header_footer::HeaderFooter;
+pub use api::dependency::{CellArea, DependencyGraph, SheetCell};
 pub use api::filter::Filter;
 pub use api::filter::Filters;
 pub use result::WorkbookResult;
@@ -13,6 +13,8 @@ pub type RowResult<T> = Result<T, RowError>;
 pub enum RowError {
     RowNotFound,
     CellError(CellError),
+    /// The inserted rows would push cells with values, merged cells or tables past the last row of the worksheet
+    OutOfSheet,
 }
 
 impl From<CellError> for RowError { fn from(err: CellError) -> RowError { RowError::CellError(err) } }
@@ -21,6 +23,8 @@ pub type ColResult<T> = Result<T, ColError>;
 #[derive(Debug)]
 pub enum ColError {
     ColNotFound,
+    /// The inserted columns would push cells with values, merged cells or tables past the last column of the worksheet
+    OutOfSheet,
 }
 
 pub type WorkSheetResult<T> = Result<T, WorkSheetError>;
@@ -33,7 +37,14 @@ pub enum WorkSheetError {
     RowError(RowError),
     ColError(ColError),
     DuplicatedSheets,
+    /// The sheet name is empty, longer than 31 characters, has one of `[]:*?/\` or starts or ends with `'`,
+    /// or is `History`, which Excel keeps for itself
+    InvalidSheetName,
     FormatError,
+    TableError(TableError),
+    DataValidationError(DataValidationError),
+    ChartError(ChartError),
+    HeaderFooterError(HeaderFooterError),
 }
 
 impl From<DeError> for WorkSheetError { fn from(err: DeError) -> WorkSheetError { WorkSheetError::DeError(err) } }
@@ -42,15 +53,23 @@ impl From<io::Error> for WorkSheetError { fn from(err: io::Error) -> WorkSheetEr
     } }
 impl From<RowError> for WorkSheetError { fn from(err: RowError) -> WorkSheetError { WorkSheetError::RowError(err) } }
 impl From<ColError> for WorkSheetError { fn from(err: ColError) -> WorkSheetError { WorkSheetError::ColError(err) } }
+impl From<TableError> for WorkSheetError { fn from(err: TableError) -> WorkSheetError { WorkSheetError::TableError(err) } }
+impl From<DataValidationError> for WorkSheetError { fn from(err: DataValidationError) -> WorkSheetError { WorkSheetError::DataValidationError(err) } }
+impl From<ChartError> for WorkSheetError { fn from(err: ChartError) -> WorkSheetError { WorkSheetError::ChartError(err) } }
+impl From<HeaderFooterError> for WorkSheetError { fn from(err: HeaderFooterError) -> WorkSheetError { WorkSheetError::HeaderFooterError(err) } }
 
 pub type WorkbookResult<T> = Result<T, WorkbookError>;
 #[derive(Debug)]
 pub enum WorkbookError {
     Io(io::Error),
+    /// A part of the package, like the shared strings, can't be read
+    DeError(DeError),
     ZipError(ZipError),
     SheetError(WorkSheetError),
     FileNotFound,
     RelationshipError(RelationshipError),
+    /// The only worksheet of a workbook can't be removed
+    LastWorksheet,
 }
 
 impl From<io::Error> for WorkbookError {
@@ -59,6 +78,12 @@ impl From<io::Error> for WorkbookError {
     }
 }
 
+impl From<DeError> for WorkbookError {
+    fn from(err: DeError) -> WorkbookError {
+        WorkbookError::DeError(err)
+    }
+}
+
 impl From<ZipError> for WorkbookError {
     fn from(err: ZipError) -> WorkbookError {
         WorkbookError::ZipError(err)
@@ -71,6 +96,56 @@ impl From<WorkSheetError> for WorkbookError {
     }
 }
 
+#[derive(Debug)]
+pub enum TableError {
+    /// The range is too small for the header, the data and the totals rows, or for the columns
+    InvalidRange,
+    /// The range overlaps another table
+    Overlap,
+    /// A table or a defined name of the workbook already has the name, regardless of the case
+    DuplicatedName,
+    /// The name has a space or another character than letters, digits, underscores, dots and backslashes,
+    /// doesn't start with a letter, an underscore or a backslash, is longer than 255 characters
+    /// or could be read as a cell like `A1` or `R1C1`
+    InvalidName,
+    /// The inserted or deleted cells would delete the first row or change the columns of a table
+    InvalidShift,
+}
+
+#[derive(Debug)]
+pub enum DataValidationError {
+    /// The validation compares the values but has no formula
+    MissingFormula,
+    /// The values of a list are longer than 255 characters
+    ListTooLong,
+}
+
+#[derive(Debug)]
+pub enum ChartError {
+    /// The chart has no series to draw
+    NoSeries,
+    /// The style is not one of the 48 styles of Excel
+    InvalidStyle,
+}
+
+#[derive(Debug)]
+pub enum HeaderFooterError {
+    /// The header or the footer is longer than 255 characters
+    TooLong,
+    /// A section shows more than one image
+    TooManyImages,
+    /// The size of an image can't be read, it is not a PNG, JPEG, GIF or BMP image
+    UnknownImage,
+}
+
+#[derive(Debug)]
+pub enum FormulaError {
+    /// A token which can't be at its place, like the second operator of `1+*2`
+    UnexpectedToken(String),
+    /// The formula ends before its expression, like `SUM(1,`
+    UnexpectedEnd,
+}
+
 pub type RelationshipResult<T> = Result<T, RelationshipError>;
 
 #[derive(Debug)]
@@ -1,3 +1,6 @@
 pub(crate) mod col_helper;
 pub(crate) mod zip_util;
-pub(crate) mod id_util;
\ No newline at end of file
+pub(crate) mod reference_helper;
+
+pub(crate) mod password_util;
+pub(crate) mod image_util;
@@ -0,0 +1,42 @@
+///
+/// The width and the height in pixels of a PNG, JPEG, GIF or BMP image
+///
+pub(crate) fn image_size(data: &[u8]) -> Option<(u32, u32)> {
+    let u16_be = |at: usize| Some(u16::from_be_bytes(data.get(at..at + 2)?.try_into().ok()?) as u32);
+    let u16_le = |at: usize| Some(u16::from_le_bytes(data.get(at..at + 2)?.try_into().ok()?) as u32);
+    let u32_be = |at: usize| Some(u32::from_be_bytes(data.get(at..at + 4)?.try_into().ok()?));
+    let i32_le = |at: usize| Some(i32::from_le_bytes(data.get(at..at + 4)?.try_into().ok()?).unsigned_abs());
+    if data.starts_with(b"\x89PNG\r\n\x1a\n") {
+        Some((u32_be(16)?, u32_be(20)?))
+    } else if data.starts_with(b"GIF8") {
+        Some((u16_le(6)?, u16_le(8)?))
+    } else if data.starts_with(b"BM") {
+        Some((i32_le(18)?, i32_le(22)?))
+    } else if data.starts_with(&[0xff, 0xd8]) {
+        // the size is in the first start of frame segment
+        let mut at = 2;
+        while *data.get(at)? == 0xff {
+            let marker = *data.get(at + 1)?;
+            if matches!(marker, 0xc0..=0xcf) && !matches!(marker, 0xc4 | 0xc8 | 0xcc) {
+                return Some((u16_be(at + 7)?, u16_be(at + 5)?));
+            }
+            at += 2 + u16_be(at + 2)? as usize;
+        }
+        None
+    } else {
+        None
+    }
+}
+
+#[test]
+fn test_image_size() {
+    let png = std::fs::read("examples/pics/ferris.png").unwrap();
+    assert_eq!(image_size(&png), Some((379, 133)));
+    let bmp = std::fs::read("examples/pics/capybara.bmp").unwrap();
+    assert_eq!(image_size(&bmp), Some((64, 64)));
+    let gif = b"GIF89a\x0a\x00\x14\x00";
+    assert_eq!(image_size(gif), Some((10, 20)));
+    let jpeg = b"\xff\xd8\xff\xe0\x00\x04\x00\x00\xff\xc0\x00\x11\x08\x00\x30\x00\x40";
+    assert_eq!(image_size(jpeg), Some((64, 48)));
+    assert_eq!(image_size(b"text"), None);
+}
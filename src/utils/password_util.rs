@@ -0,0 +1,77 @@
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD;
+use sha2::{Digest, Sha512};
+
+/// The number of times Excel hashes a password by default
+pub(crate) const SPIN_COUNT: u32 = 100_000;
+
+///
+/// The legacy 16 bits hash of a password, as 4 hexadecimal digits
+///
+pub(crate) fn legacy_hash(password: &str) -> String {
+    let mut hash = 0u16;
+    for byte in password.bytes().rev() {
+        hash = ((hash >> 14) & 0x01) | ((hash << 1) & 0x7fff);
+        hash ^= byte as u16;
+    }
+    hash = ((hash >> 14) & 0x01) | ((hash << 1) & 0x7fff);
+    hash ^= password.len() as u16;
+    hash ^= 0xce4b;
+    format!("{hash:04X}")
+}
+
+///
+/// The SHA-512 hash of a password in UTF-16, hashed again `spin_count` times, with its salt, both in base64
+///
+pub(crate) fn sha512_hash(password: &str, salt: &[u8], spin_count: u32) -> (String, String) {
+    let password = password.encode_utf16().flat_map(u16::to_le_bytes).collect::<Vec<u8>>();
+    let mut hash = Sha512::new().chain_update(salt).chain_update(password).finalize();
+    for i in 0..spin_count {
+        hash = Sha512::new().chain_update(hash).chain_update(i.to_le_bytes()).finalize();
+    }
+    (STANDARD.encode(hash), STANDARD.encode(salt))
+}
+
+///
+/// A password hashed the way Excel does, with SHA-512 and a random salt
+///
+pub(crate) struct PasswordHash {
+    pub(crate) algorithm_name: String,
+    pub(crate) hash_value: String,
+    pub(crate) salt_value: String,
+    pub(crate) spin_count: u32,
+}
+
+impl PasswordHash {
+    pub(crate) fn new(password: &str) -> PasswordHash {
+        let (hash_value, salt_value) = sha512_hash(password, &new_salt(), SPIN_COUNT);
+        PasswordHash {
+            algorithm_name: "SHA-512".to_string(),
+            hash_value,
+            salt_value,
+            spin_count: SPIN_COUNT,
+        }
+    }
+}
+
+///
+/// A random salt of 16 bytes from the randomness of the operating system
+///
+pub(crate) fn new_salt() -> [u8; 16] {
+    let mut salt = [0; 16];
+    getrandom::getrandom(&mut salt).expect("the operating system has no source of randomness");
+    salt
+}
+
+#[test]
+fn test_legacy_hash() {
+    assert_eq!(legacy_hash("password"), "83AF");
+    assert_eq!(legacy_hash(""), "CE4B");
+}
+
+#[test]
+fn test_sha512_hash() {
+    let (hash, salt) = sha512_hash("p", b"0123456789abcdef", 10);
+    assert_eq!(salt, "MDEyMzQ1Njc4OWFiY2RlZg==");
+    assert_eq!(hash, "OuOOXkMEor3sFEIyCTvO77ArKKPJ2+9gHni7POgxFYMjql3vxeMLlZbPZxS5ZX/7TkuFL6rdf4WYRXL6GAEk7A==");
+}
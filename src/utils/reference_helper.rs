@@ -0,0 +1,305 @@
+use crate::api::cell::formula::token::{is_r1c1_reference, CellReference, LineReference, ParsedFormula, ReferenceArea};
+
+pub(crate) const MAX_ROW: u32 = 1_048_576;
+pub(crate) const MAX_COL: u32 = 16_384;
+
+///
+/// Rows or columns inserted or deleted in a worksheet, moving the cells after them.
+/// The first value is the first row or column inserted or deleted, the second one their count.
+///
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum Shift {
+    InsertRows(u32, u32),
+    DeleteRows(u32, u32),
+    InsertColumns(u32, u32),
+    DeleteColumns(u32, u32),
+}
+
+impl Shift {
+    pub(crate) fn is_row(self) -> bool {
+        matches!(self, Shift::InsertRows(..) | Shift::DeleteRows(..))
+    }
+
+    pub(crate) fn is_insert(self) -> bool {
+        matches!(self, Shift::InsertRows(..) | Shift::InsertColumns(..))
+    }
+
+    ///
+    /// The rows or columns are in the worksheet, and there is at least one of them
+    ///
+    pub(crate) fn is_valid(self) -> bool {
+        let (at, count) = self.at_count();
+        if at == 0 || count == 0 {
+            return false;
+        }
+        // the last row or column inserted or deleted
+        at.checked_add(count - 1).is_some_and(|last| last <= self.max_index())
+    }
+
+    ///
+    /// The first row or column inserted or deleted, and their count
+    ///
+    pub(crate) fn at_count(self) -> (u32, u32) {
+        match self {
+            Shift::InsertRows(at, count) | Shift::DeleteRows(at, count)
+            | Shift::InsertColumns(at, count) | Shift::DeleteColumns(at, count) => (at, count),
+        }
+    }
+
+    ///
+    /// The last row or column of a worksheet on the axis of the shift
+    ///
+    fn max_index(self) -> u32 {
+        if self.is_row() { MAX_ROW } else { MAX_COL }
+    }
+
+    ///
+    /// The new index of a row or a column on the axis of the shift,
+    /// None if it is deleted or if it is pushed past the last row or column of the worksheet
+    ///
+    pub(crate) fn shift_index(self, index: u32) -> Option<u32> {
+        let (at, count) = self.at_count();
+        if index < at {
+            Some(index)
+        } else if self.is_insert() {
+            index.checked_add(count).filter(|&index| index <= self.max_index())
+        } else if index - at >= count {
+            Some(index - count)
+        } else {
+            None
+        }
+    }
+
+    ///
+    /// The new index of a row or a column, a deleted one is moved to the first row or column after the deletion,
+    /// and one pushed past the last row or column stays on it
+    ///
+    pub(crate) fn move_index(self, index: u32) -> u32 {
+        match self.is_insert() {
+            true => self.shift_index(index).unwrap_or(self.max_index()),
+            false => self.shift_index(index).unwrap_or(self.at_count().0),
+        }
+    }
+
+    ///
+    /// The new first and last rows or columns of a span on the axis of the shift, None if they are all deleted
+    /// or pushed past the last row or column of the worksheet.
+    /// A span is extended by the rows or columns inserted inside it, and shrunk by the deleted ones.
+    ///
+    pub(crate) fn shift_span(self, first: u32, last: u32) -> Option<(u32, u32)> {
+        let at = self.at_count().0;
+        let first = match self.shift_index(first) {
+            Some(first) => first,
+            None if self.is_insert() => return None,
+            None => at,
+        };
+        // a span pushed past the last row or column is cut there, like the whole rows `1:1048576`
+        let last = match self.shift_index(last) {
+            Some(last) => last,
+            None if self.is_insert() => self.max_index(),
+            None => at.checked_sub(1)?,
+        };
+        if first > last { None } else { Some((first, last)) }
+    }
+
+    pub(crate) fn shift_location(self, (row, col): (u32, u32)) -> Option<(u32, u32)> {
+        match self.is_row() {
+            true => Some((self.shift_index(row)?, col)),
+            false => Some((row, self.shift_index(col)?)),
+        }
+    }
+
+    pub(crate) fn shift_range(self, (first_row, first_col, last_row, last_col): (u32, u32, u32, u32)) -> Option<(u32, u32, u32, u32)> {
+        match self.is_row() {
+            true => {
+                let (first_row, last_row) = self.shift_span(first_row, last_row)?;
+                Some((first_row, first_col, last_row, last_col))
+            }
+            false => {
+                let (first_col, last_col) = self.shift_span(first_col, last_col)?;
+                Some((first_row, first_col, last_row, last_col))
+            }
+        }
+    }
+
+    ///
+    /// The new area of a reference, None if it is deleted.
+    /// Whole rows are only moved by rows, and whole columns by columns.
+    ///
+    pub(crate) fn shift_area(self, area: ReferenceArea) -> Option<ReferenceArea> {
+        let cell = |cell: CellReference| {
+            let (row, col) = self.shift_location((cell.row, cell.col))?;
+            Some(CellReference { row, col, ..cell })
+        };
+        let span = |first: LineReference, last: LineReference| {
+            let (first_index, last_index) = self.shift_span(first.index, last.index)?;
+            Some((LineReference { index: first_index, ..first }, LineReference { index: last_index, ..last }))
+        };
+        match area {
+            ReferenceArea::Cell(first) => cell(first).map(ReferenceArea::Cell),
+            ReferenceArea::Range(first, last) => {
+                let (first_row, first_col, last_row, last_col) = self.shift_range((first.row, first.col, last.row, last.col))?;
+                Some(ReferenceArea::Range(
+                    CellReference { row: first_row, col: first_col, ..first },
+                    CellReference { row: last_row, col: last_col, ..last },
+                ))
+            }
+            ReferenceArea::Rows(first, last) if self.is_row() => span(first, last).map(|(first, last)| ReferenceArea::Rows(first, last)),
+            ReferenceArea::Columns(first, last) if !self.is_row() => span(first, last).map(|(first, last)| ReferenceArea::Columns(first, last)),
+            area => Some(area),
+        }
+    }
+
+    ///
+    /// A cell like A1 or a range like A1:B2, None if it is deleted
+    ///
+    pub(crate) fn shift_ref(self, reference: &str) -> Option<String> {
+        match ReferenceArea::parse(reference) {
+            Some(area) => self.shift_area(area).map(|area| area.to_string()),
+            None => Some(reference.to_string()),
+        }
+    }
+
+    ///
+    /// A list of cells and ranges separated by spaces, None if they are all deleted
+    ///
+    pub(crate) fn shift_sqref(self, sqref: &str) -> Option<String> {
+        let sqref = sqref.split_whitespace()
+            .filter_map(|reference| self.shift_ref(reference))
+            .collect::<Vec<String>>();
+        if sqref.is_empty() { None } else { Some(sqref.join(" ")) }
+    }
+
+    ///
+    /// Shift the references of a formula to the cells of a worksheet.
+    /// The references without a sheet name refer to the worksheet if the formula is in it.
+    /// The deleted references are replaced by `#REF!`.
+    ///
+    pub(crate) fn shift_formula(self, formula: &str, sheet_name: &str, in_sheet: bool) -> String {
+        let mut parsed = ParsedFormula::parse(formula);
+        parsed.shift(self, sheet_name, in_sheet);
+        parsed.to_string()
+    }
+}
+
+///
+/// Make the references of a formula to a worksheet, and to the names local to it, refer to the worksheet renamed
+///
+pub(crate) fn rename_sheet_in_formula(formula: &str, old_name: &str, new_name: &str) -> String {
+    // the formulas not mentioning the worksheet are kept as they are written
+    if !mentions_sheet(formula, old_name) {
+        return formula.to_string();
+    }
+    let mut parsed = ParsedFormula::parse(formula);
+    parsed.rename_sheet(old_name, new_name);
+    parsed.to_string()
+}
+
+///
+/// Make the references of a formula to a removed worksheet, and to the names local to it, `#REF!`
+///
+pub(crate) fn remove_sheet_in_formula(formula: &str, sheet_name: &str) -> String {
+    if !mentions_sheet(formula, sheet_name) {
+        return formula.to_string();
+    }
+    let mut parsed = ParsedFormula::parse(formula);
+    parsed.remove_sheet(sheet_name);
+    parsed.to_string()
+}
+
+///
+/// A name of a table or a defined name starts with a letter, an underscore or a backslash,
+/// has only letters, digits, underscores, dots and backslashes, is at most 255 characters long,
+/// and can't be read as a cell, in the A1 or the R1C1 style
+///
+pub(crate) fn is_valid_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    let Some(first) = chars.next() else {
+        return false;
+    };
+    (first.is_alphabetic() || first == '_' || first == '\\')
+        && chars.all(|c| c.is_alphanumeric() || matches!(c, '_' | '.' | '\\'))
+        && name.chars().count() <= 255
+        && !matches!(ReferenceArea::parse(&name.to_ascii_uppercase()), Some(ReferenceArea::Cell(_)))
+        && !is_r1c1_reference(name)
+}
+
+///
+/// Two names are the names of the same worksheet, which Excel compares regardless of the case
+///
+pub(crate) fn is_same_sheet(name: &str, other: &str) -> bool {
+    name.to_lowercase() == other.to_lowercase()
+}
+
+///
+/// The name of a worksheet is in a formula, quoted or not, regardless of the case
+///
+pub(crate) fn mentions_sheet(formula: &str, sheet_name: &str) -> bool {
+    let (lowercase, sheet_lowercase) = (formula.to_lowercase(), sheet_name.to_lowercase());
+    lowercase.contains(&sheet_lowercase) || lowercase.contains(&sheet_lowercase.replace('\'', "''"))
+}
+
+///
+/// Make the structured references of a formula to tables refer to the tables renamed, given by their old and new names
+///
+pub(crate) fn rename_tables_in_formula(formula: &str, names: &[(String, String)]) -> String {
+    let lowercase = formula.to_lowercase();
+    if !names.iter().any(|(old_name, _)| lowercase.contains(&old_name.to_lowercase())) {
+        return formula.to_string();
+    }
+    let mut parsed = ParsedFormula::parse(formula);
+    parsed.rename_tables(names);
+    parsed.to_string()
+}
+
+#[test]
+fn test_shift_ref() {
+    assert_eq!(Shift::InsertRows(3, 2).shift_ref("B2:C5"), Some("B2:C7".to_string()));
+    assert_eq!(Shift::InsertRows(2, 2).shift_ref("B2"), Some("B4".to_string()));
+    assert_eq!(Shift::DeleteRows(2, 2).shift_ref("B2:C5"), Some("B2:C3".to_string()));
+    assert_eq!(Shift::DeleteRows(2, 2).shift_ref("B3"), None);
+    assert_eq!(Shift::DeleteColumns(1, 1).shift_ref("A1:A9"), None);
+    assert_eq!(Shift::InsertColumns(1, 1).shift_sqref("A1 C3:D4"), Some("B1 D3:E4".to_string()));
+    assert_eq!(Shift::InsertRows(1, 1).shift_ref("A1:A1048576"), Some("A2:A1048576".to_string()));
+    assert_eq!(Shift::InsertRows(1, 1).shift_ref("A1048576"), None);
+    assert_eq!(Shift::InsertRows(2, u32::MAX).shift_ref("A1:A3"), Some("A1:A1048576".to_string()));
+    assert!(!Shift::InsertRows(1, u32::MAX).is_valid());
+    assert!(!Shift::DeleteColumns(16384, 2).is_valid());
+}
+
+#[test]
+fn test_shift_formula() {
+    let shift = Shift::InsertRows(3, 2);
+    assert_eq!(shift.shift_formula("SUM(A1:A5)*$B$3+LOG10(C2)", "Sheet1", true), "SUM(A1:A7)*$B$5+LOG10(C2)");
+    assert_eq!(shift.shift_formula("Sheet1!A4&\"A4\"&Sheet2!A4", "Sheet1", false), "Sheet1!A6&\"A4\"&Sheet2!A4");
+    assert_eq!(shift.shift_formula("'My sheet'!A4+'It''s'!A4", "It's", false), "'My sheet'!A4+'It''s'!A6");
+    assert_eq!(shift.shift_formula("SUM(3:4)+SUM(B:B)+[1]Sheet1!A4+Table1[Amount]", "Sheet1", true), "SUM(5:6)+SUM(B:B)+[1]Sheet1!A4+Table1[Amount]");
+    assert_eq!(Shift::DeleteRows(4, 1).shift_formula("A4+A5+#N/A", "Sheet1", true), "#REF!+A4+#N/A");
+    assert_eq!(Shift::DeleteColumns(2, 1).shift_formula("Sheet1:Sheet3!C1+C1", "Sheet1", true), "Sheet1:Sheet3!C1+B1");
+}
+
+#[test]
+fn test_rename_sheet_in_formula() {
+    assert_eq!(rename_sheet_in_formula("SUM(Sheet1!A1:A3)+sheet1!B1+Sheet10!A1", "Sheet1", "Sales"), "SUM(Sales!A1:A3)+Sales!B1+Sheet10!A1");
+    assert_eq!(rename_sheet_in_formula("'It''s'!A1*2", "It's", "Q1 Report"), "'Q1 Report'!A1*2");
+    assert_eq!(rename_sheet_in_formula("'Q1 Report'!$A$1&\"Q1 Report\"", "Q1 Report", "Sales"), "Sales!$A$1&\"Q1 Report\"");
+    assert_eq!(rename_sheet_in_formula("Sheet1!A1", "Sheet1", "R1C1"), "'R1C1'!A1");
+    assert_eq!(rename_sheet_in_formula("SUM(A1:A3)", "Sheet1", "Sales"), "SUM(A1:A3)");
+}
+
+#[test]
+fn test_is_valid_name() {
+    for name in ["Sales", "_2024", "\\Data.Q1", "ABCD1", "RC1C"] {
+        assert!(is_valid_name(name), "{name}");
+    }
+    for name in ["", "my table", "1st", "A1", "xfd1048576", "R1C1", "rc", "C", "A-B", &"A".repeat(256)] {
+        assert!(!is_valid_name(name), "{name}");
+    }
+}
+
+#[test]
+fn test_rename_tables_in_formula() {
+    let names = [("Table1".to_string(), "Table2".to_string()), ("Table2".to_string(), "Table3".to_string())];
+    assert_eq!(rename_tables_in_formula("SUM(Table1[Amount])+table2[[#This Row],[Tax]]", &names), "SUM(Table2[Amount])+Table3[[#This Row],[Tax]]");
+    assert_eq!(rename_tables_in_formula("Table10[Amount]+[@Amount]&\"Table1\"", &names), "Table10[Amount]+[@Amount]&\"Table1\"");
+}
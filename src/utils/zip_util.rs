@@ -1,103 +1,101 @@
-use std::{fs, io};
-use std::fs::File;
-use std::io::{Read, Write};
-use std::path::Path;
-use walkdir::WalkDir;
-use zip::CompressionMethod;
-use zip::result::ZipError;
+use std::collections::HashSet;
+use std::io::{Read, Seek, Write};
+use zip::{CompressionMethod, ZipArchive, ZipWriter};
+use zip::result::ZipResult;
 use zip::write::FileOptions;
+use crate::file::XlsxFileWriter;
 
-pub(crate) fn extract_dir<P: AsRef<Path>>(file_path: P, target: &str) -> zip::result::ZipResult<String> {
-    // read file from file path
-    let file = File::open(&file_path)?;
-    let mut archive = zip::ZipArchive::new(file)?;
-    // construct a base path for extracted files
-    let base_path = Path::new(&target);
-    match fs::create_dir(&base_path) {
-        Err(why) => println!("! {:?}", why.kind()),
-        Ok(_) => {},
-    }
-    for i in 0..archive.len() {
-        let mut file = archive.by_index(i)?;
-        let out_path = match file.enclosed_name() {
-            Some(path) => path.to_owned(),
-            None => continue,
-        };
-        let out_path = &base_path.join(out_path);
-        {
-            let comment = file.comment();
-            if !comment.is_empty() {
-                println!("File {i} comment: {comment}");
-            }
+///
+/// Write a package: the entries of the source archive are copied as they are
+/// unless they are rewritten or removed in `parts`, the new parts follow them.
+///
+pub(crate) fn write_package<R: Read + Seek, W: Write + Seek>(source: &mut ZipArchive<R>, parts: &XlsxFileWriter, writer: W) -> ZipResult<W> {
+    let mut zip = ZipWriter::new(writer);
+    let options = FileOptions::default()
+        .compression_method(CompressionMethod::Deflated)
+        .unix_permissions(0o755);
+    let mut written = HashSet::new();
+    for i in 0..source.len() {
+        let file = source.by_index_raw(i)?;
+        let name = file.name().to_string();
+        if parts.is_removed(&name) {
+            continue;
         }
-        if (*file.name()).ends_with('/') {
-            fs::create_dir_all(&out_path)?;
-        } else {
-            if let Some(p) = out_path.parent() {
-                if !p.exists() {
-                    fs::create_dir_all(p)?;
-                }
+        match parts.parts().iter().find(|(part_name, _)| *part_name == name) {
+            Some((_, content)) => {
+                zip.start_file(&name, options)?;
+                zip.write_all(content)?;
             }
-            let mut outfile = fs::File::create(&out_path)?;
-            io::copy(&mut file, &mut outfile)?;
+            None => zip.raw_copy_file(file)?,
         }
-        // Get and Set permissions
-        #[cfg(unix)]
-        {
-            use std::os::unix::fs::PermissionsExt;
-
-            if let Some(mode) = file.unix_mode() {
-                fs::set_permissions(&out_path, fs::Permissions::from_mode(mode))?;
-            }
+        written.insert(name);
+    }
+    for (name, content) in parts.parts() {
+        if !written.contains(name) {
+            zip.start_file(name, options)?;
+            zip.write_all(content)?;
         }
     }
-    let tmp_dir = base_path.to_str().ok_or(ZipError::FileNotFound)?.to_string();
-    Ok(tmp_dir)
+    zip.finish()
 }
 
-pub(crate) fn zip_dir<P: AsRef<Path>>(prefix: &str, file_path: P) -> zip::result::ZipResult<()> {
-    let writer = File::create(&file_path)?;
-    let walk_dir = WalkDir::new(&prefix);
-    let it = walk_dir.into_iter();
-    let it = &mut it.filter_map(|e| e.ok());
-
-    let mut zip = zip::ZipWriter::new(writer);
-    let options = FileOptions::default()
-        .compression_method(CompressionMethod::Deflated)
-        .unix_permissions(0o755);
+///
+/// Write a package of the parts only
+///
+pub(crate) fn write_parts<W: Write + Seek>(parts: &[(String, Vec<u8>)], writer: W) -> ZipResult<W> {
+    let mut zip = ZipWriter::new(writer);
+    let options = FileOptions::default().compression_method(CompressionMethod::Stored);
+    for (name, content) in parts {
+        zip.start_file(name, options)?;
+        zip.write_all(content)?;
+    }
+    zip.finish()
+}
 
-    let mut buffer = Vec::new();
-    for entry in it {
-        let path = entry.path();
-        let name = path.strip_prefix(Path::new(prefix)).unwrap();
-        // Write file or directory explicitly
-        // Some unzip tools unzip files with directory paths correctly, some do not!
-        if path.is_file() {
-            // println!("adding file {path:?} as {name:?} ...");
-            #[allow(deprecated)]
-            zip.start_file_from_path(name, options)?;
-            let mut f = File::open(path)?;
+///
+/// The part of the relationships of a part, like `xl/worksheets/_rels/sheet1.xml.rels` for `xl/worksheets/sheet1.xml`
+///
+pub(crate) fn rels_part_name(part_name: &str) -> String {
+    match part_name.rsplit_once('/') {
+        Some((dir, name)) => format!("{dir}/_rels/{name}.rels"),
+        None => format!("_rels/{part_name}.rels"),
+    }
+}
 
-            f.read_to_end(&mut buffer)?;
-            zip.write_all(&buffer)?;
-            buffer.clear();
-        } else if !name.as_os_str().is_empty() {
-            // Only if not root! Avoids path spec / warning
-            // and mapname conversion failed error on unzip
-            // println!("adding dir {path:?} as {name:?} ...");
-            #[allow(deprecated)]
-            zip.add_directory_from_path(name, options)?;
+///
+/// The part targeted by a relationship of a part, like `xl/drawings/drawing1.xml`
+/// for the target `../drawings/drawing1.xml` of `xl/worksheets/sheet1.xml`
+///
+pub(crate) fn resolve_target(part_name: &str, target: &str) -> String {
+    let mut names = match target.strip_prefix('/') {
+        Some(_) => vec![],
+        None => part_name.split('/').collect::<Vec<&str>>(),
+    };
+    // the name of the part itself
+    names.pop();
+    for name in target.split('/') {
+        match name {
+            "" | "." => {}
+            ".." => { names.pop(); }
+            name => names.push(name),
         }
     }
-    zip.finish()?;
-    Ok(())
+    names.join("/")
 }
 
 #[test]
-fn test() -> io::Result<()> {
-    let file = File::open("./examples/xlsx/accounting.xlsx")?;
+fn test_resolve_target() {
+    assert_eq!(rels_part_name("xl/worksheets/sheet1.xml"), "xl/worksheets/_rels/sheet1.xml.rels");
+    assert_eq!(resolve_target("xl/worksheets/sheet1.xml", "../drawings/drawing1.xml"), "xl/drawings/drawing1.xml");
+    assert_eq!(resolve_target("xl/workbook.xml", "worksheets/sheet2.xml"), "xl/worksheets/sheet2.xml");
+    assert_eq!(resolve_target("xl/workbook.xml", "/xl/theme/theme1.xml"), "xl/theme/theme1.xml");
+}
+
+#[test]
+fn test() -> std::io::Result<()> {
+    let file = std::fs::File::open("./examples/xlsx/accounting.xlsx")?;
     // 创建 ZipArchive 对象
-    let mut archive = zip::ZipArchive::new(file)?;
+    let mut archive = ZipArchive::new(file)?;
     let file_path = "xl/styles.xml";
 
     for i in 0..archive.len() {
@@ -109,13 +107,5 @@ fn test() -> io::Result<()> {
             println!("File contents: {}", contents);
         }
     }
-    // if let Some(mut file) = find_file(&mut archive, file_path)? {
-    //     // 读取文件内容
-    //     let mut contents = String::new();
-    //     file.read_to_string(&mut contents)?;
-    //     println!("File contents: {}", contents);
-    // } else {
-    //     println!("File not found: {}", file_path);
-    // }
     Ok(())
-}
\ No newline at end of file
+}
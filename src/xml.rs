@@ -8,7 +8,11 @@ pub(crate) mod content_types;
 pub(crate) mod medias;
 pub(crate) mod relationships;
 pub(crate) mod drawings;
+pub(crate) mod chart;
+pub(crate) mod comments;
+pub(crate) mod table;
 pub(crate) mod metadata;
+pub(crate) mod calc_chain;
 pub(crate) mod extension;
 pub(crate) mod core_properties;
 pub(crate) mod app_properties;
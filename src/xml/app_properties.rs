@@ -1,10 +1,6 @@
-use std::fs::File;
-use std::io;
-use std::io::Read;
-use std::path::Path;
-use quick_xml::{de, se};
+use quick_xml::se;
 use serde::{Deserialize, Serialize};
-use crate::file::{XlsxFileReader, XlsxFileType, XlsxFileWriter};
+use crate::file::{XlsxFileType, XlsxFileWriter};
 use crate::Properties;
 
 #[derive(Debug, Deserialize, Serialize)]
@@ -94,20 +90,9 @@ struct VtI4 {
 }
 
 impl AppProperties {
-    pub(crate) fn from_path<P: AsRef<Path>>(file_path: P) -> io::Result<AppProperties> {
-        let file = File::open(&file_path)?;
-        let mut archive = zip::ZipArchive::new(file)?;
-        let mut file = archive.by_name("docProps/app.xml")?;
-        let mut xml = String::new();
-        file.read_to_string(&mut xml).unwrap();
-        let properties: AppProperties = de::from_str(&xml).unwrap();
-        Ok(properties)
-    }
-
-    pub(crate) fn save<P: AsRef<Path>>(&self, file_path: P) {
+    pub(crate) fn save(&self, writer: &mut XlsxFileWriter) {
         let xml = se::to_string_with_root("Properties", &self).unwrap();
         let xml = format!("<?xml version=\"1.0\" encoding=\"UTF-8\" standalone=\"yes\"?>\n{}", xml);
-        let mut file = XlsxFileWriter::from_path(file_path, XlsxFileType::AppProperties).unwrap();
-        file.write_all(xml.as_ref()).unwrap();
+        writer.write_all(XlsxFileType::AppProperties, xml.as_ref());
     }
 }
\ No newline at end of file
@@ -0,0 +1,98 @@
+use std::collections::HashSet;
+use quick_xml::se;
+use serde::{Deserialize, Serialize};
+use crate::api::cell::location::Location;
+use crate::file::{XlsxFileType, XlsxFileWriter};
+use crate::xml::io::{Io, IoV2};
+
+///
+/// The order in which the formula cells of the workbook were last calculated
+///
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename = "calcChain")]
+pub(crate) struct CalcChain {
+    #[serde(rename = "@xmlns")]
+    xmlns: String,
+    #[serde(rename = "c", default)]
+    cells: Vec<CalcCell>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+struct CalcCell {
+    #[serde(rename = "@r")]
+    reference: String,
+    /// The sheet id, the one of the previous cell if omitted
+    #[serde(rename = "@i", skip_serializing_if = "Option::is_none")]
+    sheet_id: Option<u32>,
+    #[serde(rename = "@s", skip_serializing_if = "Option::is_none")]
+    child_chain: Option<String>,
+    #[serde(rename = "@l", skip_serializing_if = "Option::is_none")]
+    new_level: Option<String>,
+    #[serde(rename = "@t", skip_serializing_if = "Option::is_none")]
+    new_thread: Option<String>,
+    #[serde(rename = "@a", skip_serializing_if = "Option::is_none")]
+    array: Option<String>,
+}
+
+impl Default for CalcChain {
+    fn default() -> Self {
+        CalcChain {
+            xmlns: "http://schemas.openxmlformats.org/spreadsheetml/2006/main".to_string(),
+            cells: vec![],
+        }
+    }
+}
+
+impl CalcCell {
+    fn new(sheet_id: u32, row: u32, col: u32) -> CalcCell {
+        CalcCell {
+            reference: (row, col).to_ref(),
+            sheet_id: Some(sheet_id),
+            child_chain: None,
+            new_level: None,
+            new_thread: None,
+            array: None,
+        }
+    }
+}
+
+impl CalcChain {
+    pub(crate) fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    ///
+    /// The chain of the formula cells present, by sheet id: the cells which are no longer formulas are dropped,
+    /// the new formula cells are calculated last.
+    ///
+    pub(crate) fn rebuild(&self, formula_cells: &[(u32, Vec<(u32, u32)>)]) -> CalcChain {
+        let mut remaining = formula_cells.iter()
+            .flat_map(|(sheet_id, cells)| cells.iter().map(|&(row, col)| (*sheet_id, row, col)))
+            .collect::<HashSet<_>>();
+        let mut cells = vec![];
+        let mut sheet_id = 0;
+        for cell in &self.cells {
+            sheet_id = cell.sheet_id.unwrap_or(sheet_id);
+            let (row, col) = cell.reference.as_str().to_location();
+            if remaining.remove(&(sheet_id, row, col)) {
+                cells.push(CalcCell { sheet_id: Some(sheet_id), ..cell.clone() });
+            }
+        }
+        for (sheet_id, formula_cells) in formula_cells {
+            cells.extend(formula_cells.iter()
+                .filter(|&&(row, col)| remaining.contains(&(*sheet_id, row, col)))
+                .map(|&(row, col)| CalcCell::new(*sheet_id, row, col)));
+        }
+        CalcChain { xmlns: self.xmlns.clone(), cells }
+    }
+}
+
+impl Io<CalcChain> for CalcChain {
+    fn save(&self, writer: &mut XlsxFileWriter) {
+        let xml = se::to_string_with_root("calcChain", &self).unwrap();
+        let xml = format!("<?xml version=\"1.0\" encoding=\"UTF-8\" standalone=\"yes\"?>\n{}", xml);
+        writer.write_all(XlsxFileType::CalcChain, xml.as_ref());
+    }
+}
+
+impl IoV2<CalcChain> for CalcChain {}
@@ -0,0 +1,744 @@
+use std::io::{Read, Seek};
+use quick_xml::{de, se};
+use quick_xml::escape::{escape, unescape};
+use serde::{Deserialize, Serialize};
+use zip::ZipArchive;
+use crate::api::chart::{Chart as ApiChart, ChartAxis, ChartGrouping, ChartLegendPosition, ChartSeries, ChartType};
+use crate::file::{XlsxFileType, XlsxFileWriter};
+use crate::FormatColor;
+use crate::utils::reference_helper::{mentions_sheet, remove_sheet_in_formula, rename_sheet_in_formula, Shift};
+use crate::xml::common::Element;
+use crate::xml::io::IoV2;
+
+// The ids of the axes of the charts, any number unique in a chart
+const X_AXIS_ID: u32 = 50010001;
+const Y_AXIS_ID: u32 = 50010002;
+
+// The colors of a theme in the order of their indexes
+const SCHEME_COLORS: [&str; 12] = [
+    "lt1", "dk1", "lt2", "dk2", "accent1", "accent2", "accent3", "accent4", "accent5", "accent6", "hlink", "folHlink",
+];
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub(crate) struct ChartSpace {
+    #[serde(rename = "@xmlns:c", default, skip_serializing_if = "String::is_empty")]
+    xmlns_c: String,
+    #[serde(rename = "@xmlns:a", default, skip_serializing_if = "String::is_empty")]
+    xmlns_a: String,
+    #[serde(rename = "@xmlns:r", default, skip_serializing_if = "String::is_empty")]
+    xmlns_r: String,
+    #[serde(rename(serialize = "c:roundedCorners", deserialize = "roundedCorners"), default, skip_serializing_if = "Option::is_none")]
+    rounded_corners: Option<Element<u8>>,
+    #[serde(rename(serialize = "c:style", deserialize = "style"), default, skip_serializing_if = "Option::is_none")]
+    style: Option<Element<u8>>,
+    #[serde(rename(serialize = "c:chart", deserialize = "chart"))]
+    chart: Chart,
+    // Loaded charts are copied from the source, as only a part of them is read
+    #[serde(skip)]
+    changed: bool,
+    // The xml of a loaded chart, saved instead of the source when the sheets it refers to are renamed
+    #[serde(skip)]
+    xml: Option<String>,
+    #[serde(skip)]
+    xml_changed: bool,
+}
+
+impl ChartSpace {
+    pub(crate) fn new(chart: &ApiChart) -> ChartSpace {
+        ChartSpace {
+            xmlns_c: "http://schemas.openxmlformats.org/drawingml/2006/chart".to_string(),
+            xmlns_a: "http://schemas.openxmlformats.org/drawingml/2006/main".to_string(),
+            xmlns_r: "http://schemas.openxmlformats.org/officeDocument/2006/relationships".to_string(),
+            rounded_corners: Some(Element::from_val(0)),
+            style: Some(Element::from_val(chart.style)),
+            chart: Chart::from_api_chart(chart),
+            changed: true,
+            xml: None,
+            xml_changed: false,
+        }
+    }
+
+    pub(crate) fn from_archive<R: Read + Seek>(archive: &mut ZipArchive<R>, id: u32) -> Option<ChartSpace> {
+        let mut file = archive.by_name(&format!("xl/charts/chart{id}.xml")).ok()?;
+        let mut xml = String::new();
+        file.read_to_string(&mut xml).ok()?;
+        let mut chart_space: ChartSpace = de::from_str(&xml).ok()?;
+        chart_space.xml = Some(xml);
+        Some(chart_space)
+    }
+
+    ///
+    /// Make the references of the series and of the title to a worksheet follow it renamed
+    ///
+    pub(crate) fn rename_sheet(&mut self, old_name: &str, new_name: &str) {
+        self.map_formulas(|formula| rename_sheet_in_formula(formula, old_name, new_name));
+    }
+
+    ///
+    /// Make the references of the series and of the title to a removed worksheet `#REF!`
+    ///
+    pub(crate) fn remove_sheet(&mut self, sheet_name: &str) {
+        self.map_formulas(|formula| remove_sheet_in_formula(formula, sheet_name));
+    }
+
+    ///
+    /// Make the references of the series and of the title follow the cells of a worksheet moved by a shift
+    ///
+    pub(crate) fn shift(&mut self, shift: Shift, sheet_name: &str) {
+        self.map_formulas(|formula| match mentions_sheet(formula, sheet_name) {
+            true => shift.shift_formula(formula, sheet_name, false),
+            false => formula.to_string(),
+        });
+    }
+
+    ///
+    /// Rewrite the references of the series and of the title, in the xml of a loaded chart too
+    ///
+    fn map_formulas<F: Fn(&str) -> String>(&mut self, map: F) {
+        self.chart.references_mut().into_iter().for_each(|reference| reference.f = map(&reference.f));
+        if let Some(xml) = &mut self.xml {
+            let mut mapped = String::with_capacity(xml.len());
+            let mut rest = xml.as_str();
+            while let Some(start) = rest.find("<c:f>") {
+                let (head, tail) = rest.split_at(start + "<c:f>".len());
+                let end = tail.find("</c:f>").unwrap_or(tail.len());
+                let formula = unescape(&tail[..end]).map(|formula| map(&formula)).unwrap_or(tail[..end].to_string());
+                mapped.push_str(head);
+                mapped.push_str(&escape(&formula));
+                rest = &tail[end..];
+            }
+            mapped.push_str(rest);
+            if mapped != *xml {
+                *xml = mapped;
+                self.xml_changed = true;
+            }
+        }
+    }
+
+    ///
+    /// The chart of the api, None for the kinds of charts not supported
+    ///
+    pub(crate) fn to_api_chart(&self) -> Option<ApiChart> {
+        let mut chart = self.chart.to_api_chart()?;
+        chart.style = self.style.as_ref().map_or(2, |style| style.val);
+        Some(chart)
+    }
+
+    ///
+    /// A copy of the chart, saved in a file of its own
+    ///
+    pub(crate) fn copy(&self) -> ChartSpace {
+        ChartSpace {
+            xml_changed: self.xml.is_some(),
+            ..self.clone()
+        }
+    }
+
+    pub(crate) fn save(&self, writer: &mut XlsxFileWriter, chart_id: u32) {
+        if !self.changed {
+            if let (Some(xml), true) = (&self.xml, self.xml_changed) {
+                writer.write_all(XlsxFileType::Charts(chart_id), xml.as_ref());
+            }
+            return;
+        }
+        let xml = se::to_string_with_root("c:chartSpace", &self).unwrap();
+        let xml = format!("<?xml version=\"1.0\" encoding=\"UTF-8\" standalone=\"yes\"?>\n{}", xml);
+        writer.write_all(XlsxFileType::Charts(chart_id), xml.as_ref());
+    }
+}
+
+impl IoV2<ChartSpace> for ChartSpace {}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+struct Chart {
+    #[serde(rename(serialize = "c:title", deserialize = "title"), default, skip_serializing_if = "Option::is_none")]
+    title: Option<Title>,
+    #[serde(rename(serialize = "c:autoTitleDeleted", deserialize = "autoTitleDeleted"), default, skip_serializing_if = "Option::is_none")]
+    auto_title_deleted: Option<Element<u8>>,
+    #[serde(rename(serialize = "c:plotArea", deserialize = "plotArea"))]
+    plot_area: PlotArea,
+    #[serde(rename(serialize = "c:legend", deserialize = "legend"), default, skip_serializing_if = "Option::is_none")]
+    legend: Option<Legend>,
+    #[serde(rename(serialize = "c:plotVisOnly", deserialize = "plotVisOnly"), default, skip_serializing_if = "Option::is_none")]
+    plot_vis_only: Option<Element<u8>>,
+    #[serde(rename(serialize = "c:dispBlanksAs", deserialize = "dispBlanksAs"), default, skip_serializing_if = "Option::is_none")]
+    disp_blanks_as: Option<Element<String>>,
+}
+
+impl Chart {
+    fn from_api_chart(chart: &ApiChart) -> Chart {
+        Chart {
+            title: chart.title.as_deref().map(Title::from_text),
+            auto_title_deleted: Some(Element::from_val(chart.title.is_none() as u8)),
+            plot_area: PlotArea::from_api_chart(chart),
+            legend: chart.legend_position.to_name().map(|position| Legend {
+                legend_pos: Some(Element::from_val(position.to_string())),
+                overlay: Some(Element::from_val(0)),
+            }),
+            plot_vis_only: Some(Element::from_val(1)),
+            disp_blanks_as: Some(Element::from_val("gap".to_string())),
+        }
+    }
+
+    fn references_mut(&mut self) -> Vec<&mut Reference> {
+        let title = self.title.as_mut().and_then(|title| title.tx.as_mut()?.str_ref.as_mut());
+        let plot_area = &mut self.plot_area;
+        let series = [&mut plot_area.bar_chart, &mut plot_area.line_chart, &mut plot_area.pie_chart, &mut plot_area.scatter_chart, &mut plot_area.area_chart]
+            .into_iter()
+            .flatten()
+            .flat_map(|group| group.ser.iter_mut())
+            .flat_map(|series| {
+                let name = series.tx.as_mut().and_then(|tx| tx.str_ref.as_mut());
+                let sources = [&mut series.cat, &mut series.val, &mut series.x_val, &mut series.y_val]
+                    .into_iter()
+                    .flatten()
+                    .flat_map(|source| [source.str_ref.as_mut(), source.num_ref.as_mut()]);
+                sources.chain([name]).flatten()
+            });
+        title.into_iter().chain(series).collect()
+    }
+
+    fn to_api_chart(&self) -> Option<ApiChart> {
+        let plot_area = &self.plot_area;
+        let (chart_type, group) = [
+            (ChartType::Column, &plot_area.bar_chart),
+            (ChartType::Line, &plot_area.line_chart),
+            (ChartType::Pie, &plot_area.pie_chart),
+            (ChartType::Scatter, &plot_area.scatter_chart),
+            (ChartType::Area, &plot_area.area_chart),
+        ].into_iter().find_map(|(chart_type, group)| Some((chart_type, group.as_ref()?)))?;
+        let chart_type = match group.bar_dir.as_ref() {
+            Some(bar_dir) if bar_dir.val == "bar" => ChartType::Bar,
+            _ => chart_type,
+        };
+        let axis = |i: usize| group.ax_id.get(i)
+            .and_then(|ax_id| plot_area.get_axis(ax_id.val))
+            .map(Axis::to_api_axis)
+            .unwrap_or_default();
+        Some(ApiChart {
+            chart_type,
+            grouping: ChartGrouping::from_name(group.grouping.as_ref().map_or("", |grouping| grouping.val.as_str())),
+            title: self.title.as_ref().and_then(Title::get_text),
+            series: group.ser.iter().map(Series::to_api_series).collect(),
+            x_axis: axis(0),
+            y_axis: axis(1),
+            legend_position: match &self.legend {
+                Some(legend) => ChartLegendPosition::from_name(legend.legend_pos.as_ref().map_or("", |position| position.val.as_str())),
+                None => ChartLegendPosition::None,
+            },
+            style: 2,
+        })
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+struct Title {
+    #[serde(rename(serialize = "c:tx", deserialize = "tx"), default, skip_serializing_if = "Option::is_none")]
+    tx: Option<Tx>,
+    #[serde(rename(serialize = "c:overlay", deserialize = "overlay"), default, skip_serializing_if = "Option::is_none")]
+    overlay: Option<Element<u8>>,
+}
+
+impl Title {
+    fn from_text(text: &str) -> Title {
+        let tx = match text.strip_prefix('=') {
+            Some(formula) => Tx { str_ref: Some(Reference::new(formula)), ..Default::default() },
+            None => Tx { rich: Some(Rich::new(text)), ..Default::default() },
+        };
+        Title {
+            tx: Some(tx),
+            overlay: Some(Element::from_val(0)),
+        }
+    }
+
+    ///
+    /// The text of the title, None if the title is the one of Excel
+    ///
+    fn get_text(&self) -> Option<String> {
+        self.tx.as_ref()?.get_text()
+    }
+}
+
+///
+/// A text, a reference to a cell holding it, or a rich text for titles
+///
+#[derive(Debug, Clone, Deserialize, Serialize, Default)]
+struct Tx {
+    #[serde(rename(serialize = "c:strRef", deserialize = "strRef"), default, skip_serializing_if = "Option::is_none")]
+    str_ref: Option<Reference>,
+    #[serde(rename(serialize = "c:rich", deserialize = "rich"), default, skip_serializing_if = "Option::is_none")]
+    rich: Option<Rich>,
+    #[serde(rename(serialize = "c:v", deserialize = "v"), default, skip_serializing_if = "Option::is_none")]
+    v: Option<String>,
+}
+
+impl Tx {
+    ///
+    /// The text of a series name
+    ///
+    fn from_text(text: &str) -> Tx {
+        match text.strip_prefix('=') {
+            Some(formula) => Tx { str_ref: Some(Reference::new(formula)), ..Default::default() },
+            None => Tx { v: Some(text.to_string()), ..Default::default() },
+        }
+    }
+
+    fn get_text(&self) -> Option<String> {
+        if let Some(str_ref) = &self.str_ref {
+            return Some(format!("={}", str_ref.f));
+        }
+        if let Some(rich) = &self.rich {
+            return Some(rich.get_text());
+        }
+        self.v.clone()
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+struct Rich {
+    #[serde(rename(serialize = "a:bodyPr", deserialize = "bodyPr"), default)]
+    body_pr: BodyPr,
+    #[serde(rename(serialize = "a:lstStyle", deserialize = "lstStyle"), default)]
+    lst_style: LstStyle,
+    #[serde(rename(serialize = "a:p", deserialize = "p"), default)]
+    p: Vec<Paragraph>,
+}
+
+impl Rich {
+    fn new(text: &str) -> Rich {
+        Rich {
+            body_pr: BodyPr::default(),
+            lst_style: LstStyle::default(),
+            p: vec![Paragraph { r: vec![Run { t: text.to_string() }] }],
+        }
+    }
+
+    fn get_text(&self) -> String {
+        self.p.iter()
+            .map(|p| p.r.iter().map(|r| r.t.as_str()).collect::<String>())
+            .collect::<Vec<String>>()
+            .join("\n")
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, Default)]
+struct BodyPr {}
+
+#[derive(Debug, Clone, Deserialize, Serialize, Default)]
+struct LstStyle {}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+struct Paragraph {
+    #[serde(rename(serialize = "a:r", deserialize = "r"), default)]
+    r: Vec<Run>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+struct Run {
+    #[serde(rename(serialize = "a:t", deserialize = "t"), default)]
+    t: String,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+struct Reference {
+    #[serde(rename(serialize = "c:f", deserialize = "f"), default)]
+    f: String,
+}
+
+impl Reference {
+    fn new(formula: &str) -> Reference {
+        Reference { f: formula.to_string() }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+struct PlotArea {
+    #[serde(rename(serialize = "c:layout", deserialize = "layout"), default)]
+    layout: Layout,
+    #[serde(rename(serialize = "c:barChart", deserialize = "barChart"), default, skip_serializing_if = "Option::is_none")]
+    bar_chart: Option<ChartGroup>,
+    #[serde(rename(serialize = "c:lineChart", deserialize = "lineChart"), default, skip_serializing_if = "Option::is_none")]
+    line_chart: Option<ChartGroup>,
+    #[serde(rename(serialize = "c:pieChart", deserialize = "pieChart"), default, skip_serializing_if = "Option::is_none")]
+    pie_chart: Option<ChartGroup>,
+    #[serde(rename(serialize = "c:scatterChart", deserialize = "scatterChart"), default, skip_serializing_if = "Option::is_none")]
+    scatter_chart: Option<ChartGroup>,
+    #[serde(rename(serialize = "c:areaChart", deserialize = "areaChart"), default, skip_serializing_if = "Option::is_none")]
+    area_chart: Option<ChartGroup>,
+    #[serde(rename(serialize = "c:catAx", deserialize = "catAx"), default, skip_serializing_if = "Vec::is_empty")]
+    cat_ax: Vec<Axis>,
+    #[serde(rename(serialize = "c:valAx", deserialize = "valAx"), default, skip_serializing_if = "Vec::is_empty")]
+    val_ax: Vec<Axis>,
+}
+
+impl PlotArea {
+    fn from_api_chart(chart: &ApiChart) -> PlotArea {
+        let chart_type = chart.chart_type;
+        let group = Some(ChartGroup::from_api_chart(chart));
+        let mut plot_area = PlotArea {
+            layout: Layout::default(),
+            bar_chart: None,
+            line_chart: None,
+            pie_chart: None,
+            scatter_chart: None,
+            area_chart: None,
+            cat_ax: vec![],
+            val_ax: vec![],
+        };
+        match chart_type {
+            ChartType::Column | ChartType::Bar => plot_area.bar_chart = group,
+            ChartType::Line => plot_area.line_chart = group,
+            ChartType::Pie => plot_area.pie_chart = group,
+            ChartType::Scatter => plot_area.scatter_chart = group,
+            ChartType::Area => plot_area.area_chart = group,
+        }
+        let (x_position, y_position) = match chart_type {
+            ChartType::Bar => ("l", "b"),
+            _ => ("b", "l"),
+        };
+        let y_axis = Axis::from_api_axis(&chart.y_axis, Y_AXIS_ID, X_AXIS_ID, y_position, false);
+        match chart_type {
+            ChartType::Pie => {}
+            // the x values of scatter charts are numbers
+            ChartType::Scatter => {
+                plot_area.val_ax.push(Axis::from_api_axis(&chart.x_axis, X_AXIS_ID, Y_AXIS_ID, x_position, false));
+                plot_area.val_ax.push(y_axis);
+                for axis in &mut plot_area.val_ax {
+                    axis.cross_between = Some(Element::from_val("midCat".to_string()));
+                }
+            }
+            _ => {
+                plot_area.cat_ax.push(Axis::from_api_axis(&chart.x_axis, X_AXIS_ID, Y_AXIS_ID, x_position, true));
+                plot_area.val_ax.push(y_axis);
+            }
+        }
+        plot_area
+    }
+
+    fn get_axis(&self, ax_id: u32) -> Option<&Axis> {
+        self.cat_ax.iter()
+            .chain(self.val_ax.iter())
+            .find(|axis| axis.ax_id.val == ax_id)
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, Default)]
+struct Layout {}
+
+///
+/// A group of series of the same type, the elements of each type are a part of these ones
+///
+#[derive(Debug, Clone, Deserialize, Serialize)]
+struct ChartGroup {
+    #[serde(rename(serialize = "c:barDir", deserialize = "barDir"), default, skip_serializing_if = "Option::is_none")]
+    bar_dir: Option<Element<String>>,
+    #[serde(rename(serialize = "c:scatterStyle", deserialize = "scatterStyle"), default, skip_serializing_if = "Option::is_none")]
+    scatter_style: Option<Element<String>>,
+    #[serde(rename(serialize = "c:grouping", deserialize = "grouping"), default, skip_serializing_if = "Option::is_none")]
+    grouping: Option<Element<String>>,
+    #[serde(rename(serialize = "c:varyColors", deserialize = "varyColors"), default, skip_serializing_if = "Option::is_none")]
+    vary_colors: Option<Element<u8>>,
+    #[serde(rename(serialize = "c:ser", deserialize = "ser"), default)]
+    ser: Vec<Series>,
+    #[serde(rename(serialize = "c:gapWidth", deserialize = "gapWidth"), default, skip_serializing_if = "Option::is_none")]
+    gap_width: Option<Element<u32>>,
+    #[serde(rename(serialize = "c:overlap", deserialize = "overlap"), default, skip_serializing_if = "Option::is_none")]
+    overlap: Option<Element<i32>>,
+    #[serde(rename(serialize = "c:marker", deserialize = "marker"), default, skip_serializing_if = "Option::is_none")]
+    marker: Option<Element<u8>>,
+    #[serde(rename(serialize = "c:firstSliceAng", deserialize = "firstSliceAng"), default, skip_serializing_if = "Option::is_none")]
+    first_slice_ang: Option<Element<u32>>,
+    #[serde(rename(serialize = "c:axId", deserialize = "axId"), default)]
+    ax_id: Vec<Element<u32>>,
+}
+
+impl ChartGroup {
+    fn from_api_chart(chart: &ApiChart) -> ChartGroup {
+        let chart_type = chart.chart_type;
+        let is_bar = matches!(chart_type, ChartType::Column | ChartType::Bar);
+        ChartGroup {
+            bar_dir: match chart_type {
+                ChartType::Column => Some(Element::from_val("col".to_string())),
+                ChartType::Bar => Some(Element::from_val("bar".to_string())),
+                _ => None,
+            },
+            scatter_style: match chart_type {
+                ChartType::Scatter => Some(Element::from_val("lineMarker".to_string())),
+                _ => None,
+            },
+            grouping: match chart_type {
+                ChartType::Pie | ChartType::Scatter => None,
+                _ => Some(Element::from_val(chart.grouping.to_name(chart_type).to_string())),
+            },
+            vary_colors: Some(Element::from_val((chart_type == ChartType::Pie) as u8)),
+            ser: chart.series.iter()
+                .enumerate()
+                .map(|(i, series)| Series::from_api_series(i as u32, series, chart_type))
+                .collect(),
+            gap_width: if is_bar { Some(Element::from_val(150)) } else { None },
+            overlap: match chart.grouping {
+                ChartGrouping::Stacked | ChartGrouping::PercentStacked if is_bar => Some(Element::from_val(100)),
+                _ => None,
+            },
+            marker: if chart_type == ChartType::Line { Some(Element::from_val(1)) } else { None },
+            first_slice_ang: if chart_type == ChartType::Pie { Some(Element::from_val(0)) } else { None },
+            ax_id: match chart_type.has_axes() {
+                true => vec![Element::from_val(X_AXIS_ID), Element::from_val(Y_AXIS_ID)],
+                false => vec![],
+            },
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+struct Series {
+    #[serde(rename(serialize = "c:idx", deserialize = "idx"))]
+    idx: Element<u32>,
+    #[serde(rename(serialize = "c:order", deserialize = "order"))]
+    order: Element<u32>,
+    #[serde(rename(serialize = "c:tx", deserialize = "tx"), default, skip_serializing_if = "Option::is_none")]
+    tx: Option<Tx>,
+    #[serde(rename(serialize = "c:spPr", deserialize = "spPr"), default, skip_serializing_if = "Option::is_none")]
+    sp_pr: Option<ShapeProperties>,
+    #[serde(rename(serialize = "c:cat", deserialize = "cat"), default, skip_serializing_if = "Option::is_none")]
+    cat: Option<DataSource>,
+    #[serde(rename(serialize = "c:val", deserialize = "val"), default, skip_serializing_if = "Option::is_none")]
+    val: Option<DataSource>,
+    #[serde(rename(serialize = "c:xVal", deserialize = "xVal"), default, skip_serializing_if = "Option::is_none")]
+    x_val: Option<DataSource>,
+    #[serde(rename(serialize = "c:yVal", deserialize = "yVal"), default, skip_serializing_if = "Option::is_none")]
+    y_val: Option<DataSource>,
+}
+
+impl Series {
+    fn from_api_series(idx: u32, series: &ChartSeries, chart_type: ChartType) -> Series {
+        let fill = series.color.as_ref().and_then(SolidFill::from_format_color);
+        let sp_pr = fill.map(|fill| match chart_type {
+            // lines are colored by their outline
+            ChartType::Line | ChartType::Scatter => ShapeProperties { solid_fill: None, ln: Some(Line { solid_fill: Some(fill) }) },
+            _ => ShapeProperties { solid_fill: Some(fill), ln: None },
+        });
+        let mut data_series = Series {
+            idx: Element::from_val(idx),
+            order: Element::from_val(idx),
+            tx: series.name.as_deref().map(Tx::from_text),
+            sp_pr,
+            cat: None,
+            val: None,
+            x_val: None,
+            y_val: None,
+        };
+        let values = Some(DataSource::num_ref(&series.values));
+        if chart_type == ChartType::Scatter {
+            data_series.x_val = series.categories.as_deref().map(DataSource::num_ref);
+            data_series.y_val = values;
+        } else {
+            data_series.cat = series.categories.as_deref().map(DataSource::str_ref);
+            data_series.val = values;
+        }
+        data_series
+    }
+
+    fn to_api_series(&self) -> ChartSeries {
+        let sp_pr = self.sp_pr.as_ref();
+        let fill = sp_pr.and_then(|sp_pr| sp_pr.solid_fill.as_ref())
+            .or(sp_pr.and_then(|sp_pr| sp_pr.ln.as_ref()?.solid_fill.as_ref()));
+        ChartSeries {
+            name: self.tx.as_ref().and_then(Tx::get_text),
+            categories: self.cat.as_ref().or(self.x_val.as_ref()).and_then(DataSource::get_formula),
+            values: self.val.as_ref().or(self.y_val.as_ref()).and_then(DataSource::get_formula).unwrap_or_default(),
+            color: fill.and_then(SolidFill::to_format_color),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+struct DataSource {
+    #[serde(rename(serialize = "c:strRef", deserialize = "strRef"), default, skip_serializing_if = "Option::is_none")]
+    str_ref: Option<Reference>,
+    #[serde(rename(serialize = "c:numRef", deserialize = "numRef"), default, skip_serializing_if = "Option::is_none")]
+    num_ref: Option<Reference>,
+}
+
+impl DataSource {
+    fn str_ref(formula: &str) -> DataSource {
+        DataSource { str_ref: Some(Reference::new(formula)), num_ref: None }
+    }
+
+    fn num_ref(formula: &str) -> DataSource {
+        DataSource { str_ref: None, num_ref: Some(Reference::new(formula)) }
+    }
+
+    fn get_formula(&self) -> Option<String> {
+        self.str_ref.as_ref().or(self.num_ref.as_ref()).map(|reference| reference.f.clone())
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+struct ShapeProperties {
+    #[serde(rename(serialize = "a:solidFill", deserialize = "solidFill"), default, skip_serializing_if = "Option::is_none")]
+    solid_fill: Option<SolidFill>,
+    #[serde(rename(serialize = "a:ln", deserialize = "ln"), default, skip_serializing_if = "Option::is_none")]
+    ln: Option<Line>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+struct Line {
+    #[serde(rename(serialize = "a:solidFill", deserialize = "solidFill"), default, skip_serializing_if = "Option::is_none")]
+    solid_fill: Option<SolidFill>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+struct SolidFill {
+    #[serde(rename(serialize = "a:srgbClr", deserialize = "srgbClr"), default, skip_serializing_if = "Option::is_none")]
+    srgb_clr: Option<Element<String>>,
+    #[serde(rename(serialize = "a:schemeClr", deserialize = "schemeClr"), default, skip_serializing_if = "Option::is_none")]
+    scheme_clr: Option<Element<String>>,
+}
+
+impl SolidFill {
+    ///
+    /// The tints of the theme colors are not kept
+    ///
+    fn from_format_color(color: &FormatColor) -> Option<SolidFill> {
+        match color {
+            FormatColor::RGB(r, g, b) => Some(SolidFill {
+                srgb_clr: Some(Element::from_val(format!("{r:02X}{g:02X}{b:02X}"))),
+                scheme_clr: None,
+            }),
+            FormatColor::Theme(theme, _) => Some(SolidFill {
+                srgb_clr: None,
+                scheme_clr: Some(Element::from_val(SCHEME_COLORS.get(*theme as usize)?.to_string())),
+            }),
+            _ => None,
+        }
+    }
+
+    fn to_format_color(&self) -> Option<FormatColor> {
+        if let Some(srgb_clr) = &self.srgb_clr {
+            let rgb = u32::from_str_radix(&srgb_clr.val, 16).ok()?;
+            return Some(FormatColor::RGB((rgb >> 16) as u8, (rgb >> 8) as u8, rgb as u8));
+        }
+        let scheme_clr = self.scheme_clr.as_ref()?;
+        let theme = SCHEME_COLORS.iter().position(|name| *name == scheme_clr.val)?;
+        Some(FormatColor::Theme(theme as u8, 0.0))
+    }
+}
+
+///
+/// An axis of categories or of values, the elements of each kind are a part of these ones
+///
+#[derive(Debug, Clone, Deserialize, Serialize)]
+struct Axis {
+    #[serde(rename(serialize = "c:axId", deserialize = "axId"))]
+    ax_id: Element<u32>,
+    #[serde(rename(serialize = "c:scaling", deserialize = "scaling"), default)]
+    scaling: Scaling,
+    #[serde(rename(serialize = "c:delete", deserialize = "delete"), default, skip_serializing_if = "Option::is_none")]
+    delete: Option<Element<u8>>,
+    #[serde(rename(serialize = "c:axPos", deserialize = "axPos"), default)]
+    ax_pos: Element<String>,
+    #[serde(rename(serialize = "c:majorGridlines", deserialize = "majorGridlines"), default, skip_serializing_if = "Option::is_none")]
+    major_gridlines: Option<Gridlines>,
+    #[serde(rename(serialize = "c:title", deserialize = "title"), default, skip_serializing_if = "Option::is_none")]
+    title: Option<Title>,
+    #[serde(rename(serialize = "c:numFmt", deserialize = "numFmt"), default, skip_serializing_if = "Option::is_none")]
+    num_fmt: Option<NumFmt>,
+    #[serde(rename(serialize = "c:majorTickMark", deserialize = "majorTickMark"), default, skip_serializing_if = "Option::is_none")]
+    major_tick_mark: Option<Element<String>>,
+    #[serde(rename(serialize = "c:minorTickMark", deserialize = "minorTickMark"), default, skip_serializing_if = "Option::is_none")]
+    minor_tick_mark: Option<Element<String>>,
+    #[serde(rename(serialize = "c:tickLblPos", deserialize = "tickLblPos"), default, skip_serializing_if = "Option::is_none")]
+    tick_lbl_pos: Option<Element<String>>,
+    #[serde(rename(serialize = "c:crossAx", deserialize = "crossAx"), default)]
+    cross_ax: Element<u32>,
+    #[serde(rename(serialize = "c:crosses", deserialize = "crosses"), default, skip_serializing_if = "Option::is_none")]
+    crosses: Option<Element<String>>,
+    #[serde(rename(serialize = "c:auto", deserialize = "auto"), default, skip_serializing_if = "Option::is_none")]
+    auto: Option<Element<u8>>,
+    #[serde(rename(serialize = "c:lblAlgn", deserialize = "lblAlgn"), default, skip_serializing_if = "Option::is_none")]
+    lbl_algn: Option<Element<String>>,
+    #[serde(rename(serialize = "c:lblOffset", deserialize = "lblOffset"), default, skip_serializing_if = "Option::is_none")]
+    lbl_offset: Option<Element<u32>>,
+    #[serde(rename(serialize = "c:crossBetween", deserialize = "crossBetween"), default, skip_serializing_if = "Option::is_none")]
+    cross_between: Option<Element<String>>,
+    #[serde(rename(serialize = "c:majorUnit", deserialize = "majorUnit"), default, skip_serializing_if = "Option::is_none")]
+    major_unit: Option<Element<f64>>,
+}
+
+impl Axis {
+    fn from_api_axis(axis: &ChartAxis, ax_id: u32, cross_ax: u32, position: &str, category: bool) -> Axis {
+        let text = |text: &str| Some(Element::from_val(text.to_string()));
+        Axis {
+            ax_id: Element::from_val(ax_id),
+            scaling: Scaling {
+                orientation: Element::from_val(if axis.reverse { "maxMin" } else { "minMax" }.to_string()),
+                max: axis.max.map(Element::from_val),
+                min: axis.min.map(Element::from_val),
+            },
+            delete: Some(Element::from_val(axis.hidden as u8)),
+            ax_pos: Element::from_val(position.to_string()),
+            major_gridlines: if axis.major_gridlines { Some(Gridlines::default()) } else { None },
+            title: axis.title.as_deref().map(Title::from_text),
+            num_fmt: axis.num_format.as_ref().map(|num_format| NumFmt { format_code: num_format.clone(), source_linked: 0 }),
+            major_tick_mark: text("out"),
+            minor_tick_mark: text("none"),
+            tick_lbl_pos: text("nextTo"),
+            cross_ax: Element::from_val(cross_ax),
+            crosses: text("autoZero"),
+            auto: if category { Some(Element::from_val(1)) } else { None },
+            lbl_algn: if category { text("ctr") } else { None },
+            lbl_offset: if category { Some(Element::from_val(100)) } else { None },
+            cross_between: match category {
+                true => None,
+                false => text("between"),
+            },
+            // the labels of categories are skipped instead
+            major_unit: if category { None } else { axis.major_unit.map(Element::from_val) },
+        }
+    }
+
+    fn to_api_axis(&self) -> ChartAxis {
+        ChartAxis {
+            title: self.title.as_ref().and_then(Title::get_text),
+            min: self.scaling.min.as_ref().map(|min| min.val),
+            max: self.scaling.max.as_ref().map(|max| max.val),
+            major_unit: self.major_unit.as_ref().map(|major_unit| major_unit.val),
+            num_format: self.num_fmt.as_ref()
+                .filter(|num_fmt| num_fmt.source_linked == 0)
+                .map(|num_fmt| num_fmt.format_code.clone()),
+            major_gridlines: self.major_gridlines.is_some(),
+            reverse: self.scaling.orientation.val == "maxMin",
+            hidden: self.delete.as_ref().is_some_and(|delete| delete.val == 1),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, Default)]
+struct Scaling {
+    #[serde(rename(serialize = "c:orientation", deserialize = "orientation"), default)]
+    orientation: Element<String>,
+    #[serde(rename(serialize = "c:max", deserialize = "max"), default, skip_serializing_if = "Option::is_none")]
+    max: Option<Element<f64>>,
+    #[serde(rename(serialize = "c:min", deserialize = "min"), default, skip_serializing_if = "Option::is_none")]
+    min: Option<Element<f64>>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, Default)]
+struct Gridlines {}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+struct NumFmt {
+    #[serde(rename = "@formatCode")]
+    format_code: String,
+    #[serde(rename = "@sourceLinked", default)]
+    source_linked: u8,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+struct Legend {
+    #[serde(rename(serialize = "c:legendPos", deserialize = "legendPos"), default, skip_serializing_if = "Option::is_none")]
+    legend_pos: Option<Element<String>>,
+    #[serde(rename(serialize = "c:overlay", deserialize = "overlay"), default, skip_serializing_if = "Option::is_none")]
+    overlay: Option<Element<u8>>,
+}
@@ -0,0 +1,163 @@
+use std::io::{Read, Seek};
+use quick_xml::{de, se};
+use serde::{Deserialize, Serialize};
+use zip::ZipArchive;
+use crate::api::cell::location::Location;
+use crate::file::{XlsxFileType, XlsxFileWriter};
+use crate::utils::reference_helper::Shift;
+use crate::xml::common::XmlnsAttrs;
+use crate::xml::io::IoV2;
+use crate::xml::shared_string::{escape_text_whitespace, StringItem};
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename = "comments")]
+pub(crate) struct Comments {
+    #[serde(flatten)]
+    xmlns_attrs: XmlnsAttrs,
+    #[serde(rename = "authors", default)]
+    authors: Authors,
+    #[serde(rename = "commentList", default)]
+    comment_list: CommentList,
+    #[serde(skip)]
+    changed: bool,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, Default)]
+struct Authors {
+    #[serde(rename = "author", default)]
+    author: Vec<String>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, Default)]
+struct CommentList {
+    #[serde(rename = "comment", default)]
+    comment: Vec<Comment>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+struct Comment {
+    #[serde(rename = "@ref")]
+    reference: String,
+    #[serde(rename = "@authorId", default)]
+    author_id: usize,
+    #[serde(rename = "@shapeId", skip_serializing_if = "Option::is_none")]
+    shape_id: Option<u32>,
+    #[serde(rename(serialize = "@xr:uid", deserialize = "@uid"), skip_serializing_if = "Option::is_none")]
+    xr_uid: Option<String>,
+    #[serde(rename = "text", default)]
+    text: StringItem,
+}
+
+impl Default for Comments {
+    fn default() -> Self {
+        Self {
+            xmlns_attrs: XmlnsAttrs::shared_string_default(),
+            authors: Default::default(),
+            comment_list: Default::default(),
+            changed: false,
+        }
+    }
+}
+
+impl Comment {
+    fn location(&self) -> (u32, u32) {
+        self.reference.as_str().to_location()
+    }
+}
+
+impl Comments {
+    ///
+    /// The plain text and the author of the comment in a cell
+    ///
+    pub(crate) fn get_comment<L: Location>(&self, loc: &L) -> Option<(String, Option<String>)> {
+        let loc = loc.to_location();
+        let comment = self.comment_list.comment.iter()
+            .find(|comment| comment.location() == loc)?;
+        let author = self.authors.author.get(comment.author_id).cloned();
+        Some((comment.text.plain_text(), author))
+    }
+
+    ///
+    /// Move the comments with their cells, the ones of deleted cells are removed
+    ///
+    pub(crate) fn shift(&mut self, shift: Shift) {
+        self.comment_list.comment.retain_mut(|comment| match shift.shift_ref(&comment.reference) {
+            Some(reference) => {
+                comment.reference = reference;
+                true
+            }
+            None => false,
+        });
+        self.changed = true;
+    }
+
+    ///
+    /// Add a comment to a cell, an existing comment of the cell is replaced
+    ///
+    pub(crate) fn set_comment<L: Location>(&mut self, loc: &L, text: &str, author: &str) {
+        let author_id = self.get_or_add_author(author);
+        let comment = Comment {
+            reference: loc.to_ref(),
+            author_id,
+            shape_id: Some(0),
+            xr_uid: None,
+            text: StringItem::new(text),
+        };
+        let loc = loc.to_location();
+        let comments = &mut self.comment_list.comment;
+        match comments.iter().position(|comment| comment.location() == loc) {
+            Some(position) => comments[position] = comment,
+            None => {
+                // comments are kept in the order of the cells
+                let position = comments.iter()
+                    .position(|comment| comment.location() > loc)
+                    .unwrap_or(comments.len());
+                comments.insert(position, comment);
+            }
+        }
+        self.changed = true;
+    }
+
+    fn get_or_add_author(&mut self, author: &str) -> usize {
+        let authors = &mut self.authors.author;
+        match authors.iter().position(|a| a == author) {
+            Some(author_id) => author_id,
+            None => {
+                authors.push(author.to_string());
+                authors.len() - 1
+            }
+        }
+    }
+
+    ///
+    /// A copy of the comments, saved in a file of its own
+    ///
+    pub(crate) fn copy(&self) -> Comments {
+        Comments {
+            changed: true,
+            ..self.clone()
+        }
+    }
+
+    pub(crate) fn save(&self, writer: &mut XlsxFileWriter, comments_id: u32) {
+        // unchanged comments are copied from the source
+        if !self.changed {
+            return;
+        }
+        let xml = se::to_string_with_root("comments", &self).unwrap();
+        let xml = format!("<?xml version=\"1.0\" encoding=\"UTF-8\" standalone=\"yes\"?>\n{}", xml);
+        writer.write_all(XlsxFileType::Comments(comments_id), xml.as_ref());
+    }
+}
+
+impl IoV2<Comments> for Comments {
+    fn from_zip_file<R: Read + Seek>(archive: &mut ZipArchive<R>, path: &str) -> Option<Comments> {
+        if let Ok(mut file) = archive.by_name(path) {
+            let mut xml = String::new();
+            file.read_to_string(&mut xml).unwrap();
+            de::from_str(&escape_text_whitespace(&xml)).ok()
+        } else {
+            None
+        }
+    }
+}
@@ -9,8 +9,10 @@ use serde::{Deserialize, Serialize};
 pub(crate) struct PhoneticPr {
     #[serde(rename = "@fontId")]
     font_id: u32,
-    #[serde(rename = "@type")]
-    phonetic_pr_type: String
+    #[serde(rename = "@type", skip_serializing_if = "Option::is_none")]
+    phonetic_pr_type: Option<String>,
+    #[serde(rename = "@alignment", skip_serializing_if = "Option::is_none")]
+    alignment: Option<String>,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
@@ -268,6 +270,17 @@ impl XmlnsAttrs {
 pub(crate) fn is_zero(num: &u32) -> bool {
     num.eq(&0)
 }
+
+///
+/// Write an xsd:boolean attribute as 1 or 0 the way Excel does, any spelling of it is read as a bool
+///
+pub(crate) fn serialize_bool<S: serde::Serializer>(value: &Option<bool>, serializer: S) -> Result<S::Ok, S::Error> {
+    match value {
+        Some(value) => serializer.serialize_u8(*value as u8),
+        None => serializer.serialize_none(),
+    }
+}
+
 pub(crate) trait FromFormat<T: Default>: Default {
     fn set_attrs_by_format(&mut self, format: &T);
     fn set_format(&self, format: &mut T);
@@ -1,26 +1,27 @@
 use std::collections::HashSet;
 use std::fs::File;
 use std::hash::Hash;
-use std::io;
 use std::io::Read;
-use std::path::Path;
 use quick_xml::{de, se};
 use serde::{Deserialize, Serialize};
-use zip::read::ZipFile;
-use zip::ZipArchive;
-use crate::file::{XlsxFileReader, XlsxFileType, XlsxFileWriter};
+use crate::file::{XlsxFileType, XlsxFileWriter};
 use crate::xml::io::Io;
-use crate::xml::relationships::Relationships;
 
-#[derive(Debug, Deserialize, Serialize, Default)]
+#[derive(Debug, Clone, Deserialize, Serialize, Default)]
 pub(crate) struct ContentTypes {
     #[serde(rename = "@xmlns")]
     xmlns: String,
     #[serde(rename = "$value")]
     content_types: HashSet<ContentType>,
+    // VML drawings share a default content type, so their ids are recorded when loading
+    #[serde(skip)]
+    vml_drawing_ids: Vec<u32>,
+    // Tables are numbered in the workbook, their ids are recorded when loading
+    #[serde(skip)]
+    table_ids: Vec<u32>,
 }
 
-#[derive(Debug, Deserialize, Serialize, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq, Hash)]
 enum ContentType{
     Default {
         #[serde(rename = "@Extension")]
@@ -38,7 +39,7 @@ enum ContentType{
 
 impl ContentType {
     fn get_extension(&self) -> Option<&str> {
-        if let ContentType::Default { extension, content_type } = self {
+        if let ContentType::Default { extension, content_type: _ } = self {
             Some(&extension)
         } else {
             None
@@ -46,6 +47,21 @@ impl ContentType {
     }
 }
 
+impl ContentType {
+    ///
+    /// The number in the name of an overridden part, like 1 for `/xl/comments1.xml` with the prefix `/xl/comments`
+    ///
+    fn get_part_id(&self, prefix: &str) -> Option<u32> {
+        match self {
+            ContentType::Override { part_name, content_type: _ } => {
+                let id = part_name.strip_prefix(prefix)?.strip_suffix(".xml")?;
+                id.parse().ok()
+            },
+            ContentType::Default { .. } => None,
+        }
+    }
+}
+
 impl ContentTypes {
     fn get_mut_by_extension(&self, extension: &str) -> bool {
         self.content_types.iter().find(|c| c.get_extension() == Some(extension)).is_some()
@@ -62,8 +78,128 @@ impl ContentTypes {
         }
         self.content_types.insert(ContentType::octet_stream_default(extension));
     }
-    pub(crate) fn add_drawing(&mut self, id: u32) { self.content_types.insert(ContentType::drawing_override(id)); }
     pub(crate) fn add_metadata(&mut self) { self.content_types.insert(ContentType::metadata_override()); }
+    pub(crate) fn add_shared_strings(&mut self) { self.content_types.insert(ContentType::shared_strings_override()); }
+    pub(crate) fn add_calc_chain(&mut self) { self.content_types.insert(ContentType::calc_chain_override()); }
+    pub(crate) fn remove_calc_chain(&mut self) { self.content_types.remove(&ContentType::calc_chain_override()); }
+
+    ///
+    /// Remove the content type overridden for a part, like `xl/comments1.xml`
+    ///
+    pub(crate) fn remove_part(&mut self, part_name: &str) {
+        self.content_types.retain(|c| !matches!(c, ContentType::Override { part_name: name, .. } if name.trim_start_matches('/') == part_name));
+    }
+
+    ///
+    /// Add a comments part and return the id of its file
+    ///
+    pub(crate) fn add_comments(&mut self) -> u32 {
+        let id = 1 + self.content_types.iter()
+            .filter_map(|c| c.get_part_id("/xl/comments"))
+            .max()
+            .unwrap_or_default();
+        self.content_types.insert(ContentType::comments_override(id));
+        id
+    }
+
+    ///
+    /// Add a drawing part and return the id of its file
+    ///
+    pub(crate) fn add_drawing(&mut self) -> u32 {
+        let id = 1 + self.content_types.iter()
+            .filter_map(|c| c.get_part_id("/xl/drawings/drawing"))
+            .max()
+            .unwrap_or_default();
+        self.content_types.insert(ContentType::drawing_override(id));
+        id
+    }
+
+    ///
+    /// Add a chart part and return the id of its file
+    ///
+    pub(crate) fn add_chart(&mut self) -> u32 {
+        let id = 1 + self.content_types.iter()
+            .filter_map(|c| c.get_part_id("/xl/charts/chart"))
+            .max()
+            .unwrap_or_default();
+        self.content_types.insert(ContentType::chart_override(id));
+        id
+    }
+
+    ///
+    /// Add a vml drawing and return the id of its file
+    ///
+    pub(crate) fn add_vml_drawing(&mut self) -> u32 {
+        let id = 1 + self.vml_drawing_ids.iter().max().copied().unwrap_or_default();
+        self.vml_drawing_ids.push(id);
+        self.content_types.insert(ContentType::vml_default());
+        id
+    }
+
+    ///
+    /// Add a table part and return the id of its file, which is also the id of the table
+    ///
+    pub(crate) fn add_table(&mut self) -> u32 {
+        let id = 1 + self.content_types.iter()
+            .filter_map(|c| c.get_part_id("/xl/tables/table"))
+            .chain(self.table_ids.iter().copied())
+            .max()
+            .unwrap_or_default();
+        self.table_ids.push(id);
+        self.content_types.insert(ContentType::table_override(id));
+        id
+    }
+
+    ///
+    /// The parts with a content type of their own, and the vml drawings
+    ///
+    pub(crate) fn get_part_names(&self) -> Vec<String> {
+        self.content_types.iter()
+            .filter_map(|c| match c {
+                ContentType::Override { part_name, .. } => Some(part_name.trim_start_matches('/').to_string()),
+                ContentType::Default { .. } => None,
+            })
+            .chain(self.vml_drawing_ids.iter().map(|id| format!("xl/drawings/vmlDrawing{id}.vml")))
+            .collect()
+    }
+
+    ///
+    /// Give a part the content type a part of another package has there, by its name or by its extension
+    ///
+    pub(crate) fn add_part_like(&mut self, source: &ContentTypes, source_part_name: &str, part_name: &str) {
+        let source_part_name = format!("/{source_part_name}");
+        let content_type = source.content_types.iter().find_map(|c| match c {
+            ContentType::Override { part_name, content_type } if *part_name == source_part_name => Some(content_type),
+            _ => None,
+        });
+        if let Some(content_type) = content_type {
+            self.content_types.insert(ContentType::Override {
+                part_name: format!("/{part_name}"),
+                content_type: content_type.clone(),
+            });
+            return;
+        }
+        let extension = part_name.rsplit_once('.').map_or("", |(_, extension)| extension);
+        let default = source.content_types.iter()
+            .find(|c| c.get_extension().is_some_and(|source_extension| source_extension.eq_ignore_ascii_case(extension)));
+        if let (Some(ContentType::Default { content_type, .. }), false) = (default, self.get_mut_by_extension(extension)) {
+            self.content_types.insert(ContentType::Default {
+                extension: extension.to_string(),
+                content_type: content_type.clone(),
+            });
+        }
+    }
+
+    pub(crate) fn add_existed_table(&mut self, id: u32) {
+        self.table_ids.push(id);
+    }
+
+    pub(crate) fn add_existed_vml_drawing(&mut self, file_name: &str) {
+        let id = file_name.chars().filter(char::is_ascii_digit).collect::<String>();
+        if let Ok(id) = id.parse() {
+            self.vml_drawing_ids.push(id);
+        }
+    }
 }
 
 impl ContentType {
@@ -88,12 +224,54 @@ impl ContentType {
         }
     }
 
+    fn chart_override(id: u32) -> ContentType {
+        ContentType::Override {
+            part_name: format!("/xl/charts/chart{id}.xml"),
+            content_type: "application/vnd.openxmlformats-officedocument.drawingml.chart+xml".to_string(),
+        }
+    }
+
+    fn vml_default() -> ContentType {
+        ContentType::Default {
+            extension: "vml".to_string(),
+            content_type: "application/vnd.openxmlformats-officedocument.vmlDrawing".to_string(),
+        }
+    }
+
+    fn comments_override(id: u32) -> ContentType {
+        ContentType::Override {
+            part_name: format!("/xl/comments{id}.xml"),
+            content_type: "application/vnd.openxmlformats-officedocument.spreadsheetml.comments+xml".to_string(),
+        }
+    }
+
+    fn table_override(id: u32) -> ContentType {
+        ContentType::Override {
+            part_name: format!("/xl/tables/table{id}.xml"),
+            content_type: "application/vnd.openxmlformats-officedocument.spreadsheetml.table+xml".to_string(),
+        }
+    }
+
     fn metadata_override() -> ContentType {
         ContentType::Override {
             part_name: "/xl/metadata.xml".to_string(),
             content_type: "application/vnd.openxmlformats-officedocument.spreadsheetml.sheetMetadata+xml".to_string(),
         }
     }
+
+    fn calc_chain_override() -> ContentType {
+        ContentType::Override {
+            part_name: "/xl/calcChain.xml".to_string(),
+            content_type: "application/vnd.openxmlformats-officedocument.spreadsheetml.calcChain+xml".to_string(),
+        }
+    }
+
+    fn shared_strings_override() -> ContentType {
+        ContentType::Override {
+            part_name: "/xl/sharedStrings.xml".to_string(),
+            content_type: "application/vnd.openxmlformats-officedocument.spreadsheetml.sharedStrings+xml".to_string(),
+        }
+    }
 }
 
 impl ContentTypes {
@@ -115,10 +293,9 @@ impl ContentTypes {
 }
 
 impl Io<ContentTypes> for ContentTypes {
-    fn save<P: AsRef<Path>>(& self, file_path: P) {
+    fn save(&self, writer: &mut XlsxFileWriter) {
         let xml = se::to_string_with_root("Types", &self).unwrap();
         let xml = format!("<?xml version=\"1.0\" encoding=\"UTF-8\" standalone=\"yes\"?>\n{}", xml);
-        let mut file = XlsxFileWriter::from_path(file_path, XlsxFileType::ContentTypes).unwrap();
-        file.write_all(xml.as_ref()).unwrap();
+        writer.write_all(XlsxFileType::ContentTypes, xml.as_ref());
     }
 }
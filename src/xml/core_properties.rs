@@ -1,13 +1,7 @@
-use std::fs::File;
-use std::io;
-use std::io::Read;
-use std::path::Path;
-use quick_xml::{de, se};
+use quick_xml::se;
 use serde::{Deserialize, Serialize};
-use zip::read::ZipFile;
-use crate::file::{XlsxFileReader, XlsxFileType, XlsxFileWriter};
+use crate::file::{XlsxFileType, XlsxFileWriter};
 use crate::Properties;
-use crate::xml::io::Io;
 
 #[derive(Debug, Deserialize, Serialize)]
 #[serde(rename(serialize = "cp:coreProperties", deserialize = "coreProperties"))]
@@ -79,20 +73,9 @@ impl CoreProperties {
 }
 
 impl CoreProperties {
-    pub(crate) fn from_path<P: AsRef<Path>>(file_path: P) -> io::Result<CoreProperties> {
-        let file = File::open(&file_path)?;
-        let mut archive = zip::ZipArchive::new(file)?;
-        let mut file = archive.by_name("docProps/core.xml")?;
-        let mut xml = String::new();
-        file.read_to_string(&mut xml).unwrap();
-        let properties: CoreProperties = de::from_str(&xml).unwrap();
-        Ok(properties)
-    }
-
-    pub(crate) fn save<P: AsRef<Path>>(&self, file_path: P) {
+    pub(crate) fn save(&self, writer: &mut XlsxFileWriter) {
         let xml = se::to_string_with_root("cp:coreProperties", &self).unwrap();
         let xml = format!("<?xml version=\"1.0\" encoding=\"UTF-8\" standalone=\"yes\"?>\n{}", xml);
-        let mut file = XlsxFileWriter::from_path(file_path, XlsxFileType::CoreProperties).unwrap();
-        file.write_all(xml.as_ref()).unwrap();
+        writer.write_all(XlsxFileType::CoreProperties, xml.as_ref());
     }
 }
\ No newline at end of file
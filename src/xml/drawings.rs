@@ -1,14 +1,15 @@
 pub(crate) mod vml_drawing;
+pub(crate) mod header_footer_drawing;
 
-use std::io;
-use std::io::Read;
-use std::path::Path;
-use quick_xml::{de, se};
+use std::io::{Read, Seek};
+use quick_xml::se;
 use serde::{Deserialize, Serialize};
-use zip::read::ZipFile;
+use zip::ZipArchive;
 use crate::api::cell::location::{Location, LocationRange};
 use crate::api::relationship::Rel;
-use crate::file::{XlsxFileReader, XlsxFileType, XlsxFileWriter};
+use crate::file::{XlsxFileType, XlsxFileWriter};
+use crate::utils::reference_helper::Shift;
+use crate::xml::drawings::vml_drawing::replace_tag;
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
 #[serde(rename(serialize = "xdr:wsDr", deserialize = "wsDr"))]
@@ -18,7 +19,12 @@ pub(crate) struct Drawings {
     #[serde(rename(serialize = "@xmlns:a", deserialize = "@xmlns:a"), default, skip_serializing_if = "String::is_empty")]
     xmlns_a: String,
     #[serde(rename(serialize = "xdr:twoCellAnchor", deserialize = "twoCellAnchor"), default)]
-    drawing: Vec<Drawing>
+    drawing: Vec<Drawing>,
+    // The loaded drawing, whose anchors are kept as they are and followed by the added ones
+    #[serde(skip)]
+    xml: Option<String>,
+    #[serde(skip)]
+    next_shape_id: u32,
 }
 
 impl Default for Drawings {
@@ -27,43 +33,86 @@ impl Default for Drawings {
             xmlns_xdr: "http://schemas.openxmlformats.org/drawingml/2006/spreadsheetDrawing".to_string(),
             xmlns_a: "http://schemas.openxmlformats.org/drawingml/2006/main".to_string(),
             drawing: vec![],
+            xml: None,
+            next_shape_id: 2,
         }
     }
 }
 
 impl Drawings {
-    pub(crate) fn next_id(&self) -> u32 {
-        let id = 1 + self.drawing.len() as u32;
-        id
+    pub(crate) fn from_archive<R: Read + Seek>(archive: &mut ZipArchive<R>, id: u32) -> Option<Drawings> {
+        let mut file = archive.by_name(&format!("xl/drawings/drawing{id}.xml")).ok()?;
+        let mut xml = String::new();
+        file.read_to_string(&mut xml).ok()?;
+        // the shapes of a drawing have unique ids
+        let max_shape_id = xml.split("cNvPr ").skip(1)
+            .filter_map(|shape| shape.split("id=\"").nth(1)?.split('"').next()?.parse::<u32>().ok())
+            .max()
+            .unwrap_or(1);
+        Some(Drawings {
+            xml: Some(xml),
+            next_shape_id: max_shape_id + 1,
+            ..Default::default()
+        })
     }
 
     pub(crate) fn add_drawing<L: LocationRange>(&mut self, from_to: L, r_id: u32) {
-        self.drawing.push(Drawing::new(from_to, r_id));
+        let id = self.next_shape_id;
+        self.next_shape_id += 1;
+        self.drawing.push(Drawing::new(from_to, Some(Picture::from_id(id, r_id)), None));
+    }
+
+    pub(crate) fn add_chart<L: LocationRange>(&mut self, from_to: L, r_id: u32) {
+        let id = self.next_shape_id;
+        self.next_shape_id += 1;
+        self.drawing.push(Drawing::new(from_to, None, Some(GraphicFrame::from_id(id, r_id))));
+    }
+
+    ///
+    /// Move the anchors of the drawings with their cells,
+    /// the ones of deleted cells are moved to the first cell after them
+    ///
+    pub(crate) fn shift(&mut self, shift: Shift) {
+        self.drawing.iter_mut().for_each(|drawing| {
+            drawing.from.shift(shift);
+            drawing.to.shift(shift);
+        });
+        if let Some(xml) = &self.xml {
+            let tag = if shift.is_row() { "xdr:row" } else { "xdr:col" };
+            self.xml = Some(replace_tag(xml, tag, |index| match index.trim().parse::<u32>() {
+                Ok(index) => (shift.move_index(index + 1) - 1).to_string(),
+                Err(_) => index.to_string(),
+            }));
+        }
     }
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
 struct Drawing {
-    #[serde(rename = "@editAs")]
-    edit_as: String,
+    #[serde(rename = "@editAs", skip_serializing_if = "Option::is_none")]
+    edit_as: Option<String>,
     #[serde(rename(serialize = "xdr:from", deserialize = "from"))]
     from: DrawingLocation,
     #[serde(rename(serialize = "xdr:to", deserialize = "to"))]
     to: DrawingLocation,
-    #[serde(rename(serialize = "xdr:pic", deserialize = "pic"))]
-    pic: Picture,
+    #[serde(rename(serialize = "xdr:pic", deserialize = "pic"), skip_serializing_if = "Option::is_none")]
+    pic: Option<Picture>,
+    #[serde(rename(serialize = "xdr:graphicFrame", deserialize = "graphicFrame"), skip_serializing_if = "Option::is_none")]
+    graphic_frame: Option<GraphicFrame>,
     #[serde(rename(serialize = "xdr:clientData", deserialize = "clientData"))]
     client_data: ClientData,
 }
 
 impl Drawing {
-    fn new<L: LocationRange>(from_to: L, r_id: u32) -> Drawing {
+    fn new<L: LocationRange>(from_to: L, pic: Option<Picture>, graphic_frame: Option<GraphicFrame>) -> Drawing {
         let (from_row, from_col, to_row, to_col) = from_to.to_range();
         Drawing {
-            edit_as: String::from("oneCell"),
+            // pictures keep their size when the cells are resized, charts don't
+            edit_as: pic.as_ref().map(|_| String::from("oneCell")),
             from: DrawingLocation::from_location((from_row, from_col)),
             to: DrawingLocation::from_location((to_row, to_col)),
-            pic: Picture::from_id(r_id),
+            pic,
+            graphic_frame,
             client_data: ClientData::default(),
         }
     }
@@ -91,6 +140,13 @@ impl DrawingLocation {
             row_off: 0,
         }
     }
+
+    fn shift(&mut self, shift: Shift) {
+        match shift.is_row() {
+            true => self.row = shift.move_index(self.row + 1) - 1,
+            false => self.col = shift.move_index(self.col + 1) - 1,
+        }
+    }
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
@@ -104,9 +160,9 @@ struct Picture {
 }
 
 impl Picture {
-    fn from_id(r_id: u32) -> Self {
+    fn from_id(id: u32, r_id: u32) -> Self {
         Self {
-            pic_pr: PicPr::from_id(r_id),
+            pic_pr: PicPr::from_id(id),
             blip_fill: BlipFill::from_id(r_id),
             sp_pr: SpPr::default(),
         }
@@ -147,6 +203,109 @@ impl CNvPr {
     }
 }
 
+#[derive(Debug, Clone, Deserialize, Serialize)]
+struct GraphicFrame {
+    #[serde(rename = "@macro", default)]
+    macro_name: String,
+    #[serde(rename(serialize = "xdr:nvGraphicFramePr", deserialize = "nvGraphicFramePr"))]
+    nv_graphic_frame_pr: NvGraphicFramePr,
+    #[serde(rename(serialize = "xdr:xfrm", deserialize = "xfrm"))]
+    xfrm: Xfrm,
+    #[serde(rename(serialize = "a:graphic", deserialize = "graphic"))]
+    graphic: Graphic,
+}
+
+impl GraphicFrame {
+    fn from_id(id: u32, r_id: u32) -> Self {
+        Self {
+            macro_name: String::new(),
+            nv_graphic_frame_pr: NvGraphicFramePr {
+                c_nv_pr: CNvPr {
+                    id: id.to_string(),
+                    name: format!("Chart {id}"),
+                },
+                c_nv_graphic_frame_pr: Default::default(),
+            },
+            xfrm: Default::default(),
+            graphic: Graphic::from_id(r_id),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+struct NvGraphicFramePr {
+    #[serde(rename(serialize = "xdr:cNvPr", deserialize = "cNvPr"))]
+    c_nv_pr: CNvPr,
+    #[serde(rename(serialize = "xdr:cNvGraphicFramePr", deserialize = "cNvGraphicFramePr"))]
+    c_nv_graphic_frame_pr: CNvGraphicFramePr,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, Default)]
+struct CNvGraphicFramePr {}
+
+#[derive(Debug, Clone, Deserialize, Serialize, Default)]
+struct Xfrm {
+    #[serde(rename(serialize = "a:off", deserialize = "off"))]
+    off: Off,
+    #[serde(rename(serialize = "a:ext", deserialize = "ext"))]
+    ext: Ext,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, Default)]
+struct Off {
+    #[serde(rename = "@x")]
+    x: i64,
+    #[serde(rename = "@y")]
+    y: i64,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, Default)]
+struct Ext {
+    #[serde(rename = "@cx")]
+    cx: i64,
+    #[serde(rename = "@cy")]
+    cy: i64,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+struct Graphic {
+    #[serde(rename(serialize = "a:graphicData", deserialize = "graphicData"))]
+    graphic_data: GraphicData,
+}
+
+impl Graphic {
+    fn from_id(r_id: u32) -> Self {
+        Self {
+            graphic_data: GraphicData {
+                uri: "http://schemas.openxmlformats.org/drawingml/2006/chart".to_string(),
+                chart: ChartReference {
+                    xmlns_c: "http://schemas.openxmlformats.org/drawingml/2006/chart".to_string(),
+                    xmlns_r: "http://schemas.openxmlformats.org/officeDocument/2006/relationships".to_string(),
+                    r_id: Rel::from_id(r_id),
+                },
+            },
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+struct GraphicData {
+    #[serde(rename = "@uri")]
+    uri: String,
+    #[serde(rename(serialize = "c:chart", deserialize = "chart"))]
+    chart: ChartReference,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+struct ChartReference {
+    #[serde(rename = "@xmlns:c", default)]
+    xmlns_c: String,
+    #[serde(rename = "@xmlns:r", default)]
+    xmlns_r: String,
+    #[serde(rename(serialize = "@r:id", deserialize = "@id"))]
+    r_id: Rel,
+}
+
 #[derive(Debug, Clone, Deserialize, Serialize, Default)]
 struct CNvPicPr {
     #[serde(rename(serialize = "a:picLocks", deserialize = "picLocks"))]
@@ -249,18 +408,20 @@ struct AvLst {}
 struct ClientData {}
 
 impl Drawings {
-    pub(crate) fn from_path<P: AsRef<Path>>(file_path: P, drawing_id: u32) -> io::Result<Drawings> {
-        let mut file = XlsxFileReader::from_path(file_path, XlsxFileType::Drawings(drawing_id))?;
-        let mut xml = String::new();
-        file.read_to_string(&mut xml).unwrap();
-        let drawings: Drawings = de::from_str(&xml).unwrap();
-        Ok(drawings)
-    }
-
-    pub(crate) fn save<P: AsRef<Path>>(& self, file_path: P, drawing_id: u32) {
-        let xml = se::to_string_with_root("xdr:wsDr", &self).unwrap();
-        let xml = format!("<?xml version=\"1.0\" encoding=\"UTF-8\" standalone=\"yes\"?>\n{}", xml);
-        let mut file = XlsxFileWriter::from_path(file_path, XlsxFileType::Drawings(drawing_id)).unwrap();
-        file.write_all(xml.as_ref()).unwrap();
+    pub(crate) fn save(&self, writer: &mut XlsxFileWriter, drawing_id: u32) {
+        let xml = match &self.xml {
+            Some(xml) => {
+                let anchors = self.drawing.iter()
+                    .map(|drawing| se::to_string_with_root("xdr:twoCellAnchor", drawing).unwrap())
+                    .collect::<String>();
+                let end = xml.rfind("</").unwrap_or(xml.len());
+                format!("{}{anchors}{}", &xml[..end], &xml[end..])
+            }
+            None => {
+                let xml = se::to_string_with_root("xdr:wsDr", &self).unwrap();
+                format!("<?xml version=\"1.0\" encoding=\"UTF-8\" standalone=\"yes\"?>\n{}", xml)
+            }
+        };
+        writer.write_all(XlsxFileType::Drawings(drawing_id), xml.as_ref());
     }
 }
\ No newline at end of file
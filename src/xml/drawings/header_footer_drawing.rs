@@ -0,0 +1,161 @@
+use std::io::{Read, Seek};
+use quick_xml::escape::escape;
+use quick_xml::se;
+use zip::ZipArchive;
+use crate::file::{XlsxFileType, XlsxFileWriter};
+use crate::xml::drawings::vml_drawing::{find_shape, id_map_data, max_shape_id};
+use crate::xml::io::IoV2;
+use crate::xml::namespaces::office as o;
+use crate::xml::relationships::Relationships;
+
+// The shape type of the pictures, as written by Excel
+const PICTURE_SHAPE_TYPE: &str = "<v:shapetype id=\"_x0000_t75\" coordsize=\"21600,21600\" o:spt=\"75\" o:preferrelative=\"t\" \
+    path=\"m@4@5l@4@11@9@11@9@5xe\" filled=\"f\" stroked=\"f\"><v:stroke joinstyle=\"miter\"/><v:formulas>\
+    <v:f eqn=\"if lineDrawn pixelLineWidth 0\"/><v:f eqn=\"sum @0 1 0\"/><v:f eqn=\"sum 0 0 @1\"/><v:f eqn=\"prod @2 1 2\"/>\
+    <v:f eqn=\"prod @3 21600 pixelWidth\"/><v:f eqn=\"prod @3 21600 pixelHeight\"/><v:f eqn=\"sum @0 0 1\"/><v:f eqn=\"prod @6 1 2\"/>\
+    <v:f eqn=\"prod @7 21600 pixelWidth\"/><v:f eqn=\"sum @8 21600 0\"/><v:f eqn=\"prod @7 21600 pixelHeight\"/><v:f eqn=\"sum @10 21600 0\"/>\
+    </v:formulas><v:path o:extrusionok=\"f\" gradientshapeok=\"t\" o:connecttype=\"rect\"/><o:lock v:ext=\"edit\" aspectratio=\"t\"/></v:shapetype>";
+
+///
+/// The legacy drawing holding the pictures of the headers and the footers of a worksheet.
+/// Each picture is a shape named after its place, like `LH` for the left of the header or `CFFIRST` for the center of the first footer.
+///
+#[derive(Debug, Clone)]
+pub(crate) struct HeaderFooterDrawing {
+    pub(crate) id: u32,
+    // The drawing without the pictures
+    xml: String,
+    pictures: Vec<Picture>,
+    rel: Relationships,
+    next_shape_id: u32,
+    changed: bool,
+}
+
+#[derive(Debug, Clone)]
+struct Picture {
+    position: String,
+    r_id: Option<u32>,
+    xml: String,
+}
+
+impl Picture {
+    fn from_xml(xml: &str) -> Picture {
+        let attribute = |name: &str| {
+            let start = xml.find(&format!(" {name}=\""))? + name.len() + 3;
+            xml[start..].split_once('"').map(|(value, _)| value.to_string())
+        };
+        Picture {
+            position: attribute("id").unwrap_or_default(),
+            r_id: attribute("o:relid").and_then(|r_id| r_id.trim_start_matches("rId").parse().ok()),
+            xml: xml.to_string(),
+        }
+    }
+}
+
+impl HeaderFooterDrawing {
+    pub(crate) fn new(id: u32) -> HeaderFooterDrawing {
+        let shape_layout = se::to_string_with_root("o:shapelayout", &o::ShapeLayout::new(id)).unwrap();
+        let xml = format!(
+            "<xml xmlns:v=\"urn:schemas-microsoft-com:vml\" xmlns:o=\"urn:schemas-microsoft-com:office:office\" xmlns:x=\"urn:schemas-microsoft-com:office:excel\">{shape_layout}{PICTURE_SHAPE_TYPE}</xml>"
+        );
+        HeaderFooterDrawing {
+            id,
+            xml,
+            pictures: vec![],
+            rel: Relationships::default(),
+            next_shape_id: 1024 * id + 1,
+            changed: true,
+        }
+    }
+
+    pub(crate) fn from_archive<R: Read + Seek>(archive: &mut ZipArchive<R>, id: u32) -> Option<HeaderFooterDrawing> {
+        let mut file = archive.by_name(&format!("xl/drawings/vmlDrawing{id}.vml")).ok()?;
+        let mut xml = String::new();
+        file.read_to_string(&mut xml).ok()?;
+        drop(file);
+        let rel = Relationships::from_zip_file(archive, &format!("xl/drawings/_rels/vmlDrawing{id}.vml.rels")).unwrap_or_default();
+        let next_shape_id = 1 + max_shape_id(&xml).max(1024 * id_map_data(&xml).unwrap_or(id));
+        let mut kept = String::with_capacity(xml.len());
+        let mut pictures = vec![];
+        let mut rest = xml.as_str();
+        while let Some(start) = find_shape(rest) {
+            kept.push_str(&rest[..start]);
+            let tail = &rest[start..];
+            let end = tail.find("</v:shape>").map_or(tail.len(), |end| end + "</v:shape>".len());
+            pictures.push(Picture::from_xml(&tail[..end]));
+            rest = &tail[end..];
+        }
+        kept.push_str(rest);
+        if !kept.contains("\"_x0000_t75\"") {
+            let end = kept.rfind("</xml>").unwrap_or(kept.len());
+            kept.insert_str(end, PICTURE_SHAPE_TYPE);
+        }
+        Some(HeaderFooterDrawing {
+            id,
+            xml: kept,
+            pictures,
+            rel,
+            next_shape_id,
+            changed: false,
+        })
+    }
+
+    ///
+    /// Show an image at a place of a header or a footer, the image already there is replaced.
+    /// The image is added to the relationships of the drawing, and its size is given in points.
+    ///
+    pub(crate) fn set_picture(&mut self, position: &str, image_id: u32, extension: &str, title: &str, width: f64, height: f64) {
+        self.remove_picture(position);
+        let r_id = self.rel.add_image(image_id, extension);
+        let xml = format!(
+            "<v:shape id=\"{position}\" o:spid=\"_x0000_s{}\" type=\"#_x0000_t75\" \
+            style='position:absolute;margin-left:0;margin-top:0;width:{width}pt;height:{height}pt;z-index:{}'>\
+            <v:imagedata o:relid=\"rId{r_id}\" o:title=\"{}\"/><o:lock v:ext=\"edit\" rotation=\"t\"/></v:shape>",
+            self.next_shape_id,
+            self.pictures.len() + 1,
+            escape(title),
+        );
+        self.next_shape_id += 1;
+        self.pictures.push(Picture { position: position.to_string(), r_id: Some(r_id), xml });
+        self.changed = true;
+    }
+
+    pub(crate) fn remove_picture(&mut self, position: &str) {
+        let rel = &mut self.rel;
+        let count = self.pictures.len();
+        self.pictures.retain(|picture| {
+            if picture.position != position {
+                return true;
+            }
+            if let Some(r_id) = picture.r_id {
+                rel.remove(r_id);
+            }
+            false
+        });
+        self.changed |= self.pictures.len() != count;
+    }
+
+    ///
+    /// A copy of the drawing, saved in the file of the id
+    ///
+    pub(crate) fn copy(&self, id: u32) -> HeaderFooterDrawing {
+        HeaderFooterDrawing {
+            id,
+            changed: true,
+            ..self.clone()
+        }
+    }
+
+    pub(crate) fn save(&self, writer: &mut XlsxFileWriter) {
+        // an unchanged drawing is copied from the source
+        if !self.changed {
+            return;
+        }
+        let end = self.xml.rfind("</xml>").unwrap_or(self.xml.len());
+        let mut xml = self.xml[..end].to_string();
+        self.pictures.iter().for_each(|picture| xml.push_str(&picture.xml));
+        xml.push_str(&self.xml[end..]);
+        writer.write_all(XlsxFileType::VmlDrawings(self.id), xml.as_ref());
+        self.rel.save(writer, XlsxFileType::VmlDrawingRels(self.id));
+    }
+}
@@ -1,40 +1,288 @@
-use std::io;
-use std::path::Path;
+use std::io::{Read, Seek};
 use quick_xml::{de, se};
-use serde::{Deserialize, Serialize};
-use crate::file::{XlsxFileReader, XlsxFileType, XlsxFileWriter};
+use zip::ZipArchive;
+use crate::api::cell::location::Location;
+use crate::file::{XlsxFileType, XlsxFileWriter};
+use crate::utils::reference_helper::Shift;
+use crate::xml::namespaces::excel as x;
 use crate::xml::namespaces::office as o;
 use crate::xml::namespaces::vml as v;
 
-#[derive(Debug, Deserialize, Serialize)]
+// Default sizes of the cells in pixels, used to anchor new notes
+const COLUMN_WIDTH: u32 = 64;
+const ROW_HEIGHT: u32 = 20;
+
+///
+/// A legacy drawing of a worksheet.
+/// Only the shapes of notes are managed, the rest of the drawing is kept as it is loaded.
+///
+#[derive(Debug, Clone)]
 pub(crate) struct VmlDrawing {
-    #[serde(rename(serialize = "@xmlns:v", deserialize = "@xmlns:v"))]
-    xmlns_v: String,
-    #[serde(rename(serialize = "@xmlns:o", deserialize = "@xmlns:o"))]
-    xmlns_o: String,
-    #[serde(rename(serialize = "@xmlns:x", deserialize = "@xmlns:x"))]
-    xmlns_x: String,
-    #[serde(rename(serialize = "o:shapelayout", deserialize = "shapelayout"))]
-    shapelayout: o::ShapeLayout,
-    #[serde(rename(serialize = "v:shapetype", deserialize = "shapetype"))]
-    shapetype: v::ShapeType,
-    #[serde(rename(serialize = "v:shape", deserialize = "shape"))]
-    shape: v::Shape,
+    pub(crate) id: u32,
+    // The drawing without the shapes of notes
+    xml: String,
+    notes: Vec<Note>,
+    next_shape_id: u32,
+    changed: bool,
+}
+
+#[derive(Debug, Clone)]
+struct Note {
+    row: u32,
+    col: u32,
+    xml: String,
+}
+
+impl Note {
+    fn from_xml(xml: &str) -> Option<Note> {
+        if !xml.contains("ObjectType=\"Note\"") {
+            return None;
+        }
+        let shape: v::Shape = de::from_str(xml).ok()?;
+        Some(Note {
+            row: shape.clientdata.row.value + 1,
+            col: shape.clientdata.column.value + 1,
+            xml: xml.to_string(),
+        })
+    }
+
+    ///
+    /// Move the note with its cell, None if the cell is deleted
+    ///
+    fn shift(mut self, shift: Shift) -> Option<Note> {
+        let (row, col) = shift.shift_location((self.row, self.col))?;
+        let (index_tag, anchor_indexes) = match shift.is_row() {
+            true => ("x:Row", [2, 6]),
+            false => ("x:Column", [0, 4]),
+        };
+        self.xml = replace_tag(&self.xml, index_tag, |index| match index.trim().parse::<u32>() {
+            Ok(index) => (shift.move_index(index + 1) - 1).to_string(),
+            Err(_) => index.to_string(),
+        });
+        self.xml = replace_tag(&self.xml, "x:Anchor", |anchor| {
+            let mut values: Vec<String> = anchor.split(',').map(|value| value.trim().to_string()).collect();
+            for i in anchor_indexes {
+                if let Some(index) = values.get(i).and_then(|value| value.parse::<u32>().ok()) {
+                    values[i] = (shift.move_index(index + 1) - 1).to_string();
+                }
+            }
+            values.join(", ")
+        });
+        self.row = row;
+        self.col = col;
+        Some(self)
+    }
+
+    ///
+    /// The visibility and the size in points of the note
+    ///
+    fn get_shape(&self) -> Option<(bool, f64, f64)> {
+        let shape: v::Shape = de::from_str(&self.xml).ok()?;
+        let style_value = |key: &str| shape.style.split(';')
+            .filter_map(|style| style.split_once(':'))
+            .find(|(k, _)| k.trim() == key)
+            .map(|(_, value)| value.trim().to_string());
+        let size = |key: &str| style_value(key)
+            .and_then(|value| value.trim_end_matches("pt").parse().ok())
+            .unwrap_or_default();
+        let visible = shape.clientdata.visible.is_some()
+            || style_value("visibility").as_deref() == Some("visible");
+        Some((visible, size("width"), size("height")))
+    }
 }
 
 impl VmlDrawing {
-    pub(crate) fn from_path<P: AsRef<Path>>(file_path: P, drawing_id: u32) -> io::Result<VmlDrawing> {
-        let mut file = XlsxFileReader::from_path(file_path, XlsxFileType::VmlDrawings(drawing_id))?;
+    pub(crate) fn new(id: u32) -> VmlDrawing {
+        let shape_layout = se::to_string_with_root("o:shapelayout", &o::ShapeLayout::new(id)).unwrap();
+        let xml = format!(
+            "<xml xmlns:v=\"urn:schemas-microsoft-com:vml\" xmlns:o=\"urn:schemas-microsoft-com:office:office\" xmlns:x=\"urn:schemas-microsoft-com:office:excel\">{shape_layout}</xml>"
+        );
+        VmlDrawing {
+            id,
+            xml,
+            notes: vec![],
+            next_shape_id: 1024 * id + 1,
+            changed: true,
+        }
+    }
+
+    pub(crate) fn from_archive<R: Read + Seek>(archive: &mut ZipArchive<R>, id: u32) -> Option<VmlDrawing> {
+        let mut file = archive.by_name(&format!("xl/drawings/vmlDrawing{id}.vml")).ok()?;
         let mut xml = String::new();
-        file.read_to_string(&mut xml).unwrap();
-        let drawing: VmlDrawing = de::from_str(&xml).unwrap();
-        Ok(drawing)
+        file.read_to_string(&mut xml).ok()?;
+        let next_shape_id = 1 + max_shape_id(&xml).max(1024 * id_map_data(&xml).unwrap_or(id));
+        let (xml, notes) = split_notes(&xml);
+        Some(VmlDrawing {
+            id,
+            xml,
+            notes,
+            next_shape_id,
+            changed: false,
+        })
+    }
+
+    ///
+    /// The visibility and the size in points of the note of a cell
+    ///
+    pub(crate) fn get_note<L: Location>(&self, loc: &L) -> Option<(bool, f64, f64)> {
+        let (row, col) = loc.to_location();
+        self.notes.iter()
+            .find(|note| note.row == row && note.col == col)
+            .and_then(|note| note.get_shape())
+    }
+
+    ///
+    /// Add a note to a cell, an existing note of the cell is replaced
+    ///
+    pub(crate) fn set_note<L: Location>(&mut self, loc: &L, visible: bool, width: f64, height: f64) {
+        if !self.xml.contains("\"_x0000_t202\"") {
+            let shape_type = se::to_string_with_root("v:shapetype", &v::ShapeType::text_box()).unwrap();
+            let end = self.xml.rfind("</xml>").unwrap_or(self.xml.len());
+            self.xml.insert_str(end, &shape_type);
+        }
+        let (row, col) = loc.to_location();
+        let xml = self.note_xml(row, col, visible, width, height);
+        match self.notes.iter_mut().find(|note| note.row == row && note.col == col) {
+            Some(note) => note.xml = xml,
+            None => self.notes.push(Note { row, col, xml }),
+        }
+        self.changed = true;
+    }
+
+    fn note_xml(&mut self, row: u32, col: u32, visible: bool, width: f64, height: f64) -> String {
+        // the note is shown at the right of the cell
+        let (left_col, left_offset) = (col, 15);
+        let (top_row, top_offset) = (row.saturating_sub(2), 10);
+        let right = left_offset + (width * 4.0 / 3.0).round() as u32;
+        let bottom = top_offset + (height * 4.0 / 3.0).round() as u32;
+        let anchor = [
+            left_col, left_offset, top_row, top_offset,
+            left_col + right / COLUMN_WIDTH, right % COLUMN_WIDTH,
+            top_row + bottom / ROW_HEIGHT, bottom % ROW_HEIGHT,
+        ];
+        let style = format!(
+            "position:absolute;margin-left:{}pt;margin-top:{}pt;width:{width}pt;height:{height}pt;z-index:{};visibility:{}",
+            (left_col * COLUMN_WIDTH + left_offset) as f64 * 0.75,
+            (top_row * ROW_HEIGHT + top_offset) as f64 * 0.75,
+            self.notes.len() + 1,
+            if visible { "visible" } else { "hidden" },
+        );
+        let clientdata = x::ClientData::note(row - 1, col - 1, anchor, visible);
+        let shape = v::Shape::note(self.next_shape_id, &style, clientdata);
+        self.next_shape_id += 1;
+        se::to_string_with_root("v:shape", &shape).unwrap()
+    }
+
+    ///
+    /// Move the notes with their cells, the ones of deleted cells are removed
+    ///
+    pub(crate) fn shift(&mut self, shift: Shift) {
+        self.notes = self.notes.drain(..)
+            .filter_map(|note| note.shift(shift))
+            .collect();
+        self.changed = true;
+    }
+
+    ///
+    /// A copy of the drawing, saved in the file of the id
+    ///
+    pub(crate) fn copy(&self, id: u32) -> VmlDrawing {
+        VmlDrawing {
+            id,
+            changed: true,
+            ..self.clone()
+        }
+    }
+
+    pub(crate) fn save(&self, writer: &mut XlsxFileWriter) {
+        // an unchanged drawing is copied from the source
+        if !self.changed {
+            return;
+        }
+        let end = self.xml.rfind("</xml>").unwrap_or(self.xml.len());
+        let mut xml = self.xml[..end].to_string();
+        self.notes.iter().for_each(|note| xml.push_str(&note.xml));
+        xml.push_str(&self.xml[end..]);
+        writer.write_all(XlsxFileType::VmlDrawings(self.id), xml.as_ref());
     }
+}
+
+///
+/// Take the shapes of notes out of the drawing
+///
+fn split_notes(xml: &str) -> (String, Vec<Note>) {
+    let mut kept = String::with_capacity(xml.len());
+    let mut notes = vec![];
+    let mut rest = xml;
+    while let Some(start) = find_shape(rest) {
+        kept.push_str(&rest[..start]);
+        let tail = &rest[start..];
+        let tag_end = tail.find('>').map_or(tail.len(), |tag_end| tag_end + 1);
+        let end = if tail[..tag_end].ends_with("/>") {
+            tag_end
+        } else {
+            tail.find("</v:shape>").map_or(tail.len(), |end| end + "</v:shape>".len())
+        };
+        let (shape, remaining) = tail.split_at(end);
+        match Note::from_xml(shape) {
+            Some(note) => notes.push(note),
+            None => kept.push_str(shape),
+        }
+        rest = remaining;
+    }
+    kept.push_str(rest);
+    (kept, notes)
+}
+
+///
+/// Replace the text of the elements of a tag
+///
+pub(crate) fn replace_tag<F: Fn(&str) -> String>(xml: &str, tag: &str, f: F) -> String {
+    let (start_tag, end_tag) = (format!("<{tag}>"), format!("</{tag}>"));
+    let mut parts = xml.split(&start_tag);
+    let mut replaced = parts.next().unwrap_or_default().to_string();
+    for part in parts {
+        replaced.push_str(&start_tag);
+        match part.split_once(&end_tag) {
+            Some((text, rest)) => replaced.push_str(&format!("{}{end_tag}{rest}", f(text))),
+            None => replaced.push_str(part),
+        }
+    }
+    replaced
+}
+
+pub(crate) fn find_shape(xml: &str) -> Option<usize> {
+    xml.match_indices("<v:shape")
+        .find(|(start, tag)| xml[start + tag.len()..].starts_with(char::is_whitespace))
+        .map(|(start, _)| start)
+}
+
+pub(crate) fn max_shape_id(xml: &str) -> u32 {
+    xml.split("_x0000_s")
+        .skip(1)
+        .filter_map(|id| id.chars().take_while(char::is_ascii_digit).collect::<String>().parse().ok())
+        .max()
+        .unwrap_or_default()
+}
+
+pub(crate) fn id_map_data(xml: &str) -> Option<u32> {
+    let id_map = &xml[xml.find("<o:idmap")?..];
+    let data = &id_map[id_map.find("data=")? + "data=".len() + 1..];
+    data.chars().take_while(char::is_ascii_digit).collect::<String>().parse().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::xml::drawings::vml_drawing::split_notes;
 
-    pub(crate) fn save<P: AsRef<Path>>(&self, file_path: P, drawing_id: u32) {
-        let xml = se::to_string_with_root("xml", &self).unwrap();
-        // let xml = format!("<?xml version=\"1.0\" encoding=\"UTF-8\" standalone=\"yes\"?>\n{}", xml);
-        let mut file = XlsxFileWriter::from_path(file_path, XlsxFileType::VmlDrawings(drawing_id)).unwrap();
-        file.write_all(xml.as_ref()).unwrap();
+    #[test]
+    fn test_split_notes() {
+        let xml = "<xml><v:shapetype id=\"_x0000_t202\"/><v:shape id=\"_x0000_s1025\" style='width:96pt;height:55.5pt;visibility:hidden'>\
+            <x:ClientData ObjectType=\"Note\"><x:Row>3</x:Row><x:Column>1</x:Column></x:ClientData></v:shape>\
+            <v:shape id=\"_x0000_s1026\"><x:ClientData ObjectType=\"Button\"/></v:shape></xml>";
+        let (kept, notes) = split_notes(xml);
+        assert_eq!(kept, "<xml><v:shapetype id=\"_x0000_t202\"/><v:shape id=\"_x0000_s1026\"><x:ClientData ObjectType=\"Button\"/></v:shape></xml>");
+        assert_eq!((notes[0].row, notes[0].col), (4, 2));
+        assert_eq!(notes[0].get_shape(), Some((false, 96.0, 55.5)));
     }
 }
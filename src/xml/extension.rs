@@ -2,11 +2,13 @@ mod xda_dynamic_array_properties;
 mod x15_workbook_pr;
 mod x14_slicer_styles;
 mod x15_timeline_styles;
+pub(crate) mod x14_conditional_formattings;
 
 use std::collections::HashSet;
 use std::hash::{Hash, Hasher};
 use serde::{Deserialize, Serialize};
 use crate::xml::common::XmlnsAttrs;
+use crate::xml::extension::x14_conditional_formattings::{X14ConditionalFormatting, X14ConditionalFormattings};
 use crate::xml::extension::x14_slicer_styles::X14SlicerStyles;
 use crate::xml::extension::x15_timeline_styles::X15TimelineStyles;
 use crate::xml::extension::x15_workbook_pr::X15WorkbookPr;
@@ -33,8 +35,67 @@ impl ExtensionList {
     pub(crate) fn is_empty(&self) -> bool {
         self.ext.is_empty()
     }
+
+    ///
+    /// The extension list of a conditional formatting rule, linking it to its x14 rule
+    ///
+    pub(crate) fn from_x14_id(id: &str) -> ExtensionList {
+        let mut extension = Extension::new_x14_id();
+        extension.x14_id = Some(id.to_string());
+        ExtensionList { ext: HashSet::from([extension]) }
+    }
+
+    pub(crate) fn get_x14_id(&self) -> Option<&str> {
+        self.ext.iter().find_map(|extension| extension.x14_id.as_deref())
+    }
+
+    pub(crate) fn add_x14_conditional_formatting(&mut self, conditional_formatting: X14ConditionalFormatting) {
+        let mut extension = self.ext.take(&Extension::new_x14_conditional_formattings())
+            .unwrap_or(Extension::new_x14_conditional_formattings());
+        extension.x14_conditional_formattings
+            .get_or_insert(Default::default())
+            .conditional_formatting
+            .push(conditional_formatting);
+        self.ext.insert(extension);
+    }
+
+    pub(crate) fn get_x14_conditional_formattings(&self) -> Vec<&X14ConditionalFormatting> {
+        self.ext.iter()
+            .filter_map(|extension| extension.x14_conditional_formattings.as_ref())
+            .flat_map(|conditional_formattings| &conditional_formattings.conditional_formatting)
+            .collect()
+    }
+
+    ///
+    /// Keep the x14 conditional formattings for which the function is true, after it changed them
+    ///
+    pub(crate) fn retain_x14_conditional_formattings<F: FnMut(&mut X14ConditionalFormatting) -> bool>(&mut self, f: F) {
+        if let Some(mut extension) = self.ext.take(&Extension::new_x14_conditional_formattings()) {
+            if let Some(conditional_formattings) = &mut extension.x14_conditional_formattings {
+                conditional_formattings.conditional_formatting.retain_mut(f);
+                if conditional_formattings.conditional_formatting.is_empty() {
+                    return;
+                }
+            }
+            self.ext.insert(extension);
+        }
+    }
+
+    ///
+    /// Drop the extensions whose content is not read, they would be written empty
+    ///
+    pub(crate) fn retain_known(&mut self) {
+        self.ext.retain(|extension| extension.x15_workbook_pr.is_some()
+            || extension.x14_slicer_styles.is_some()
+            || extension.x15_timeline_styles.is_some()
+            || extension.xda_dynamic_array_properties.is_some()
+            || extension.x14_conditional_formattings.is_some()
+            || extension.x14_id.is_some());
+    }
 }
 
+const XMLNS_X14: &str = "http://schemas.microsoft.com/office/spreadsheetml/2009/9/main";
+
 impl AddExtension for ExtensionList {
     fn add_extension(&mut self, e: ExtensionType) {
         self.ext.insert(Extension::from_extension_type(e));
@@ -57,6 +118,10 @@ struct Extension {
     x15_timeline_styles: Option<X15TimelineStyles>,
     #[serde(rename(serialize = "xda:dynamicArrayProperties", deserialize = "dynamicArrayProperties"), skip_serializing_if = "Option::is_none")]
     xda_dynamic_array_properties: Option<XdaDynamicArrayProperties>,
+    #[serde(rename(serialize = "x14:conditionalFormattings", deserialize = "conditionalFormattings"), skip_serializing_if = "Option::is_none")]
+    x14_conditional_formattings: Option<X14ConditionalFormattings>,
+    #[serde(rename(serialize = "x14:id", deserialize = "id"), skip_serializing_if = "Option::is_none")]
+    x14_id: Option<String>,
 }
 
 impl PartialEq for Extension {
@@ -92,6 +157,8 @@ impl Extension {
             x14_slicer_styles: None,
             x15_timeline_styles: None,
             xda_dynamic_array_properties: None,
+            x14_conditional_formattings: None,
+            x14_id: None,
         }
     }
 
@@ -104,6 +171,8 @@ impl Extension {
             x14_slicer_styles: Some(Default::default()),
             x15_timeline_styles: None,
             xda_dynamic_array_properties: None,
+            x14_conditional_formattings: None,
+            x14_id: None,
         }
     }
 
@@ -116,6 +185,8 @@ impl Extension {
             x14_slicer_styles: None,
             x15_timeline_styles: Some(Default::default()),
             xda_dynamic_array_properties: None,
+            x14_conditional_formattings: None,
+            x14_id: None,
         }
     }
 
@@ -128,6 +199,36 @@ impl Extension {
             x14_slicer_styles: None,
             x15_timeline_styles: None,
             xda_dynamic_array_properties: Some(Default::default()),
+            x14_conditional_formattings: None,
+            x14_id: None,
+        }
+    }
+
+    fn new_x14_conditional_formattings() -> Self {
+        Self {
+            uri: "{78C0D931-6437-407d-A8EE-F0AAD7539E65}".to_string(),
+            xmlns_x14: Some(XMLNS_X14.to_string()),
+            xmlns_x15: None,
+            x15_workbook_pr: None,
+            x14_slicer_styles: None,
+            x15_timeline_styles: None,
+            xda_dynamic_array_properties: None,
+            x14_conditional_formattings: Some(Default::default()),
+            x14_id: None,
+        }
+    }
+
+    fn new_x14_id() -> Self {
+        Self {
+            uri: "{B025F937-C7B1-47D3-B67F-A62EFF666E3E}".to_string(),
+            xmlns_x14: Some(XMLNS_X14.to_string()),
+            xmlns_x15: None,
+            x15_workbook_pr: None,
+            x14_slicer_styles: None,
+            x15_timeline_styles: None,
+            xda_dynamic_array_properties: None,
+            x14_conditional_formattings: None,
+            x14_id: None,
         }
     }
 }
\ No newline at end of file
@@ -0,0 +1,239 @@
+use serde::{Deserialize, Serialize};
+use crate::api::conditional_format::{ConditionalFormatRule, ConditionalFormatValue, DataBar, DataBarAxisPosition, IconSet, IconSetType};
+use crate::xml::common::FromFormat;
+use crate::xml::style::color::Color;
+
+#[derive(Debug, Clone, Deserialize, Serialize, Default)]
+pub(crate) struct X14ConditionalFormattings {
+    #[serde(rename(serialize = "x14:conditionalFormatting", deserialize = "conditionalFormatting"), default)]
+    pub(crate) conditional_formatting: Vec<X14ConditionalFormatting>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub(crate) struct X14ConditionalFormatting {
+    #[serde(rename = "@xmlns:xm", default = "xmlns_xm")]
+    xmlns_xm: String,
+    #[serde(rename(serialize = "x14:cfRule", deserialize = "cfRule"), default)]
+    pub(crate) cf_rule: Vec<X14CfRule>,
+    #[serde(rename(serialize = "xm:sqref", deserialize = "sqref"), default)]
+    pub(crate) sqref: String,
+}
+
+fn xmlns_xm() -> String {
+    "http://schemas.microsoft.com/office/excel/2006/main".to_string()
+}
+
+impl X14ConditionalFormatting {
+    pub(crate) fn new(sqref: &str, cf_rule: X14CfRule) -> X14ConditionalFormatting {
+        X14ConditionalFormatting {
+            xmlns_xm: xmlns_xm(),
+            cf_rule: vec![cf_rule],
+            sqref: sqref.to_string(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, Default)]
+pub(crate) struct X14CfRule {
+    #[serde(rename = "@type", default, skip_serializing_if = "Option::is_none")]
+    pub(crate) tp: Option<String>,
+    #[serde(rename = "@priority", default, skip_serializing_if = "Option::is_none")]
+    pub(crate) priority: Option<u32>,
+    #[serde(rename = "@id", default, skip_serializing_if = "Option::is_none")]
+    pub(crate) id: Option<String>,
+    #[serde(rename(serialize = "x14:dataBar", deserialize = "dataBar"), default, skip_serializing_if = "Option::is_none")]
+    pub(crate) data_bar: Option<X14DataBar>,
+    #[serde(rename(serialize = "x14:iconSet", deserialize = "iconSet"), default, skip_serializing_if = "Option::is_none")]
+    pub(crate) icon_set: Option<X14IconSet>,
+}
+
+impl X14CfRule {
+    ///
+    /// The part of a rule only known by Excel 2010 and later, None if the rule has no such part
+    ///
+    pub(crate) fn from_api_rule(rule: &ConditionalFormatRule) -> Option<X14CfRule> {
+        let x14_cf_rule = match rule {
+            ConditionalFormatRule::DataBar(data_bar) => X14CfRule {
+                tp: Some("dataBar".to_string()),
+                data_bar: Some(X14DataBar::from_api_data_bar(data_bar)),
+                ..Default::default()
+            },
+            ConditionalFormatRule::IconSet(icon_set) if icon_set.icon_set_type.is_x14() => X14CfRule {
+                tp: Some("iconSet".to_string()),
+                icon_set: Some(X14IconSet::from_api_icon_set(icon_set)),
+                ..Default::default()
+            },
+            _ => return None,
+        };
+        Some(x14_cf_rule)
+    }
+
+    ///
+    /// The rule of a rule only written in the extension list, None for the kinds of rules not supported
+    ///
+    pub(crate) fn to_api_rule(&self) -> Option<ConditionalFormatRule> {
+        match self.tp.as_deref()? {
+            "iconSet" => Some(ConditionalFormatRule::IconSet(self.icon_set.as_ref()?.to_api_icon_set())),
+            _ => None,
+        }
+    }
+
+    ///
+    /// Complete a data bar read from the rule linked to this one
+    ///
+    pub(crate) fn update_data_bar(&self, data_bar: &mut DataBar) {
+        if let Some(x14_data_bar) = &self.data_bar {
+            x14_data_bar.update_data_bar(data_bar);
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, Default)]
+pub(crate) struct X14DataBar {
+    #[serde(rename = "@minLength", default, skip_serializing_if = "Option::is_none")]
+    min_length: Option<u32>,
+    #[serde(rename = "@maxLength", default, skip_serializing_if = "Option::is_none")]
+    max_length: Option<u32>,
+    #[serde(rename = "@gradient", default, skip_serializing_if = "Option::is_none")]
+    gradient: Option<u8>,
+    #[serde(rename = "@border", default, skip_serializing_if = "Option::is_none")]
+    border: Option<u8>,
+    #[serde(rename = "@negativeBarColorSameAsPositive", default, skip_serializing_if = "Option::is_none")]
+    negative_bar_color_same_as_positive: Option<u8>,
+    #[serde(rename = "@negativeBarBorderColorSameAsPositive", default, skip_serializing_if = "Option::is_none")]
+    negative_bar_border_color_same_as_positive: Option<u8>,
+    #[serde(rename = "@axisPosition", default, skip_serializing_if = "Option::is_none")]
+    axis_position: Option<String>,
+    #[serde(rename(serialize = "x14:cfvo", deserialize = "cfvo"), default)]
+    cfvo: Vec<X14Cfvo>,
+    #[serde(rename(serialize = "x14:borderColor", deserialize = "borderColor"), default, skip_serializing_if = "Option::is_none")]
+    border_color: Option<Color>,
+    #[serde(rename(serialize = "x14:negativeFillColor", deserialize = "negativeFillColor"), default, skip_serializing_if = "Option::is_none")]
+    negative_fill_color: Option<Color>,
+    #[serde(rename(serialize = "x14:negativeBorderColor", deserialize = "negativeBorderColor"), default, skip_serializing_if = "Option::is_none")]
+    negative_border_color: Option<Color>,
+    #[serde(rename(serialize = "x14:axisColor", deserialize = "axisColor"), default, skip_serializing_if = "Option::is_none")]
+    axis_color: Option<Color>,
+}
+
+impl X14DataBar {
+    fn from_api_data_bar(data_bar: &DataBar) -> X14DataBar {
+        let border = data_bar.border_color.is_some();
+        X14DataBar {
+            min_length: Some(0),
+            max_length: Some(100),
+            gradient: if data_bar.solid_fill { Some(0) } else { None },
+            border: if border { Some(1) } else { None },
+            negative_bar_color_same_as_positive: if data_bar.negative_color.is_none() { Some(1) } else { None },
+            negative_bar_border_color_same_as_positive: if border && data_bar.negative_border_color.is_some() { Some(0) } else { None },
+            axis_position: match data_bar.axis_position {
+                DataBarAxisPosition::Automatic => None,
+                axis_position => Some(axis_position.to_name().to_string()),
+            },
+            cfvo: vec![X14Cfvo::from_api_value(&data_bar.min), X14Cfvo::from_api_value(&data_bar.max)],
+            border_color: data_bar.border_color.as_ref().map(Color::from_format),
+            negative_fill_color: data_bar.negative_color.as_ref().map(Color::from_format),
+            negative_border_color: match border {
+                true => data_bar.negative_border_color.as_ref().map(Color::from_format),
+                false => None,
+            },
+            axis_color: Some(Color::from_format(&data_bar.axis_color)),
+        }
+    }
+
+    fn update_data_bar(&self, data_bar: &mut DataBar) {
+        if let (Some(min), Some(max)) = (self.cfvo.first(), self.cfvo.get(1)) {
+            data_bar.min = min.to_api_value();
+            data_bar.max = max.to_api_value();
+        }
+        data_bar.solid_fill = self.gradient == Some(0);
+        data_bar.border_color = match self.border {
+            Some(1) => self.border_color.as_ref().map(Color::get_format),
+            _ => None,
+        };
+        data_bar.negative_color = match self.negative_bar_color_same_as_positive {
+            Some(1) => None,
+            _ => self.negative_fill_color.as_ref().map(Color::get_format),
+        };
+        data_bar.negative_border_color = match (self.border, self.negative_bar_border_color_same_as_positive) {
+            (Some(1), Some(0)) => self.negative_border_color.as_ref().map(Color::get_format),
+            _ => None,
+        };
+        data_bar.axis_position = DataBarAxisPosition::from_name(self.axis_position.as_deref().unwrap_or_default());
+        if let Some(axis_color) = &self.axis_color {
+            data_bar.axis_color = axis_color.get_format();
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, Default)]
+pub(crate) struct X14Cfvo {
+    #[serde(rename = "@type")]
+    tp: String,
+    #[serde(rename = "@gte", default, skip_serializing_if = "Option::is_none")]
+    gte: Option<u8>,
+    #[serde(rename(serialize = "xm:f", deserialize = "f"), default, skip_serializing_if = "Option::is_none")]
+    f: Option<String>,
+}
+
+impl X14Cfvo {
+    fn from_api_value(value: &ConditionalFormatValue) -> X14Cfvo {
+        let (tp, f) = value.to_cfvo();
+        X14Cfvo { tp: tp.to_string(), gte: None, f }
+    }
+
+    fn to_api_value(&self) -> ConditionalFormatValue {
+        ConditionalFormatValue::from_cfvo(&self.tp, self.f.as_deref())
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, Default)]
+pub(crate) struct X14IconSet {
+    #[serde(rename = "@iconSet", default, skip_serializing_if = "Option::is_none")]
+    pub(crate) icon_set: Option<String>,
+    #[serde(rename = "@showValue", default, skip_serializing_if = "Option::is_none")]
+    pub(crate) show_value: Option<u8>,
+    #[serde(rename = "@reverse", default, skip_serializing_if = "Option::is_none")]
+    pub(crate) reverse: Option<u8>,
+    #[serde(rename = "@custom", default, skip_serializing_if = "Option::is_none")]
+    custom: Option<u8>,
+    #[serde(rename(serialize = "x14:cfvo", deserialize = "cfvo"), default)]
+    pub(crate) cfvo: Vec<X14Cfvo>,
+    #[serde(rename(serialize = "x14:cfIcon", deserialize = "cfIcon"), default, skip_serializing_if = "Vec::is_empty")]
+    cf_icon: Vec<X14CfIcon>,
+}
+
+impl X14IconSet {
+    fn from_api_icon_set(icon_set: &IconSet) -> X14IconSet {
+        let mut cfvo = vec![X14Cfvo::from_api_value(&ConditionalFormatValue::Percent(0.0))];
+        cfvo.extend(icon_set.thresholds.iter().map(X14Cfvo::from_api_value));
+        X14IconSet {
+            icon_set: Some(icon_set.icon_set_type.to_name().to_string()),
+            show_value: if icon_set.icons_only { Some(0) } else { None },
+            reverse: if icon_set.reverse { Some(1) } else { None },
+            custom: None,
+            cfvo,
+            cf_icon: vec![],
+        }
+    }
+
+    ///
+    /// The custom icons of the set are read as the icons of its type
+    ///
+    fn to_api_icon_set(&self) -> IconSet {
+        IconSet {
+            icon_set_type: IconSetType::from_name(self.icon_set.as_deref().unwrap_or_default()),
+            thresholds: self.cfvo.iter().skip(1).map(X14Cfvo::to_api_value).collect(),
+            reverse: self.reverse == Some(1),
+            icons_only: self.show_value == Some(0),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+struct X14CfIcon {
+    #[serde(rename = "@iconSet")]
+    icon_set: String,
+    #[serde(rename = "@iconId")]
+    icon_id: u32,
+}
@@ -1,7 +1,5 @@
-use std::io;
 use std::path::{Path, PathBuf};
-use zip::read::ZipFile;
-use crate::file::{XlsxFileReader, XlsxFileType, XlsxFileWriter};
+use crate::file::{XlsxFileType, XlsxFileWriter};
 use crate::xml::io::Io;
 
 #[derive(Debug, Default)]
@@ -23,6 +21,15 @@ impl Medias {
         self.medias.iter().map(|m| m.id).max().unwrap_or_default()
     }
 
+    ///
+    /// Number a media copied from another package, whose file is saved with the parts of its worksheet
+    ///
+    pub(crate) fn add_copied_media(&mut self) -> u32 {
+        let id = 1 + self.max_id();
+        self.medias.push(Media::by_id(id));
+        id
+    }
+
     pub(crate) fn add_existed_media(&mut self, file_name: &str) -> u32 {
         let id: u32 = file_name
             .chars()
@@ -56,8 +63,8 @@ impl Io<Medias> for Medias {
     //     })
     // }
 
-    fn save<P: AsRef<Path>>(& self, file_path: P) {
-        self.medias.iter().for_each(|m| { m.save(&file_path) });
+    fn save(&self, writer: &mut XlsxFileWriter) {
+        self.medias.iter().for_each(|m| { m.save(writer) });
     }
 }
 
@@ -84,11 +91,11 @@ impl Media {
 }
 
 impl Io<Media> for Media {
-    fn save<P: AsRef<Path>>(&self, file_path: P) {
+    fn save(&self, writer: &mut XlsxFileWriter) {
         if let Some(path) = &self.file_path {
             let extension = path.extension().unwrap_or("png".as_ref()).to_string_lossy();
             let file_name = format!("image{}.{}", self.id, extension);
-            XlsxFileWriter::copy_from(&file_path, XlsxFileType::Medias(file_name), &path).unwrap();
+            writer.copy_from(XlsxFileType::Medias(file_name), path).unwrap();
         }
     }
 }
\ No newline at end of file
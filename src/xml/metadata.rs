@@ -1,11 +1,8 @@
 use std::fs::File;
-use std::io;
 use std::io::Read;
-use std::path::Path;
 use quick_xml::{de, se};
 use serde::{Deserialize, Serialize};
-use zip::read::ZipFile;
-use crate::file::{XlsxFileReader, XlsxFileType, XlsxFileWriter};
+use crate::file::{XlsxFileType, XlsxFileWriter};
 use crate::xml::extension::{AddExtension, ExtensionList, ExtensionType};
 use crate::xml::io::Io;
 
@@ -201,10 +198,9 @@ impl Metadata {
 }
 
 impl Io<Metadata> for Metadata {
-    fn save<P: AsRef<Path>>(& self, file_path: P) {
+    fn save(&self, writer: &mut XlsxFileWriter) {
         let xml = se::to_string_with_root("metadata", &self).unwrap();
         let xml = format!("<?xml version=\"1.0\" encoding=\"UTF-8\" standalone=\"yes\"?>\n{}", xml);
-        let mut file = XlsxFileWriter::from_path(file_path, XlsxFileType::MetaData).unwrap();
-        file.write_all(xml.as_ref()).unwrap();
+        writer.write_all(XlsxFileType::MetaData, xml.as_ref());
     }
 }
\ No newline at end of file
@@ -4,48 +4,75 @@ use serde::{Deserialize, Serialize};
 /// xmlns:x="urn:schemas-microsoft-com:office:excel"
 ///
 
-#[derive(Debug, Deserialize, Serialize)]
-#[serde(rename(serialize = "x:ClientData", deserialize = "ClientData"))]
+#[derive(Debug, Deserialize, Serialize, Default)]
+#[serde(rename(serialize = "x:ClientData", deserialize = "ClientData"), default)]
 pub(crate) struct ClientData {
     #[serde(rename(serialize = "@ObjectType", deserialize = "@ObjectType"))]
-    object_type: String,
+    pub(crate) object_type: String,
     #[serde(rename(serialize = "x:MoveWithCells", deserialize = "MoveWithCells"))]
     move_with_cells: MoveWithCells,
     #[serde(rename(serialize = "x:SizeWithCells", deserialize = "SizeWithCells"))]
     size_with_cells: SizeWithCells,
     #[serde(rename(serialize = "x:Anchor", deserialize = "Anchor"))]
     anchor: Anchor,
+    #[serde(rename(serialize = "x:AutoFill", deserialize = "AutoFill"))]
+    auto_fill: AutoFill,
     #[serde(rename(serialize = "x:Row", deserialize = "Row"))]
-    row: Row,
+    pub(crate) row: Row,
     #[serde(rename(serialize = "x:Column", deserialize = "Column"))]
-    column: Column,
+    pub(crate) column: Column,
+    #[serde(rename(serialize = "x:Visible", deserialize = "Visible"), skip_serializing_if = "Option::is_none")]
+    pub(crate) visible: Option<Visible>,
 }
 
-#[derive(Debug, Deserialize, Serialize)]
+impl ClientData {
+    ///
+    /// The client data of a note, row and col are 0-based
+    ///
+    pub(crate) fn note(row: u32, col: u32, anchor: [u32; 8], visible: bool) -> ClientData {
+        ClientData {
+            object_type: "Note".to_string(),
+            move_with_cells: MoveWithCells {},
+            size_with_cells: SizeWithCells {},
+            anchor: Anchor {
+                value: anchor.map(|a| a.to_string()).join(", "),
+            },
+            auto_fill: AutoFill { value: "False".to_string() },
+            row: Row { value: row },
+            column: Column { value: col },
+            visible: if visible { Some(Visible {}) } else { None },
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, Default)]
 struct MoveWithCells {}
 
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Debug, Deserialize, Serialize, Default)]
 struct SizeWithCells {
 }
 
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Debug, Deserialize, Serialize, Default)]
+pub(crate) struct Visible {}
+
+#[derive(Debug, Deserialize, Serialize, Default)]
 struct Anchor {
     #[serde(rename = "$value", default, skip_serializing_if = "String::is_empty")]
     value: String,// Vec<u32>,
 }
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Debug, Deserialize, Serialize, Default)]
 #[serde(rename(serialize = "x:AutoFill", deserialize = "AutoFill"))]
 struct AutoFill {
     #[serde(rename = "$value", default)]
-    value: bool,
+    value: String,
 }
-#[derive(Debug, Deserialize, Serialize)]
-struct Row {
+#[derive(Debug, Deserialize, Serialize, Default)]
+pub(crate) struct Row {
     #[serde(rename = "$value", default)]
-    value: u32,
+    pub(crate) value: u32,
 }
-#[derive(Debug, Deserialize, Serialize)]
-struct Column {
+#[derive(Debug, Deserialize, Serialize, Default)]
+pub(crate) struct Column {
     #[serde(rename = "$value", default)]
-    value: u32,
+    pub(crate) value: u32,
 }
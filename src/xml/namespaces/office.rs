@@ -16,4 +16,16 @@ struct IdMap {
     v_ext: String,
     #[serde(rename = "@data")]
     data: u32,
-}
\ No newline at end of file
+}
+
+impl ShapeLayout {
+    ///
+    /// Shapes of the drawing take the ids from `1024 * data + 1`
+    ///
+    pub(crate) fn new(data: u32) -> ShapeLayout {
+        ShapeLayout {
+            v_ext: "edit".to_string(),
+            id_map: IdMap { v_ext: "edit".to_string(), data },
+        }
+    }
+}
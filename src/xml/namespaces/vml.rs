@@ -1,4 +1,4 @@
-use serde::{Deserialize, Serialize, Serializer};
+use serde::{Deserialize, Serialize};
 use crate::xml::namespaces::excel as x;
 
 ///
@@ -22,14 +22,15 @@ pub(crate) struct ShapeType {
     path: Path,
 }
 
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Debug, Deserialize, Serialize, Default)]
+#[serde(default)]
 pub(crate) struct Shape {
     #[serde(rename = "@id")]
     id: String,
     #[serde(rename = "@type")]
     param_type: String,
     #[serde(rename = "@style")]
-    style: String,
+    pub(crate) style: String,
     #[serde(rename = "@fillcolor")]
     fillcolor: String,
     #[serde(rename(serialize = "@o:insetmode", deserialize = "@insetmode"))]
@@ -43,32 +44,32 @@ pub(crate) struct Shape {
     #[serde(rename(serialize = "v:textbox", deserialize = "textbox"))]
     textbox: TextBox,
     #[serde(rename(serialize = "x:ClientData", deserialize = "ClientData"))]
-    clientdata: x::ClientData,
+    pub(crate) clientdata: x::ClientData,
 }
 
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Debug, Deserialize, Serialize, Default)]
 struct Stroke {
     #[serde(rename = "@joinstyle")]
     join_style :String
 }
 
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Debug, Deserialize, Serialize, Default)]
 struct Path {
     #[serde(rename = "@gradientshapeok", skip_serializing_if = "Option::is_none")]
     gradient_shape_ok: Option<String>,
-    #[serde(rename(serialize = "@o:connecttype", deserialize = "@connecttype"))]
+    #[serde(rename(serialize = "@o:connecttype", deserialize = "@connecttype"), default)]
     o_connect_type: String,
 }
 
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Debug, Deserialize, Serialize, Default)]
 #[serde(rename(serialize = "v:fill", deserialize = "fill"))]
 struct Fill {
-    #[serde(rename = "@color2")]
+    #[serde(rename = "@color2", default)]
     color2: String,
 }
 
-#[derive(Debug, Deserialize, Serialize)]
-#[serde(rename(serialize = "v:shadow", deserialize = "shadow"))]
+#[derive(Debug, Deserialize, Serialize, Default)]
+#[serde(rename(serialize = "v:shadow", deserialize = "shadow"), default)]
 struct Shadow {
     #[serde(rename = "@on")]
     on: String,
@@ -78,17 +79,63 @@ struct Shadow {
     obscured: String,
 }
 
-#[derive(Debug, Deserialize, Serialize)]
-#[serde(rename(serialize = "v:textbox", deserialize = "textbox"))]
+#[derive(Debug, Deserialize, Serialize, Default)]
+#[serde(rename(serialize = "v:textbox", deserialize = "textbox"), default)]
 struct TextBox {
     #[serde(rename = "@style")]
     style: String,
     div: Div
 }
 
-#[derive(Debug, Deserialize, Serialize)]
-#[serde(rename = "div")]
+#[derive(Debug, Deserialize, Serialize, Default)]
+#[serde(rename = "div", default)]
 struct Div {
     #[serde(rename = "@style")]
     style: String,
-}
\ No newline at end of file
+}
+
+impl ShapeType {
+    ///
+    /// The shape type of the text boxes of notes
+    ///
+    pub(crate) fn text_box() -> ShapeType {
+        ShapeType {
+            id: "_x0000_t202".to_string(),
+            coord_size: "21600,21600".to_string(),
+            o_spt: "202".to_string(),
+            param_path: "m,l,21600r21600,l21600,xe".to_string(),
+            stroke: Stroke { join_style: "miter".to_string() },
+            path: Path {
+                gradient_shape_ok: Some("t".to_string()),
+                o_connect_type: "rect".to_string(),
+            },
+        }
+    }
+}
+
+impl Shape {
+    ///
+    /// The text box showing a note
+    ///
+    pub(crate) fn note(shape_id: u32, style: &str, clientdata: x::ClientData) -> Shape {
+        Shape {
+            id: format!("_x0000_s{shape_id}"),
+            param_type: "#_x0000_t202".to_string(),
+            style: style.to_string(),
+            fillcolor: "#ffffe1".to_string(),
+            o_insetmode: "auto".to_string(),
+            fill: Fill { color2: "#ffffe1".to_string() },
+            shadow: Shadow {
+                on: "t".to_string(),
+                color: "black".to_string(),
+                obscured: "t".to_string(),
+            },
+            path: Path { gradient_shape_ok: None, o_connect_type: "none".to_string() },
+            textbox: TextBox {
+                style: "mso-direction-alt:auto".to_string(),
+                div: Div { style: "text-align:left".to_string() },
+            },
+            clientdata,
+        }
+    }
+}
@@ -2,19 +2,13 @@ mod rel_type;
 mod rel;
 
 use std::collections::HashMap;
-use std::fs::File;
 use serde::{Deserialize, Serialize};
-use std::io;
-use std::io::Read;
 use std::path::Path;
-use quick_xml::{de, se};
-use zip::read::ZipFile;
-use zip::ZipArchive;
+use quick_xml::se;
 use crate::api::relationship::Rel;
-use crate::file::{XlsxFileReader, XlsxFileType, XlsxFileWriter};
+use crate::file::{XlsxFileType, XlsxFileWriter};
 use crate::xml::relationships::rel::RelationShip;
 use crate::xml::relationships::rel_type::RelType;
-use crate::xml::workbook::Workbook;
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub(crate) struct Relationships {
@@ -67,11 +61,12 @@ impl Targets {
             RelType::MetaData => "metadata.xml".to_string(),
             RelType::CalcChain => "calcChain.xml".to_string(),
             RelType::Table => format!("../tables/table{id}.xml"),
-            RelType::SharedStrings => { "".to_string() }
+            RelType::SharedStrings => "sharedStrings.xml".to_string(),
             RelType::PrinterSettings => { "".to_string() }
-            RelType::VmlDrawing => { "".to_string() }
-            RelType::Comments => { "".to_string() }
-            RelType::Unknown => { "".to_string() }
+            RelType::VmlDrawing => format!("../drawings/vmlDrawing{id}.vml"),
+            RelType::Comments => format!("../comments{id}.xml"),
+            RelType::Chart => format!("../charts/chart{id}.xml"),
+            RelType::Unknown(_) => { "".to_string() }
         }
     }
 }
@@ -92,7 +87,7 @@ impl Default for Relationships {
 
 impl Relationships {
     pub(crate) fn next_id(&self) -> u32 {
-        1 + self.relationship.len() as u32
+        1 + self.relationship.iter().map(|r| r.id.get_id()).max().unwrap_or(0)
     }
 
     ///
@@ -138,8 +133,19 @@ impl Relationships {
         // let target = self.get_target(rid);
     }
     
-    pub(crate) fn get_vml_drawing_rid(&self) -> Option<u32> {
-        self.get_rid_by_type(RelType::VmlDrawing).first().copied()
+    ///
+    /// The number in the file name of the comments, like 1 for `comments1.xml`
+    ///
+    pub(crate) fn get_comments_id(&self) -> Option<u32> {
+        self.get_target_id_by_type(RelType::Comments)
+    }
+
+    ///
+    /// The number in the file name of a target, like 1 for `../drawings/vmlDrawing1.vml`
+    ///
+    pub(crate) fn get_target_id(&self, r_id: u32) -> Option<u32> {
+        let rel = self.relationship.iter().find(|r| r.id.get_id() == r_id)?;
+        target_id(&rel.target)
     }
 
     pub(crate) fn get_target(&self, r_id: &Rel) -> (&String, u32) {
@@ -151,6 +157,16 @@ impl Relationships {
         (target, target_id)
     }
 
+    ///
+    /// The targets inside the package, relative to the part of the relationships
+    ///
+    pub(crate) fn get_internal_targets(&self) -> Vec<&str> {
+        self.relationship.iter()
+            .filter(|r| !r.is_external())
+            .map(|r| r.target.as_str())
+            .collect()
+    }
+
     fn get_rid_by_type(&self, rel_type: RelType) -> Vec<u32> {
         self.relationship
             .iter()
@@ -159,6 +175,10 @@ impl Relationships {
             .collect()
     }
 
+    fn get_target_id_by_type(&self, rel_type: RelType) -> Option<u32> {
+        target_id(self.get_target_by_type(rel_type).first()?)
+    }
+
     fn get_target_by_type(&self, rel_type: RelType) -> Vec<String> {
         self.relationship
             .iter()
@@ -167,21 +187,6 @@ impl Relationships {
             .collect()
     }
 
-    fn exist_type(&self, rel_type: RelType) -> bool {
-        self.relationship
-            .iter()
-            .filter(|r| r.rel_type == rel_type)
-            .count() > 0
-    }
-
-    pub(crate) fn add_worksheet(&mut self, id: u32) -> (u32, String) {
-        let r_id = self.next_id();
-        let target = self.targets.next_target(RelType::Worksheets, id);
-        let rel = RelationShip::new(r_id, RelType::Worksheets, &target, None);
-        self.relationship.push(rel);
-        (r_id, target.clone())
-    }
-
     pub(crate) fn add_image(&mut self, id: u32, image_extension: &str) -> u32 {
         let r_id = self.next_id();
         self.relationship.push(RelationShip::new_image(r_id, id, image_extension));
@@ -200,6 +205,36 @@ impl Relationships {
         r_id
     }
 
+    pub(crate) fn add_chart(&mut self, id: u32) -> u32 {
+        let r_id = self.next_id();
+        self.relationship.push(RelationShip::new_chart(r_id, id));
+        r_id
+    }
+
+    pub(crate) fn get_chart_ids(&self) -> Vec<u32> {
+        self.get_target_by_type(RelType::Chart).iter()
+            .filter_map(|target| target_id(target))
+            .collect()
+    }
+
+    pub(crate) fn add_vml_drawing(&mut self, id: u32) -> u32 {
+        let r_id = self.next_id();
+        self.relationship.push(RelationShip::new_vml_drawing(r_id, id));
+        r_id
+    }
+
+    pub(crate) fn add_comments(&mut self, id: u32) -> u32 {
+        let r_id = self.next_id();
+        self.relationship.push(RelationShip::new_comments(r_id, id));
+        r_id
+    }
+
+    pub(crate) fn add_table(&mut self, id: u32) -> u32 {
+        let r_id = self.next_id();
+        self.relationship.push(RelationShip::new_table(r_id, id));
+        r_id
+    }
+
     pub(crate) fn get_or_add_metadata(&mut self) -> u32 {
         let r_id = self.get_rid_by_type(RelType::MetaData);
         if r_id.is_empty() {
@@ -209,31 +244,66 @@ impl Relationships {
         }
         return r_id[0]
     }
-}
 
-impl Relationships {
-    pub(crate) async fn from_path_async<P: AsRef<Path>>(file_path: P, rel_type: XlsxFileType) -> io::Result<Relationships> {
-        Self::from_path(file_path, rel_type)
+    pub(crate) fn get_or_add_calc_chain(&mut self) -> u32 {
+        let r_id = self.get_rid_by_type(RelType::CalcChain);
+        if r_id.is_empty() {
+            let r_id = self.next_id();
+            self.relationship.push(RelationShip::new(r_id, RelType::CalcChain, "calcChain.xml", None));
+            return r_id;
+        }
+        r_id[0]
+    }
+
+    pub(crate) fn remove_calc_chain(&mut self) {
+        self.relationship.retain(|r| r.rel_type != RelType::CalcChain);
     }
 
-    pub(crate) async fn save_async<P: AsRef<Path>>(&self, file_path: P, rel_type: XlsxFileType) {
-        self.save(file_path, rel_type)
+    ///
+    /// Point the targets inside the package to other parts, the relationships to the parts left out are removed
+    ///
+    pub(crate) fn retarget<F: FnMut(&str) -> Option<String>>(&mut self, mut new_target: F) {
+        self.relationship.retain_mut(|r| {
+            if r.is_external() {
+                return true;
+            }
+            match new_target(&r.target) {
+                Some(target) => {
+                    r.target = target;
+                    true
+                }
+                None => false,
+            }
+        });
+    }
+
+    pub(crate) fn remove(&mut self, r_id: u32) {
+        self.relationship.retain(|r| r.id.get_id() != r_id);
+    }
+
+    pub(crate) fn get_or_add_shared_strings(&mut self) -> u32 {
+        let r_id = self.get_rid_by_type(RelType::SharedStrings);
+        if r_id.is_empty() {
+            let r_id = self.next_id();
+            self.relationship.push(RelationShip::new_shared_strings(r_id));
+            return r_id;
+        }
+        r_id[0]
     }
+}
+
+fn target_id(target: &str) -> Option<u32> {
+    let name = Path::new(target).file_stem()?.to_str()?;
+    name.chars().filter(char::is_ascii_digit).collect::<String>().parse().ok()
+}
 
-    pub(crate) fn from_path<P: AsRef<Path>>(file_path: P, rel_type: XlsxFileType) -> io::Result<Relationships> {
-        let mut file = XlsxFileReader::from_path(file_path, rel_type)?;
-        let mut xml = String::new();
-        file.read_to_string(&mut xml).unwrap();
-        let mut rel: Relationships = de::from_str(&xml).unwrap();
-        rel.relationship.iter()
-            .for_each(|r| rel.targets.add_target(&r.rel_type, &r.target));
-        Ok(rel)
+impl Relationships {
+    pub(crate) fn save(&self, writer: &mut XlsxFileWriter, rel_type: XlsxFileType) {
+        writer.write_all(rel_type, self.to_xml().as_ref());
     }
 
-    pub(crate) fn save<P: AsRef<Path>>(&self, file_path: P, rel_type: XlsxFileType) {
+    pub(crate) fn to_xml(&self) -> String {
         let xml = se::to_string_with_root("Relationships", &self).unwrap();
-        let xml = format!("<?xml version=\"1.0\" encoding=\"UTF-8\" standalone=\"yes\"?>\n{}", xml);
-        let mut file = XlsxFileWriter::from_path(file_path, rel_type).unwrap();
-        file.write_all(xml.as_ref()).unwrap();
+        format!("<?xml version=\"1.0\" encoding=\"UTF-8\" standalone=\"yes\"?>\n{}", xml)
     }
 }
\ No newline at end of file
@@ -16,6 +16,10 @@ pub(crate) struct RelationShip {
 }
 
 impl RelationShip {
+    pub(crate) fn is_external(&self) -> bool {
+        self.target_mode.as_deref() == Some("External")
+    }
+
     pub(crate) fn new(r_id: u32, rel_type: RelType, target: &str, target_mode: Option<String>) -> RelationShip {
         RelationShip {
             id: Rel::from_id(r_id),
@@ -80,6 +84,15 @@ impl RelationShip {
         }
     }
 
+    pub(crate) fn new_chart(r_id: u32, id: u32) -> RelationShip {
+        RelationShip {
+            id: Rel::from_id(r_id),
+            rel_type: RelType::Chart,
+            target: format!("../charts/chart{id}.xml"),
+            target_mode: None,
+        }
+    }
+
     pub(crate) fn new_metadata(r_id: u32) -> RelationShip {
         RelationShip {
             id: Rel::from_id(r_id),
@@ -88,4 +101,40 @@ impl RelationShip {
             target_mode: None,
         }
     }
-}
\ No newline at end of file
+
+    pub(crate) fn new_shared_strings(r_id: u32) -> RelationShip {
+        RelationShip {
+            id: Rel::from_id(r_id),
+            rel_type: RelType::SharedStrings,
+            target: "sharedStrings.xml".to_string(),
+            target_mode: None,
+        }
+    }
+
+    pub(crate) fn new_vml_drawing(r_id: u32, id: u32) -> RelationShip {
+        RelationShip {
+            id: Rel::from_id(r_id),
+            rel_type: RelType::VmlDrawing,
+            target: format!("../drawings/vmlDrawing{id}.vml"),
+            target_mode: None,
+        }
+    }
+
+    pub(crate) fn new_comments(r_id: u32, id: u32) -> RelationShip {
+        RelationShip {
+            id: Rel::from_id(r_id),
+            rel_type: RelType::Comments,
+            target: format!("../comments{id}.xml"),
+            target_mode: None,
+        }
+    }
+
+    pub(crate) fn new_table(r_id: u32, id: u32) -> RelationShip {
+        RelationShip {
+            id: Rel::from_id(r_id),
+            rel_type: RelType::Table,
+            target: format!("../tables/table{id}.xml"),
+            target_mode: None,
+        }
+    }
+}
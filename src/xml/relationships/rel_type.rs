@@ -12,7 +12,9 @@ pub(crate) enum RelType {
     Drawings,
     VmlDrawing,
     Comments,
-    Unknown,
+    Chart,
+    // A relationship not managed, keeping its type to be written back
+    Unknown(String),
     MetaData,
     SharedStrings,
     PrinterSettings,
@@ -39,7 +41,7 @@ impl<'de> Visitor<'de> for RelType {
 }
 impl<'de> Deserialize<'de> for RelType {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error> where D: Deserializer<'de> {
-        deserializer.deserialize_string(RelType::Unknown)
+        deserializer.deserialize_string(RelType::Unknown(String::new()))
     }
 }
 
@@ -59,7 +61,8 @@ impl RelType {
             RelType::PrinterSettings => "http://schemas.openxmlformats.org/officeDocument/2006/relationships/printerSettings",
             RelType::CalcChain => "http://schemas.openxmlformats.org/officeDocument/2006/relationships/calcChain",
             RelType::Table => "http://schemas.openxmlformats.org/officeDocument/2006/relationships/table",
-            RelType::Unknown => "",
+            RelType::Chart => "http://schemas.openxmlformats.org/officeDocument/2006/relationships/chart",
+            RelType::Unknown(namespace) => namespace,
         }
     }
 
@@ -78,7 +81,8 @@ impl RelType {
             "http://schemas.openxmlformats.org/officeDocument/2006/relationships/printerSettings" => RelType::PrinterSettings,
             "http://schemas.openxmlformats.org/officeDocument/2006/relationships/calcChain" => RelType::CalcChain,
             "http://schemas.openxmlformats.org/officeDocument/2006/relationships/table" => RelType::Table,
-            &_ => RelType::Unknown
+            "http://schemas.openxmlformats.org/officeDocument/2006/relationships/chart" => RelType::Chart,
+            namespace => RelType::Unknown(namespace.to_string()),
         }
     }
 }
\ No newline at end of file
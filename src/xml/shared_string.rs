@@ -1,91 +1,334 @@
-use std::fs::File;
-use std::io;
-use std::io::Read;
-use std::path::Path;
+use std::collections::HashMap;
+use std::io::{Read, Seek};
 use quick_xml::{de, se};
 use serde::{Deserialize, Serialize};
-use zip::read::ZipFile;
-use crate::file::{XlsxFileReader, XlsxFileType, XlsxFileWriter};
+use zip::ZipArchive;
+use crate::{Format, RichText, Value};
+use crate::file::{XlsxFileType, XlsxFileWriter};
+use crate::xml::common::{Element, FromFormat, PhoneticPr};
 use crate::xml::io::Io;
+use crate::xml::style::color::Color;
+use crate::WorkbookResult;
 
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Debug, Clone, Deserialize, Serialize)]
 #[serde(rename="sst")]
 pub(crate) struct SharedString {
-    // #[serde(flatten)]
-    // xmlns_attrs: XmlnsAttrs,
-    // #[serde(rename = "@count", default)]
-    // count: u32,
-    // #[serde(rename = "@uniqueCount", default)]
-    // unique_count: u32,
+    #[serde(rename = "@xmlns", default = "default_xmlns")]
+    xmlns: String,
+    #[serde(rename = "@count", default)]
+    count: usize,
+    #[serde(rename = "@uniqueCount", default)]
+    unique_count: usize,
     #[serde(rename = "si", default = "Vec::new")]
     string_item: Vec<StringItem>,
+    // Plain texts of the items before `indexed`, mapped to their first position
+    #[serde(skip)]
+    index: HashMap<String, usize>,
+    #[serde(skip)]
+    indexed: usize,
+}
+
+fn default_xmlns() -> String {
+    "http://schemas.openxmlformats.org/spreadsheetml/2006/main".to_string()
 }
 
 impl Default for SharedString {
     fn default() -> Self {
         Self {
-            // xmlns_attrs: XmlnsAttrs::shared_string_default(),
-            // count: 0,
-            // unique_count: 0,
+            xmlns: default_xmlns(),
+            count: 0,
+            unique_count: 0,
             string_item: vec![],
+            index: HashMap::new(),
+            indexed: 0,
         }
     }
 }
 
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
 pub(crate) struct StringItem {
+    #[serde(rename = "t", skip_serializing_if = "Option::is_none")]
+    text: Option<Text>,
+    #[serde(rename = "r", default, skip_serializing_if = "Vec::is_empty")]
+    runs: Vec<Run>,
+    #[serde(rename = "rPh", default, skip_serializing_if = "Vec::is_empty")]
+    phonetic_runs: Vec<PhoneticRun>,
+    #[serde(rename = "phoneticPr", skip_serializing_if = "Option::is_none")]
+    phonetic_pr: Option<PhoneticPr>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub(crate) struct Text {
+    #[serde(rename(serialize = "@xml:space", deserialize = "@space"), skip_serializing_if = "Option::is_none")]
+    space: Option<String>,
+    #[serde(rename = "$value", default)]
+    value: String,
+}
+
+///
+/// A run of rich text, the text shares the same font properties
+///
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub(crate) struct Run {
+    #[serde(rename = "rPr", skip_serializing_if = "Option::is_none")]
+    pub(crate) run_properties: Option<RunProperties>,
     #[serde(rename = "t", default)]
-    text: String,
-    // #[serde(rename = "phoneticPr", skip_serializing_if = "Option::is_none")]
-    // phonetic_pr: Option<PhoneticPr>,
+    text: Text,
 }
 
-impl SharedString {
-    pub(crate) fn get_text(&self, id: usize) -> Option<&str> {
-        match self.string_item.get(id) {
-            Some(string_item) => Some(string_item.text.as_str()),
-            None => None
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub(crate) struct RunProperties {
+    #[serde(rename = "rFont", skip_serializing_if = "Option::is_none")]
+    pub(crate) font: Option<Element<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    charset: Option<Element<u8>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    family: Option<Element<u8>>,
+    #[serde(rename = "b", skip_serializing_if = "Option::is_none")]
+    pub(crate) bold: Option<ToggleElement>,
+    #[serde(rename = "i", skip_serializing_if = "Option::is_none")]
+    pub(crate) italic: Option<ToggleElement>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) strike: Option<ToggleElement>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    outline: Option<ToggleElement>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    shadow: Option<ToggleElement>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    condense: Option<ToggleElement>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    extend: Option<ToggleElement>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) color: Option<Color>,
+    #[serde(rename = "sz", skip_serializing_if = "Option::is_none")]
+    pub(crate) size: Option<Element<f64>>,
+    #[serde(rename = "u", skip_serializing_if = "Option::is_none")]
+    pub(crate) underline: Option<ToggleElement>,
+    #[serde(rename = "vertAlign", skip_serializing_if = "Option::is_none")]
+    vert_align: Option<Element<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    scheme: Option<Element<String>>,
+}
+
+///
+/// An element like `<b/>`, whose `val` is optional
+///
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub(crate) struct ToggleElement {
+    #[serde(rename = "@val", skip_serializing_if = "Option::is_none")]
+    pub(crate) val: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+struct PhoneticRun {
+    #[serde(rename = "@sb")]
+    start_base: u32,
+    #[serde(rename = "@eb")]
+    end_base: u32,
+    #[serde(rename = "t", default)]
+    text: Text,
+}
+
+impl ToggleElement {
+    pub(crate) fn is_on(&self) -> bool {
+        !matches!(self.val.as_deref(), Some("0") | Some("false"))
+    }
+}
+
+impl Text {
+    fn new(text: &str) -> Text {
+        // leading and trailing whitespace would be dropped by spreadsheet applications
+        let preserve = text.starts_with(char::is_whitespace) || text.ends_with(char::is_whitespace);
+        Text {
+            space: if preserve { Some("preserve".to_string()) } else { None },
+            value: text.to_string(),
         }
     }
-    // pub(crate) fn add_text(&mut self, text: &str) -> u32 {
-    //     let item = StringItem { text: String::from(text), phonetic_pr: None };
-    //     for i in 0..self.string_item.len() {
-    //         if self.string_item[i].text == item.text {
-    //             return i as u32;
-    //         }
-    //     }
-    //     self.count += 1;
-    //     self.unique_count += 1;
-    //     self.string_item.push(item);
-    //     self.string_item.len() as u32 - 1
-    // }
+}
+
+impl StringItem {
+    pub(crate) fn new(text: &str) -> StringItem {
+        StringItem {
+            text: Some(Text::new(text)),
+            ..Default::default()
+        }
+    }
+
+    ///
+    /// The plain text of the item, runs of rich text are concatenated
+    ///
+    pub(crate) fn plain_text(&self) -> String {
+        let mut text = match &self.text {
+            Some(text) => text.value.clone(),
+            None => String::new(),
+        };
+        self.runs.iter().for_each(|run| text.push_str(&run.text.value));
+        text
+    }
+
+    fn is_rich_text(&self) -> bool {
+        !self.runs.is_empty()
+    }
+
+    pub(crate) fn to_value(&self) -> Value {
+        if !self.is_rich_text() {
+            return Value::String(self.plain_text());
+        }
+        let mut rich_text = RichText::new();
+        if let Some(text) = &self.text {
+            rich_text = rich_text.add_run(&text.value, None);
+        }
+        for run in &self.runs {
+            let format = run.run_properties.as_ref().map(|run_properties| Format {
+                font: run_properties.get_format(),
+                ..Default::default()
+            });
+            rich_text = rich_text.add_run(&run.text.value, format.as_ref());
+        }
+        Value::RichText(rich_text)
+    }
 }
 
 impl SharedString {
+    pub(crate) fn get_text(&self, id: usize) -> Option<String> {
+        self.string_item.get(id).map(|string_item| string_item.plain_text())
+    }
 
-    pub(crate) fn from_file(file: &File) -> SharedString {
-        let mut xml = String::new();
-        let mut archive = zip::ZipArchive::new(file).unwrap();
-        let file_path = "xl/sharedStrings.xml";
-        let shared_string = match archive.by_name(&file_path) {
-            Ok(mut file) => {
-                file.read_to_string(&mut xml).unwrap();
-                de::from_str(&xml).unwrap()
-            }
-            Err(_) => {
-                SharedString::default()
+    pub(crate) fn get_value(&self, id: usize) -> Option<Value> {
+        self.string_item.get(id).map(|string_item| string_item.to_value())
+    }
+
+    ///
+    /// Add a text to the table and return its index, an existing index is reused
+    ///
+    pub(crate) fn add_text(&mut self, text: &str) -> usize {
+        self.update_index();
+        if let Some(&id) = self.index.get(text) {
+            return id;
+        }
+        self.string_item.push(StringItem::new(text));
+        self.update_index();
+        self.string_item.len() - 1
+    }
+
+    ///
+    /// Add an item of another table and return its index here, a plain text reuses an existing index
+    ///
+    pub(crate) fn import_item(&mut self, source: &SharedString, id: usize) -> Option<usize> {
+        let item = source.string_item.get(id)?;
+        if !item.is_rich_text() && item.phonetic_runs.is_empty() {
+            return Some(self.add_text(&item.plain_text()));
+        }
+        self.string_item.push(item.clone());
+        self.update_index();
+        Some(self.string_item.len() - 1)
+    }
+
+    ///
+    /// Update the counts before saving, `count` is the number of cells referencing the table
+    ///
+    pub(crate) fn set_count(&mut self, count: usize) {
+        self.count = count;
+        self.unique_count = self.string_item.len();
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.string_item.is_empty()
+    }
+
+    fn update_index(&mut self) {
+        for id in self.indexed..self.string_item.len() {
+            let item = &self.string_item[id];
+            if !item.is_rich_text() && item.phonetic_runs.is_empty() {
+                self.index.entry(item.plain_text()).or_insert(id);
             }
-        };
-        shared_string
+        }
+        self.indexed = self.string_item.len();
     }
 }
 
 impl Io<SharedString> for SharedString {
-    fn save<P: AsRef<Path>>(&self, file_path: P) {
-        return;
-        // let xml = se::to_string_with_root("sst", &self).unwrap();
-        // let xml = format!("<?xml version=\"1.0\" encoding=\"UTF-8\" standalone=\"yes\"?>\n{}", xml);
-        // let mut file = XlsxFileWriter::from_path(file_path, XlsxFileType::SharedStringFile).unwrap();
-        // file.write_all(xml.as_ref()).unwrap();
-    }
-}
\ No newline at end of file
+    fn save(&self, writer: &mut XlsxFileWriter) {
+        let xml = se::to_string_with_root("sst", &self).unwrap();
+        let xml = format!("<?xml version=\"1.0\" encoding=\"UTF-8\" standalone=\"yes\"?>\n{}", xml);
+        writer.write_all(XlsxFileType::SharedStringFile, xml.as_ref());
+    }
+}
+
+impl SharedString {
+    ///
+    /// Load the shared strings of a package, an empty table if it has none
+    ///
+    pub(crate) fn from_archive<R: Read + Seek>(archive: &mut ZipArchive<R>, path: &str) -> WorkbookResult<SharedString> {
+        let Ok(mut file) = archive.by_name(path) else {
+            return Ok(SharedString::default());
+        };
+        let mut xml = String::new();
+        file.read_to_string(&mut xml)?;
+        Ok(de::from_str(&escape_text_whitespace(&xml))?)
+    }
+}
+
+///
+/// The deserializer trims texts, so the leading and trailing whitespace
+/// of every `<t>` element is escaped as character references to keep it.
+///
+pub(crate) fn escape_text_whitespace(xml: &str) -> String {
+    let mut escaped = String::with_capacity(xml.len());
+    let mut rest = xml;
+    while let Some(start) = rest.find("<t") {
+        let (head, tail) = rest.split_at(start);
+        escaped.push_str(head);
+        let tag_end = match tail.find('>') {
+            Some(tag_end) => tag_end + 1,
+            None => break,
+        };
+        let tag = &tail[..tag_end];
+        escaped.push_str(tag);
+        rest = &tail[tag_end..];
+        let is_text_tag = (tag == "<t>" || tag.starts_with("<t ")) && !tag.ends_with("/>");
+        if !is_text_tag {
+            continue;
+        }
+        let text_end = rest.find('<').unwrap_or(rest.len());
+        let text = &rest[..text_end];
+        let trimmed = text.trim();
+        if trimmed.len() == text.len() {
+            escaped.push_str(text);
+        } else {
+            let leading = &text[..text.len() - text.trim_start().len()];
+            let trailing = &text[text.trim_end().len().max(leading.len())..];
+            leading.chars().for_each(|c| escaped.push_str(&format!("&#x{:X};", c as u32)));
+            escaped.push_str(trimmed);
+            trailing.chars().for_each(|c| escaped.push_str(&format!("&#x{:X};", c as u32)));
+        }
+        rest = &rest[text_end..];
+    }
+    escaped.push_str(rest);
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use quick_xml::de;
+    use crate::xml::shared_string::{escape_text_whitespace, SharedString};
+
+    #[test]
+    fn test_add_text() {
+        let mut shared_string = SharedString::default();
+        assert_eq!(shared_string.add_text("Label"), 0);
+        assert_eq!(shared_string.add_text("Other"), 1);
+        assert_eq!(shared_string.add_text("Label"), 0);
+        shared_string.set_count(3);
+        assert_eq!((shared_string.count, shared_string.unique_count), (3, 2));
+    }
+
+    #[test]
+    fn test_preserve_space() {
+        let xml = r#"<sst><si><t xml:space="preserve"> padded </t></si><si><r><t>rich</t></r><r><t xml:space="preserve"> text</t></r></si><si><t/></si></sst>"#;
+        let shared_string: SharedString = de::from_str(&escape_text_whitespace(xml)).unwrap();
+        assert_eq!(shared_string.get_text(0).unwrap(), " padded ");
+        assert_eq!(shared_string.get_text(1).unwrap(), "rich text");
+        assert_eq!(shared_string.get_text(2).unwrap(), "");
+    }
+}
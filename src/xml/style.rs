@@ -4,18 +4,15 @@ pub(crate) mod fill;
 pub(crate) mod alignment;
 pub(crate) mod xf;
 pub(crate) mod color;
-mod num_fmt;
+pub(crate) mod num_fmt;
+pub(crate) mod protection;
 
 use std::fs::File;
-use std::hash::Hash;
-use std::io;
 use std::io::Read;
-use std::path::Path;
 use quick_xml::{de, se};
 use serde::{Deserialize, Serialize};
-use zip::read::ZipFile;
-use crate::api::format::Format;
-use crate::file::{XlsxFileReader, XlsxFileType, XlsxFileWriter};
+use crate::api::format::{Format, FormatNumber, FormatProtection};
+use crate::file::{XlsxFileType, XlsxFileWriter};
 use crate::xml::common;
 use crate::xml::common::{FromFormat, XmlnsAttrs};
 use crate::xml::extension::ExtensionList;
@@ -23,9 +20,10 @@ use crate::xml::io::Io;
 use crate::xml::style::alignment::Alignment;
 use crate::xml::style::border::{Border, Borders};
 use crate::xml::style::color::Color;
-use crate::xml::style::fill::{Fill, Fills};
+use crate::xml::style::fill::{Fill, Fills, PatternFill};
 use crate::xml::style::font::{Font, Fonts};
-use crate::xml::style::num_fmt::{NumFmt, NumFmts};
+use crate::xml::style::num_fmt::{is_date_format_code, is_date_num_fmt_id, NumFmt, NumFmts};
+use crate::xml::style::protection::Protection;
 use crate::xml::style::xf::Xf;
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
@@ -128,9 +126,9 @@ struct CellStyle {
     name: String,
     #[serde(rename = "@xfId", default)]
     xf_id: u32,
-    #[serde(rename = "@builtinId", default)]
-    builtin_id: u32,
-    #[serde(rename = "@customBuiltin", default)]
+    #[serde(rename = "@builtinId", default, skip_serializing_if = "Option::is_none")]
+    builtin_id: Option<u32>,
+    #[serde(rename = "@customBuiltin", default, skip_serializing_if = "Option::is_none")]
     custom_builtin: Option<u32>,
 }
 
@@ -139,7 +137,7 @@ impl Default for CellStyle {
         CellStyle {
             name: "Normal".to_string(),
             xf_id: 0,
-            builtin_id: 0,
+            builtin_id: Some(0),
             custom_builtin: None,
         }
     }
@@ -153,7 +151,18 @@ struct Dxfs {
     dxf: Vec<Dxf>
 }
 
-#[derive(Debug, Clone, Deserialize, Serialize, Default)]
+impl Dxfs {
+    fn add_dxf(&mut self, dxf: &Dxf) -> u32 {
+        if let Some(id) = self.dxf.iter().position(|d| d == dxf) {
+            return id as u32;
+        }
+        self.dxf.push(dxf.clone());
+        self.count = self.dxf.len() as u32;
+        self.dxf.len() as u32 - 1
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, Default, PartialEq)]
 struct Dxf {
     #[serde(rename = "font", skip_serializing_if = "Option::is_none")]
     font: Option<Font>,
@@ -167,6 +176,82 @@ struct Dxf {
     border: Option<Vec<Border>>,
 }
 
+impl Dxf {
+    ///
+    /// A differential format with only the parts of the format that differ from the default one
+    ///
+    fn from_format(format: &Format, num_fmt: Option<NumFmt>) -> Dxf {
+        let default_format = Format::default();
+        let font = if format.font != default_format.font {
+            let mut font = Font::from_format(&format.font);
+            if format.font.size == default_format.font.size {
+                font.sz = None;
+            }
+            if format.font.name == default_format.font.name {
+                font.name = None;
+            }
+            font.color = font.color.filter(|color| !color.is_empty());
+            Some(font)
+        } else {
+            None
+        };
+        // a solid fill of a differential format is colored by its background color
+        let fill = match format.fill.pattern_type.as_str() {
+            "none" => None,
+            "solid" => Some(Fill {
+                pattern_fill: PatternFill {
+                    pattern_type: String::new(),
+                    fg_color: Color::default(),
+                    bg_color: Color::from_format(&format.fill.fg_color),
+                }
+            }),
+            _ => Some(Fill::from_format(&format.fill)),
+        };
+        // conditional formats can't draw diagonal borders
+        let border = if format.border != default_format.border {
+            let mut border = Border::from_format(&format.border);
+            border.diagonal = None;
+            for element in [&mut border.left, &mut border.right, &mut border.top, &mut border.bottom].into_iter().flatten() {
+                element.color = element.color.take().filter(|color| !color.is_empty());
+            }
+            Some(vec![border])
+        } else {
+            None
+        };
+        Dxf {
+            font,
+            num_fmt,
+            fill,
+            alignment: None,
+            border,
+        }
+    }
+
+    fn to_format(&self) -> Format {
+        let mut format = Format::default();
+        if let Some(font) = &self.font {
+            format.font = font.get_format();
+        }
+        if let Some(num_fmt) = &self.num_fmt {
+            format.num_format = FormatNumber::from_num_fmt_id(num_fmt.num_fmt_id(), Some(num_fmt.format_code()));
+        }
+        if let Some(fill) = &self.fill {
+            let pattern_fill = &fill.pattern_fill;
+            if matches!(pattern_fill.pattern_type.as_str(), "" | "solid") {
+                let color = if pattern_fill.bg_color.is_empty() { &pattern_fill.fg_color } else { &pattern_fill.bg_color };
+                format.fill.pattern_type = "solid".to_string();
+                format.fill.fg_color = color.get_format();
+            } else {
+                format.fill = fill.get_format();
+            }
+        }
+        if let Some(border) = self.border.as_ref().and_then(|border| border.first()) {
+            format.border = border.get_format();
+        }
+        format
+    }
+}
+
 #[derive(Debug, Clone, Deserialize, Serialize)]
 struct TableStyles {
     #[serde(rename = "@count", default)]
@@ -262,9 +347,19 @@ impl StyleSheet {
         let fills = self.fills.get_or_insert(Fills::default());
         let fill = Fill::from_format(&format.fill);
         let fill_id = fills.add_fill(&fill);
+        let num_fmt_id = match format.num_format.num_fmt_id {
+            Some(num_fmt_id) => num_fmt_id,
+            None => self.num_fmts.get_or_insert(NumFmts::default()).add_format_code(&format.num_format.format_code),
+        };
         let mut xf = Xf::default();
         let align = Alignment::from_format(&format.align);
         xf.alignment = Some(align);
+        if format.protection != FormatProtection::default() {
+            xf.protection = Some(Protection::from_format(&format.protection));
+            xf.apply_protection = 1;
+        }
+        xf.num_fmt_id = num_fmt_id;
+        xf.apply_number_format = if num_fmt_id == 0 { 0 } else { 1 };
         xf.font_id = font_id;
         xf.border_id = border_id;
         xf.fill_id = fill_id;
@@ -272,6 +367,146 @@ impl StyleSheet {
         cell_xfs.add_xf(&xf)
     }
 
+    ///
+    /// Get a style like `style_id` that displays dates, the style is kept if it already does
+    ///
+    pub(crate) fn add_date_format(&mut self, style_id: Option<u32>, num_fmt_id: u32) -> u32 {
+        if let Some(style_id) = style_id {
+            if self.is_date_style(style_id) {
+                return style_id;
+            }
+        }
+        let cell_xfs = self.cell_xfs.get_or_insert(CellXfs::default());
+        let mut xf = style_id
+            .and_then(|style_id| cell_xfs.get_xf(style_id))
+            .cloned()
+            .unwrap_or_else(Xf::default);
+        xf.num_fmt_id = num_fmt_id;
+        xf.apply_number_format = 1;
+        cell_xfs.add_xf(&xf)
+    }
+
+    ///
+    /// Add a differential format, used by conditional formats, and return its id
+    ///
+    pub(crate) fn add_dxf(&mut self, format: &Format) -> u32 {
+        let num_fmt = if format.num_format != FormatNumber::default() {
+            let num_fmt_id = match format.num_format.num_fmt_id {
+                Some(num_fmt_id) => num_fmt_id,
+                None => self.num_fmts.get_or_insert(NumFmts::default()).add_format_code(&format.num_format.format_code),
+            };
+            Some(NumFmt::new(num_fmt_id, &format.num_format.format_code))
+        } else {
+            None
+        };
+        let dxf = Dxf::from_format(format, num_fmt);
+        self.dxfs.get_or_insert(Dxfs::default()).add_dxf(&dxf)
+    }
+
+    ///
+    /// Add a cell format of another style sheet with its font, fill, border, number format and named cell style,
+    /// and return its id here
+    ///
+    pub(crate) fn import_xf(&mut self, source: &StyleSheet, style_id: u32) -> u32 {
+        let Some(mut xf) = source.cell_xfs.as_ref().and_then(|cell_xfs| cell_xfs.get_xf(style_id)).cloned() else {
+            return 0;
+        };
+        self.import_xf_parts(source, &mut xf);
+        xf.xf_id = self.import_cell_style(source, xf.xf_id);
+        self.cell_xfs.get_or_insert(CellXfs::default()).add_xf(&xf)
+    }
+
+    ///
+    /// The id here of the format of a named cell style of another style sheet.
+    /// A cell style named like one here is taken as it is here, like Excel does.
+    ///
+    fn import_cell_style(&mut self, source: &StyleSheet, xf_id: u32) -> u32 {
+        let Some(mut xf) = source.cell_style_xfs.as_ref().and_then(|cell_style_xfs| cell_style_xfs.xf.get(xf_id as usize)).cloned() else {
+            return 0;
+        };
+        let cell_style = source.cell_styles.as_ref()
+            .and_then(|cell_styles| cell_styles.cell_styles.iter().find(|cell_style| cell_style.xf_id == xf_id));
+        let default_cell_styles = CellStyles::default();
+        let existing = cell_style.and_then(|cell_style| self.cell_styles.as_ref().unwrap_or(&default_cell_styles).cell_styles.iter()
+            .find(|existing| existing.name.eq_ignore_ascii_case(&cell_style.name)));
+        if let Some(existing) = existing {
+            return existing.xf_id;
+        }
+        self.import_xf_parts(source, &mut xf);
+        let cell_style_xfs = self.cell_style_xfs.get_or_insert(CellStyleXfs::default());
+        let id = match cell_style_xfs.xf.iter().position(|existing| *existing == xf) {
+            Some(id) if cell_style.is_none() => id as u32,
+            _ => {
+                cell_style_xfs.xf.push(xf);
+                cell_style_xfs.count = cell_style_xfs.xf.len() as u32;
+                cell_style_xfs.xf.len() as u32 - 1
+            }
+        };
+        if let Some(cell_style) = cell_style {
+            let cell_styles = self.cell_styles.get_or_insert(CellStyles::default());
+            cell_styles.cell_styles.push(CellStyle { xf_id: id, ..cell_style.clone() });
+            cell_styles.count = cell_styles.cell_styles.len() as u32;
+        }
+        id
+    }
+
+    ///
+    /// Point a format of another style sheet to its font, fill, border and number format copied here
+    ///
+    fn import_xf_parts(&mut self, source: &StyleSheet, xf: &mut Xf) {
+        if let Some(font) = source.fonts.as_ref().and_then(|fonts| fonts.get_font(xf.font_id)) {
+            xf.font_id = self.fonts.get_or_insert(Fonts::default()).add_font(font);
+        }
+        if let Some(fill) = source.fills.as_ref().and_then(|fills| fills.get_fill(xf.fill_id)) {
+            xf.fill_id = self.fills.get_or_insert(Fills::default()).add_fill(fill);
+        }
+        if let Some(border) = source.borders.as_ref().and_then(|borders| borders.get_border(xf.border_id)) {
+            xf.border_id = self.borders.get_or_insert(Borders::default()).add_border(border);
+        }
+        xf.num_fmt_id = self.import_num_fmt(source, xf.num_fmt_id);
+    }
+
+    ///
+    /// Add a differential format of another style sheet and return its id here
+    ///
+    pub(crate) fn import_dxf(&mut self, source: &StyleSheet, dxf_id: u32) -> u32 {
+        let Some(mut dxf) = source.dxfs.as_ref().and_then(|dxfs| dxfs.dxf.get(dxf_id as usize)).cloned() else {
+            return 0;
+        };
+        if let Some(num_fmt) = &dxf.num_fmt {
+            let num_fmt_id = self.import_num_fmt(source, num_fmt.num_fmt_id());
+            dxf.num_fmt = Some(NumFmt::new(num_fmt_id, num_fmt.format_code()));
+        }
+        self.dxfs.get_or_insert(Dxfs::default()).add_dxf(&dxf)
+    }
+
+    ///
+    /// The id here of a number format of another style sheet, the built-in formats keep their ids
+    ///
+    fn import_num_fmt(&mut self, source: &StyleSheet, num_fmt_id: u32) -> u32 {
+        match source.num_fmts.as_ref().and_then(|num_fmts| num_fmts.get_format_code(num_fmt_id)) {
+            Some(format_code) => self.num_fmts.get_or_insert(NumFmts::default()).add_format_code(format_code),
+            None => num_fmt_id,
+        }
+    }
+
+    pub(crate) fn get_dxf_format(&self, dxf_id: u32) -> Option<Format> {
+        self.dxfs.as_ref()?.dxf.get(dxf_id as usize).map(Dxf::to_format)
+    }
+
+    pub(crate) fn is_date_style(&self, style_id: u32) -> bool {
+        let num_fmt_id = match self.cell_xfs.as_ref().and_then(|cell_xfs| cell_xfs.get_xf(style_id)) {
+            Some(xf) => xf.num_fmt_id,
+            None => return false,
+        };
+        if is_date_num_fmt_id(num_fmt_id) {
+            return true;
+        }
+        self.num_fmts.as_ref()
+            .and_then(|num_fmts| num_fmts.get_format_code(num_fmt_id))
+            .is_some_and(is_date_format_code)
+    }
+
     pub(crate) fn update_format(&self, format: &mut Format, style_id: u32) {
         if let Some(cell_xfs) = &self.cell_xfs {
             if let Some(xf) = cell_xfs.get_xf(style_id) {
@@ -281,6 +516,9 @@ impl StyleSheet {
                 format.border = border.get_format();
                 let fill = &self.fills.as_ref().unwrap().get_fill(xf.fill_id);
                 format.fill = fill.get_format();
+                let format_code = self.num_fmts.as_ref().and_then(|num_fmts| num_fmts.get_format_code(xf.num_fmt_id));
+                format.num_format = FormatNumber::from_num_fmt_id(xf.num_fmt_id, format_code);
+                format.protection = xf.protection.as_ref().get_format();
             }
         }
     }
@@ -305,10 +543,9 @@ impl StyleSheet {
 }
 
 impl Io<StyleSheet> for StyleSheet {
-    fn save<P: AsRef<Path>>(&self, file_path: P) {
+    fn save(&self, writer: &mut XlsxFileWriter) {
         let xml = se::to_string_with_root("styleSheet", &self).unwrap();
         let xml = format!("<?xml version=\"1.0\" encoding=\"UTF-8\" standalone=\"yes\"?>\n{}", xml);
-        let mut file = XlsxFileWriter::from_path(file_path, XlsxFileType::StylesFile).unwrap();
-        file.write_all(xml.as_ref()).unwrap();
+        writer.write_all(XlsxFileType::StylesFile, xml.as_ref());
     }
 }
\ No newline at end of file
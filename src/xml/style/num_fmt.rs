@@ -1,6 +1,6 @@
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone, Deserialize, Serialize)]
+#[derive(Debug, Clone, Deserialize, Serialize, Default)]
 pub(crate) struct NumFmts {
     #[serde(rename = "@count", default)]
     count: u32,
@@ -8,10 +8,152 @@ pub(crate) struct NumFmts {
     num_fmt: Vec<NumFmt>,
 }
 
-#[derive(Debug, Clone, Deserialize, Serialize, Default)]
+#[derive(Debug, Clone, Deserialize, Serialize, Default, PartialEq)]
 pub(crate) struct NumFmt {
     #[serde(rename = "@numFmtId", default)]
     num_fmt_id: u32,
     #[serde(rename = "@formatCode", default)]
     format_code: String
-}
\ No newline at end of file
+}
+
+impl NumFmt {
+    pub(crate) fn new(num_fmt_id: u32, format_code: &str) -> NumFmt {
+        NumFmt { num_fmt_id, format_code: format_code.to_string() }
+    }
+
+    pub(crate) fn num_fmt_id(&self) -> u32 {
+        self.num_fmt_id
+    }
+
+    pub(crate) fn format_code(&self) -> &str {
+        &self.format_code
+    }
+}
+
+impl NumFmts {
+    pub(crate) fn get_format_code(&self, num_fmt_id: u32) -> Option<&str> {
+        self.num_fmt.iter()
+            .find(|num_fmt| num_fmt.num_fmt_id == num_fmt_id)
+            .map(|num_fmt| num_fmt.format_code.as_str())
+    }
+
+    ///
+    /// Add a custom format code and return its id, ids of custom formats start from 164
+    ///
+    pub(crate) fn add_format_code(&mut self, format_code: &str) -> u32 {
+        if let Some(num_fmt) = self.num_fmt.iter().find(|num_fmt| num_fmt.format_code == format_code) {
+            return num_fmt.num_fmt_id;
+        }
+        let num_fmt_id = self.num_fmt.iter()
+            .map(|num_fmt| num_fmt.num_fmt_id + 1)
+            .max()
+            .unwrap_or_default()
+            .max(164);
+        self.num_fmt.push(NumFmt { num_fmt_id, format_code: format_code.to_string() });
+        self.count = self.num_fmt.len() as u32;
+        num_fmt_id
+    }
+}
+
+const BUILTIN_FORMAT_CODES: [(u32, &str); 36] = [
+    (0, "General"),
+    (1, "0"),
+    (2, "0.00"),
+    (3, "#,##0"),
+    (4, "#,##0.00"),
+    (5, "($#,##0_);($#,##0)"),
+    (6, "($#,##0_);[Red]($#,##0)"),
+    (7, "($#,##0.00_);($#,##0.00)"),
+    (8, "($#,##0.00_);[Red]($#,##0.00)"),
+    (9, "0%"),
+    (10, "0.00%"),
+    (11, "0.00E+00"),
+    (12, "# ?/?"),
+    (13, "# ??/??"),
+    (14, "m/d/yy"),
+    (15, "d-mmm-yy"),
+    (16, "d-mmm"),
+    (17, "mmm-yy"),
+    (18, "h:mm AM/PM"),
+    (19, "h:mm:ss AM/PM"),
+    (20, "h:mm"),
+    (21, "h:mm:ss"),
+    (22, "m/d/yy h:mm"),
+    (37, "#,##0_);(#,##0)"),
+    (38, "#,##0_);[Red](#,##0)"),
+    (39, "#,##0.00_);(#,##0.00)"),
+    (40, "#,##0.00_);[Red](#,##0.00)"),
+    (41, "_(* #,##0_);_(* \\(#,##0\\);_(* \"-\"_);_(@_)"),
+    (42, "_(\"$\"* #,##0_);_(\"$\"* \\(#,##0\\);_(\"$\"* \"-\"_);_(@_)"),
+    (43, "_(* #,##0.00_);_(* \\(#,##0.00\\);_(* \"-\"??_);_(@_)"),
+    (44, "_(\"$\"* #,##0.00_);_(\"$\"* \\(#,##0.00\\);_(\"$\"* \"-\"??_);_(@_)"),
+    (45, "mm:ss"),
+    (46, "[h]:mm:ss"),
+    (47, "mm:ss.0"),
+    (48, "##0.0E+0"),
+    (49, "@"),
+];
+
+pub(crate) fn builtin_format_code(num_fmt_id: u32) -> Option<&'static str> {
+    BUILTIN_FORMAT_CODES.iter()
+        .find(|(id, _)| *id == num_fmt_id)
+        .map(|(_, format_code)| *format_code)
+}
+
+pub(crate) fn builtin_num_fmt_id(format_code: &str) -> Option<u32> {
+    BUILTIN_FORMAT_CODES.iter()
+        .find(|(_, code)| *code == format_code)
+        .map(|(id, _)| *id)
+}
+
+///
+/// Built-in number formats that display dates or times
+///
+pub(crate) fn is_date_num_fmt_id(num_fmt_id: u32) -> bool {
+    matches!(num_fmt_id, 14..=22 | 27..=36 | 45..=47 | 50..=58)
+}
+
+///
+/// Whether a format code displays dates or times, texts in quotes, escaped characters
+/// and sections in brackets other than elapsed times like `[h]` are ignored
+///
+pub(crate) fn is_date_format_code(format_code: &str) -> bool {
+    let mut chars = format_code.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' => {
+                chars.by_ref().find(|&c| c == '"');
+            }
+            '\\' | '_' | '*' => {
+                chars.next();
+            }
+            '[' => {
+                let section: String = chars.by_ref().take_while(|&c| c != ']').collect();
+                let section = section.to_ascii_lowercase();
+                if !section.is_empty() && section.chars().all(|c| matches!(c, 'h' | 'm' | 's')) {
+                    return true;
+                }
+            }
+            ';' => return false,
+            'd' | 'D' | 'm' | 'M' | 'y' | 'Y' | 'h' | 'H' | 's' | 'S' => return true,
+            _ => {}
+        }
+    }
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::xml::style::num_fmt::is_date_format_code;
+
+    #[test]
+    fn test_date_format_code() {
+        assert!(is_date_format_code("yyyy-mm-dd"));
+        assert!(is_date_format_code("[$-409]h:mm AM/PM"));
+        assert!(is_date_format_code("[h]:mm"));
+        assert!(!is_date_format_code("#,##0.00"));
+        assert!(!is_date_format_code("\"Days\" 0"));
+        assert!(!is_date_format_code("[Red]0.00"));
+        assert!(!is_date_format_code("General"));
+    }
+}
@@ -0,0 +1,10 @@
+use serde::{Deserialize, Serialize};
+use crate::xml::common::serialize_bool;
+
+#[derive(Debug, Deserialize, Serialize, PartialEq, Clone, Default)]
+pub(crate) struct Protection {
+    #[serde(rename = "@locked", default, serialize_with = "serialize_bool", skip_serializing_if = "Option::is_none")]
+    pub(crate) locked: Option<bool>,
+    #[serde(rename = "@hidden", default, serialize_with = "serialize_bool", skip_serializing_if = "Option::is_none")]
+    pub(crate) hidden: Option<bool>,
+}
@@ -1,12 +1,13 @@
 use serde::{Deserialize, Serialize};
 use crate::api::format::FormatAlign;
 use crate::xml::style::alignment::Alignment;
+use crate::xml::style::protection::Protection;
 use crate::xml::common;
 
 #[derive(Debug, Deserialize, Serialize, PartialEq, Clone)]
 pub(crate) struct Xf {
     #[serde(rename = "@numFmtId", default)]
-    num_fmt_id: u32,
+    pub(crate) num_fmt_id: u32,
     #[serde(rename = "@fontId", default)]
     pub(crate) font_id: u32,
     #[serde(rename = "@fillId", default)]
@@ -14,7 +15,7 @@ pub(crate) struct Xf {
     #[serde(rename = "@borderId", default)]
     pub(crate) border_id: u32,
     #[serde(rename = "@xfId", default)] //, skip_serializing_if = "common::is_zero")]
-    xf_id: u32,
+    pub(crate) xf_id: u32,
     #[serde(rename = "@applyNumberFormat", default, skip_serializing_if = "common::is_zero")]
     pub(crate) apply_number_format: u32,
     #[serde(rename = "@applyFont", default, skip_serializing_if = "common::is_zero")]
@@ -26,9 +27,11 @@ pub(crate) struct Xf {
     #[serde(rename = "@applyAlignment", default, skip_serializing_if = "common::is_zero")]
     pub(crate) apply_alignment: u32,
     #[serde(rename = "@applyProtection", default, skip_serializing_if = "common::is_zero")]
-    apply_protection: u32,
+    pub(crate) apply_protection: u32,
     #[serde(rename = "alignment", skip_serializing_if = "Option::is_none")]
     pub(crate) alignment: Option<Alignment>,
+    #[serde(rename = "protection", skip_serializing_if = "Option::is_none")]
+    pub(crate) protection: Option<Protection>,
 }
 
 impl Xf {
@@ -46,6 +49,7 @@ impl Xf {
             apply_number_format: 0,
             alignment: None,
             apply_protection: 0,
+            protection: None,
         }
     }
     
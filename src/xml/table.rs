@@ -0,0 +1,234 @@
+use quick_xml::se;
+use serde::{Deserialize, Serialize};
+use crate::api::cell::location::LocationRange;
+use crate::api::table::{Table as ApiTable, TableColumn as ApiTableColumn, TableFunction, TableOptions};
+use crate::file::{XlsxFileType, XlsxFileWriter};
+use crate::utils::reference_helper::Shift;
+use crate::xml::common::XmlnsAttrs;
+use crate::xml::io::IoV2;
+use crate::xml::worksheet::auto_filter::AutoFilter;
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename = "table")]
+pub(crate) struct Table {
+    #[serde(flatten)]
+    xmlns_attrs: XmlnsAttrs,
+    #[serde(rename = "@id")]
+    id: u32,
+    #[serde(rename = "@name")]
+    name: String,
+    #[serde(rename = "@displayName")]
+    display_name: String,
+    #[serde(rename = "@ref")]
+    sqref: String,
+    #[serde(rename = "@tableType", skip_serializing_if = "Option::is_none")]
+    table_type: Option<String>,
+    #[serde(rename = "@headerRowCount", skip_serializing_if = "Option::is_none")]
+    header_row_count: Option<u32>,
+    #[serde(rename = "@totalsRowCount", skip_serializing_if = "Option::is_none")]
+    totals_row_count: Option<u32>,
+    #[serde(rename = "@totalsRowShown", skip_serializing_if = "Option::is_none")]
+    totals_row_shown: Option<u8>,
+    #[serde(rename = "@headerRowDxfId", skip_serializing_if = "Option::is_none")]
+    header_row_dxf_id: Option<u32>,
+    #[serde(rename = "@dataDxfId", skip_serializing_if = "Option::is_none")]
+    data_dxf_id: Option<u32>,
+    #[serde(rename = "@totalsRowDxfId", skip_serializing_if = "Option::is_none")]
+    totals_row_dxf_id: Option<u32>,
+    #[serde(rename = "@tableBorderDxfId", skip_serializing_if = "Option::is_none")]
+    table_border_dxf_id: Option<u32>,
+    #[serde(rename = "autoFilter", skip_serializing_if = "Option::is_none")]
+    auto_filter: Option<AutoFilter>,
+    #[serde(rename = "tableColumns")]
+    table_columns: TableColumns,
+    #[serde(rename = "tableStyleInfo", skip_serializing_if = "Option::is_none")]
+    table_style_info: Option<TableStyleInfo>,
+    // Only new tables are saved, the loaded ones are copied from the source
+    #[serde(skip)]
+    changed: bool,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, Default)]
+struct TableColumns {
+    #[serde(rename = "@count", default)]
+    count: u32,
+    #[serde(rename = "tableColumn", default)]
+    table_column: Vec<TableColumn>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+struct TableColumn {
+    #[serde(rename = "@id")]
+    id: u32,
+    #[serde(rename(serialize = "@xr3:uid", deserialize = "@uid"), skip_serializing_if = "Option::is_none")]
+    xr3_uid: Option<String>,
+    #[serde(rename = "@name")]
+    name: String,
+    #[serde(rename = "@totalsRowFunction", skip_serializing_if = "Option::is_none")]
+    totals_row_function: Option<String>,
+    #[serde(rename = "@totalsRowLabel", skip_serializing_if = "Option::is_none")]
+    totals_row_label: Option<String>,
+    #[serde(rename = "@dataDxfId", skip_serializing_if = "Option::is_none")]
+    data_dxf_id: Option<u32>,
+    #[serde(rename = "calculatedColumnFormula", skip_serializing_if = "Option::is_none")]
+    calculated_column_formula: Option<String>,
+    #[serde(rename = "totalsRowFormula", skip_serializing_if = "Option::is_none")]
+    totals_row_formula: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+struct TableStyleInfo {
+    #[serde(rename = "@name", skip_serializing_if = "Option::is_none")]
+    name: Option<String>,
+    #[serde(rename = "@showFirstColumn", default)]
+    show_first_column: u8,
+    #[serde(rename = "@showLastColumn", default)]
+    show_last_column: u8,
+    #[serde(rename = "@showRowStripes", default)]
+    show_row_stripes: u8,
+    #[serde(rename = "@showColumnStripes", default)]
+    show_column_stripes: u8,
+}
+
+impl Table {
+    ///
+    /// A new table over the range, the headers name the columns in order
+    ///
+    pub(crate) fn new<L: LocationRange>(id: u32, name: &str, loc_range: &L, headers: &[String], options: &TableOptions) -> Table {
+        let (first_row, first_col, last_row, last_col) = loc_range.to_range();
+        let table_column = headers.iter().enumerate().map(|(i, header)| {
+            let column = options.columns.get(i).filter(|_| options.total_row);
+            TableColumn {
+                id: 1 + i as u32,
+                xr3_uid: None,
+                name: header.clone(),
+                totals_row_function: column.and_then(|c| c.total_function).map(|f| f.to_name().to_string()),
+                totals_row_label: column.and_then(|c| c.total_label.clone()),
+                data_dxf_id: None,
+                calculated_column_formula: None,
+                totals_row_formula: None,
+            }
+        }).collect::<Vec<TableColumn>>();
+        let auto_filter = if options.header_row && options.autofilter {
+            let last_row = if options.total_row { last_row - 1 } else { last_row };
+            Some(AutoFilter::new(&(first_row, first_col, last_row, last_col).to_range_ref()))
+        } else {
+            None
+        };
+        Table {
+            xmlns_attrs: XmlnsAttrs::shared_string_default(),
+            id,
+            name: name.to_string(),
+            display_name: name.to_string(),
+            sqref: loc_range.to_range_ref(),
+            table_type: None,
+            header_row_count: if options.header_row { None } else { Some(0) },
+            totals_row_count: if options.total_row { Some(1) } else { None },
+            totals_row_shown: if options.total_row { None } else { Some(0) },
+            header_row_dxf_id: None,
+            data_dxf_id: None,
+            totals_row_dxf_id: None,
+            table_border_dxf_id: None,
+            auto_filter,
+            table_columns: TableColumns {
+                count: table_column.len() as u32,
+                table_column,
+            },
+            table_style_info: Some(TableStyleInfo {
+                name: Some(options.style.clone()),
+                show_first_column: options.show_first_column as u8,
+                show_last_column: options.show_last_column as u8,
+                show_row_stripes: options.show_row_stripes as u8,
+                show_column_stripes: options.show_column_stripes as u8,
+            }),
+            changed: true,
+        }
+    }
+
+    pub(crate) fn get_id(&self) -> u32 {
+        self.id
+    }
+
+    pub(crate) fn get_range(&self) -> (u32, u32, u32, u32) {
+        self.sqref.as_str().to_range()
+    }
+
+    ///
+    /// A table keeps its first row and its columns when cells are inserted or deleted
+    ///
+    pub(crate) fn can_shift(&self, shift: Shift) -> bool {
+        let (first_row, first_col, _, last_col) = self.get_range();
+        let first_index = if shift.is_row() { first_row } else { first_col };
+        match shift.shift_range(self.get_range()) {
+            Some((_, new_first_col, _, new_last_col)) => shift.shift_index(first_index).is_some()
+                && new_last_col - new_first_col == last_col - first_col,
+            None => false,
+        }
+    }
+
+    pub(crate) fn shift(&mut self, shift: Shift) {
+        if let Some(sqref) = shift.shift_ref(&self.sqref) {
+            if sqref != self.sqref {
+                self.sqref = sqref;
+                self.auto_filter = self.auto_filter.take().and_then(|auto_filter| auto_filter.shift(shift));
+                self.changed = true;
+            }
+        }
+    }
+
+    ///
+    /// Number and name a table copied from another workbook, with its differential formats there
+    ///
+    pub(crate) fn import<D: FnMut(u32) -> u32>(&mut self, id: u32, name: &str, dxf: &mut D) {
+        self.id = id;
+        self.name = name.to_string();
+        self.display_name = name.to_string();
+        for dxf_id in [&mut self.header_row_dxf_id, &mut self.data_dxf_id, &mut self.totals_row_dxf_id, &mut self.table_border_dxf_id] {
+            *dxf_id = dxf_id.map(&mut *dxf);
+        }
+        self.table_columns.table_column.iter_mut()
+            .for_each(|column| column.data_dxf_id = column.data_dxf_id.map(&mut *dxf));
+        self.changed = true;
+    }
+
+    ///
+    /// Rewrite the formulas of the calculated columns and of the totals row
+    ///
+    pub(crate) fn map_formulas<F: FnMut(&str) -> String>(&mut self, map: &mut F) {
+        for column in &mut self.table_columns.table_column {
+            for formula in [&mut column.calculated_column_formula, &mut column.totals_row_formula].into_iter().flatten() {
+                let mapped = map(formula);
+                if mapped != *formula {
+                    *formula = mapped;
+                    self.changed = true;
+                }
+            }
+        }
+    }
+
+    pub(crate) fn to_api_table(&self) -> ApiTable {
+        ApiTable {
+            name: self.display_name.clone(),
+            range: self.get_range(),
+            columns: self.table_columns.table_column.iter().map(|column| ApiTableColumn {
+                header: column.name.clone(),
+                total_function: column.totals_row_function.as_deref().and_then(TableFunction::from_name),
+                total_label: column.totals_row_label.clone(),
+            }).collect(),
+            style: self.table_style_info.as_ref().and_then(|info| info.name.clone()),
+            header_row: self.header_row_count != Some(0),
+            total_row: self.totals_row_count.unwrap_or_default() > 0,
+        }
+    }
+
+    pub(crate) fn save(&self, writer: &mut XlsxFileWriter, table_id: u32) {
+        if !self.changed {
+            return;
+        }
+        let xml = se::to_string_with_root("table", &self).unwrap();
+        let xml = format!("<?xml version=\"1.0\" encoding=\"UTF-8\" standalone=\"yes\"?>\n{}", xml);
+        writer.write_all(XlsxFileType::Tables(table_id), xml.as_ref());
+    }
+}
+
+impl IoV2<Table> for Table {}
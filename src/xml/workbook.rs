@@ -1,22 +1,20 @@
 mod bookviews;
-mod defined_names;
+pub(crate) mod defined_names;
 
-use std::fs::File;
-use std::io;
+use std::cmp::Ordering;
 use std::io::Read;
-use std::path::Path;
 use quick_xml::{de, se};
 use serde::{Deserialize, Serialize};
 use zip::read::ZipFile;
-use zip::ZipArchive;
 use crate::api::relationship::Rel;
-use crate::file::{XlsxFileReader, XlsxFileType, XlsxFileWriter};
+use crate::file::{XlsxFileType, XlsxFileWriter};
 use crate::result::{WorkSheetError, WorkbookError};
 use crate::WorkbookResult;
-use crate::xml::common::{XmlnsAttrs};
+use crate::utils::password_util::PasswordHash;
+use crate::utils::reference_helper::is_same_sheet;
+use crate::xml::common::{serialize_bool, XmlnsAttrs};
 use crate::xml::extension::ExtensionList;
 use crate::xml::io::Io;
-use crate::xml::style::StyleSheet;
 use crate::xml::workbook::bookviews::BookViews;
 use crate::xml::workbook::defined_names::DefinedNames;
 
@@ -33,6 +31,8 @@ pub(crate) struct Workbook {
     workbook_pr: WorkbookPr,
     #[serde(rename(serialize = "xr:revisionPtr", deserialize = "revisionPtr"), skip_serializing_if = "Option::is_none")]
     xr_revision_ptr: Option<XrRevisionPtr>,
+    #[serde(rename = "workbookProtection", skip_serializing_if = "Option::is_none")]
+    workbook_protection: Option<WorkbookProtection>,
     #[serde(rename = "bookViews")]
     pub(crate) book_views: BookViews,
     #[serde(rename = "sheets")]
@@ -43,6 +43,9 @@ pub(crate) struct Workbook {
     calc_pr: Option<CalcPr>,
     #[serde(rename = "extLst", skip_serializing_if = "Option::is_none")]
     ext_lst: Option<ExtensionList>,
+    // The names of the tables of all the worksheets by the ids of their parts
+    #[serde(skip)]
+    table_names: Vec<(u32, String)>,
 }
 
 unsafe impl Sync for Workbook {}
@@ -50,14 +53,116 @@ unsafe impl Send for Workbook {}
 
 
 impl Workbook {
+    ///
+    /// Whether dates are counted from 1904-01-01 instead of 1900-01-01
+    ///
+    pub(crate) fn is_date1904(&self) -> bool {
+        self.workbook_pr.date1904.unwrap_or_default()
+    }
+
+    pub(crate) fn set_date1904(&mut self, date1904: bool) {
+        self.workbook_pr.date1904 = if date1904 { Some(true) } else { None };
+    }
+
+    ///
+    /// Ask the spreadsheet application to recalculate every formula when the file is opened
+    ///
+    pub(crate) fn set_full_calc_on_load(&mut self) {
+        self.calc_pr.get_or_insert_with(Default::default).full_calc_on_load = Some(true);
+    }
+
+    ///
+    /// Lock the structure or the windows of the workbook, with the password replacing the previous one if any
+    ///
+    pub(crate) fn protect(&mut self, lock_structure: bool, lock_windows: bool, password: Option<&str>) {
+        let workbook_protection = self.workbook_protection.get_or_insert_with(Default::default);
+        if lock_structure {
+            workbook_protection.lock_structure = Some(true);
+        }
+        if lock_windows {
+            workbook_protection.lock_windows = Some(true);
+        }
+        if let Some(password) = password {
+            let hash = PasswordHash::new(password);
+            workbook_protection.workbook_password = None;
+            workbook_protection.workbook_algorithm_name = Some(hash.algorithm_name);
+            workbook_protection.workbook_hash_value = Some(hash.hash_value);
+            workbook_protection.workbook_salt_value = Some(hash.salt_value);
+            workbook_protection.workbook_spin_count = Some(hash.spin_count);
+        }
+    }
+
+    ///
+    /// A table or a defined name has the name, regardless of the case
+    ///
+    pub(crate) fn is_name_taken(&self, name: &str) -> bool {
+        self.defined_names.has_name(name)
+            || self.table_names.iter().any(|(_, table_name)| table_name.eq_ignore_ascii_case(name))
+    }
+
+    ///
+    /// Name the table of a part, or rename it
+    ///
+    pub(crate) fn set_table_name(&mut self, id: u32, name: &str) {
+        match self.table_names.iter_mut().find(|(table_id, _)| *table_id == id) {
+            Some((_, table_name)) => *table_name = name.to_string(),
+            None => self.table_names.push((id, name.to_string())),
+        }
+    }
+
+    ///
+    /// A name for the table of a part named after its id, like `Table3`, or `Table3_2` if a table or a defined name has it
+    ///
+    pub(crate) fn free_table_name(&self, id: u32) -> String {
+        (1..)
+            .map(|number| if number == 1 { format!("Table{id}") } else { format!("Table{id}_{number}") })
+            .find(|name| !self.is_name_taken(name))
+            .unwrap()
+    }
+
+    ///
+    /// A name for the copy of a table, numbered like `Table1_2` after the name of the table
+    ///
+    pub(crate) fn free_copied_table_name(&self, name: &str) -> String {
+        (2..)
+            .map(|number| format!("{name}_{number}"))
+            .find(|name| !self.is_name_taken(name))
+            .unwrap()
+    }
+
+    pub(crate) fn remove_table_name(&mut self, id: u32) {
+        self.table_names.retain(|(table_id, _)| *table_id != id);
+    }
+
     pub(crate) fn next_sheet_id(&self) -> u32 {
         let max_sheet_id = self.sheets.sheets.iter().max_by_key(|s| { s.sheet_id }).unwrap().sheet_id;
         1 + max_sheet_id
     }
 
+    ///
+    /// A sheet has the name, regardless of the case
+    ///
+    pub(crate) fn has_sheet_name(&self, name: &str) -> bool {
+        self.sheets.sheets.iter().any(|sheet| is_same_sheet(&sheet.name, name))
+    }
+
+    ///
+    /// Rename a sheet and the references to it in the defined names, unless another sheet has the name
+    ///
+    pub(crate) fn rename_sheet(&mut self, old_name: &str, new_name: &str) -> Result<(), WorkSheetError> {
+        if self.sheets.sheets.iter().any(|sheet| sheet.name != old_name && is_same_sheet(&sheet.name, new_name)) {
+            return Err(WorkSheetError::DuplicatedSheets);
+        }
+        self.sheets.sheets.iter_mut()
+            .filter(|sheet| sheet.name == old_name)
+            .for_each(|sheet| sheet.name = new_name.to_string());
+        self.defined_names.rename_sheet(old_name, new_name);
+        Ok(())
+    }
+
     pub(crate) fn add_worksheet(&mut self, id: u32, r_id: u32) -> WorkbookResult<String> {
         let name = format!("Sheet{id}");
-        if let Some(_) = self.sheets.sheets.iter().find(|s| { s.name == name }) {
+        if self.has_sheet_name(&name) {
             return Err(WorkbookError::SheetError(WorkSheetError::DuplicatedSheets));
         }
         self.sheets.sheets.push(Sheet::by_name(r_id, id, &name));
@@ -67,15 +172,45 @@ impl Workbook {
     pub(crate) fn add_worksheet_v2(&mut self, r_id: u32, default_name: Option<&str>) -> WorkbookResult<(u32, String)> {
         let id = self.next_sheet_id();
         let name = if let Some(default_name) = default_name { default_name.to_string() } else { format!("Sheet{id}") };
-        if let Some(_) = self.sheets.sheets.iter().find(|s| { s.name == name }) {
+        if self.has_sheet_name(&name) {
             return Err(WorkbookError::SheetError(WorkSheetError::DuplicatedSheets));
         }
         self.sheets.sheets.push(Sheet::by_name(r_id, id, &name));
         Ok((id, name))
     }
 
+    ///
+    /// Remove the sheet at a position with the names local to it, and return the relationship id of the sheet
+    ///
+    pub(crate) fn remove_sheet(&mut self, position: usize) -> u32 {
+        let sheet = self.sheets.sheets.remove(position);
+        let (position, last) = (position as u32, self.sheets.sheets.len() as u32 - 1);
+        self.defined_names.move_local_sheets(|local| match local.cmp(&position) {
+            Ordering::Less => Some(local),
+            Ordering::Equal => None,
+            Ordering::Greater => Some(local - 1),
+        });
+        // the tab of the removed sheet goes to the sheet taking its place
+        self.book_views.move_tabs(|tab| if tab > position { tab - 1 } else { tab.min(last) });
+        sheet.r_id.get_id()
+    }
+
+    pub(crate) fn move_sheet(&mut self, from: usize, to: usize) {
+        let sheet = self.sheets.sheets.remove(from);
+        self.sheets.sheets.insert(to, sheet);
+        let (from, to) = (from as u32, to as u32);
+        let new_position = |position: u32| match position {
+            _ if position == from => to,
+            _ if from < to && (from..=to).contains(&position) => position - 1,
+            _ if to < from && (to..from).contains(&position) => position + 1,
+            _ => position,
+        };
+        self.defined_names.move_local_sheets(|local| Some(new_position(local)));
+        self.book_views.move_tabs(new_position);
+    }
+
     pub(crate) fn add_worksheet_by_name(&mut self, id: u32, r_id: u32, name: &str) -> WorkbookResult<()> { 
-        if let Some(_) = self.sheets.sheets.iter().find(|s| { s.name == name }) {
+        if self.has_sheet_name(&name) {
             return Err(WorkbookError::SheetError(WorkSheetError::DuplicatedSheets));
         }
         self.sheets.sheets.push(Sheet::by_name(r_id, id, &name));
@@ -122,12 +257,47 @@ impl Default for FileSharing {
 
 #[derive(Debug, Deserialize, Serialize, Default)]
 struct WorkbookPr {
+    #[serde(rename = "@date1904", skip_serializing_if = "Option::is_none")]
+    date1904: Option<bool>,
     #[serde(rename = "@filterPrivacy", skip_serializing_if = "Option::is_none")]
     filter_privacy: Option<u32>,
     #[serde(rename = "@defaultThemeVersion", skip_serializing_if = "Option::is_none")]
     default_theme_version: Option<String>,
 }
 
+///
+/// The protection of the structure and the windows of the workbook, and of its revisions
+///
+#[derive(Debug, Deserialize, Serialize, Default)]
+struct WorkbookProtection {
+    #[serde(rename = "@workbookPassword", skip_serializing_if = "Option::is_none")]
+    workbook_password: Option<String>,
+    #[serde(rename = "@revisionsPassword", skip_serializing_if = "Option::is_none")]
+    revisions_password: Option<String>,
+    #[serde(rename = "@lockStructure", default, serialize_with = "serialize_bool", skip_serializing_if = "Option::is_none")]
+    lock_structure: Option<bool>,
+    #[serde(rename = "@lockWindows", default, serialize_with = "serialize_bool", skip_serializing_if = "Option::is_none")]
+    lock_windows: Option<bool>,
+    #[serde(rename = "@lockRevision", default, serialize_with = "serialize_bool", skip_serializing_if = "Option::is_none")]
+    lock_revision: Option<bool>,
+    #[serde(rename = "@revisionsAlgorithmName", skip_serializing_if = "Option::is_none")]
+    revisions_algorithm_name: Option<String>,
+    #[serde(rename = "@revisionsHashValue", skip_serializing_if = "Option::is_none")]
+    revisions_hash_value: Option<String>,
+    #[serde(rename = "@revisionsSaltValue", skip_serializing_if = "Option::is_none")]
+    revisions_salt_value: Option<String>,
+    #[serde(rename = "@revisionsSpinCount", skip_serializing_if = "Option::is_none")]
+    revisions_spin_count: Option<u32>,
+    #[serde(rename = "@workbookAlgorithmName", skip_serializing_if = "Option::is_none")]
+    workbook_algorithm_name: Option<String>,
+    #[serde(rename = "@workbookHashValue", skip_serializing_if = "Option::is_none")]
+    workbook_hash_value: Option<String>,
+    #[serde(rename = "@workbookSaltValue", skip_serializing_if = "Option::is_none")]
+    workbook_salt_value: Option<String>,
+    #[serde(rename = "@workbookSpinCount", skip_serializing_if = "Option::is_none")]
+    workbook_spin_count: Option<u32>,
+}
+
 #[derive(Debug, Deserialize, Serialize)]
 pub(crate) struct Sheets {
     #[serde(rename = "sheet")]
@@ -165,6 +335,31 @@ impl Default for Sheet {
     }
 }
 
+///
+/// Check a sheet name against the rules of Excel
+///
+///
+/// The name of a copy of a sheet like `Data Duplicated`, the name of the sheet is cut to keep it at 31 characters
+///
+pub(crate) fn duplicated_sheet_name(name: &str) -> String {
+    const SUFFIX: &str = " Duplicated";
+    let name = name.chars().take(31 - SUFFIX.len()).collect::<String>();
+    format!("{name}{SUFFIX}")
+}
+
+pub(crate) fn check_sheet_name(name: &str) -> Result<(), WorkSheetError> {
+    let invalid = name.is_empty()
+        || name.chars().count() > 31
+        || name.contains(['[', ']', ':', '*', '?', '/', '\\'])
+        || name.starts_with('\'')
+        || name.ends_with('\'')
+        || name.eq_ignore_ascii_case("History");
+    match invalid {
+        true => Err(WorkSheetError::InvalidSheetName),
+        false => Ok(()),
+    }
+}
+
 impl Sheet {
     pub(crate) fn by_id(r_id: u32, id: u32) -> Sheet {
         Sheet {
@@ -194,12 +389,15 @@ impl Sheet {
 struct CalcPr {
     #[serde(rename = "@calcId", default, skip_serializing_if = "String::is_empty")]
     calc_id: String,
+    #[serde(rename = "@fullCalcOnLoad", skip_serializing_if = "Option::is_none")]
+    full_calc_on_load: Option<bool>,
 }
 
 impl Default for CalcPr {
     fn default() -> Self {
         CalcPr {
             calc_id: String::from("162913"),
+            full_calc_on_load: None,
         }
     }
 }
@@ -226,11 +424,13 @@ impl Default for Workbook {
             file_sharing: None,
             workbook_pr: Default::default(),
             xr_revision_ptr: None,
+            workbook_protection: None,
             book_views: Default::default(),
             sheets: Default::default(),
             defined_names: Default::default(),
             calc_pr: Default::default(),
             ext_lst: Some(Default::default()),
+            table_names: vec![],
         }
     }
 }
@@ -245,10 +445,9 @@ impl Workbook {
 }
 
 impl Io<Workbook> for Workbook {
-    fn save<P: AsRef<Path>>(& self, file_path: P) {
+    fn save(&self, writer: &mut XlsxFileWriter) {
         let xml = se::to_string_with_root("workbook", &self).unwrap();
         let xml = format!("<?xml version=\"1.0\" encoding=\"UTF-8\" standalone=\"yes\"?>\n{}", xml);
-        let mut file = XlsxFileWriter::from_path(file_path, XlsxFileType::WorkbookFile).unwrap();
-        file.write_all(xml.as_ref()).unwrap();
+        writer.write_all(XlsxFileType::WorkbookFile, xml.as_ref());
     }
 }
\ No newline at end of file
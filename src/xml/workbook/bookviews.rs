@@ -18,6 +18,8 @@ pub(crate) struct WorkbookView {
     pub(crate) window_height: u32,
     #[serde(rename = "@tabRatio", skip_serializing_if = "Option::is_none")]
     pub(crate) tab_ratio: Option<u32>,
+    #[serde(rename = "@firstSheet", skip_serializing_if = "Option::is_none")]
+    first_sheet: Option<u32>,
     #[serde(rename = "@activeTab", skip_serializing_if = "Option::is_none")]
     pub(crate) active_tab: Option<u32>
 }
@@ -38,4 +40,18 @@ impl BookViews {
     pub(crate) fn set_active_tab(&mut self, active_tab: u32) {
         self.book_views[0].active_tab = Some(active_tab);
     }
+
+    pub(crate) fn get_active_tab(&self) -> u32 {
+        self.book_views.first().and_then(|book_view| book_view.active_tab).unwrap_or_default()
+    }
+
+    ///
+    /// Follow the sheets to their new positions, in the active tab and the first tab shown of each window
+    ///
+    pub(crate) fn move_tabs<F: Fn(u32) -> u32>(&mut self, new_position: F) {
+        for book_view in &mut self.book_views {
+            book_view.first_sheet = book_view.first_sheet.map(&new_position).filter(|&tab| tab > 0);
+            book_view.active_tab = book_view.active_tab.map(&new_position).filter(|&tab| tab > 0);
+        }
+    }
 }
\ No newline at end of file
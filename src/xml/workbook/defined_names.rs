@@ -1,4 +1,5 @@
 use serde::{Deserialize, Serialize};
+use crate::utils::reference_helper::{remove_sheet_in_formula, rename_sheet_in_formula, Shift};
 
 #[derive(Debug, Deserialize, Serialize, Default)]
 pub(crate) struct DefinedNames {
@@ -15,6 +16,91 @@ impl DefinedNames {
         let defined_name = DefinedName::new(name, value, local_sheet_id);
         self.defined_names.push(defined_name)
     }
+
+    ///
+    /// Replace the value of a name with the same scope, or add it
+    ///
+    pub(crate) fn set_define_name(&mut self, name: &str, value: &str, local_sheet_id: Option<u32>) {
+        match self.defined_names.iter_mut()
+            .find(|defined_name| defined_name.name.eq_ignore_ascii_case(name) && defined_name.local_sheet_id == local_sheet_id) {
+            Some(defined_name) => defined_name.value = value.to_string(),
+            None => self.add_define_name(name, value, local_sheet_id),
+        }
+    }
+
+    ///
+    /// The value of a name in exactly this scope
+    ///
+    pub(crate) fn get_scoped_value(&self, name: &str, local_sheet_id: Option<u32>) -> Option<&str> {
+        self.defined_names.iter()
+            .find(|defined_name| defined_name.name.eq_ignore_ascii_case(name) && defined_name.local_sheet_id == local_sheet_id)
+            .map(|defined_name| defined_name.value.as_str())
+    }
+
+    ///
+    /// The value of a name, the one local to a sheet first, then the one of the workbook
+    ///
+    pub(crate) fn get_value(&self, name: &str, local_sheet_id: u32) -> Option<String> {
+        let names = self.defined_names.iter().filter(|defined_name| defined_name.name.eq_ignore_ascii_case(name));
+        names.min_by_key(|defined_name| match defined_name.local_sheet_id {
+            Some(id) if id == local_sheet_id => 0,
+            None => 1,
+            Some(_) => 2,
+        })
+            .filter(|defined_name| defined_name.local_sheet_id.is_none_or(|id| id == local_sheet_id))
+            .map(|defined_name| defined_name.value.clone())
+    }
+
+    ///
+    /// Follow the sheets to their new positions in the names local to them, the names of a removed sheet are removed
+    ///
+    pub(crate) fn move_local_sheets<F: Fn(u32) -> Option<u32>>(&mut self, new_position: F) {
+        self.defined_names.retain_mut(|defined_name| match defined_name.local_sheet_id {
+            Some(local_sheet_id) => match new_position(local_sheet_id) {
+                Some(local_sheet_id) => {
+                    defined_name.local_sheet_id = Some(local_sheet_id);
+                    true
+                }
+                None => false,
+            },
+            None => true,
+        });
+    }
+
+    ///
+    /// The names local to a sheet with their values
+    ///
+    pub(crate) fn get_local_names(&self, local_sheet_id: u32) -> Vec<(&str, &str)> {
+        self.defined_names.iter()
+            .filter(|defined_name| defined_name.local_sheet_id == Some(local_sheet_id))
+            .map(|defined_name| (defined_name.name.as_str(), defined_name.value.as_str()))
+            .collect()
+    }
+
+    ///
+    /// Whether a name is defined, in the workbook or in a worksheet
+    ///
+    pub(crate) fn has_name(&self, name: &str) -> bool {
+        self.defined_names.iter().any(|defined_name| defined_name.name.eq_ignore_ascii_case(name))
+    }
+
+    pub(crate) fn rename_sheet(&mut self, old_name: &str, new_name: &str) {
+        self.defined_names.iter_mut()
+            .for_each(|defined_name| defined_name.value = rename_sheet_in_formula(&defined_name.value, old_name, new_name));
+    }
+
+    pub(crate) fn remove_sheet(&mut self, sheet_name: &str) {
+        self.defined_names.iter_mut()
+            .for_each(|defined_name| defined_name.value = remove_sheet_in_formula(&defined_name.value, sheet_name));
+    }
+
+    ///
+    /// Shift the references of the names to the cells of a worksheet
+    ///
+    pub(crate) fn shift(&mut self, shift: Shift, sheet_name: &str) {
+        self.defined_names.iter_mut()
+            .for_each(|defined_name| defined_name.value = shift.shift_formula(&defined_name.value, sheet_name, false));
+    }
 }
 
 #[derive(Debug, Deserialize, Serialize)]
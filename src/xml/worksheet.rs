@@ -1,25 +1,30 @@
 use std::collections::HashMap;
-use std::io::Read;
-use std::path::Path;
-use quick_xml::{de, se};
+use quick_xml::se;
 use serde::{Deserialize, Serialize};
-use zip::read::ZipFile;
 use crate::api::cell::location::{Location, LocationRange};
+use crate::api::data_validation::DataValidation as ApiDataValidation;
+use crate::api::protection::ProtectionOptions;
 use crate::api::relationship::Rel;
-use crate::file::{XlsxFileReader, XlsxFileType, XlsxFileWriter};
+use crate::file::{XlsxFileType, XlsxFileWriter};
 use crate::{Column, Filters, FormatColor};
-use crate::result::{ColResult, WorkSheetResult};
+use crate::result::ColResult;
+use crate::utils::reference_helper::{remove_sheet_in_formula, rename_sheet_in_formula, MAX_COL, MAX_ROW, Shift};
 use crate::xml::common::{PhoneticPr, XmlnsAttrs};
+use crate::xml::extension::ExtensionList;
+use crate::xml::extension::x14_conditional_formattings::{X14CfRule, X14ConditionalFormatting};
 use crate::xml::worksheet::auto_filter::AutoFilter;
 use crate::xml::worksheet::columns::{Col, Cols};
-use crate::xml::worksheet::conditional_formatting::ConditionalFormatting;
+use crate::xml::worksheet::conditional_formatting::{CfRule, ConditionalFormatting};
 use crate::xml::worksheet::data_validations::DataValidations;
 use crate::xml::worksheet::hyperlinks::Hyperlinks;
 use crate::xml::worksheet::ignore_errors::IgnoredErrors;
 use crate::xml::worksheet::merge_cells::MergeCells;
 use crate::xml::worksheet::page_margins::PageMargins;
-use crate::xml::worksheet::row_breaks::RowBreaks;
+use crate::xml::worksheet::page_setup::{PageSetup, PrintOptions};
+use crate::xml::worksheet::header_footer::HeaderFooter;
+use crate::xml::worksheet::page_breaks::PageBreaks;
 use crate::xml::worksheet::sheet_format::SheetFormatPr;
+use crate::xml::worksheet::sheet_protection::{ProtectedRanges, SheetProtection};
 use crate::xml::worksheet::table_parts::TableParts;
 use self::sheet_views::SheetViews;
 use self::sheet_data::SheetData;
@@ -33,12 +38,15 @@ mod merge_cells;
 mod columns;
 mod ignore_errors;
 mod hyperlinks;
+mod page_breaks;
 mod page_margins;
-mod auto_filter;
-mod row_breaks;
-mod conditional_formatting;
+mod page_setup;
+mod header_footer;
+pub(crate) mod auto_filter;
+pub(crate) mod conditional_formatting;
 mod data_validations;
 mod table_parts;
+mod sheet_protection;
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
 #[serde(rename="worksheet")]
@@ -57,6 +65,10 @@ pub(crate) struct WorkSheet {
     pub(crate) cols: Option<Cols>,
     #[serde(rename = "sheetData", default)]
     pub(crate) sheet_data: SheetData,
+    #[serde(rename = "sheetProtection", default, skip_serializing_if = "Option::is_none")]
+    sheet_protection: Option<SheetProtection>,
+    #[serde(rename = "protectedRanges", default, skip_serializing_if = "Option::is_none")]
+    protected_ranges: Option<ProtectedRanges>,
     #[serde(rename = "mergeCells", default, skip_serializing_if = "Option::is_none")]
     merge_cells: Option<MergeCells>,
     #[serde(rename = "phoneticPr", default, skip_serializing_if = "Option::is_none")]
@@ -75,20 +87,26 @@ pub(crate) struct WorkSheet {
     page_margins: PageMargins,
     #[serde(rename = "pageSetup", default, skip_serializing_if = "Option::is_none")]
     page_setup: Option<PageSetup>,
-    #[serde(rename = "autoFilter", default, skip_serializing_if = "Option::is_none")]
-    header_footer: Option<HeaderFooter>,
+    #[serde(rename = "headerFooter", default, skip_serializing_if = "Option::is_none")]
+    pub(crate) header_footer: Option<HeaderFooter>,
     #[serde(rename = "rowBreaks", default, skip_serializing_if = "Option::is_none")]
-    row_breakers: Option<RowBreaks>,
+    row_breaks: Option<PageBreaks>,
+    #[serde(rename = "colBreaks", default, skip_serializing_if = "Option::is_none")]
+    col_breaks: Option<PageBreaks>,
     #[serde(rename = "ignoredErrors", default, skip_serializing_if = "Option::is_none")]
     ignored_errors: Option<IgnoredErrors>,
     #[serde(rename = "drawing", default, skip_serializing_if = "Option::is_none")]
     drawing: Option<Drawing>,
     #[serde(rename = "legacyDrawing", default, skip_serializing_if = "Option::is_none")]
     legacy_drawing: Option<Drawing>,
-    #[serde(rename = "tableParts", default, skip_serializing_if = "Option::is_none")]
-    table_parts: Option<TableParts>,
+    #[serde(rename = "legacyDrawingHF", default, skip_serializing_if = "Option::is_none")]
+    legacy_drawing_hf: Option<Drawing>,
     #[serde(rename = "picture", default, skip_serializing_if = "Option::is_none")]
     picture: Option<Picture>,
+    #[serde(rename = "tableParts", default, skip_serializing_if = "Option::is_none")]
+    table_parts: Option<TableParts>,
+    #[serde(rename = "extLst", default, skip_serializing_if = "Option::is_none")]
+    ext_lst: Option<ExtensionList>,
 }
 
 impl WorkSheet {
@@ -103,6 +121,99 @@ impl WorkSheet {
     }
 }
 
+///
+/// Protection xml method
+///
+impl WorkSheet {
+    pub(crate) fn protect(&mut self, options: &ProtectionOptions) {
+        self.sheet_protection = Some(SheetProtection::from_options(options));
+    }
+
+    pub(crate) fn unprotect(&mut self) {
+        self.sheet_protection = None;
+    }
+
+    pub(crate) fn is_protected(&self) -> bool {
+        self.sheet_protection.as_ref().is_some_and(SheetProtection::is_protected)
+    }
+
+    pub(crate) fn add_protected_range<L: LocationRange>(&mut self, name: &str, loc_range: &L, password: Option<&str>) {
+        let protected_ranges = self.protected_ranges.get_or_insert(Default::default());
+        protected_ranges.add_protected_range(name, &loc_range.to_range_ref(), password);
+    }
+
+    pub(crate) fn get_protected_ranges(&self) -> Vec<(String, String)> {
+        match &self.protected_ranges {
+            Some(protected_ranges) => protected_ranges.get_protected_ranges(),
+            None => vec![],
+        }
+    }
+}
+
+///
+/// Page setup xml method
+///
+impl WorkSheet {
+    pub(crate) fn set_orientation(&mut self, orientation: &str) {
+        self.page_setup.get_or_insert_with(Default::default).orientation = Some(orientation.to_string());
+    }
+
+    pub(crate) fn set_paper_size(&mut self, paper_size: u32) {
+        self.page_setup.get_or_insert_with(Default::default).paper_size = Some(paper_size);
+    }
+
+    pub(crate) fn get_paper_size(&self) -> Option<u32> {
+        self.page_setup.as_ref().and_then(|page_setup| page_setup.paper_size)
+    }
+
+    pub(crate) fn set_print_scale(&mut self, scale: u32) {
+        self.page_setup.get_or_insert_with(Default::default).scale = Some(scale);
+        if let Some(sheet_pr) = &mut self.sheet_pr {
+            sheet_pr.set_fit_to_page(false);
+        }
+    }
+
+    pub(crate) fn fit_to_pages(&mut self, width: u32, height: u32) {
+        let page_setup = self.page_setup.get_or_insert_with(Default::default);
+        // one page is the default of both directions
+        page_setup.fit_to_width = if width == 1 { None } else { Some(width) };
+        page_setup.fit_to_height = if height == 1 { None } else { Some(height) };
+        self.sheet_pr.get_or_insert_with(Default::default).set_fit_to_page(true);
+    }
+
+    pub(crate) fn set_margins(&mut self, left: f64, right: f64, top: f64, bottom: f64, header: f64, footer: f64) {
+        self.page_margins.set_margins(left, right, top, bottom, header, footer);
+    }
+
+    pub(crate) fn set_print_options<F: FnOnce(&mut PrintOptions)>(&mut self, set: F) {
+        let print_options = self.print_options.get_or_insert_with(Default::default);
+        set(print_options);
+        if print_options.is_empty() {
+            self.print_options = None;
+        }
+    }
+
+    pub(crate) fn set_row_breaks(&mut self, rows: &[u32]) {
+        let rows = rows.iter().copied().filter(|row| (1..MAX_ROW).contains(row)).collect::<Vec<u32>>();
+        let row_breaks = PageBreaks::new(&rows, MAX_COL - 1);
+        self.row_breaks = (!row_breaks.is_empty()).then_some(row_breaks);
+    }
+
+    pub(crate) fn get_row_breaks(&self) -> Vec<u32> {
+        self.row_breaks.as_ref().map(PageBreaks::get_ids).unwrap_or_default()
+    }
+
+    pub(crate) fn set_col_breaks(&mut self, cols: &[u32]) {
+        let cols = cols.iter().copied().filter(|col| (1..MAX_COL).contains(col)).collect::<Vec<u32>>();
+        let col_breaks = PageBreaks::new(&cols, MAX_ROW - 1);
+        self.col_breaks = (!col_breaks.is_empty()).then_some(col_breaks);
+    }
+
+    pub(crate) fn get_col_breaks(&self) -> Vec<u32> {
+        self.col_breaks.as_ref().map(PageBreaks::get_ids).unwrap_or_default()
+    }
+}
+
 ///
 /// Column xml method
 ///
@@ -193,6 +304,225 @@ impl WorkSheet {
         drawing.r_id = Rel::from_id(r_id);
     }
 
+    pub(crate) fn get_legacy_drawing_rid(&self) -> Option<u32> {
+        self.legacy_drawing.as_ref().map(|legacy_drawing| legacy_drawing.r_id.get_id())
+    }
+
+    pub(crate) fn set_legacy_drawing(&mut self, r_id: u32) {
+        self.legacy_drawing = Some(Drawing { r_id: Rel::from_id(r_id) });
+    }
+
+    pub(crate) fn get_legacy_drawing_hf_rid(&self) -> Option<u32> {
+        self.legacy_drawing_hf.as_ref().map(|legacy_drawing_hf| legacy_drawing_hf.r_id.get_id())
+    }
+
+    pub(crate) fn set_legacy_drawing_hf(&mut self, r_id: u32) {
+        self.legacy_drawing_hf = Some(Drawing { r_id: Rel::from_id(r_id) });
+    }
+
+    pub(crate) fn add_table_part(&mut self, r_id: u32) {
+        let table_parts = self.table_parts.get_or_insert(TableParts::default());
+        table_parts.add_table_part(r_id);
+    }
+
+    pub(crate) fn add_data_validation<L: LocationRange>(&mut self, loc_range: &L, data_validation: &ApiDataValidation) {
+        let data_validations = self.data_validations.get_or_insert(DataValidations::default());
+        data_validations.add_data_validation(&loc_range.to_range_ref(), data_validation);
+    }
+
+    pub(crate) fn get_data_validations(&self) -> Vec<(String, ApiDataValidation)> {
+        self.data_validations.as_ref().map(|data_validations| data_validations.get_data_validations()).unwrap_or_default()
+    }
+
+    ///
+    /// Add a rule of a range, after the rules of the worksheet in priority.
+    /// The part of the rule only known by Excel 2010 and later is written in the extension list,
+    /// linked to the rule by an id, or instead of the rule when Excel 2007 doesn't know the rule.
+    ///
+    pub(crate) fn add_conditional_formatting<L: LocationRange>(&mut self, loc_range: &L, mut cf_rule: CfRule, x14_cf_rule: Option<X14CfRule>) {
+        let x14_cf_rules = self.get_x14_conditional_formatting().into_iter()
+            .flat_map(|conditional_formatting| &conditional_formatting.cf_rule);
+        let priority = 1 + self.conditional_formatting.iter()
+            .flat_map(|conditional_formatting| conditional_formatting.get_rules())
+            .map(|cf_rule| cf_rule.priority)
+            .chain(x14_cf_rules.filter_map(|x14_cf_rule| x14_cf_rule.priority))
+            .max()
+            .unwrap_or_default();
+        let sqref = loc_range.to_range_ref();
+        match x14_cf_rule {
+            Some(mut x14_cf_rule) => {
+                let id = format!("{{00000000-0000-0000-0000-{priority:012X}}}");
+                if x14_cf_rule.data_bar.is_some() {
+                    cf_rule.priority = priority;
+                    cf_rule.ext_lst = Some(ExtensionList::from_x14_id(&id));
+                    self.conditional_formatting.push(ConditionalFormatting::new(&sqref, cf_rule));
+                } else {
+                    x14_cf_rule.priority = Some(priority);
+                }
+                x14_cf_rule.id = Some(id);
+                self.ext_lst.get_or_insert(Default::default())
+                    .add_x14_conditional_formatting(X14ConditionalFormatting::new(&sqref, x14_cf_rule));
+            }
+            None => {
+                cf_rule.priority = priority;
+                self.conditional_formatting.push(ConditionalFormatting::new(&sqref, cf_rule));
+            }
+        }
+    }
+
+    pub(crate) fn get_conditional_formatting(&self) -> &Vec<ConditionalFormatting> {
+        &self.conditional_formatting
+    }
+
+    pub(crate) fn get_x14_conditional_formatting(&self) -> Vec<&X14ConditionalFormatting> {
+        match &self.ext_lst {
+            Some(ext_lst) => ext_lst.get_x14_conditional_formattings(),
+            None => vec![],
+        }
+    }
+
+    ///
+    /// Keep the extensions of a loaded worksheet which are written back
+    ///
+    pub(crate) fn retain_known_extensions(&mut self) {
+        if let Some(ext_lst) = &mut self.ext_lst {
+            ext_lst.retain_known();
+            if ext_lst.is_empty() {
+                self.ext_lst = None;
+            }
+        }
+    }
+
+    ///
+    /// The last row or column of the cells with a value or a formula and of the merged cells, 0 if there is none
+    ///
+    pub(crate) fn last_used_index(&self, is_row: bool) -> u32 {
+        let merged = self.merge_cells.as_ref().map_or(vec![], |merge_cells| merge_cells.get_ranges());
+        merged.into_iter()
+            .map(|(_, _, last_row, last_col)| if is_row { last_row } else { last_col })
+            .fold(self.sheet_data.last_filled_index(is_row), u32::max)
+    }
+
+    ///
+    /// Move the cells after inserted or deleted rows or columns, with the ranges and the formulas referring to them
+    ///
+    pub(crate) fn shift(&mut self, shift: Shift, sheet_name: &str) {
+        self.sheet_data.shift(shift, sheet_name);
+        if let Some(dimension) = &mut self.dimension {
+            dimension.refer = shift.shift_ref(&dimension.refer).unwrap_or(String::from("A1"));
+        }
+        self.cols = self.cols.as_ref().and_then(|cols| cols.shift(shift));
+        if let Some(merge_cells) = &mut self.merge_cells {
+            merge_cells.shift(shift);
+            if merge_cells.is_empty() {
+                self.merge_cells = None;
+            }
+        }
+        self.conditional_formatting = self.conditional_formatting.drain(..)
+            .filter_map(|conditional_formatting| conditional_formatting.shift(shift, sheet_name))
+            .collect();
+        if let Some(ext_lst) = &mut self.ext_lst {
+            ext_lst.retain_x14_conditional_formattings(|conditional_formatting| match shift.shift_sqref(&conditional_formatting.sqref) {
+                Some(sqref) => {
+                    conditional_formatting.sqref = sqref;
+                    true
+                }
+                None => false,
+            });
+        }
+        if let Some(data_validations) = &mut self.data_validations {
+            data_validations.shift(shift, sheet_name);
+            if data_validations.is_empty() {
+                self.data_validations = None;
+            }
+        }
+        if let Some(hyperlinks) = &mut self.hyperlinks {
+            hyperlinks.shift(shift, sheet_name);
+            if hyperlinks.is_empty() {
+                self.hyperlinks = None;
+            }
+        }
+        self.auto_filter = self.auto_filter.take().and_then(|auto_filter| auto_filter.shift(shift));
+        if let Some(protected_ranges) = &mut self.protected_ranges {
+            protected_ranges.shift(shift);
+            if protected_ranges.is_empty() {
+                self.protected_ranges = None;
+            }
+        }
+        let page_breaks = if shift.is_row() { &mut self.row_breaks } else { &mut self.col_breaks };
+        if let Some(breaks) = page_breaks.as_mut() {
+            breaks.shift(shift);
+            if breaks.is_empty() {
+                *page_breaks = None;
+            }
+        }
+    }
+
+    ///
+    /// Shift the references of the formulas and of the links to the cells of another worksheet
+    ///
+    pub(crate) fn shift_formulas(&mut self, shift: Shift, sheet_name: &str) {
+        self.sheet_data.shift_formulas(shift, sheet_name, false);
+        self.conditional_formatting.iter_mut()
+            .for_each(|conditional_formatting| conditional_formatting.shift_formulas(shift, sheet_name, false));
+        if let Some(data_validations) = &mut self.data_validations {
+            data_validations.shift_formulas(shift, sheet_name, false);
+        }
+        if let Some(hyperlinks) = &mut self.hyperlinks {
+            hyperlinks.shift_formulas(shift, sheet_name, false);
+        }
+    }
+
+    ///
+    /// Make the formulas and the links referring to a worksheet follow it renamed
+    ///
+    pub(crate) fn rename_sheet(&mut self, old_name: &str, new_name: &str) {
+        self.map_formulas(&mut |formula| rename_sheet_in_formula(formula, old_name, new_name));
+    }
+
+    ///
+    /// Make the references of the formulas and of the links to a removed worksheet `#REF!`
+    ///
+    pub(crate) fn remove_sheet(&mut self, sheet_name: &str) {
+        self.map_formulas(&mut |formula| remove_sheet_in_formula(formula, sheet_name));
+    }
+
+    ///
+    /// Rewrite the formulas of the cells, the conditional formats and the data validations, and the links
+    ///
+    pub(crate) fn map_formulas<F: FnMut(&str) -> String>(&mut self, map: &mut F) {
+        self.sheet_data.map_formulas(map);
+        self.conditional_formatting.iter_mut()
+            .for_each(|conditional_formatting| conditional_formatting.map_formulas(map));
+        if let Some(data_validations) = &mut self.data_validations {
+            data_validations.map_formulas(map);
+        }
+        if let Some(hyperlinks) = &mut self.hyperlinks {
+            hyperlinks.map_formulas(map);
+        }
+    }
+
+    ///
+    /// Point the cells, the rows and the columns to the styles and the shared strings of another workbook,
+    /// and the conditional formats to its differential formats
+    ///
+    pub(crate) fn import_ids<S, D, T>(&mut self, style: &mut S, dxf: &mut D, shared_string: &mut T)
+    where S: FnMut(u32) -> u32, D: FnMut(u32) -> u32, T: FnMut(usize) -> Option<usize> {
+        self.sheet_data.import_ids(style, shared_string);
+        if let Some(cols) = &mut self.cols {
+            cols.import_styles(style);
+        }
+        self.conditional_formatting.iter_mut()
+            .for_each(|conditional_formatting| conditional_formatting.import_dxfs(dxf));
+    }
+
+    pub(crate) fn get_table_rids(&self) -> Vec<u32> {
+        match &self.table_parts {
+            Some(table_parts) => table_parts.get_rids(),
+            None => vec![],
+        }
+    }
+
     pub(crate) fn add_hyperlink<L: Location>(&mut self, loc: &L, r_id: u32) {
         let hyperlinks = self.hyperlinks.get_or_insert(Default::default());
         hyperlinks.add_hyperlink(loc, r_id);
@@ -275,6 +605,8 @@ impl Default for WorkSheet {
             sheet_format_pr: SheetFormatPr::default(),
             cols: None,
             sheet_data: SheetData::default(),
+            sheet_protection: None,
+            protected_ranges: None,
             merge_cells: None,
             conditional_formatting: vec![],
             data_validations: None,
@@ -284,60 +616,26 @@ impl Default for WorkSheet {
             table_parts: None,
             header_footer: None,
             print_options: None,
-            row_breakers: None,
+            row_breaks: None,
+            col_breaks: None,
             ignored_errors: None,
             picture: None,
             hyperlinks: None,
             drawing: None,
             auto_filter: None,
             legacy_drawing: None,
+            legacy_drawing_hf: None,
+            ext_lst: None,
         }
     }
 }
 
-#[derive(Debug, Clone, Deserialize, Serialize)]
-struct PageSetup {
-    #[serde(rename = "@paperSize", skip_serializing_if = "Option::is_none")]
-    paper_size: Option<u8>,
-    #[serde(rename = "@scale", skip_serializing_if = "Option::is_none")]
-    scale: Option<u32>,
-    #[serde(rename = "@orientation", skip_serializing_if = "Option::is_none")]
-    orientation: Option<String>,
-    #[serde(rename = "@horizontalDpi", skip_serializing_if = "Option::is_none")]
-    horizontal_dpi: Option<i32>,
-    #[serde(rename = "@verticalDpi", skip_serializing_if = "Option::is_none")]
-    vertical_dpi: Option<i32>,
-    #[serde(rename(serialize = "@r:id", deserialize = "@id"), skip_serializing_if = "Option::is_none")]
-    r_id: Option<Rel>,
-}
-
-#[derive(Debug, Clone, Deserialize, Serialize)]
-struct HeaderFooter {
-    #[serde(rename = "@alignWithMargins", skip_serializing_if = "Option::is_none")]
-    align_with_margins: Option<u8>,
-}
-
-#[derive(Debug, Clone, Deserialize, Serialize)]
-struct PrintOptions {
-    #[serde(rename = "@horizontalCentered", skip_serializing_if = "Option::is_none")]
-    horizontal_centered: Option<u8>,
-}
-
 impl WorkSheet {
-    pub(crate) fn from_path<P: AsRef<Path>>(file_path: P, target: &str) -> WorkSheetResult<WorkSheet> {
-        let mut file = XlsxFileReader::from_path(file_path, XlsxFileType::SheetFile(target.to_string()))?;
-        let mut xml = String::new();
-        file.read_to_string(&mut xml)?;
-        let work_sheet = de::from_str(&xml).unwrap();
-        Ok(work_sheet)
-    }
-
-    pub(crate) fn save<P: AsRef<Path>>(& self, file_path: P, target: &str) {
+    pub(crate) fn save(&self, writer: &mut XlsxFileWriter, target: &str) {
         let xml = se::to_string_with_root("worksheet", &self).unwrap();
-        let mut xml = format!("<?xml version=\"1.0\" encoding=\"UTF-8\" standalone=\"yes\"?>\n{}", xml);
+        let xml = format!("<?xml version=\"1.0\" encoding=\"UTF-8\" standalone=\"yes\"?>\n{}", xml);
         // xml = xml.replace("&quot;", "\"");
-        let mut file = XlsxFileWriter::from_path(file_path, XlsxFileType::SheetFile(target.to_string())).unwrap();
-        file.write_all(xml.as_ref()).unwrap();
+        writer.write_all(XlsxFileType::SheetFile(target.to_string()), xml.as_ref());
     }
 }
 
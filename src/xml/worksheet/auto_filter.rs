@@ -1,16 +1,25 @@
 use serde::{Deserialize, Serialize};
 use crate::Filters as ApiFilters;
 use crate::Filter as ApiFilter;
+use crate::api::cell::location::LocationRange;
+use crate::utils::reference_helper::Shift;
 
 #[derive(Debug, Clone, Deserialize, Serialize, Default)]
 pub(crate) struct AutoFilter {
     #[serde(rename = "@ref")]
     pub(crate) sqref: String,
-    #[serde(rename = "filterColumn")]
+    #[serde(rename = "filterColumn", default, skip_serializing_if = "Vec::is_empty")]
     filter_column: Vec<FilterColumn>,
 }
 
 impl AutoFilter {
+    pub(crate) fn new(sqref: &str) -> AutoFilter {
+        AutoFilter {
+            sqref: sqref.to_string(),
+            filter_column: vec![],
+        }
+    }
+
     pub(crate) fn add_filters(&mut self, col: u32, filters: &ApiFilters) {
         let mut filter_column = FilterColumn::new(col);
         if filters.is_custom_filters() {
@@ -20,6 +29,26 @@ impl AutoFilter {
         }
         self.filter_column.push(filter_column);
     }
+
+    ///
+    /// Move the range of the filter, None if it is deleted.
+    /// The columns of the filters are relative to the range, and follow the columns of the range.
+    ///
+    pub(crate) fn shift(mut self, shift: Shift) -> Option<AutoFilter> {
+        let first_col = self.sqref.as_str().to_range().1;
+        self.sqref = shift.shift_ref(&self.sqref)?;
+        if !shift.is_row() {
+            let new_first_col = self.sqref.as_str().to_range().1;
+            self.filter_column.retain_mut(|filter_column| match shift.shift_index(first_col + filter_column.col_id) {
+                Some(col) => {
+                    filter_column.col_id = col - new_first_col;
+                    true
+                }
+                None => false,
+            });
+        }
+        Some(self)
+    }
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
@@ -4,6 +4,7 @@ use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use crate::api::worksheet::col::Column;
 use crate::core::internal_tree::InternalTree;
 use crate::result::ColResult;
+use crate::utils::reference_helper::Shift;
 
 #[derive(Debug, Clone, Deserialize, Serialize, Default)]
 pub(crate) struct Cols {
@@ -78,6 +79,38 @@ impl Cols {
     pub(crate) fn is_empty(&self) -> bool {
         self.col.is_empty()
     }
+
+    ///
+    /// Point the columns to the styles of another workbook
+    ///
+    pub(crate) fn import_styles<S: FnMut(u32) -> u32>(&mut self, style: &mut S) {
+        let cols: Vec<(i32, i32, Col)> = self.col_tree.to_vec().into_iter()
+            .map(|(l, r, mut col)| {
+                col.style = col.style.map(&mut *style);
+                (l, r, col)
+            })
+            .collect();
+        if !cols.is_empty() {
+            self.col_tree = InternalTree::from_vec(&cols);
+        }
+    }
+
+    ///
+    /// Move the columns after inserted or deleted columns, None if no column is left
+    ///
+    pub(crate) fn shift(&self, shift: Shift) -> Option<Cols> {
+        let cols: Vec<(i32, i32, Col)> = self.col_tree.to_vec().into_iter()
+            .filter(|(l, r, _)| *l > 0 && *r > *l)
+            .filter_map(|(l, r, col)| {
+                let (min, max) = shift.shift_span(l as u32, r as u32 - 1)?;
+                Some((min as i32, max as i32 + 1, col))
+            })
+            .collect();
+        if cols.is_empty() {
+            return None;
+        }
+        Some(Cols { col: vec![], col_tree: InternalTree::from_vec(&cols) })
+    }
 }
 
 #[derive(Debug, Deserialize, Serialize, PartialEq, Default, Copy, Clone)]
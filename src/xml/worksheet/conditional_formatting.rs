@@ -1,4 +1,10 @@
 use serde::{Deserialize, Serialize};
+use crate::api::conditional_format::{ColorScale as ApiColorScale, ConditionalFormatOperator, ConditionalFormatRule, ConditionalFormatTimePeriod, ConditionalFormatValue, DataBar as ApiDataBar, IconSet as ApiIconSet, IconSetType};
+use crate::FormatColor;
+use crate::utils::reference_helper::Shift;
+use crate::xml::common::FromFormat;
+use crate::xml::extension::ExtensionList;
+use crate::xml::style::color::Color;
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub(crate) struct ConditionalFormatting {
@@ -8,16 +14,344 @@ pub(crate) struct ConditionalFormatting {
     sqref: String,
 }
 
-#[derive(Debug, Clone, Deserialize, Serialize)]
-struct CfRule {
+impl ConditionalFormatting {
+    pub(crate) fn new(sqref: &str, cf_rule: CfRule) -> ConditionalFormatting {
+        ConditionalFormatting {
+            cf_rule: vec![cf_rule],
+            sqref: sqref.to_string(),
+        }
+    }
+
+    pub(crate) fn get_sqref(&self) -> &str {
+        &self.sqref
+    }
+
+    pub(crate) fn get_rules(&self) -> &Vec<CfRule> {
+        &self.cf_rule
+    }
+
+    ///
+    /// Move the range of the rules, None if all its cells are deleted
+    ///
+    pub(crate) fn shift(mut self, shift: Shift, sheet_name: &str) -> Option<ConditionalFormatting> {
+        self.sqref = shift.shift_sqref(&self.sqref)?;
+        self.shift_formulas(shift, sheet_name, true);
+        Some(self)
+    }
+
+    pub(crate) fn shift_formulas(&mut self, shift: Shift, sheet_name: &str, in_sheet: bool) {
+        self.cf_rule.iter_mut()
+            .flat_map(|cf_rule| cf_rule.formula.iter_mut())
+            .for_each(|formula| *formula = shift.shift_formula(formula, sheet_name, in_sheet));
+    }
+
+    ///
+    /// Point the rules to the differential formats of another workbook
+    ///
+    pub(crate) fn import_dxfs<D: FnMut(u32) -> u32>(&mut self, dxf: &mut D) {
+        self.cf_rule.iter_mut().for_each(|cf_rule| cf_rule.dxf_id = cf_rule.dxf_id.map(&mut *dxf));
+    }
+
+    pub(crate) fn map_formulas<F: FnMut(&str) -> String>(&mut self, map: &mut F) {
+        self.cf_rule.iter_mut()
+            .flat_map(|cf_rule| cf_rule.formula.iter_mut())
+            .for_each(|formula| *formula = map(formula));
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, Default)]
+pub(crate) struct CfRule {
     #[serde(rename = "@type", default, skip_serializing_if = "Option::is_none")]
     tp: Option<String>,
     #[serde(rename = "@dxfId", default, skip_serializing_if = "Option::is_none")]
-    dxf_id: Option<u8>,
-    #[serde(rename = "@priority", default, skip_serializing_if = "Option::is_none")]
-    priority: Option<u8>,
+    pub(crate) dxf_id: Option<u32>,
+    #[serde(rename = "@priority", default)]
+    pub(crate) priority: u32,
+    #[serde(rename = "@stopIfTrue", default, skip_serializing_if = "Option::is_none")]
+    stop_if_true: Option<u8>,
+    #[serde(rename = "@aboveAverage", default, skip_serializing_if = "Option::is_none")]
+    above_average: Option<u8>,
+    #[serde(rename = "@percent", default, skip_serializing_if = "Option::is_none")]
+    percent: Option<u8>,
+    #[serde(rename = "@bottom", default, skip_serializing_if = "Option::is_none")]
+    bottom: Option<u8>,
     #[serde(rename = "@operator", default, skip_serializing_if = "Option::is_none")]
     operator: Option<String>,
-    #[serde(rename = "formula", default, skip_serializing_if = "Option::is_none")]
-    formula: Option<String>,
-}
\ No newline at end of file
+    #[serde(rename = "@text", default, skip_serializing_if = "Option::is_none")]
+    text: Option<String>,
+    #[serde(rename = "@timePeriod", default, skip_serializing_if = "Option::is_none")]
+    time_period: Option<String>,
+    #[serde(rename = "@rank", default, skip_serializing_if = "Option::is_none")]
+    rank: Option<u32>,
+    #[serde(rename = "@stdDev", default, skip_serializing_if = "Option::is_none")]
+    std_dev: Option<u32>,
+    #[serde(rename = "@equalAverage", default, skip_serializing_if = "Option::is_none")]
+    equal_average: Option<u8>,
+    #[serde(rename = "formula", default, skip_serializing_if = "Vec::is_empty")]
+    formula: Vec<String>,
+    #[serde(rename = "colorScale", default, skip_serializing_if = "Option::is_none")]
+    color_scale: Option<ColorScale>,
+    #[serde(rename = "dataBar", default, skip_serializing_if = "Option::is_none")]
+    data_bar: Option<DataBar>,
+    #[serde(rename = "iconSet", default, skip_serializing_if = "Option::is_none")]
+    icon_set: Option<IconSet>,
+    #[serde(rename = "extLst", default, skip_serializing_if = "Option::is_none")]
+    pub(crate) ext_lst: Option<ExtensionList>,
+}
+
+impl CfRule {
+    ///
+    /// A rule of a range whose top left cell is `cell`, the formulas of the rule refer to it
+    ///
+    pub(crate) fn from_api_rule(rule: &ConditionalFormatRule, cell: &str) -> CfRule {
+        let typed = |tp: &str| CfRule { tp: Some(tp.to_string()), ..Default::default() };
+        let text_rule = |tp: &str, operator: &str, text: &str, formula: String| CfRule {
+            operator: Some(operator.to_string()),
+            text: Some(text.to_string()),
+            formula: vec![formula],
+            ..typed(tp)
+        };
+        let top10 = |rank: u32, percent: bool, bottom: bool| CfRule {
+            rank: Some(rank),
+            percent: if percent { Some(1) } else { None },
+            bottom: if bottom { Some(1) } else { None },
+            ..typed("top10")
+        };
+        let with_formula = |tp: &str, formula: String| CfRule { formula: vec![formula], ..typed(tp) };
+        match rule {
+            ConditionalFormatRule::CellValue(operator, formula1, formula2) => {
+                let mut formula = vec![formula1.trim_start_matches('=').to_string()];
+                if let (ConditionalFormatOperator::Between | ConditionalFormatOperator::NotBetween, Some(formula2)) = (operator, formula2) {
+                    formula.push(formula2.trim_start_matches('=').to_string());
+                }
+                CfRule { operator: Some(operator.to_name().to_string()), formula, ..typed("cellIs") }
+            }
+            ConditionalFormatRule::TextContains(text) => {
+                text_rule("containsText", "containsText", text, format!("NOT(ISERROR(SEARCH({},{cell})))", quote(text)))
+            }
+            ConditionalFormatRule::TextNotContains(text) => {
+                text_rule("notContainsText", "notContains", text, format!("ISERROR(SEARCH({},{cell}))", quote(text)))
+            }
+            ConditionalFormatRule::TextBeginsWith(text) => {
+                text_rule("beginsWith", "beginsWith", text, format!("LEFT({cell},LEN({0}))={0}", quote(text)))
+            }
+            ConditionalFormatRule::TextEndsWith(text) => {
+                text_rule("endsWith", "endsWith", text, format!("RIGHT({cell},LEN({0}))={0}", quote(text)))
+            }
+            ConditionalFormatRule::Top(rank) => top10(*rank, false, false),
+            ConditionalFormatRule::Bottom(rank) => top10(*rank, false, true),
+            ConditionalFormatRule::TopPercent(rank) => top10(*rank, true, false),
+            ConditionalFormatRule::BottomPercent(rank) => top10(*rank, true, true),
+            ConditionalFormatRule::AboveAverage => typed("aboveAverage"),
+            ConditionalFormatRule::BelowAverage => CfRule { above_average: Some(0), ..typed("aboveAverage") },
+            ConditionalFormatRule::Duplicate => typed("duplicateValues"),
+            ConditionalFormatRule::Unique => typed("uniqueValues"),
+            ConditionalFormatRule::Blanks => with_formula("containsBlanks", format!("LEN(TRIM({cell}))=0")),
+            ConditionalFormatRule::NoBlanks => with_formula("notContainsBlanks", format!("LEN(TRIM({cell}))>0")),
+            ConditionalFormatRule::Errors => with_formula("containsErrors", format!("ISERROR({cell})")),
+            ConditionalFormatRule::NoErrors => with_formula("notContainsErrors", format!("NOT(ISERROR({cell}))")),
+            ConditionalFormatRule::TimePeriod(time_period) => CfRule {
+                time_period: Some(time_period.to_name().to_string()),
+                formula: vec![time_period.to_formula(cell)],
+                ..typed("timePeriod")
+            },
+            ConditionalFormatRule::Formula(formula) => with_formula("expression", formula.trim_start_matches('=').to_string()),
+            ConditionalFormatRule::ColorScale(color_scale) => CfRule {
+                color_scale: Some(ColorScale::from_api_color_scale(color_scale)),
+                ..typed("colorScale")
+            },
+            ConditionalFormatRule::DataBar(data_bar) => CfRule {
+                data_bar: Some(DataBar::from_api_data_bar(data_bar)),
+                ..typed("dataBar")
+            },
+            ConditionalFormatRule::IconSet(icon_set) => CfRule {
+                icon_set: Some(IconSet::from_api_icon_set(icon_set)),
+                ..typed("iconSet")
+            },
+        }
+    }
+
+    ///
+    /// The rule of the api, None for the kinds of rules not supported
+    ///
+    pub(crate) fn to_api_rule(&self) -> Option<ConditionalFormatRule> {
+        let text = || self.text.clone().unwrap_or_default();
+        let rank = self.rank.unwrap_or(10);
+        let rule = match self.tp.as_deref()? {
+            "cellIs" => {
+                let operator = ConditionalFormatOperator::from_name(self.operator.as_deref()?)?;
+                ConditionalFormatRule::CellValue(operator, self.formula.first()?.clone(), self.formula.get(1).cloned())
+            }
+            "containsText" => ConditionalFormatRule::TextContains(text()),
+            "notContainsText" => ConditionalFormatRule::TextNotContains(text()),
+            "beginsWith" => ConditionalFormatRule::TextBeginsWith(text()),
+            "endsWith" => ConditionalFormatRule::TextEndsWith(text()),
+            "top10" => match (self.percent == Some(1), self.bottom == Some(1)) {
+                (false, false) => ConditionalFormatRule::Top(rank),
+                (false, true) => ConditionalFormatRule::Bottom(rank),
+                (true, false) => ConditionalFormatRule::TopPercent(rank),
+                (true, true) => ConditionalFormatRule::BottomPercent(rank),
+            },
+            "aboveAverage" if self.above_average == Some(0) => ConditionalFormatRule::BelowAverage,
+            "aboveAverage" => ConditionalFormatRule::AboveAverage,
+            "duplicateValues" => ConditionalFormatRule::Duplicate,
+            "uniqueValues" => ConditionalFormatRule::Unique,
+            "containsBlanks" => ConditionalFormatRule::Blanks,
+            "notContainsBlanks" => ConditionalFormatRule::NoBlanks,
+            "containsErrors" => ConditionalFormatRule::Errors,
+            "notContainsErrors" => ConditionalFormatRule::NoErrors,
+            "timePeriod" => ConditionalFormatRule::TimePeriod(ConditionalFormatTimePeriod::from_name(self.time_period.as_deref()?)?),
+            "expression" => ConditionalFormatRule::Formula(self.formula.first()?.clone()),
+            "colorScale" => ConditionalFormatRule::ColorScale(self.color_scale.as_ref()?.to_api_color_scale()?),
+            "dataBar" => ConditionalFormatRule::DataBar(self.data_bar.as_ref()?.to_api_data_bar()?),
+            "iconSet" => ConditionalFormatRule::IconSet(self.icon_set.as_ref()?.to_api_icon_set()),
+            _ => return None,
+        };
+        Some(rule)
+    }
+
+    pub(crate) fn set_stop_if_true(&mut self, stop_if_true: bool) {
+        self.stop_if_true = if stop_if_true { Some(1) } else { None };
+    }
+
+    pub(crate) fn is_stop_if_true(&self) -> bool {
+        self.stop_if_true == Some(1)
+    }
+
+    ///
+    /// The id of the rule in the extension list of the worksheet
+    ///
+    pub(crate) fn get_x14_id(&self) -> Option<&str> {
+        self.ext_lst.as_ref()?.get_x14_id()
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+struct Cfvo {
+    #[serde(rename = "@type")]
+    tp: String,
+    #[serde(rename = "@val", default, skip_serializing_if = "Option::is_none")]
+    val: Option<String>,
+    #[serde(rename = "@gte", default, skip_serializing_if = "Option::is_none")]
+    gte: Option<u8>,
+}
+
+impl Cfvo {
+    ///
+    /// The automatic thresholds of data bars are only known by the extension list
+    ///
+    fn from_api_value(value: &ConditionalFormatValue) -> Cfvo {
+        let (tp, val) = match value.to_cfvo() {
+            ("autoMin", val) => ("min", val),
+            ("autoMax", val) => ("max", val),
+            cfvo => cfvo,
+        };
+        Cfvo { tp: tp.to_string(), val, gte: None }
+    }
+
+    fn to_api_value(&self) -> ConditionalFormatValue {
+        ConditionalFormatValue::from_cfvo(&self.tp, self.val.as_deref())
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+struct ColorScale {
+    #[serde(rename = "cfvo", default)]
+    cfvo: Vec<Cfvo>,
+    #[serde(rename = "color", default)]
+    color: Vec<Color>,
+}
+
+impl ColorScale {
+    fn from_api_color_scale(color_scale: &ApiColorScale) -> ColorScale {
+        let stops = [Some(&color_scale.min), color_scale.mid.as_ref(), Some(&color_scale.max)];
+        let stops = stops.into_iter().flatten();
+        ColorScale {
+            cfvo: stops.clone().map(|(value, _)| Cfvo::from_api_value(value)).collect(),
+            color: stops.map(|(_, color)| Color::from_format(color)).collect(),
+        }
+    }
+
+    fn to_api_color_scale(&self) -> Option<ApiColorScale> {
+        let mut stops = self.cfvo.iter()
+            .zip(self.color.iter())
+            .map(|(cfvo, color)| (cfvo.to_api_value(), color.get_format()))
+            .collect::<Vec<(ConditionalFormatValue, FormatColor)>>();
+        let max = stops.pop()?;
+        let mid = if stops.len() > 1 { stops.pop() } else { None };
+        let min = stops.pop()?;
+        Some(ApiColorScale { min, mid, max })
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+struct DataBar {
+    #[serde(rename = "@showValue", default, skip_serializing_if = "Option::is_none")]
+    show_value: Option<u8>,
+    #[serde(rename = "cfvo", default)]
+    cfvo: Vec<Cfvo>,
+    #[serde(rename = "color", default, skip_serializing_if = "Option::is_none")]
+    color: Option<Color>,
+}
+
+impl DataBar {
+    fn from_api_data_bar(data_bar: &ApiDataBar) -> DataBar {
+        DataBar {
+            show_value: if data_bar.bar_only { Some(0) } else { None },
+            cfvo: vec![Cfvo::from_api_value(&data_bar.min), Cfvo::from_api_value(&data_bar.max)],
+            color: Some(Color::from_format(&data_bar.color)),
+        }
+    }
+
+    ///
+    /// The data bar as written before Excel 2010, without borders, negative colors and axis
+    ///
+    fn to_api_data_bar(&self) -> Option<ApiDataBar> {
+        let mut data_bar = ApiDataBar::new(self.color.as_ref()?.get_format())
+            .set_range(self.cfvo.first()?.to_api_value(), self.cfvo.get(1)?.to_api_value())
+            .set_bar_only(self.show_value == Some(0));
+        data_bar.negative_color = None;
+        Some(data_bar)
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+struct IconSet {
+    #[serde(rename = "@iconSet", default, skip_serializing_if = "Option::is_none")]
+    icon_set: Option<String>,
+    #[serde(rename = "@showValue", default, skip_serializing_if = "Option::is_none")]
+    show_value: Option<u8>,
+    #[serde(rename = "@reverse", default, skip_serializing_if = "Option::is_none")]
+    reverse: Option<u8>,
+    #[serde(rename = "cfvo", default)]
+    cfvo: Vec<Cfvo>,
+}
+
+impl IconSet {
+    fn from_api_icon_set(icon_set: &ApiIconSet) -> IconSet {
+        let mut cfvo = vec![Cfvo::from_api_value(&ConditionalFormatValue::Percent(0.0))];
+        cfvo.extend(icon_set.thresholds.iter().map(Cfvo::from_api_value));
+        IconSet {
+            icon_set: Some(icon_set.icon_set_type.to_name().to_string()),
+            show_value: if icon_set.icons_only { Some(0) } else { None },
+            reverse: if icon_set.reverse { Some(1) } else { None },
+            cfvo,
+        }
+    }
+
+    fn to_api_icon_set(&self) -> ApiIconSet {
+        ApiIconSet {
+            icon_set_type: IconSetType::from_name(self.icon_set.as_deref().unwrap_or_default()),
+            thresholds: self.cfvo.iter().skip(1).map(Cfvo::to_api_value).collect(),
+            reverse: self.reverse == Some(1),
+            icons_only: self.show_value == Some(0),
+        }
+    }
+}
+
+///
+/// A text as a string of a formula
+///
+fn quote(text: &str) -> String {
+    format!("\"{}\"", text.replace('"', "\"\""))
+}
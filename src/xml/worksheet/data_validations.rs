@@ -1,6 +1,8 @@
 use serde::{Deserialize, Serialize};
+use crate::utils::reference_helper::Shift;
+use crate::api::data_validation::{DataValidation as ApiDataValidation, DataValidationErrorStyle, DataValidationOperator, DataValidationType};
 
-#[derive(Debug, Clone, Deserialize, Serialize)]
+#[derive(Debug, Clone, Deserialize, Serialize, Default)]
 pub(crate) struct DataValidations {
     #[serde(rename = "@count", default)]
     count: u32,
@@ -8,18 +10,152 @@ pub(crate) struct DataValidations {
     data_validation: Vec<DataValidation>
 }
 
+impl DataValidations {
+    pub(crate) fn add_data_validation(&mut self, sqref: &str, data_validation: &ApiDataValidation) {
+        self.data_validation.push(DataValidation::from_api_data_validation(sqref, data_validation));
+        self.count = self.data_validation.len() as u32;
+    }
+
+    pub(crate) fn get_data_validations(&self) -> Vec<(String, ApiDataValidation)> {
+        self.data_validation.iter()
+            .map(|data_validation| (data_validation.sqref.clone().unwrap_or_default(), data_validation.to_api_data_validation()))
+            .collect()
+    }
+
+    ///
+    /// Move the ranges of the validations, the ones of the deleted cells are removed
+    ///
+    pub(crate) fn shift(&mut self, shift: Shift, sheet_name: &str) {
+        self.data_validation.retain_mut(|data_validation| {
+            if let Some(sqref) = &data_validation.sqref {
+                match shift.shift_sqref(sqref) {
+                    Some(sqref) => data_validation.sqref = Some(sqref),
+                    None => return false,
+                }
+            }
+            data_validation.shift_formulas(shift, sheet_name, true);
+            true
+        });
+        self.count = self.data_validation.len() as u32;
+    }
+
+    pub(crate) fn shift_formulas(&mut self, shift: Shift, sheet_name: &str, in_sheet: bool) {
+        self.data_validation.iter_mut()
+            .for_each(|data_validation| data_validation.shift_formulas(shift, sheet_name, in_sheet));
+    }
+
+    pub(crate) fn map_formulas<F: FnMut(&str) -> String>(&mut self, map: &mut F) {
+        self.data_validation.iter_mut()
+            .flat_map(|data_validation| [&mut data_validation.formula1, &mut data_validation.formula2])
+            .flatten()
+            .for_each(|formula| *formula = map(formula));
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.data_validation.is_empty()
+    }
+}
+
 #[derive(Debug, Clone, Deserialize, Serialize)]
 struct DataValidation {
     #[serde(rename = "@type", default, skip_serializing_if = "Option::is_none")]
     tp: Option<String>,
+    #[serde(rename = "@errorStyle", default, skip_serializing_if = "Option::is_none")]
+    error_style: Option<String>,
+    #[serde(rename = "@imeMode", default, skip_serializing_if = "Option::is_none")]
+    ime_mode: Option<String>,
+    #[serde(rename = "@operator", default, skip_serializing_if = "Option::is_none")]
+    operator: Option<String>,
     #[serde(rename = "@allowBlank", default, skip_serializing_if = "Option::is_none")]
     allow_blank: Option<u8>,
+    #[serde(rename = "@showDropDown", default, skip_serializing_if = "Option::is_none")]
+    show_drop_down: Option<u8>,
     #[serde(rename = "@showInputMessage", default, skip_serializing_if = "Option::is_none")]
     show_input_message: Option<u8>,
     #[serde(rename = "@showErrorMessage", default, skip_serializing_if = "Option::is_none")]
     show_error_message: Option<u8>,
+    #[serde(rename = "@errorTitle", default, skip_serializing_if = "Option::is_none")]
+    error_title: Option<String>,
+    #[serde(rename = "@error", default, skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+    #[serde(rename = "@promptTitle", default, skip_serializing_if = "Option::is_none")]
+    prompt_title: Option<String>,
+    #[serde(rename = "@prompt", default, skip_serializing_if = "Option::is_none")]
+    prompt: Option<String>,
     #[serde(rename = "@sqref", default, skip_serializing_if = "Option::is_none")]
     sqref: Option<String>,
+    #[serde(rename(serialize = "@xr:uid", deserialize = "@uid"), default, skip_serializing_if = "Option::is_none")]
+    xr_uid: Option<String>,
     #[serde(rename = "formula1", default, skip_serializing_if = "Option::is_none")]
     formula1: Option<String>,
-}
\ No newline at end of file
+    #[serde(rename = "formula2", default, skip_serializing_if = "Option::is_none")]
+    formula2: Option<String>,
+}
+
+impl DataValidation {
+    fn shift_formulas(&mut self, shift: Shift, sheet_name: &str, in_sheet: bool) {
+        for formula in [&mut self.formula1, &mut self.formula2].into_iter().flatten() {
+            *formula = shift.shift_formula(formula, sheet_name, in_sheet);
+        }
+    }
+
+    fn from_api_data_validation(sqref: &str, data_validation: &ApiDataValidation) -> DataValidation {
+        let flag = |value: bool| if value { Some(1) } else { None };
+        let uses_operator = !matches!(
+            data_validation.validation_type,
+            DataValidationType::Any | DataValidationType::List | DataValidationType::Custom
+        );
+        let operator = data_validation.operator;
+        DataValidation {
+            tp: match data_validation.validation_type {
+                DataValidationType::Any => None,
+                validation_type => Some(validation_type.to_name().to_string()),
+            },
+            error_style: match data_validation.error_style {
+                DataValidationErrorStyle::Stop => None,
+                error_style => Some(error_style.to_name().to_string()),
+            },
+            ime_mode: None,
+            operator: match operator {
+                DataValidationOperator::Between => None,
+                _ if !uses_operator => None,
+                operator => Some(operator.to_name().to_string()),
+            },
+            allow_blank: flag(data_validation.ignore_blank),
+            // the attribute hides the dropdown despite its name
+            show_drop_down: flag(!data_validation.dropdown && data_validation.validation_type == DataValidationType::List),
+            show_input_message: flag(data_validation.show_input_message),
+            show_error_message: flag(data_validation.show_error_message),
+            error_title: data_validation.error_title.clone(),
+            error: data_validation.error_message.clone(),
+            prompt_title: data_validation.input_title.clone(),
+            prompt: data_validation.input_message.clone(),
+            sqref: Some(sqref.to_string()),
+            xr_uid: None,
+            formula1: data_validation.formula1.clone(),
+            formula2: match operator {
+                DataValidationOperator::Between | DataValidationOperator::NotBetween if uses_operator => data_validation.formula2.clone(),
+                _ => None,
+            },
+        }
+    }
+
+    fn to_api_data_validation(&self) -> ApiDataValidation {
+        let flag = |value: Option<u8>| value.unwrap_or_default() == 1;
+        ApiDataValidation {
+            validation_type: DataValidationType::from_name(self.tp.as_deref().unwrap_or_default()),
+            operator: DataValidationOperator::from_name(self.operator.as_deref().unwrap_or_default()),
+            formula1: self.formula1.clone(),
+            formula2: self.formula2.clone(),
+            ignore_blank: flag(self.allow_blank),
+            dropdown: !flag(self.show_drop_down),
+            show_input_message: flag(self.show_input_message),
+            input_title: self.prompt_title.clone(),
+            input_message: self.prompt.clone(),
+            show_error_message: flag(self.show_error_message),
+            error_style: DataValidationErrorStyle::from_name(self.error_style.as_deref().unwrap_or_default()),
+            error_title: self.error_title.clone(),
+            error_message: self.error.clone(),
+        }
+    }
+}
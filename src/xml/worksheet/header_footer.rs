@@ -0,0 +1,28 @@
+use serde::{Deserialize, Serialize};
+
+///
+/// The headers and the footers of the printed pages, written with the control codes of Excel like `&CPage &P`
+///
+#[derive(Debug, Clone, Deserialize, Serialize, Default)]
+pub(crate) struct HeaderFooter {
+    #[serde(rename = "@differentOddEven", skip_serializing_if = "Option::is_none")]
+    pub(crate) different_odd_even: Option<u8>,
+    #[serde(rename = "@differentFirst", skip_serializing_if = "Option::is_none")]
+    pub(crate) different_first: Option<u8>,
+    #[serde(rename = "@scaleWithDoc", skip_serializing_if = "Option::is_none")]
+    scale_with_doc: Option<u8>,
+    #[serde(rename = "@alignWithMargins", skip_serializing_if = "Option::is_none")]
+    align_with_margins: Option<u8>,
+    #[serde(rename = "oddHeader", skip_serializing_if = "Option::is_none")]
+    pub(crate) odd_header: Option<String>,
+    #[serde(rename = "oddFooter", skip_serializing_if = "Option::is_none")]
+    pub(crate) odd_footer: Option<String>,
+    #[serde(rename = "evenHeader", skip_serializing_if = "Option::is_none")]
+    pub(crate) even_header: Option<String>,
+    #[serde(rename = "evenFooter", skip_serializing_if = "Option::is_none")]
+    pub(crate) even_footer: Option<String>,
+    #[serde(rename = "firstHeader", skip_serializing_if = "Option::is_none")]
+    pub(crate) first_header: Option<String>,
+    #[serde(rename = "firstFooter", skip_serializing_if = "Option::is_none")]
+    pub(crate) first_footer: Option<String>,
+}
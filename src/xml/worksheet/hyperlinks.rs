@@ -1,5 +1,6 @@
 use serde::{Deserialize, Serialize};
 use crate::api::cell::location::Location;
+use crate::utils::reference_helper::Shift;
 
 #[derive(Debug, Clone, Deserialize, Serialize, Default)]
 pub(crate) struct Hyperlinks {
@@ -23,6 +24,42 @@ impl Hyperlinks {
             Some(h) => h.display.clone()
         }
     }
+
+    ///
+    /// Move the hyperlinks of the cells, the ones of the deleted cells are removed
+    ///
+    pub(crate) fn shift(&mut self, shift: Shift, sheet_name: &str) {
+        self.hyperlink.retain_mut(|hyperlink| match shift.shift_ref(&hyperlink.hyperlink_ref) {
+            Some(hyperlink_ref) => {
+                hyperlink.hyperlink_ref = hyperlink_ref;
+                true
+            }
+            None => false,
+        });
+        self.shift_formulas(shift, sheet_name, true);
+    }
+
+    ///
+    /// Make the links to the cells of a worksheet follow them moved by a shift
+    ///
+    pub(crate) fn shift_formulas(&mut self, shift: Shift, sheet_name: &str, in_sheet: bool) {
+        self.hyperlink.iter_mut()
+            .filter_map(|hyperlink| hyperlink.location.as_mut())
+            .for_each(|location| *location = shift.shift_formula(location, sheet_name, in_sheet));
+    }
+
+    ///
+    /// Make the links to the cells of a worksheet follow it renamed
+    ///
+    pub(crate) fn map_formulas<F: FnMut(&str) -> String>(&mut self, map: &mut F) {
+        self.hyperlink.iter_mut()
+            .filter_map(|hyperlink| hyperlink.location.as_mut())
+            .for_each(|location| *location = map(location));
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.hyperlink.is_empty()
+    }
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
@@ -1,5 +1,7 @@
 use serde::{Deserialize, Serialize};
+use crate::api::cell::location::LocationRange;
 use crate::utils::col_helper;
+use crate::utils::reference_helper::Shift;
 
 #[derive(Debug, Clone, Deserialize, Serialize, Default)]
 pub(crate) struct MergeCells {
@@ -15,6 +17,31 @@ impl MergeCells {
         self.merge_cell.push(merge_cell);
         self.count += 1;
     }
+
+    ///
+    /// Move the merged cells, the ones left with a single cell are unmerged
+    ///
+    pub(crate) fn shift(&mut self, shift: Shift) {
+        self.merge_cell.retain_mut(|merge_cell| match shift.shift_ref(&merge_cell.cell_ref) {
+            Some(cell_ref) if cell_ref.contains(':') => {
+                merge_cell.cell_ref = cell_ref;
+                true
+            }
+            _ => false,
+        });
+        self.count = self.merge_cell.len() as u32;
+    }
+
+    ///
+    /// The ranges of the merged cells, as (first_row, first_col, last_row, last_col)
+    ///
+    pub(crate) fn get_ranges(&self) -> Vec<(u32, u32, u32, u32)> {
+        self.merge_cell.iter().map(|merge_cell| merge_cell.cell_ref.as_str().to_range()).collect()
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.merge_cell.is_empty()
+    }
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
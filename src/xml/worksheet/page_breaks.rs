@@ -0,0 +1,72 @@
+use serde::{Deserialize, Serialize};
+use crate::utils::reference_helper::Shift;
+
+///
+/// The page breaks of the rows or of the columns, each break is after the row or the column of its id
+///
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub(crate) struct PageBreaks {
+    #[serde(rename = "@count", default)]
+    count: u32,
+    #[serde(rename = "@manualBreakCount", default)]
+    manual_break_count: u32,
+    #[serde(rename = "brk", default, skip_serializing_if = "Vec::is_empty")]
+    brk: Vec<Break>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+struct Break {
+    #[serde(rename = "@id", skip_serializing_if = "Option::is_none")]
+    id: Option<u32>,
+    #[serde(rename = "@min", skip_serializing_if = "Option::is_none")]
+    min: Option<u32>,
+    #[serde(rename = "@max", skip_serializing_if = "Option::is_none")]
+    max: Option<u32>,
+    #[serde(rename = "@man", skip_serializing_if = "Option::is_none")]
+    man: Option<u32>,
+    #[serde(rename = "@pt", skip_serializing_if = "Option::is_none")]
+    pt: Option<u32>,
+}
+
+impl PageBreaks {
+    ///
+    /// The manual breaks spanning the whole worksheet, `max` is the last index of the other direction
+    ///
+    pub(crate) fn new(ids: &[u32], max: u32) -> PageBreaks {
+        let mut ids = ids.to_vec();
+        ids.sort_unstable();
+        ids.dedup();
+        let brk = ids.into_iter()
+            .map(|id| Break { id: Some(id), min: None, max: Some(max), man: Some(1), pt: None })
+            .collect::<Vec<Break>>();
+        PageBreaks {
+            count: brk.len() as u32,
+            manual_break_count: brk.len() as u32,
+            brk,
+        }
+    }
+
+    pub(crate) fn get_ids(&self) -> Vec<u32> {
+        self.brk.iter().filter_map(|brk| brk.id).collect()
+    }
+
+    ///
+    /// Move the breaks with their rows or columns, the ones of the deleted rows or columns are removed
+    ///
+    pub(crate) fn shift(&mut self, shift: Shift) {
+        self.brk.retain_mut(|brk| match brk.id.map(|id| shift.shift_index(id)) {
+            Some(Some(id)) => {
+                brk.id = Some(id);
+                true
+            }
+            Some(None) => false,
+            None => true,
+        });
+        self.count = self.brk.len() as u32;
+        self.manual_break_count = self.brk.iter().filter(|brk| brk.man == Some(1)).count() as u32;
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.brk.is_empty()
+    }
+}
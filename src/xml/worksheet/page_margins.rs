@@ -27,4 +27,10 @@ impl Default for PageMargins {
             footer: 0.3,
         }
     }
-}
\ No newline at end of file
+}
+
+impl PageMargins {
+    pub(crate) fn set_margins(&mut self, left: f64, right: f64, top: f64, bottom: f64, header: f64, footer: f64) {
+        *self = PageMargins { left, right, top, bottom, header, footer };
+    }
+}
@@ -0,0 +1,61 @@
+use serde::{Deserialize, Serialize};
+use crate::api::relationship::Rel;
+
+#[derive(Debug, Clone, Deserialize, Serialize, Default)]
+pub(crate) struct PageSetup {
+    #[serde(rename = "@paperSize", skip_serializing_if = "Option::is_none")]
+    pub(crate) paper_size: Option<u32>,
+    #[serde(rename = "@scale", skip_serializing_if = "Option::is_none")]
+    pub(crate) scale: Option<u32>,
+    #[serde(rename = "@firstPageNumber", skip_serializing_if = "Option::is_none")]
+    first_page_number: Option<u32>,
+    #[serde(rename = "@fitToWidth", skip_serializing_if = "Option::is_none")]
+    pub(crate) fit_to_width: Option<u32>,
+    #[serde(rename = "@fitToHeight", skip_serializing_if = "Option::is_none")]
+    pub(crate) fit_to_height: Option<u32>,
+    #[serde(rename = "@pageOrder", skip_serializing_if = "Option::is_none")]
+    page_order: Option<String>,
+    #[serde(rename = "@orientation", skip_serializing_if = "Option::is_none")]
+    pub(crate) orientation: Option<String>,
+    #[serde(rename = "@usePrinterDefaults", skip_serializing_if = "Option::is_none")]
+    use_printer_defaults: Option<u8>,
+    #[serde(rename = "@blackAndWhite", skip_serializing_if = "Option::is_none")]
+    black_and_white: Option<u8>,
+    #[serde(rename = "@draft", skip_serializing_if = "Option::is_none")]
+    draft: Option<u8>,
+    #[serde(rename = "@cellComments", skip_serializing_if = "Option::is_none")]
+    cell_comments: Option<String>,
+    #[serde(rename = "@useFirstPageNumber", skip_serializing_if = "Option::is_none")]
+    use_first_page_number: Option<u8>,
+    #[serde(rename = "@errors", skip_serializing_if = "Option::is_none")]
+    errors: Option<String>,
+    #[serde(rename = "@horizontalDpi", skip_serializing_if = "Option::is_none")]
+    horizontal_dpi: Option<i32>,
+    #[serde(rename = "@verticalDpi", skip_serializing_if = "Option::is_none")]
+    vertical_dpi: Option<i32>,
+    #[serde(rename = "@copies", skip_serializing_if = "Option::is_none")]
+    copies: Option<u32>,
+    #[serde(rename(serialize = "@r:id", deserialize = "@id"), skip_serializing_if = "Option::is_none")]
+    r_id: Option<Rel>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, Default)]
+pub(crate) struct PrintOptions {
+    #[serde(rename = "@horizontalCentered", skip_serializing_if = "Option::is_none")]
+    pub(crate) horizontal_centered: Option<u8>,
+    #[serde(rename = "@verticalCentered", skip_serializing_if = "Option::is_none")]
+    pub(crate) vertical_centered: Option<u8>,
+    #[serde(rename = "@headings", skip_serializing_if = "Option::is_none")]
+    pub(crate) headings: Option<u8>,
+    #[serde(rename = "@gridLines", skip_serializing_if = "Option::is_none")]
+    pub(crate) grid_lines: Option<u8>,
+    #[serde(rename = "@gridLinesSet", skip_serializing_if = "Option::is_none")]
+    grid_lines_set: Option<u8>,
+}
+
+impl PrintOptions {
+    pub(crate) fn is_empty(&self) -> bool {
+        self.horizontal_centered.is_none() && self.vertical_centered.is_none()
+            && self.headings.is_none() && self.grid_lines.is_none() && self.grid_lines_set.is_none()
+    }
+}
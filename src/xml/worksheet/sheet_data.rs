@@ -4,14 +4,19 @@
 pub(crate) mod cell;
 mod row;
 
+use std::collections::HashMap;
 use serde::{Deserialize, Serialize};
+use crate::api::cell::formula::token::ParsedFormula;
 use crate::api::cell::location::Location;
 use crate::api::cell::Cell as ApiCell;
 use crate::api::cell::values::{CellDisplay, CellType, CellValue};
+#[cfg(feature = "calc")]
+use crate::api::cell::values::Value;
 use crate::api::worksheet::row::Row as ApiRow;
 use crate::result::CellError::CellNotFound;
 use crate::result::RowError::RowNotFound;
 use crate::result::{RowError, RowResult};
+use crate::utils::reference_helper::{mentions_sheet, Shift};
 use crate::xml::worksheet::sheet_data::cell::Cell;
 use crate::xml::worksheet::sheet_data::row::{_OrderCell, Row};
 
@@ -37,6 +42,41 @@ impl SheetData {
         }
     }
 
+    ///
+    /// The smallest range containing every cell, as (first_row, first_col, last_row, last_col)
+    ///
+    pub(crate) fn used_range(&self) -> Option<(u32, u32, u32, u32)> {
+        let mut rows = self.rows.iter().filter(|row| !row.cells.is_empty());
+        let first_row = rows.next()?;
+        let mut range = (first_row.row, first_row.min_col(), first_row.row, first_row.max_col());
+        for row in rows {
+            range.1 = range.1.min(row.min_col());
+            range.2 = row.row;
+            range.3 = range.3.max(row.max_col());
+        }
+        Some(range)
+    }
+
+    ///
+    /// The last row or column of the cells with a value or a formula, 0 if there is none
+    ///
+    pub(crate) fn last_filled_index(&self, is_row: bool) -> u32 {
+        self.rows.iter()
+            .flat_map(|row| row.cells.iter())
+            .filter(|cell| cell.text.is_some() || cell.formula.is_some())
+            .map(|cell| if is_row { cell.loc.row } else { cell.loc.col })
+            .max()
+            .unwrap_or_default()
+    }
+
+    ///
+    /// Rows between first_row and last_row inclusive, in order
+    ///
+    pub(crate) fn rows_between(&self, first_row: u32, last_row: u32) -> impl Iterator<Item = &Row> {
+        let start = self.get_position_by_row(first_row);
+        self.rows[start..].iter().take_while(move |row| row.row <= last_row)
+    }
+
     // pub(crate) fn get_row_height(&self, row: u32) -> WorkSheetResult<f64> {
     //     match self.get_row(row) {
     //         Some(row) => row.height.ok_or(RowError(CellError(CellNotFound))),
@@ -141,6 +181,23 @@ impl SheetData {
         Ok(())
     }
 
+    pub(crate) fn write_shared_string<L: Location>(&mut self, loc: &L, id: usize, style: Option<u32>) -> RowResult<()> {
+        let (row, col) = loc.to_location();
+        let row = self.get_or_new_row(row);
+        row.add_shared_string_cell(col, id, style);
+        Ok(())
+    }
+
+    ///
+    /// Count the cells referencing the shared strings table
+    ///
+    pub(crate) fn shared_string_count(&self) -> usize {
+        self.rows.iter()
+            .flat_map(|row| row.cells.iter())
+            .filter(|cell| cell.cell_type == Some(CellType::SharedString))
+            .count()
+    }
+
     // pub(crate) fn write_formula<L: Location>(&mut self, loc: &L, formula: &str, formula_type: FormulaType, style: Option<u32>) -> RowResult<()> {
     //     let (row, col) = loc.to_location();
     //     let row = self.get_or_new_row(row);
@@ -148,24 +205,131 @@ impl SheetData {
     //     Ok(())
     // }
 
-    pub(crate) fn clean_formula_value(&mut self) {
-        // self.rows.iter_mut().for_each(
-        //     |row| row.cells.iter_mut().for_each(|cell|
-        //         if let Some(_) = cell.formula {
-        //             cell.text = None;
-        //         }
-        //     )
-        // )
+    ///
+    /// Move the rows or the cells after the inserted or deleted rows or columns,
+    /// and the references of the formulas to the moved cells
+    ///
+    pub(crate) fn shift(&mut self, shift: Shift, sheet_name: &str) {
+        self.unshare_formulas(|_| true);
+        if shift.is_row() {
+            self.rows.retain_mut(|row| match shift.shift_index(row.row) {
+                Some(index) => {
+                    row.set_row(index);
+                    true
+                }
+                None => false,
+            });
+        } else {
+            self.rows.iter_mut().for_each(|row| row.shift_columns(shift));
+        }
+        self.shift_formulas(shift, sheet_name, true);
+    }
+
+    ///
+    /// Shift the references of the formulas to the cells of a worksheet, which may be another one
+    ///
+    pub(crate) fn shift_formulas(&mut self, shift: Shift, sheet_name: &str, in_sheet: bool) {
+        self.unshare_formulas(|formula| in_sheet || mentions_sheet(formula, sheet_name));
+        self.rows.iter_mut()
+            .flat_map(|row| row.cells.iter_mut())
+            .filter_map(|cell| cell.formula.as_mut())
+            .for_each(|formula| formula.shift(shift, sheet_name, in_sheet));
+    }
+
+    ///
+    /// Give the cells of the shared formulas whose first formula is kept by a filter their own formula,
+    /// the formula of the first cell moved like a formula copied to them.
+    /// The cells sharing a formula compute their references from the first cell, so they are not moved apart.
+    ///
+    fn unshare_formulas<F: Fn(&str) -> bool>(&mut self, filter: F) {
+        let shared = self.rows.iter()
+            .flat_map(|row| row.cells.iter())
+            .filter_map(|cell| {
+                let formula = cell.formula.as_ref()?;
+                let si = formula.shared_index().filter(|_| !formula.formula.is_empty() && filter(&formula.formula))?;
+                Some((si, ((cell.loc.row, cell.loc.col), formula.formula.clone())))
+            })
+            .collect::<HashMap<i32, ((u32, u32), String)>>();
+        if shared.is_empty() {
+            return;
+        }
+        for cell in self.rows.iter_mut().flat_map(|row| row.cells.iter_mut()) {
+            let (row, col) = (cell.loc.row, cell.loc.col);
+            let Some(formula) = cell.formula.as_mut() else { continue };
+            if let Some(((first_row, first_col), shared_formula)) = formula.shared_index().and_then(|si| shared.get(&si)) {
+                let mut parsed = ParsedFormula::parse(shared_formula);
+                parsed.offset(row as i64 - *first_row as i64, col as i64 - *first_col as i64);
+                formula.unshare(parsed.to_string());
+            }
+        }
+    }
+
+    pub(crate) fn map_formulas<F: FnMut(&str) -> String>(&mut self, map: &mut F) {
+        self.rows.iter_mut()
+            .flat_map(|row| row.cells.iter_mut())
+            .filter_map(|cell| cell.formula.as_mut())
+            .for_each(|formula| formula.formula = map(&formula.formula));
+    }
+
+    ///
+    /// Point the rows and the cells to the styles and the shared strings of another workbook
+    ///
+    pub(crate) fn import_ids<S: FnMut(u32) -> u32, T: FnMut(usize) -> Option<usize>>(&mut self, style: &mut S, shared_string: &mut T) {
+        for row in &mut self.rows {
+            row.style = row.style.map(&mut *style);
+            for cell in &mut row.cells {
+                cell.style = cell.style.map(&mut *style);
+                if cell.cell_type == Some(CellType::SharedString) {
+                    cell.text = cell.text.as_deref()
+                        .and_then(|id| id.parse().ok())
+                        .and_then(&mut *shared_string)
+                        .map(|id| id.to_string());
+                }
+            }
+        }
+    }
+
+    ///
+    /// Store the results of formulas as the cached values of their cells
+    ///
+    #[cfg(feature = "calc")]
+    pub(crate) fn set_formula_values(&mut self, values: &[((u32, u32), Value)]) {
+        for ((row, col), value) in values {
+            if let Some(cell) = self.get_row_mut(*row).and_then(|row| row.get_cell_mut(*col)) {
+                cell.set_formula_value(value);
+            }
+        }
+    }
+
+    ///
+    /// The locations of the cells with a formula, row by row
+    ///
+    pub(crate) fn formula_cells(&self) -> Vec<(u32, u32)> {
+        self.rows.iter()
+            .flat_map(|row| row.cells.iter())
+            .filter(|cell| cell.formula.is_some())
+            .map(|cell| (cell.loc.row, cell.loc.col))
+            .collect()
+    }
+
+    ///
+    /// Drop the cached values of the formulas, returns whether any value was dropped
+    ///
+    pub(crate) fn clear_formula_values(&mut self) -> bool {
+        let mut cleared = false;
+        self.rows.iter_mut()
+            .flat_map(|row| row.cells.iter_mut())
+            .filter(|cell| cell.formula.is_some())
+            .for_each(|cell| cleared |= cell.clear_formula_value());
+        cleared
     }
 }
 
 trait _OrderRow {
     fn get_position_by_row(&self, row: u32) -> usize;
-    fn new_row(&mut self, row: u32) -> &mut Row;
     fn get_row_mut(&mut self, row: u32) -> Option<&mut Row>;
     fn get_row(&self, row: u32) -> Option<&Row>;
     fn get_or_new_row(&mut self, row: u32) -> &mut Row;
-    fn get_last_row(&self) -> Option<&Row>;
 }
 
 impl _OrderRow for SheetData {
@@ -185,11 +349,6 @@ impl _OrderRow for SheetData {
         }
         r
     }
-    fn new_row(&mut self, row: u32) -> &mut Row {
-        let r = self.get_position_by_row(row);
-        self.rows.insert(r, Row::new(row));
-        return &mut self.rows[r];
-    }
     fn get_row_mut(&mut self, row: u32) -> Option<&mut Row> {
         let r = self.get_position_by_row(row);
         if r >= self.rows.len() {return None}
@@ -209,7 +368,4 @@ impl _OrderRow for SheetData {
             &mut self.rows[r]
         }
     }
-    fn get_last_row(&self) -> Option<&Row> {
-        self.rows.last()
-    }
 }
@@ -7,6 +7,8 @@ use crate::api::cell::Cell as ApiCell;
 use crate::api::cell::location::Location;
 use crate::xml::worksheet::sheet_data::cell::formula::Formula;
 use crate::api::cell::values::{CellDisplay, CellValue, CellType};
+#[cfg(feature = "calc")]
+use crate::api::cell::values::Value;
 use crate::result::CellResult;
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
@@ -86,6 +88,15 @@ impl Cell {
         self.formula = None;
     }
 
+    pub(crate) fn update_by_shared_string(&mut self, id: usize, style: Option<u32>) {
+        self.text = Some(id.to_string());
+        if let Some(style) = style {
+            self.style = Some(style);
+        }
+        self.cell_type = Some(CellType::SharedString);
+        self.formula = None;
+    }
+
     pub(crate) fn update_by_api_cell<T: CellDisplay + CellValue>(&mut self, api_cell: &ApiCell<T>) -> CellResult<()> {
         if let Some(text) = &api_cell.text {
             self.text = Some(text.to_display());
@@ -103,6 +114,31 @@ impl Cell {
         }
         Ok(())
     }
+
+    ///
+    /// Drop the cached result of the formula, returns whether there was one
+    ///
+    pub(crate) fn clear_formula_value(&mut self) -> bool {
+        self.cell_type = None;
+        self.text.take().is_some()
+    }
+}
+
+#[cfg(feature = "calc")]
+impl Cell {
+    ///
+    /// Store the result of the formula, written as a formula string if it is a text
+    ///
+    pub(crate) fn set_formula_value(&mut self, value: &Value) {
+        let (cell_type, text) = match value {
+            Value::Number(number) => (CellType::Number, number.to_string()),
+            Value::Bool(bool) => (CellType::Boolean, if *bool { "1" } else { "0" }.to_string()),
+            Value::Error(error) => (CellType::Error, error.to_string()),
+            value => (CellType::String, value.to_string()),
+        };
+        self.cell_type = Some(cell_type);
+        self.text = Some(text);
+    }
 }
 
 impl Sqref {
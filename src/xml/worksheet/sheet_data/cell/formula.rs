@@ -1,5 +1,6 @@
 use serde::{Deserialize, Serialize};
 use crate::api::cell::formula::Formula as ApiFormula;
+use crate::utils::reference_helper::Shift;
 
 #[derive(Debug, Clone, Default, Deserialize, Serialize)]
 pub(crate) struct Formula {
@@ -41,6 +42,38 @@ impl Formula {
     }
 }
 
+impl Formula {
+    ///
+    /// The index of a shared formula, whose first cell holds the formula and the range of the cells sharing it
+    ///
+    pub(crate) fn shared_index(&self) -> Option<i32> {
+        self.si.filter(|_| self.formula_type.as_deref() == Some("shared"))
+    }
+
+    ///
+    /// Make a cell of a shared formula hold its own formula
+    ///
+    pub(crate) fn unshare(&mut self, formula: String) {
+        self.formula = formula;
+        self.formula_type = None;
+        self.formula_ref = None;
+        self.si = None;
+    }
+
+    ///
+    /// Shift the references to the cells of a worksheet, and the cells of an array formula in it
+    ///
+    pub(crate) fn shift(&mut self, shift: Shift, sheet_name: &str, in_sheet: bool) {
+        self.formula = shift.shift_formula(&self.formula, sheet_name, in_sheet);
+        if !in_sheet {
+            return;
+        }
+        if let Some(formula_ref) = self.formula_ref.as_deref().and_then(|formula_ref| shift.shift_ref(formula_ref)) {
+            self.formula_ref = Some(formula_ref);
+        }
+    }
+}
+
 impl Formula {
     // pub(crate) fn from_formula_type(formula: &str, formula_type: FormulaType) -> Formula {
     //     // let mut formula = formula.trim_matches(|f| f == '{' || f == '}').to_string();
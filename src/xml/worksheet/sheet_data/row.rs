@@ -5,6 +5,7 @@ use crate::api::cell::values::{CellDisplay, CellValue};
 use crate::api::worksheet::row::Row as ApiRow;
 use crate::api::cell::Cell as ApiCell;
 use crate::result::RowResult;
+use crate::utils::reference_helper::Shift;
 use crate::xml::worksheet::sheet_data::Cell;
 
 #[derive(Debug, Clone, Deserialize, Serialize, Default)]
@@ -63,6 +64,21 @@ impl Row {
         }
     }
 
+    pub(crate) fn min_col(&self) -> u32 {
+        match self.cells.first() {
+            Some(cell) => cell.loc.col,
+            None => 0
+        }
+    }
+
+    ///
+    /// Cells between first_col and last_col inclusive, in order
+    ///
+    pub(crate) fn cells_between(&self, first_col: u32, last_col: u32) -> impl Iterator<Item = &Cell> {
+        let start = self.get_position_by_col(first_col);
+        self.cells[start..].iter().take_while(move |cell| cell.loc.col <= last_col)
+    }
+
     pub(crate) fn max_col(&self) -> u32 {
         match self.cells.last() {
             Some(cell) => cell.loc.col,
@@ -70,6 +86,29 @@ impl Row {
         }
     }
 
+    ///
+    /// Renumber the row and its cells
+    ///
+    pub(crate) fn set_row(&mut self, row: u32) {
+        self.row = row;
+        self.cells.iter_mut().for_each(|cell| cell.loc.row = row);
+    }
+
+    ///
+    /// Move the cells after the inserted or deleted columns, the cells of the deleted columns are removed
+    ///
+    pub(crate) fn shift_columns(&mut self, shift: Shift) {
+        self.cells.retain_mut(|cell| match shift.shift_index(cell.loc.col) {
+            Some(col) => {
+                cell.loc.col = col;
+                true
+            }
+            None => false,
+        });
+        // the spans are optional hints
+        self.spans = None;
+    }
+
     pub(crate) fn add_by_api_cell<T: CellDisplay + CellValue>(&mut self, col: u32, api_cell: &ApiCell<T>) -> RowResult<()> {
         let cell = self.get_or_new_cell(col);
         cell.update_by_api_cell(api_cell)?;
@@ -82,6 +121,11 @@ impl Row {
         cell.update_by_display(text, style);
     }
 
+    pub(crate) fn add_shared_string_cell(&mut self, col: u32, id: usize, style: Option<u32>) {
+        let cell = self.get_or_new_cell(col);
+        cell.update_by_shared_string(id, style);
+    }
+
     pub(crate) fn get_display_cell(&self, col: u32) -> Option<&String> {
         let cell = self.get_cell(col);
         if let Some(cell) = cell {
@@ -103,7 +147,6 @@ impl Row {
 
 pub(crate) trait _OrderCell {
     fn get_position_by_col(&self, col: u32) -> usize;
-    fn new_cell(&mut self, col: u32) -> &mut Cell;
     fn get_cell(&self, col: u32) -> Option<&Cell>;
     fn get_cell_mut(&mut self, col: u32) -> Option<&mut Cell>;
     fn get_or_new_cell(&mut self, col: u32) -> &mut Cell;
@@ -126,12 +169,6 @@ impl _OrderCell for Row {
         r
     }
 
-    fn new_cell(&mut self, col: u32) -> &mut Cell {
-        let r = self.get_position_by_col(col);
-        self.cells.insert(r, Cell::new((self.row, col)));
-        return &mut self.cells[r];
-    }
-
     fn get_cell(&self, col: u32) -> Option<&Cell> {
         let r = self.get_position_by_col(col);
         if r >= self.cells.len() {return None}
@@ -7,16 +7,18 @@ use crate::xml::style::color::Color;
 pub(crate) struct SheetPr {
     #[serde(rename = "@codeName", default, skip_serializing_if = "Option::is_none")]
     code_name: Option<String>,
-    #[serde(rename = "pageSetUpPr", default, skip_serializing_if = "Option::is_none")]
-    page_set_up_pr: Option<PageSetUpPr>,
     #[serde(rename = "tabColor", default, skip_serializing_if = "Option::is_none")]
     tab_color: Option<Color>,
     #[serde(rename = "outlinePr", default, skip_serializing_if = "Option::is_none")]
     outline_pr: Option<OutlinePr>,
+    #[serde(rename = "pageSetUpPr", default, skip_serializing_if = "Option::is_none")]
+    page_set_up_pr: Option<PageSetUpPr>,
 }
 
-#[derive(Debug, Clone, Deserialize, Serialize)]
+#[derive(Debug, Clone, Deserialize, Serialize, Default)]
 struct PageSetUpPr {
+    #[serde(rename = "@autoPageBreaks", default, skip_serializing_if = "Option::is_none")]
+    auto_page_breaks: Option<u8>,
     #[serde(rename = "@fitToPage", default, skip_serializing_if = "Option::is_none")]
     fit_to_page: Option<u8>,
 }
@@ -67,4 +69,15 @@ impl SheetPr {
         outline_pr.summary_right = symbols_right as u8;
         self.outline_pr = Some(outline_pr);
     }
+
+    ///
+    /// Print the worksheet on the number of pages of its page setup, instead of at its scale
+    ///
+    pub(crate) fn set_fit_to_page(&mut self, fit_to_page: bool) {
+        let page_set_up_pr = self.page_set_up_pr.get_or_insert_with(Default::default);
+        page_set_up_pr.fit_to_page = if fit_to_page { Some(1) } else { None };
+        if page_set_up_pr.auto_page_breaks.is_none() && page_set_up_pr.fit_to_page.is_none() {
+            self.page_set_up_pr = None;
+        }
+    }
 }
@@ -0,0 +1,176 @@
+use serde::{Deserialize, Serialize};
+use crate::api::protection::ProtectionOptions;
+use crate::xml::common::serialize_bool;
+use crate::utils::password_util::{legacy_hash, PasswordHash};
+use crate::utils::reference_helper::Shift;
+
+///
+/// The protection of a worksheet, each attribute set to 1 protects the action
+///
+#[derive(Debug, Clone, Deserialize, Serialize, Default)]
+pub(crate) struct SheetProtection {
+    #[serde(rename = "@password", default, skip_serializing_if = "Option::is_none")]
+    password: Option<String>,
+    #[serde(rename = "@algorithmName", default, skip_serializing_if = "Option::is_none")]
+    algorithm_name: Option<String>,
+    #[serde(rename = "@hashValue", default, skip_serializing_if = "Option::is_none")]
+    hash_value: Option<String>,
+    #[serde(rename = "@saltValue", default, skip_serializing_if = "Option::is_none")]
+    salt_value: Option<String>,
+    #[serde(rename = "@spinCount", default, skip_serializing_if = "Option::is_none")]
+    spin_count: Option<u32>,
+    #[serde(rename = "@sheet", default, serialize_with = "serialize_bool", skip_serializing_if = "Option::is_none")]
+    sheet: Option<bool>,
+    #[serde(rename = "@objects", default, serialize_with = "serialize_bool", skip_serializing_if = "Option::is_none")]
+    objects: Option<bool>,
+    #[serde(rename = "@scenarios", default, serialize_with = "serialize_bool", skip_serializing_if = "Option::is_none")]
+    scenarios: Option<bool>,
+    #[serde(rename = "@formatCells", default, serialize_with = "serialize_bool", skip_serializing_if = "Option::is_none")]
+    format_cells: Option<bool>,
+    #[serde(rename = "@formatColumns", default, serialize_with = "serialize_bool", skip_serializing_if = "Option::is_none")]
+    format_columns: Option<bool>,
+    #[serde(rename = "@formatRows", default, serialize_with = "serialize_bool", skip_serializing_if = "Option::is_none")]
+    format_rows: Option<bool>,
+    #[serde(rename = "@insertColumns", default, serialize_with = "serialize_bool", skip_serializing_if = "Option::is_none")]
+    insert_columns: Option<bool>,
+    #[serde(rename = "@insertRows", default, serialize_with = "serialize_bool", skip_serializing_if = "Option::is_none")]
+    insert_rows: Option<bool>,
+    #[serde(rename = "@insertHyperlinks", default, serialize_with = "serialize_bool", skip_serializing_if = "Option::is_none")]
+    insert_hyperlinks: Option<bool>,
+    #[serde(rename = "@deleteColumns", default, serialize_with = "serialize_bool", skip_serializing_if = "Option::is_none")]
+    delete_columns: Option<bool>,
+    #[serde(rename = "@deleteRows", default, serialize_with = "serialize_bool", skip_serializing_if = "Option::is_none")]
+    delete_rows: Option<bool>,
+    #[serde(rename = "@selectLockedCells", default, serialize_with = "serialize_bool", skip_serializing_if = "Option::is_none")]
+    select_locked_cells: Option<bool>,
+    #[serde(rename = "@sort", default, serialize_with = "serialize_bool", skip_serializing_if = "Option::is_none")]
+    sort: Option<bool>,
+    #[serde(rename = "@autoFilter", default, serialize_with = "serialize_bool", skip_serializing_if = "Option::is_none")]
+    auto_filter: Option<bool>,
+    #[serde(rename = "@pivotTables", default, serialize_with = "serialize_bool", skip_serializing_if = "Option::is_none")]
+    pivot_tables: Option<bool>,
+    #[serde(rename = "@selectUnlockedCells", default, serialize_with = "serialize_bool", skip_serializing_if = "Option::is_none")]
+    select_unlocked_cells: Option<bool>,
+}
+
+///
+/// The attribute of an action, omitted when it is the default of the action
+///
+fn protect_flag(protected: bool, default: bool) -> Option<bool> {
+    if protected == default { None } else { Some(protected) }
+}
+
+impl SheetProtection {
+    pub(crate) fn from_options(options: &ProtectionOptions) -> SheetProtection {
+        let mut sheet_protection = SheetProtection {
+            sheet: Some(true),
+            objects: protect_flag(!options.edit_objects, false),
+            scenarios: protect_flag(!options.edit_scenarios, false),
+            format_cells: protect_flag(!options.format_cells, true),
+            format_columns: protect_flag(!options.format_columns, true),
+            format_rows: protect_flag(!options.format_rows, true),
+            insert_columns: protect_flag(!options.insert_columns, true),
+            insert_rows: protect_flag(!options.insert_rows, true),
+            insert_hyperlinks: protect_flag(!options.insert_hyperlinks, true),
+            delete_columns: protect_flag(!options.delete_columns, true),
+            delete_rows: protect_flag(!options.delete_rows, true),
+            select_locked_cells: protect_flag(!options.select_locked_cells, false),
+            sort: protect_flag(!options.sort, true),
+            auto_filter: protect_flag(!options.autofilter, true),
+            pivot_tables: protect_flag(!options.pivot_tables, true),
+            select_unlocked_cells: protect_flag(!options.select_unlocked_cells, false),
+            ..Default::default()
+        };
+        match (&options.password, options.legacy_password) {
+            (Some(password), true) => sheet_protection.password = Some(legacy_hash(password)),
+            (Some(password), false) => {
+                let hash = PasswordHash::new(password);
+                sheet_protection.algorithm_name = Some(hash.algorithm_name);
+                sheet_protection.hash_value = Some(hash.hash_value);
+                sheet_protection.salt_value = Some(hash.salt_value);
+                sheet_protection.spin_count = Some(hash.spin_count);
+            }
+            (None, _) => {}
+        }
+        sheet_protection
+    }
+
+    pub(crate) fn is_protected(&self) -> bool {
+        self.sheet == Some(true)
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, Default)]
+pub(crate) struct ProtectedRanges {
+    #[serde(rename = "protectedRange", default)]
+    protected_range: Vec<ProtectedRange>,
+}
+
+impl ProtectedRanges {
+    pub(crate) fn add_protected_range(&mut self, name: &str, sqref: &str, password: Option<&str>) {
+        let mut protected_range = ProtectedRange {
+            password: None,
+            sqref: sqref.to_string(),
+            name: name.to_string(),
+            security_descriptor: None,
+            algorithm_name: None,
+            hash_value: None,
+            salt_value: None,
+            spin_count: None,
+        };
+        if let Some(password) = password {
+            let hash = PasswordHash::new(password);
+            protected_range.algorithm_name = Some(hash.algorithm_name);
+            protected_range.hash_value = Some(hash.hash_value);
+            protected_range.salt_value = Some(hash.salt_value);
+            protected_range.spin_count = Some(hash.spin_count);
+        }
+        self.protected_range.push(protected_range);
+    }
+
+    pub(crate) fn get_protected_ranges(&self) -> Vec<(String, String)> {
+        self.protected_range.iter()
+            .map(|protected_range| (protected_range.name.clone(), protected_range.sqref.clone()))
+            .collect()
+    }
+
+    ///
+    /// Move the ranges, the ones of the deleted cells are removed
+    ///
+    pub(crate) fn shift(&mut self, shift: Shift) {
+        self.protected_range.retain_mut(|protected_range| match shift.shift_sqref(&protected_range.sqref) {
+            Some(sqref) => {
+                protected_range.sqref = sqref;
+                true
+            }
+            None => false,
+        });
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.protected_range.is_empty()
+    }
+}
+
+///
+/// A range of a protected worksheet which may be edited, with its own password
+///
+#[derive(Debug, Clone, Deserialize, Serialize)]
+struct ProtectedRange {
+    #[serde(rename = "@password", default, skip_serializing_if = "Option::is_none")]
+    password: Option<String>,
+    #[serde(rename = "@sqref")]
+    sqref: String,
+    #[serde(rename = "@name")]
+    name: String,
+    #[serde(rename = "@securityDescriptor", default, skip_serializing_if = "Option::is_none")]
+    security_descriptor: Option<String>,
+    #[serde(rename = "@algorithmName", default, skip_serializing_if = "Option::is_none")]
+    algorithm_name: Option<String>,
+    #[serde(rename = "@hashValue", default, skip_serializing_if = "Option::is_none")]
+    hash_value: Option<String>,
+    #[serde(rename = "@saltValue", default, skip_serializing_if = "Option::is_none")]
+    salt_value: Option<String>,
+    #[serde(rename = "@spinCount", default, skip_serializing_if = "Option::is_none")]
+    spin_count: Option<u32>,
+}
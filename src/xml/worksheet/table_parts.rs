@@ -13,4 +13,14 @@ pub(crate) struct TableParts {
 pub(crate) struct TablePart {
     #[serde(rename(serialize = "@r:id", deserialize = "@id"))]
     r_id: Rel,
-}
\ No newline at end of file
+}
+impl TableParts {
+    pub(crate) fn add_table_part(&mut self, r_id: u32) {
+        self.table_part.push(TablePart { r_id: Rel::from_id(r_id) });
+        self.count = self.table_part.len() as u32;
+    }
+
+    pub(crate) fn get_rids(&self) -> Vec<u32> {
+        self.table_part.iter().map(|table_part| table_part.r_id.get_id()).collect()
+    }
+}
@@ -0,0 +1,23 @@
+#![allow(dead_code)]
+
+use std::io::Read;
+
+///
+/// The content of a part of a saved workbook
+///
+pub fn read_part(path: &str, part: &str) -> String {
+    let file = std::fs::File::open(path).unwrap();
+    let mut archive = zip::ZipArchive::new(file).unwrap();
+    let mut xml = String::new();
+    archive.by_name(part).unwrap().read_to_string(&mut xml).unwrap();
+    xml
+}
+
+///
+/// The names of the parts of a saved workbook
+///
+pub fn part_names(path: &str) -> Vec<String> {
+    let file = std::fs::File::open(path).unwrap();
+    let archive = zip::ZipArchive::new(file).unwrap();
+    archive.file_names().map(String::from).collect()
+}
@@ -0,0 +1,49 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use crate::common::read_part;
+    use edit_xlsx::{Read, Value, Workbook, WorkbookResult, Write};
+
+    #[test]
+    fn test_keep_cached_values() -> WorkbookResult<()> {
+        let mut workbook = Workbook::from_path("tests/xlsx/business-budget.xlsx")?;
+        let worksheet = workbook.get_worksheet_mut(1)?;
+        let (formula, value) = worksheet.read_formula("F6")?.unwrap();
+        assert!(!formula.is_empty());
+        assert!(matches!(value, Value::Number(_)));
+        worksheet.write("Z100", "edited")?;
+        workbook.save_as("tests/output/cached_value_test_keep_cached_values.xlsx")?;
+        let workbook = Workbook::from_path("tests/output/cached_value_test_keep_cached_values.xlsx")?;
+        let worksheet = workbook.get_worksheet(1)?;
+        assert_eq!(worksheet.read_formula("F6")?, Some((formula, value)));
+        // the cells of a shared formula read the formula moved to them
+        assert_eq!(worksheet.read_formula("F7")?.map(|(formula, _)| formula), Some("D7-E7".to_string()));
+        assert_eq!(worksheet.read_formula("Z100")?, None);
+        assert_eq!(worksheet.read_formula("Z101")?, None);
+        let xml = read_part("tests/output/cached_value_test_keep_cached_values.xlsx", "xl/workbook.xml");
+        assert!(!xml.contains("fullCalcOnLoad"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_clear_formula_values() -> WorkbookResult<()> {
+        let mut workbook = Workbook::from_path("tests/xlsx/business-budget.xlsx")?;
+        workbook.clear_formula_values()?;
+        let worksheet = workbook.get_worksheet(1)?;
+        let (formula, value) = worksheet.read_formula("F6")?.unwrap();
+        assert!(!formula.is_empty());
+        assert_eq!(value, Value::Empty);
+        workbook.save_as("tests/output/cached_value_test_clear_formula_values.xlsx")?;
+        let xml = read_part("tests/output/cached_value_test_clear_formula_values.xlsx", "xl/workbook.xml");
+        assert!(xml.contains("fullCalcOnLoad=\"true\""));
+        // nothing to drop in a workbook without formulas
+        let mut workbook = Workbook::new();
+        workbook.get_worksheet_mut(1)?.write("A1", 1)?;
+        workbook.clear_formula_values()?;
+        workbook.save_as("tests/output/cached_value_test_no_formula.xlsx")?;
+        let xml = read_part("tests/output/cached_value_test_no_formula.xlsx", "xl/workbook.xml");
+        assert!(!xml.contains("fullCalcOnLoad"));
+        Ok(())
+    }
+}
@@ -0,0 +1,158 @@
+#[cfg(all(test, feature = "calc"))]
+mod tests {
+    use edit_xlsx::{ErrorKind, ExcelDateTime, Read, Value, Workbook, WorkbookResult, Write};
+
+    #[test]
+    fn test_calculate() -> WorkbookResult<()> {
+        let mut workbook = Workbook::new();
+        let worksheet = workbook.get_worksheet_mut(1)?;
+        worksheet.write("A1", 1)?;
+        worksheet.write("A2", 2)?;
+        worksheet.write("A3", 3.5)?;
+        worksheet.write("B1", "Hello")?;
+        worksheet.write_formula("C1", "SUM(A1:A3)")?;
+        worksheet.write_formula("C2", "(A1+A2)*A3-1")?;
+        worksheet.write_formula("C3", "B1&\" \"&\"World\"")?;
+        worksheet.write_formula("C4", "IF(C1>5,\"big\",\"small\")")?;
+        worksheet.write_formula("C5", "A1/0")?;
+        worksheet.write_formula("C6", "IFERROR(C5,-1)")?;
+        worksheet.write_formula("C7", "AVERAGE(A:A)")?;
+        worksheet.write_formula("C8", "ROUND(A3/3,2)")?;
+        worksheet.write_formula("C9", "TEXT(C1,\"0.00\")")?;
+        worksheet.write_formula("C10", "\"a\"=\"A\"")?;
+        worksheet.write_formula("C11", "sum(a1:a2)")?;
+        workbook.define_name("Total", "Sheet1!$C$1")?;
+        let worksheet = workbook.add_worksheet()?;
+        worksheet.write_formula("A1", "Sheet1!C1*2+Total")?;
+        worksheet.write_formula("A2", "UNKNOWN.FUNCTION(1)")?;
+        workbook.calculate()?;
+        let worksheet = workbook.get_worksheet(1)?;
+        assert_eq!(worksheet.read_value("C1")?, Value::Number(6.5));
+        assert_eq!(worksheet.read_value("C2")?, Value::Number(9.5));
+        assert_eq!(worksheet.read_value("C3")?, Value::String("Hello World".to_string()));
+        assert_eq!(worksheet.read_value("C4")?, Value::String("big".to_string()));
+        assert_eq!(worksheet.read_value("C5")?, Value::Error(ErrorKind::Div0));
+        assert_eq!(worksheet.read_value("C6")?, Value::Number(-1.0));
+        assert_eq!(worksheet.read_value("C7")?, Value::Number(6.5 / 3.0));
+        assert_eq!(worksheet.read_value("C8")?, Value::Number(1.17));
+        assert_eq!(worksheet.read_value("C9")?, Value::String("6.50".to_string()));
+        assert_eq!(worksheet.read_value("C10")?, Value::Bool(true));
+        assert_eq!(worksheet.read_value("C11")?, Value::Number(3.0));
+        let worksheet = workbook.get_worksheet(2)?;
+        assert_eq!(worksheet.read_value("A1")?, Value::Number(19.5));
+        // formulas the calculator does not support keep their values
+        assert_eq!(worksheet.read_value("A2")?, Value::Empty);
+        Ok(())
+    }
+
+    #[test]
+    fn test_calculate_lookup() -> WorkbookResult<()> {
+        let mut workbook = Workbook::new();
+        let worksheet = workbook.get_worksheet_mut(1)?;
+        for (row, (fruit, price)) in [("Apple", 3), ("Banana", 2), ("Cherry", 5), ("Banana", 4)].iter().enumerate() {
+            worksheet.write((row as u32 + 1, 1), *fruit)?;
+            worksheet.write((row as u32 + 1, 2), *price)?;
+        }
+        worksheet.write_formula("D1", "VLOOKUP(\"cherry\",A1:B4,2,FALSE)")?;
+        worksheet.write_formula("D2", "XLOOKUP(\"Banana\",A1:A4,B1:B4,0,0,-1)")?;
+        worksheet.write_formula("D3", "INDEX(B1:B4,MATCH(\"B*\",A1:A4,0))")?;
+        worksheet.write_formula("D4", "VLOOKUP(\"Durian\",A1:B4,2,FALSE)")?;
+        worksheet.write_formula("D5", "COUNTIF(A1:A4,\"Banana\")")?;
+        worksheet.write_formula("D6", "SUMIF(B1:B4,\">2\")")?;
+        worksheet.write_formula("D7", "SUMIFS(B1:B4,A1:A4,\"<>Banana\",B1:B4,\"<5\")")?;
+        worksheet.write_formula("D8", "SUMPRODUCT(B1:B4,B1:B4)")?;
+        workbook.calculate()?;
+        let worksheet = workbook.get_worksheet(1)?;
+        assert_eq!(worksheet.read_value("D1")?, Value::Number(5.0));
+        assert_eq!(worksheet.read_value("D2")?, Value::Number(4.0));
+        assert_eq!(worksheet.read_value("D3")?, Value::Number(2.0));
+        assert_eq!(worksheet.read_value("D4")?, Value::Error(ErrorKind::NA));
+        assert_eq!(worksheet.read_value("D5")?, Value::Number(2.0));
+        assert_eq!(worksheet.read_value("D6")?, Value::Number(12.0));
+        assert_eq!(worksheet.read_value("D7")?, Value::Number(3.0));
+        assert_eq!(worksheet.read_value("D8")?, Value::Number(54.0));
+        Ok(())
+    }
+
+    #[test]
+    fn test_calculate_date() -> WorkbookResult<()> {
+        let mut workbook = Workbook::new();
+        let worksheet = workbook.get_worksheet_mut(1)?;
+        worksheet.write("A1", ExcelDateTime::from_ymd_hms(2024, 1, 31, 0, 0, 0.0).unwrap())?;
+        worksheet.write_formula("B1", "YEAR(A1)*100+MONTH(A1)")?;
+        worksheet.write_formula("B2", "EDATE(A1,1)")?;
+        worksheet.write_formula("B3", "EOMONTH(A1,1)-DATE(2024,1,1)")?;
+        worksheet.write_formula("B4", "WEEKDAY(A1)")?;
+        worksheet.write_formula("B5", "TEXT(A1,\"yyyy-mm-dd\")")?;
+        workbook.calculate()?;
+        let worksheet = workbook.get_worksheet(1)?;
+        assert_eq!(worksheet.read_value("B1")?, Value::Number(202401.0));
+        assert_eq!(worksheet.read_value("B2")?, Value::Number(ExcelDateTime::from_ymd_hms(2024, 2, 29, 0, 0, 0.0).unwrap().serial()));
+        assert_eq!(worksheet.read_value("B3")?, Value::Number(59.0));
+        assert_eq!(worksheet.read_value("B4")?, Value::Number(4.0));
+        assert_eq!(worksheet.read_value("B5")?, Value::String("2024-01-31".to_string()));
+        Ok(())
+    }
+
+    #[test]
+    fn test_save_calculated() -> WorkbookResult<()> {
+        let mut workbook = Workbook::new();
+        let worksheet = workbook.get_worksheet_mut(1)?;
+        worksheet.write("A1", 20)?;
+        worksheet.write_formula("A2", "A1*2")?;
+        worksheet.write_formula("A3", "A2>10")?;
+        worksheet.write_formula("A4", "A4+1")?;
+        worksheet.write_formula("A5", "A4*2")?;
+        worksheet.write_formula("B1", "B2+1")?;
+        worksheet.write_formula("B2", "B1+1")?;
+        // saving does not calculate the workbook
+        workbook.save_as("tests/output/calc_test_save_calculated.xlsx")?;
+        let saved = Workbook::from_path("tests/output/calc_test_save_calculated.xlsx")?;
+        assert_eq!(saved.get_worksheet(1)?.read_value("A2")?, Value::Empty);
+        workbook.calculate()?;
+        workbook.save_as("tests/output/calc_test_save_calculated.xlsx")?;
+        let workbook = Workbook::from_path("tests/output/calc_test_save_calculated.xlsx")?;
+        let worksheet = workbook.get_worksheet(1)?;
+        assert_eq!(worksheet.read_cell("A2")?.formula(), Some("A1*2"));
+        assert_eq!(worksheet.read_value("A2")?, Value::Number(40.0));
+        assert_eq!(worksheet.read_value("A3")?, Value::Bool(true));
+        // the formulas in a circular reference keep their cached values
+        assert_eq!(worksheet.read_value("A4")?, Value::Empty);
+        assert_eq!(worksheet.read_value("B1")?, Value::Empty);
+        assert_eq!(worksheet.read_value("B2")?, Value::Empty);
+        assert_eq!(worksheet.read_value("A5")?, Value::Number(0.0));
+        Ok(())
+    }
+
+    #[test]
+    fn test_calculate_long_chain() -> WorkbookResult<()> {
+        let mut workbook = Workbook::new();
+        let worksheet = workbook.get_worksheet_mut(1)?;
+        for row in 1..=20000 {
+            worksheet.write_formula((row, 1), &format!("A{}+1", row + 1))?;
+        }
+        worksheet.write((20001, 1), 1)?;
+        workbook.calculate()?;
+        let worksheet = workbook.get_worksheet(1)?;
+        assert_eq!(worksheet.read_value("A1")?, Value::Number(20001.0));
+        workbook.save_as("tests/output/calc_test_long_chain.xlsx")?;
+        Ok(())
+    }
+
+    #[test]
+    fn test_calculate_from_file() -> WorkbookResult<()> {
+        let original = Workbook::from_path("tests/xlsx/business-budget.xlsx")?;
+        let mut workbook = Workbook::from_path("tests/xlsx/business-budget.xlsx")?;
+        workbook.calculate()?;
+        for (original, calculated) in original.worksheets().zip(workbook.worksheets()) {
+            for (row, cells) in original.rows() {
+                for (col, cell) in cells {
+                    if cell.formula().is_some() {
+                        assert_eq!(original.read_value((row, col))?, calculated.read_value((row, col))?);
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,112 @@
+#[cfg(test)]
+mod tests {
+    use edit_xlsx::{Chart, ChartAxis, ChartGrouping, ChartLegendPosition, ChartSeries, ChartType, FormatColor, Workbook, WorkbookResult, Write};
+
+    #[test]
+    fn test_insert_chart() -> WorkbookResult<()> {
+        let mut workbook = Workbook::new();
+        let worksheet = workbook.get_worksheet_mut(1)?;
+        worksheet.write_row("A1", &["Month", "Sales", "Costs"])?;
+        for (row, month) in ["Jan", "Feb", "Mar", "Apr"].iter().enumerate() {
+            let row = row as u32 + 2;
+            worksheet.write((row, 1), *month)?;
+            worksheet.write((row, 2), row * 10)?;
+            worksheet.write((row, 3), row * 7)?;
+        }
+        let sales = ChartSeries::new("Sheet1!$B$2:$B$5")
+            .set_name("=Sheet1!$B$1")
+            .set_categories("Sheet1!$A$2:$A$5")
+            .set_color(FormatColor::RGB(68, 114, 196));
+        let costs = ChartSeries::new("=Sheet1!$C$2:$C$5")
+            .set_name("Costs")
+            .set_categories("Sheet1!$A$2:$A$5")
+            .set_color(FormatColor::Theme(5, 0.0));
+        let charts = vec![
+            Chart::new(ChartType::Column)
+                .add_series(sales.clone())
+                .add_series(costs.clone())
+                .set_title("Sales and costs")
+                .set_x_axis(ChartAxis::default().set_title("Month"))
+                .set_y_axis(ChartAxis::default().set_title("Amount").set_min(0.0).set_max(100.0).set_major_unit(20.0).set_num_format("#,##0").set_major_gridlines(true)),
+            Chart::new(ChartType::Bar)
+                .add_series(sales.clone())
+                .add_series(costs.clone())
+                .set_grouping(ChartGrouping::Stacked)
+                .set_x_axis(ChartAxis::default().set_reverse(true))
+                .set_legend_position(ChartLegendPosition::Bottom),
+            Chart::new(ChartType::Line)
+                .add_series(sales.clone())
+                .set_title("=Sheet1!$B$1")
+                .set_style(42),
+            Chart::new(ChartType::Pie)
+                .add_series(costs.clone())
+                .set_legend_position(ChartLegendPosition::None),
+            Chart::new(ChartType::Scatter)
+                .add_series(ChartSeries::new("Sheet1!$C$2:$C$5").set_categories("Sheet1!$B$2:$B$5"))
+                .set_x_axis(ChartAxis::default().set_hidden(true)),
+            Chart::new(ChartType::Area)
+                .add_series(sales)
+                .add_series(costs)
+                .set_grouping(ChartGrouping::PercentStacked),
+        ];
+        for (i, chart) in charts.iter().enumerate() {
+            let row = i as u32 * 16 + 1;
+            worksheet.insert_chart((row, 5, row + 14, 12), chart)?;
+        }
+        workbook.save_as("tests/output/chart_test_insert_chart.xlsx")?;
+
+        let workbook = Workbook::from_path("tests/output/chart_test_insert_chart.xlsx")?;
+        assert_eq!(workbook.get_worksheet(1)?.charts(), charts);
+        Ok(())
+    }
+
+    #[test]
+    fn test_insert_chart_error() -> WorkbookResult<()> {
+        let mut workbook = Workbook::new();
+        let worksheet = workbook.get_worksheet_mut(1)?;
+        assert!(worksheet.insert_chart("A1:F10", &Chart::new(ChartType::Column)).is_err());
+        let chart = Chart::new(ChartType::Column).add_series(ChartSeries::new("Sheet1!$A$1:$A$5"));
+        assert!(worksheet.insert_chart("A1:F10", &chart.clone().set_style(0)).is_err());
+        assert!(worksheet.insert_chart("A1:F10", &chart.set_style(49)).is_err());
+        assert!(worksheet.charts().is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn test_from_chart() -> WorkbookResult<()> {
+        let mut workbook = Workbook::from_path("tests/xlsx/paycheck-calculator.xlsx")?;
+        let worksheet = workbook.get_worksheet_mut_by_name("NEW W-4")?;
+        let read = worksheet.charts();
+        assert_eq!(read.len(), 1);
+        assert_eq!(read[0].chart_type, ChartType::Column);
+        assert_eq!(read[0].series[0].categories.as_deref(), Some("'NEW W-4'!$B$37:$B$46"));
+        assert_eq!(read[0].series[0].values, "'NEW W-4'!$C$37:$C$46");
+        let chart = Chart::new(ChartType::Pie).add_series(ChartSeries::new("'NEW W-4'!$C$37:$C$46"));
+        worksheet.insert_chart("H37:M50", &chart)?;
+        workbook.save_as("tests/output/chart_test_from_chart.xlsx")?;
+
+        let workbook = Workbook::from_path("tests/output/chart_test_from_chart.xlsx")?;
+        let read_again = workbook.get_worksheet_by_name("NEW W-4")?.charts();
+        assert_eq!(read_again.len(), 2);
+        assert_eq!(read_again[0], read[0]);
+        assert_eq!(read_again[1], chart);
+        Ok(())
+    }
+
+    #[test]
+    fn test_from_one_cell_charts() -> WorkbookResult<()> {
+        let mut workbook = Workbook::from_path("tests/xlsx/world-meeting-planner.xlsx")?;
+        let worksheet = workbook.get_worksheet_mut_by_name("Clocks")?;
+        let count = worksheet.charts().len();
+        assert!(count > 0);
+        let chart = Chart::new(ChartType::Line).add_series(ChartSeries::new("Clocks!$A$1:$A$5"));
+        worksheet.insert_chart("A40:F55", &chart)?;
+        workbook.save_as("tests/output/chart_test_from_one_cell_charts.xlsx")?;
+
+        let workbook = Workbook::from_path("tests/output/chart_test_from_one_cell_charts.xlsx")?;
+        let read = workbook.get_worksheet_by_name("Clocks")?.charts();
+        assert_eq!(read.len(), count + 1);
+        assert_eq!(read[count], chart);
+        Ok(())
+    }
+}
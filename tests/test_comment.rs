@@ -0,0 +1,63 @@
+#[cfg(test)]
+mod tests {
+    use edit_xlsx::{Comment, Workbook, WorkbookResult, Write};
+
+    #[test]
+    fn test_write_comment() -> WorkbookResult<()> {
+        let mut workbook = Workbook::new();
+        let worksheet = workbook.get_worksheet_mut(1)?;
+        worksheet.write("B2", "Total")?;
+        worksheet.write_comment("B2", "Sum of the month", "Alice")?;
+        worksheet.write_comment_with_options("A1", &Comment::new("Always shown").set_author("Bob").set_visible().set_size(150.0, 60.0))?;
+        worksheet.write_comment("C5", " spaced ", "Alice")?;
+        workbook.save_as("tests/output/comment_test_write_comment.xlsx")?;
+        let workbook = Workbook::from_path("tests/output/comment_test_write_comment.xlsx")?;
+        let worksheet = workbook.get_worksheet(1)?;
+        assert_eq!(worksheet.read_comment("B2"), Some(Comment::new("Sum of the month").set_author("Alice")));
+        assert_eq!(worksheet.read_comment("A1"), Some(Comment::new("Always shown").set_author("Bob").set_visible().set_size(150.0, 60.0)));
+        assert_eq!(worksheet.read_comment("C5").unwrap().text, " spaced ");
+        assert_eq!(worksheet.read_comment("B3"), None);
+        Ok(())
+    }
+
+    #[test]
+    fn test_replace_comment() -> WorkbookResult<()> {
+        let mut workbook = Workbook::new();
+        let worksheet = workbook.get_worksheet_mut(1)?;
+        worksheet.write_comment("A1", "First", "Alice")?;
+        worksheet.write_comment("A1", "Second", "Bob")?;
+        let workbook = Workbook::from_bytes(&workbook.to_bytes()?)?;
+        let worksheet = workbook.get_worksheet(1)?;
+        assert_eq!(worksheet.read_comment("A1"), Some(Comment::new("Second").set_author("Bob")));
+        Ok(())
+    }
+
+    #[test]
+    fn test_from_comment() -> WorkbookResult<()> {
+        let workbook = Workbook::from_path("tests/xlsx/home-budget.xlsx")?;
+        let worksheet = workbook.get_worksheet(1)?;
+        let comment = worksheet.read_comment("A54").unwrap();
+        assert_eq!((comment.text.as_str(), comment.author.as_str()), ("Registration", "Jon"));
+        assert_eq!((comment.visible, comment.width, comment.height), (false, 201.0, 33.0));
+        // existing comments are kept when the workbook is saved
+        workbook.save_as("tests/output/comment_test_from_comment.xlsx")?;
+        let mut workbook = Workbook::from_path("tests/output/comment_test_from_comment.xlsx")?;
+        let worksheet = workbook.get_worksheet_mut(1)?;
+        assert_eq!(worksheet.read_comment("A54"), Some(comment.clone()));
+        // and when comments are added
+        worksheet.write_comment("B2", "Added", "Alice")?;
+        let workbook = Workbook::from_bytes(&workbook.to_bytes()?)?;
+        let worksheet = workbook.get_worksheet(1)?;
+        assert_eq!(worksheet.read_comment("A54"), Some(comment));
+        assert!(worksheet.read_comment("A52").unwrap().text.starts_with("Regular oil changes"));
+        assert_eq!(worksheet.read_comment("B2"), Some(Comment::new("Added").set_author("Alice")));
+        // a sheet without comments gets its own files
+        let mut workbook = workbook;
+        let worksheet = workbook.get_worksheet_mut(3)?;
+        worksheet.write_comment("A1", "Another sheet", "Alice")?;
+        let workbook = Workbook::from_bytes(&workbook.to_bytes()?)?;
+        assert_eq!(workbook.get_worksheet(3)?.read_comment("A1").unwrap().text, "Another sheet");
+        assert_eq!(workbook.get_worksheet(1)?.read_comment("B2").unwrap().text, "Added");
+        Ok(())
+    }
+}
@@ -0,0 +1,166 @@
+#[cfg(test)]
+mod tests {
+    use edit_xlsx::{ColorScale, ConditionalFormat, ConditionalFormatOperator, ConditionalFormatRule, ConditionalFormatTimePeriod, ConditionalFormatValue, DataBar, DataBarAxisPosition, Format, FormatBorderType, FormatColor, IconSet, IconSetType, Workbook, WorkbookResult};
+
+    #[test]
+    fn test_add_conditional_format() -> WorkbookResult<()> {
+        let mut workbook = Workbook::new();
+        let worksheet = workbook.get_worksheet_mut(1)?;
+        let red = Format::default().set_color(FormatColor::RGB(156, 0, 6)).set_background_color(FormatColor::RGB(255, 199, 206));
+        let bold = Format::default().set_bold().set_border_bottom(FormatBorderType::Thin);
+        let percent = Format::default().set_num_format("0.0%");
+        let conditional_formats = vec![
+            ("A1:A10", ConditionalFormat::new(ConditionalFormatRule::CellValue(ConditionalFormatOperator::Between, "1".to_string(), Some("10".to_string()))).set_format(&red)),
+            ("A1:A10", ConditionalFormat::new(ConditionalFormatRule::CellValue(ConditionalFormatOperator::GreaterThan, "$C$1".to_string(), None)).set_format(&bold).set_stop_if_true(true)),
+            ("B1:B10", ConditionalFormat::new(ConditionalFormatRule::TextContains("error".to_string())).set_format(&red)),
+            ("B1:B10", ConditionalFormat::new(ConditionalFormatRule::TextNotContains("ok".to_string()))),
+            ("B1:B10", ConditionalFormat::new(ConditionalFormatRule::TextBeginsWith("A".to_string()))),
+            ("B1:B10", ConditionalFormat::new(ConditionalFormatRule::TextEndsWith("z".to_string()))),
+            ("C1:C10", ConditionalFormat::new(ConditionalFormatRule::Top(3)).set_format(&percent)),
+            ("C1:C10", ConditionalFormat::new(ConditionalFormatRule::Bottom(2))),
+            ("C1:C10", ConditionalFormat::new(ConditionalFormatRule::TopPercent(10))),
+            ("C1:C10", ConditionalFormat::new(ConditionalFormatRule::BottomPercent(5))),
+            ("D1:D10", ConditionalFormat::new(ConditionalFormatRule::AboveAverage)),
+            ("D1:D10", ConditionalFormat::new(ConditionalFormatRule::BelowAverage)),
+            ("E1:E10", ConditionalFormat::new(ConditionalFormatRule::Duplicate)),
+            ("E1:E10", ConditionalFormat::new(ConditionalFormatRule::Unique)),
+            ("F1:F10", ConditionalFormat::new(ConditionalFormatRule::Blanks)),
+            ("F1:F10", ConditionalFormat::new(ConditionalFormatRule::NoBlanks)),
+            ("F1:F10", ConditionalFormat::new(ConditionalFormatRule::Errors)),
+            ("F1:F10", ConditionalFormat::new(ConditionalFormatRule::NoErrors)),
+            ("G1:G10", ConditionalFormat::new(ConditionalFormatRule::TimePeriod(ConditionalFormatTimePeriod::LastWeek))),
+            ("H1:H10", ConditionalFormat::new(ConditionalFormatRule::Formula("$H1>$G1".to_string())).set_format(&red)),
+        ];
+        for (range, conditional_format) in &conditional_formats {
+            worksheet.add_conditional_format(*range, conditional_format)?;
+        }
+        workbook.save_as("tests/output/conditional_format_test_add_conditional_format.xlsx")?;
+
+        let workbook = Workbook::from_path("tests/output/conditional_format_test_add_conditional_format.xlsx")?;
+        let worksheet = workbook.get_worksheet(1)?;
+        let read = worksheet.conditional_formats();
+        assert_eq!(read.len(), conditional_formats.len());
+        for ((range, conditional_format), (read_range, read_conditional_format)) in conditional_formats.iter().zip(read.iter()) {
+            assert_eq!(range, read_range);
+            assert_eq!(conditional_format.rule, read_conditional_format.rule);
+            assert_eq!(conditional_format.stop_if_true, read_conditional_format.stop_if_true);
+            // custom number formats are given an id when they are added
+            let format = conditional_format.format.as_ref().map(|format| (&format.font, &format.fill, &format.border, format.get_num_format()));
+            let read_format = read_conditional_format.format.as_ref().map(|format| (&format.font, &format.fill, &format.border, format.get_num_format()));
+            assert_eq!(format, read_format);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_dxf_shared() -> WorkbookResult<()> {
+        let mut workbook = Workbook::new();
+        let worksheet = workbook.get_worksheet_mut(1)?;
+        let format = Format::default().set_italic();
+        worksheet.add_conditional_format("A1:A3", &ConditionalFormat::new(ConditionalFormatRule::Duplicate).set_format(&format))?;
+        worksheet.add_conditional_format("B1:B3", &ConditionalFormat::new(ConditionalFormatRule::Unique).set_format(&format))?;
+        let workbook = Workbook::from_bytes(&workbook.to_bytes()?)?;
+        let read = workbook.get_worksheet(1)?.conditional_formats();
+        assert_eq!(read[0].1.format, Some(format.clone()));
+        assert_eq!(read[1].1.format, Some(format));
+        Ok(())
+    }
+
+    #[test]
+    fn test_from_conditional_format() -> WorkbookResult<()> {
+        let mut workbook = Workbook::from_path("tests/xlsx/wedding-budget.xlsx")?;
+        let worksheet = workbook.get_worksheet_mut_by_name("Estimator")?;
+        let read = worksheet.conditional_formats();
+        assert_eq!(read[0].0, "C18");
+        assert_eq!(read[0].1.rule, ConditionalFormatRule::CellValue(ConditionalFormatOperator::GreaterThan, "$C$4".to_string(), None));
+        assert!(read[0].1.stop_if_true);
+        assert!(read[0].1.format.as_ref().unwrap().is_bold());
+        let count = read.len();
+        worksheet.add_conditional_format("A1:A5", &ConditionalFormat::new(ConditionalFormatRule::Blanks))?;
+        workbook.save_as("tests/output/conditional_format_test_from_conditional_format.xlsx")?;
+
+        let workbook = Workbook::from_path("tests/output/conditional_format_test_from_conditional_format.xlsx")?;
+        let read = workbook.get_worksheet_by_name("Estimator")?.conditional_formats();
+        assert_eq!(read.len(), count + 1);
+        assert_eq!(read[count], ("A1:A5".to_string(), ConditionalFormat::new(ConditionalFormatRule::Blanks)));
+        Ok(())
+    }
+
+    #[test]
+    fn test_add_scales_bars_and_icons() -> WorkbookResult<()> {
+        let mut workbook = Workbook::new();
+        let worksheet = workbook.get_worksheet_mut(1)?;
+        let rules = vec![
+            ("A1:A10", ConditionalFormatRule::ColorScale(ColorScale::two_color(FormatColor::RGB(255, 255, 255), FormatColor::RGB(99, 190, 123)))),
+            ("B1:B10", ConditionalFormatRule::ColorScale(
+                ColorScale::three_color(FormatColor::RGB(248, 105, 107), FormatColor::RGB(255, 235, 132), FormatColor::RGB(99, 190, 123))
+                    .set_min(ConditionalFormatValue::Number(0.0), FormatColor::RGB(248, 105, 107))
+                    .set_max(ConditionalFormatValue::Formula("$Z$1".to_string()), FormatColor::Theme(9, 0.5))
+            )),
+            ("C1:C10", ConditionalFormatRule::DataBar(DataBar::new(FormatColor::RGB(99, 142, 198)))),
+            ("D1:D10", ConditionalFormatRule::DataBar(
+                DataBar::new(FormatColor::RGB(255, 185, 0))
+                    .set_range(ConditionalFormatValue::Percent(10.0), ConditionalFormatValue::Percentile(90.0))
+                    .set_solid_fill(true)
+                    .set_border_color(FormatColor::RGB(0, 0, 0))
+                    .set_negative_border_color(FormatColor::RGB(255, 0, 0))
+                    .set_axis(DataBarAxisPosition::Middle, FormatColor::RGB(128, 128, 128))
+                    .set_bar_only(true)
+            )),
+            ("E1:E10", ConditionalFormatRule::IconSet(IconSet::new(IconSetType::Arrows3))),
+            ("F1:F10", ConditionalFormatRule::IconSet(
+                IconSet::new(IconSetType::Rating4)
+                    .set_thresholds(&[ConditionalFormatValue::Number(1.0), ConditionalFormatValue::Number(2.0), ConditionalFormatValue::Formula("$Z$2".to_string())])
+                    .set_reverse(true)
+                    .set_icons_only(true)
+            )),
+            ("G1:G10", ConditionalFormatRule::IconSet(IconSet::new(IconSetType::Stars3))),
+            ("H1:H10", ConditionalFormatRule::IconSet(IconSet::new(IconSetType::Boxes5))),
+        ];
+        for (range, rule) in &rules {
+            worksheet.add_conditional_format(*range, &ConditionalFormat::new(rule.clone()))?;
+        }
+        worksheet.add_conditional_format("A1:H10", &ConditionalFormat::new(ConditionalFormatRule::Blanks))?;
+        workbook.save_as("tests/output/conditional_format_test_add_scales_bars_and_icons.xlsx")?;
+
+        let workbook = Workbook::from_path("tests/output/conditional_format_test_add_scales_bars_and_icons.xlsx")?;
+        let read = workbook.get_worksheet(1)?.conditional_formats();
+        assert_eq!(read.len(), rules.len() + 1);
+        for ((range, rule), (read_range, read_conditional_format)) in rules.iter().zip(read.iter()) {
+            assert_eq!(range, read_range);
+            assert_eq!(rule, &read_conditional_format.rule);
+        }
+        assert_eq!(read[rules.len()], ("A1:H10".to_string(), ConditionalFormat::new(ConditionalFormatRule::Blanks)));
+        Ok(())
+    }
+
+    #[test]
+    fn test_icon_set_thresholds() {
+        let thresholds = |icon_set_type| IconSet::new(icon_set_type).thresholds;
+        assert_eq!(thresholds(IconSetType::Flags3), vec![ConditionalFormatValue::Percent(33.0), ConditionalFormatValue::Percent(67.0)]);
+        assert_eq!(thresholds(IconSetType::Arrows4).len(), 3);
+        assert_eq!(thresholds(IconSetType::Quarters5)[0], ConditionalFormatValue::Percent(20.0));
+    }
+
+    #[test]
+    fn test_from_icon_set() -> WorkbookResult<()> {
+        let mut workbook = Workbook::from_path("tests/xlsx/checkbook-register.xlsx")?;
+        let worksheet = workbook.get_worksheet_mut(1)?;
+        let icon_sets = worksheet.conditional_formats().into_iter()
+            .filter(|(_, conditional_format)| matches!(conditional_format.rule, ConditionalFormatRule::IconSet(_)))
+            .collect::<Vec<_>>();
+        assert_eq!(icon_sets.len(), 2);
+        assert_eq!(icon_sets[0].0, "H18:H53");
+        let thresholds = vec![ConditionalFormatValue::Number(0.0), ConditionalFormatValue::Formula("$H$16".to_string())];
+        assert_eq!(icon_sets[0].1.rule, ConditionalFormatRule::IconSet(IconSet::new(IconSetType::TrafficLights3).set_thresholds(&thresholds)));
+        worksheet.add_conditional_format("A1:A5", &ConditionalFormat::new(ConditionalFormatRule::DataBar(DataBar::new(FormatColor::Theme(4, 0.0)))))?;
+        let count = worksheet.conditional_formats().len();
+        workbook.save_as("tests/output/conditional_format_test_from_icon_set.xlsx")?;
+
+        let workbook = Workbook::from_path("tests/output/conditional_format_test_from_icon_set.xlsx")?;
+        let read = workbook.get_worksheet(1)?.conditional_formats();
+        assert_eq!(read.len(), count);
+        assert_eq!(read[count - 1], ("A1:A5".to_string(), ConditionalFormat::new(ConditionalFormatRule::DataBar(DataBar::new(FormatColor::Theme(4, 0.0))))));
+        Ok(())
+    }
+}
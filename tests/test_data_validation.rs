@@ -0,0 +1,78 @@
+#[cfg(test)]
+mod tests {
+    use edit_xlsx::{DataValidation, DataValidationErrorStyle, DataValidationOperator, DataValidationType, Workbook, WorkbookResult};
+
+    #[test]
+    fn test_add_data_validation() -> WorkbookResult<()> {
+        let mut workbook = Workbook::new();
+        let worksheet = workbook.get_worksheet_mut(1)?;
+        let list = DataValidation::list(&["Yes", "No", "Maybe"])
+            .set_input_message("Answer", "Pick an answer");
+        let source = DataValidation::list_source("=$E$1:$E$5").set_dropdown(false);
+        let whole = DataValidation::new(DataValidationType::Whole)
+            .set_criteria(DataValidationOperator::Between, "1", Some("10"))
+            .set_error_message("Invalid", "Between 1 and 10")
+            .set_error_style(DataValidationErrorStyle::Warning);
+        let decimal = DataValidation::new(DataValidationType::Decimal)
+            .set_criteria(DataValidationOperator::GreaterThanOrEqual, "0.5", None)
+            .set_ignore_blank(false);
+        let date = DataValidation::new(DataValidationType::Date)
+            .set_criteria(DataValidationOperator::LessThan, "DATE(2024,1,1)", None);
+        let text_length = DataValidation::new(DataValidationType::TextLength)
+            .set_criteria(DataValidationOperator::LessThanOrEqual, "20", None);
+        let custom = DataValidation::custom("ISNUMBER(G1)");
+        worksheet.add_data_validation("A1:A10", &list)?;
+        worksheet.add_data_validation("B1:B10", &source)?;
+        worksheet.add_data_validation("C1:C10", &whole)?;
+        worksheet.add_data_validation("D1", &decimal)?;
+        worksheet.add_data_validation("E1", &date)?;
+        worksheet.add_data_validation("F1", &text_length)?;
+        worksheet.add_data_validation("G1:G10", &custom)?;
+        workbook.save_as("tests/output/data_validation_test_add_data_validation.xlsx")?;
+
+        let workbook = Workbook::from_path("tests/output/data_validation_test_add_data_validation.xlsx")?;
+        let data_validations = workbook.get_worksheet(1)?.data_validations();
+        assert_eq!(data_validations, vec![
+            ("A1:A10".to_string(), list),
+            ("B1:B10".to_string(), source),
+            ("C1:C10".to_string(), whole),
+            ("D1".to_string(), decimal),
+            ("E1".to_string(), date),
+            ("F1".to_string(), text_length),
+            ("G1:G10".to_string(), custom),
+        ]);
+        assert_eq!(data_validations[0].1.formula1.as_deref(), Some("\"Yes,No,Maybe\""));
+        assert_eq!(data_validations[1].1.formula1.as_deref(), Some("$E$1:$E$5"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_data_validation_error() -> WorkbookResult<()> {
+        let mut workbook = Workbook::new();
+        let worksheet = workbook.get_worksheet_mut(1)?;
+        let values = vec!["value"; 50];
+        assert!(worksheet.add_data_validation("A1", &DataValidation::list(&values)).is_err());
+        assert!(worksheet.add_data_validation("A1", &DataValidation::new(DataValidationType::Whole)).is_err());
+        assert!(worksheet.data_validations().is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn test_from_data_validation() -> WorkbookResult<()> {
+        let mut workbook = Workbook::from_path("tests/xlsx/paycheck-calculator.xlsx")?;
+        let worksheet = workbook.get_worksheet_mut_by_name("NEW W-4")?;
+        let data_validations = worksheet.data_validations();
+        assert_eq!(data_validations.len(), 3);
+        assert_eq!(data_validations[0].0, "C11");
+        assert_eq!(data_validations[0].1.validation_type, DataValidationType::List);
+        assert_eq!(data_validations[0].1.formula1.as_deref(), Some("\"Yes,No\""));
+        worksheet.add_data_validation("D7", &DataValidation::list(&["A", "B"]))?;
+        workbook.save_as("tests/output/data_validation_test_from_data_validation.xlsx")?;
+
+        let workbook = Workbook::from_path("tests/output/data_validation_test_from_data_validation.xlsx")?;
+        let data_validations = workbook.get_worksheet_by_name("NEW W-4")?.data_validations();
+        assert_eq!(data_validations.len(), 4);
+        assert_eq!(data_validations[3].0, "D7");
+        Ok(())
+    }
+}
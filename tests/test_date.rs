@@ -0,0 +1,117 @@
+#[cfg(test)]
+mod tests {
+    use edit_xlsx::{ExcelDateTime, Format, Read, Value, Workbook, WorkbookResult, Write};
+
+    #[test]
+    fn test_write_date() -> WorkbookResult<()> {
+        let date = ExcelDateTime::from_ymd_hms(2024, 2, 29, 0, 0, 0.0).unwrap();
+        let date_time = ExcelDateTime::from_ymd_hms(2024, 2, 29, 18, 45, 0.0).unwrap();
+        let mut workbook = Workbook::new();
+        let worksheet = workbook.get_worksheet_mut(1)?;
+        worksheet.write("A1", date)?;
+        worksheet.write("A2", date_time)?;
+        worksheet.write_with_format("A3", date, &Format::default().set_bold())?;
+        worksheet.write("A4", 45351)?;
+        workbook.save_as("tests/output/date_test_write_date.xlsx")?;
+        let workbook = Workbook::from_path("tests/output/date_test_write_date.xlsx")?;
+        let worksheet = workbook.get_worksheet(1)?;
+        // dates are stored as serial numbers
+        assert_eq!(worksheet.read_cell("A1")?.text, Some("45351".to_string()));
+        assert_eq!(worksheet.read_value("A1")?, Value::DateTime(date));
+        assert_eq!(worksheet.read_value("A2")?, Value::DateTime(date_time));
+        assert_eq!(worksheet.read_value("A3")?, Value::DateTime(date));
+        assert!(worksheet.read_cell("A3")?.format.unwrap().is_bold());
+        // a number without a date format stays a number
+        assert_eq!(worksheet.read_value("A4")?, Value::Number(45351.0));
+        Ok(())
+    }
+
+    #[test]
+    fn test_date1904() -> WorkbookResult<()> {
+        let date = ExcelDateTime::from_ymd_hms(2024, 2, 29, 12, 0, 0.0).unwrap();
+        let mut workbook = Workbook::new();
+        workbook.set_date1904(true)?;
+        let worksheet = workbook.get_worksheet_mut(1)?;
+        worksheet.write("A1", date)?;
+        let workbook = Workbook::from_bytes(&workbook.to_bytes()?)?;
+        assert!(workbook.is_date1904());
+        let worksheet = workbook.get_worksheet(1)?;
+        assert_eq!(worksheet.read_cell("A1")?.text, Some("43889.5".to_string()));
+        assert_eq!(worksheet.read_value("A1")?, Value::DateTime(date));
+        Ok(())
+    }
+
+    #[test]
+    fn test_date1904_round_trip() -> WorkbookResult<()> {
+        let dates = [(1904, 1, 2), (1904, 3, 1), (1999, 12, 31), (2024, 2, 29)]
+            .map(|(year, month, day)| ExcelDateTime::from_ymd_hms(year, month, day, 6, 30, 0.0).unwrap());
+        let mut workbook = Workbook::new();
+        workbook.set_date1904(true)?;
+        let worksheet = workbook.get_worksheet_mut(1)?;
+        for (row, date) in dates.iter().enumerate() {
+            worksheet.write((row as u32 + 1, 1), *date)?;
+        }
+        // the 1904 date system starts in 1904, earlier dates can not be serial numbers
+        worksheet.write("B1", ExcelDateTime::from_ymd_hms(1900, 1, 1, 0, 0, 0.0).unwrap())?;
+        worksheet.write("B2", ExcelDateTime::from_ymd_hms(1903, 12, 31, 0, 0, 0.0).unwrap())?;
+        let workbook = Workbook::from_bytes(&workbook.to_bytes()?)?;
+        let worksheet = workbook.get_worksheet(1)?;
+        assert!(worksheet.read_cell("A1")?.text.unwrap().starts_with("1.2708"));
+        for (row, date) in dates.iter().enumerate() {
+            assert_eq!(worksheet.read_value((row as u32 + 1, 1))?, Value::DateTime(*date));
+        }
+        assert_eq!(worksheet.read_value("B1")?, Value::String("1900-01-01T00:00:00".to_string()));
+        assert_eq!(worksheet.read_value("B2")?, Value::String("1903-12-31T00:00:00".to_string()));
+        Ok(())
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn test_chrono() -> WorkbookResult<()> {
+        use chrono::{NaiveDate, NaiveTime};
+        let date = NaiveDate::from_ymd_opt(2023, 12, 31).unwrap();
+        let date_time = date.and_hms_opt(23, 59, 30).unwrap();
+        let time = NaiveTime::from_hms_opt(6, 0, 0).unwrap();
+        let mut workbook = Workbook::new();
+        let worksheet = workbook.get_worksheet_mut(1)?;
+        worksheet.write("A1", date)?;
+        worksheet.write("A2", date_time)?;
+        worksheet.write("A3", time)?;
+        worksheet.write("A4", NaiveDate::from_ymd_opt(1850, 1, 1).unwrap())?;
+        let workbook = Workbook::from_bytes(&workbook.to_bytes()?)?;
+        let worksheet = workbook.get_worksheet(1)?;
+        let read_date_time = |loc| match worksheet.read_value(loc) {
+            Ok(Value::DateTime(date_time)) => date_time.to_naive_date_time(),
+            _ => None,
+        };
+        assert_eq!(read_date_time("A1"), Some(date.and_time(NaiveTime::MIN)));
+        assert_eq!(read_date_time("A2"), Some(date_time));
+        assert_eq!(worksheet.read_value("A3")?, Value::DateTime(ExcelDateTime::from_serial(0.25)));
+        // dates before 1900 can not be serial numbers
+        assert_eq!(worksheet.read_value("A4")?, Value::String("1850-01-01".to_string()));
+        Ok(())
+    }
+
+    #[cfg(feature = "time")]
+    #[test]
+    fn test_time() -> WorkbookResult<()> {
+        use time::{Date, Month, Time};
+        let date = Date::from_calendar_date(2023, Month::December, 31).unwrap();
+        let date_time = date.with_hms(23, 59, 30).unwrap();
+        let mut workbook = Workbook::new();
+        let worksheet = workbook.get_worksheet_mut(1)?;
+        worksheet.write("A1", date)?;
+        worksheet.write("A2", date_time)?;
+        worksheet.write("A3", Time::from_hms(18, 0, 0).unwrap())?;
+        let workbook = Workbook::from_bytes(&workbook.to_bytes()?)?;
+        let worksheet = workbook.get_worksheet(1)?;
+        let read_date_time = |loc| match worksheet.read_value(loc) {
+            Ok(Value::DateTime(date_time)) => date_time.to_primitive_date_time(),
+            _ => None,
+        };
+        assert_eq!(read_date_time("A1"), Some(date.midnight()));
+        assert_eq!(read_date_time("A2"), Some(date_time));
+        assert_eq!(worksheet.read_value("A3")?, Value::DateTime(ExcelDateTime::from_serial(0.75)));
+        Ok(())
+    }
+}
@@ -0,0 +1,108 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use crate::common::{read_part, part_names};
+    use edit_xlsx::{CellArea, SheetCell, Workbook, WorkbookResult, Write};
+
+    fn cell(sheet: &str, row: u32, col: u32) -> SheetCell {
+        SheetCell { sheet: sheet.to_string(), row, col }
+    }
+
+    #[test]
+    fn test_calc_chain() -> WorkbookResult<()> {
+        let mut workbook = Workbook::from_path("tests/xlsx/accounting.xlsx")?;
+        let worksheet = workbook.get_worksheet_mut(1)?;
+        worksheet.write("C153", 1)?;
+        worksheet.write_formula("A200", "SUM(A1:A10)")?;
+        workbook.save_as("tests/output/dependency_test_calc_chain.xlsx")?;
+        let xml = read_part("tests/output/dependency_test_calc_chain.xlsx", "xl/calcChain.xml");
+        assert!(!xml.contains("r=\"C153\""));
+        assert!(xml.contains("<c r=\"E153\" i=\"1\" l=\"1\"/>"));
+        assert!(xml.ends_with("<c r=\"A200\" i=\"1\"/></calcChain>"));
+        assert_eq!(xml.matches("<c ").count(), 10);
+        Ok(())
+    }
+
+    #[test]
+    fn test_remove_calc_chain() -> WorkbookResult<()> {
+        let mut workbook = Workbook::from_path("tests/xlsx/accounting.xlsx")?;
+        let worksheet = workbook.get_worksheet_mut(1)?;
+        for col in 3..=12 {
+            worksheet.write((153, col), 0)?;
+        }
+        workbook.save_as("tests/output/dependency_test_remove_calc_chain.xlsx")?;
+        let path = "tests/output/dependency_test_remove_calc_chain.xlsx";
+        assert!(!part_names(path).contains(&"xl/calcChain.xml".to_string()));
+        assert!(!read_part(path, "xl/_rels/workbook.xml.rels").contains("calcChain"));
+        assert!(!read_part(path, "[Content_Types].xml").contains("calcChain"));
+        // adding a formula restores the chain
+        workbook.get_worksheet_mut(1)?.write_formula("C153", "SUM(C1:C152)")?;
+        workbook.save_as(path)?;
+        assert!(read_part(path, "xl/calcChain.xml").contains("<c r=\"C153\" i=\"1\"/>"));
+        assert!(read_part(path, "xl/_rels/workbook.xml.rels").contains("calcChain"));
+        assert!(read_part(path, "[Content_Types].xml").contains("calcChain"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_dependency_graph() -> WorkbookResult<()> {
+        let mut workbook = Workbook::new();
+        let worksheet = workbook.get_worksheet_mut(1)?;
+        worksheet.write("A1", 1)?;
+        worksheet.write_formula("B1", "A1*2")?;
+        worksheet.write_formula("C1", "SUM(A1:B1)+Rate")?;
+        worksheet.write_formula("D1", "Data!A:A")?;
+        workbook.define_name("Rate", "Data!$B$2")?;
+        let worksheet = workbook.add_worksheet_by_name("Data")?;
+        worksheet.write_formula("B2", "Sheet1!B1/10")?;
+        let graph = workbook.dependency_graph();
+        assert_eq!(graph.formula_cells().len(), 4);
+        assert_eq!(graph.precedents("Sheet1", "B1"), &[CellArea { sheet: "Sheet1".to_string(), first_row: 1, first_col: 1, last_row: 1, last_col: 1 }]);
+        assert_eq!(graph.precedents("Sheet1", "C1"), &[
+            CellArea { sheet: "Sheet1".to_string(), first_row: 1, first_col: 1, last_row: 1, last_col: 2 },
+            CellArea { sheet: "Data".to_string(), first_row: 2, first_col: 2, last_row: 2, last_col: 2 },
+        ]);
+        assert!(graph.precedents("Sheet1", "A1").is_empty());
+        assert_eq!(graph.dependents("Sheet1", "A1"), vec![cell("Sheet1", 1, 2), cell("Sheet1", 1, 3)]);
+        assert_eq!(graph.dependents("data", "A100"), vec![cell("Sheet1", 1, 4)]);
+        assert_eq!(graph.dependents("Data", "B2"), vec![cell("Sheet1", 1, 3)]);
+        assert!(graph.cycles().is_empty());
+        assert!(!graph.is_circular("Sheet1", "C1"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_lowercase_references() -> WorkbookResult<()> {
+        let mut workbook = Workbook::new();
+        let worksheet = workbook.get_worksheet_mut(1)?;
+        worksheet.write_column("A1", &[1, 2, 3])?;
+        worksheet.write_formula("B1", "sum(a1:a3)")?;
+        let graph = workbook.dependency_graph();
+        assert_eq!(graph.precedents("Sheet1", "B1"), &[CellArea { sheet: "Sheet1".to_string(), first_row: 1, first_col: 1, last_row: 3, last_col: 1 }]);
+        assert_eq!(graph.dependents("Sheet1", "A2"), vec![cell("Sheet1", 1, 2)]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_cycles() -> WorkbookResult<()> {
+        let mut workbook = Workbook::new();
+        let worksheet = workbook.get_worksheet_mut(1)?;
+        worksheet.write_formula("A1", "B1+1")?;
+        worksheet.write_formula("B1", "C1+1")?;
+        worksheet.write_formula("C1", "A1+1")?;
+        worksheet.write_formula("D1", "A1")?;
+        worksheet.write_formula("E1", "E1+1")?;
+        let graph = workbook.dependency_graph();
+        assert_eq!(graph.cycles(), vec![
+            vec![cell("Sheet1", 1, 1), cell("Sheet1", 1, 2), cell("Sheet1", 1, 3)],
+            vec![cell("Sheet1", 1, 5)],
+        ]);
+        assert!(graph.is_circular("Sheet1", "B1"));
+        assert!(!graph.is_circular("Sheet1", "D1"));
+        assert!(graph.is_circular("Sheet1", "E1"));
+        let workbook = Workbook::from_path("tests/xlsx/yearly-calendar.xlsx")?;
+        assert!(workbook.dependency_graph().cycles().is_empty());
+        Ok(())
+    }
+}
@@ -1,5 +1,8 @@
+mod common;
+
 #[cfg(test)]
 mod tests {
+    use crate::common::read_part;
     use edit_xlsx::{Workbook, WorkbookResult, Write};
 
     #[test]
@@ -57,4 +60,56 @@ mod tests {
         calender_workbook.save_as("./tests/output/duplicate_test_from_yearly_calender.xlsx")?;
         Ok(())
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_duplicate_parts() -> WorkbookResult<()> {
+        let mut workbook = Workbook::from_path("tests/xlsx/checkbook-register.xlsx")?;
+        workbook.duplicate_worksheet_by_name("Register")?;
+        let path = "tests/output/duplicate_test_duplicate_parts.xlsx";
+        workbook.save_as(path)?;
+        // the copy has its own parts
+        let xml = read_part(path, "xl/worksheets/_rels/sheet5.xml.rels");
+        for target in ["../tables/table2.xml", "../comments3.xml", "../drawings/vmlDrawing3.vml"] {
+            assert!(xml.contains(&format!("Target=\"{target}\"")), "{target} is missing");
+        }
+        let xml = read_part(path, "[Content_Types].xml");
+        assert!(xml.contains("<Override PartName=\"/xl/tables/table2.xml\""));
+        assert!(xml.contains("<Override PartName=\"/xl/comments3.xml\""));
+        assert_eq!(read_part(path, "xl/drawings/vmlDrawing3.vml"), read_part(path, "xl/drawings/vmlDrawing1.vml"));
+        // with a table named after the table copied, and the structured references to it
+        let xml = read_part(path, "xl/tables/table2.xml");
+        assert!(xml.contains("id=\"2\" name=\"Table1_2\" displayName=\"Table1_2\""));
+        let xml = read_part(path, "xl/worksheets/sheet5.xml");
+        assert!(xml.contains("<f>VLOOKUP(9E+100,Table1_2[Balance],1)</f>"));
+        let xml = read_part(path, "xl/worksheets/sheet1.xml");
+        assert!(xml.contains("<f>VLOOKUP(9E+100,Table1[Balance],1)</f>"));
+
+        let workbook = Workbook::from_path(path)?;
+        let worksheet = workbook.get_worksheet_by_name("Register Duplicated")?;
+        assert_eq!(worksheet.tables()[0].name, "Table1_2");
+        let source = workbook.get_worksheet_by_name("Register")?;
+        assert_eq!(worksheet.read_comment("L1").unwrap().text, source.read_comment("L1").unwrap().text);
+        Ok(())
+    }
+
+    #[test]
+    fn test_duplicate_chart() -> WorkbookResult<()> {
+        let mut workbook = Workbook::from_path("tests/xlsx/paycheck-calculator.xlsx")?;
+        workbook.duplicate_worksheet_by_name("NEW W-4")?;
+        let path = "tests/output/duplicate_test_duplicate_chart.xlsx";
+        workbook.save_as(path)?;
+        let xml = read_part(path, "xl/worksheets/_rels/sheet4.xml.rels");
+        assert!(xml.contains("Target=\"../drawings/drawing3.xml\""));
+        let xml = read_part(path, "xl/drawings/_rels/drawing3.xml.rels");
+        assert!(xml.contains("Target=\"../charts/chart2.xml\""));
+        let xml = read_part(path, "[Content_Types].xml");
+        assert!(xml.contains("<Override PartName=\"/xl/drawings/drawing3.xml\""));
+        assert!(xml.contains("<Override PartName=\"/xl/charts/chart2.xml\""));
+        // the chart of the copy shows the cells of the copy
+        let xml = read_part(path, "xl/charts/chart2.xml");
+        assert!(xml.contains("<c:f>&apos;NEW W-4 Duplicated&apos;!$B$37:$B$46</c:f>"));
+        let xml = read_part(path, "xl/charts/chart1.xml");
+        assert!(xml.contains("<c:f>'NEW W-4'!$B$37:$B$46</c:f>"));
+        Ok(())
+    }
+}
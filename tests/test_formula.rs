@@ -0,0 +1,116 @@
+#[cfg(test)]
+mod tests {
+    use edit_xlsx::{BinaryOperator, CellReference, Expression, FormulaOperator, FormulaToken, ParsedFormula, Reference, ReferenceArea, UnaryOperator, Workbook, WorkbookResult};
+
+    fn cell(row: u32, col: u32, absolute: bool) -> CellReference {
+        CellReference { row, col, row_absolute: absolute, col_absolute: absolute }
+    }
+
+    #[test]
+    fn test_tokenize() {
+        let formula = ParsedFormula::parse("SUM('My sheet'!$A$1:B2, Sheet2!3:4)+[1]Data!C:C*Rate%-Table1[@Amount]&\"a\"\"b\"<>#N/A");
+        let references = formula.references();
+        assert_eq!(references.len(), 3);
+        assert_eq!(references[0], &Reference {
+            workbook: None,
+            sheet: Some("My sheet".to_string()),
+            last_sheet: None,
+            quoted: true,
+            area: ReferenceArea::Range(cell(1, 1, true), cell(2, 2, false)),
+        });
+        assert!(matches!(references[1].area, ReferenceArea::Rows(..)));
+        assert_eq!(references[2].workbook.as_deref(), Some("1"));
+        assert!(matches!(references[2].area, ReferenceArea::Columns(..)));
+        assert!(formula.tokens.contains(&FormulaToken::Text("a\"b".to_string())));
+        assert!(formula.tokens.contains(&FormulaToken::Error("#N/A".to_string())));
+        assert!(formula.tokens.contains(&FormulaToken::Operator(FormulaOperator::Percent)));
+        assert!(formula.tokens.contains(&FormulaToken::Function("SUM".to_string())));
+        assert!(formula.tokens.iter().any(|token| matches!(token, FormulaToken::Name(name) if name.name == "Rate")));
+        assert!(formula.tokens.iter().any(|token| matches!(token, FormulaToken::StructuredReference(reference) if reference.table.as_deref() == Some("Table1"))));
+    }
+
+    #[test]
+    fn test_lossless() {
+        let formulas = [
+            "SUM('My sheet'!$A$1:B2, Sheet2!3:4)",
+            "IF(A1>=1,\"yes\",IF(,\"no\",))",
+            "{1,2;-3,4.5E-3}",
+            "_xlfn.XLOOKUP(A1,Table1[[#This Row],[Key]],[1]!Name)",
+            "Sheet1:Sheet3!A1 + 'It''s'!#REF!",
+            "A1#+@B1:B9 C1:C9",
+        ];
+        for formula in formulas {
+            assert_eq!(ParsedFormula::parse(formula).to_string(), formula);
+            assert!(ParsedFormula::parse(formula).to_expression().is_ok(), "{formula}");
+        }
+    }
+
+    #[test]
+    fn test_expression() {
+        let expression = Expression::parse("-2^2+3*4%").unwrap();
+        let Expression::Binary(left, BinaryOperator::Add, right) = &expression else { panic!("{expression:?}") };
+        assert!(matches!(left.as_ref(), Expression::Binary(base, BinaryOperator::Power, _) if matches!(base.as_ref(), Expression::Unary(UnaryOperator::Minus, _))));
+        assert!(matches!(right.as_ref(), Expression::Binary(_, BinaryOperator::Multiply, percent) if matches!(percent.as_ref(), Expression::Unary(UnaryOperator::Percent, _))));
+        assert_eq!(expression.to_string(), "-2^2+3*4%");
+
+        let expression = Expression::parse("SUM((A1,B1),A1:B2 B1:C3,)").unwrap();
+        let Expression::Function(name, arguments) = &expression else { panic!("{expression:?}") };
+        assert_eq!(name, "SUM");
+        assert_eq!(arguments.len(), 3);
+        assert!(matches!(&arguments[0], Expression::Parenthesis(union) if matches!(union.as_ref(), Expression::Binary(_, BinaryOperator::Union, _))));
+        assert!(matches!(&arguments[1], Expression::Binary(_, BinaryOperator::Intersection, _)));
+        assert_eq!(arguments[2], Expression::Missing);
+        assert_eq!(expression.references().len(), 4);
+        assert_eq!(Expression::parse("={1,2;3,4}").unwrap(), Expression::Array(vec![
+            vec![Expression::Number(1.0), Expression::Number(2.0)],
+            vec![Expression::Number(3.0), Expression::Number(4.0)],
+        ]));
+        assert!(Expression::parse("1+*2").is_err());
+        assert!(Expression::parse("SUM(1,").is_err());
+        assert!(Expression::parse("(1").is_err());
+    }
+
+    #[test]
+    fn test_rewrite() {
+        let mut formula = ParsedFormula::parse("A1+$B$2+Sheet2!C3:D4+'Sheet 2'!A:A+Sheet3!A1");
+        formula.offset(1, 2);
+        assert_eq!(formula.to_string(), "C2+$B$2+Sheet2!E4:F5+'Sheet 2'!C:C+Sheet3!C2");
+        formula.offset(-2, 0);
+        assert_eq!(formula.to_string(), "#REF!+$B$2+Sheet2!E2:F3+'Sheet 2'!C:C+Sheet3!#REF!");
+        formula.rename_sheet("sheet2", "New sheet");
+        formula.rename_sheet("Sheet 2", "Other");
+        assert_eq!(formula.to_string(), "#REF!+$B$2+'New sheet'!E2:F3+Other!C:C+Sheet3!#REF!");
+
+        let mut formula = ParsedFormula::parse("SUM(A1:A10)+Data!B5+B5");
+        formula.shift_rows("Data", false, 3, 2);
+        assert_eq!(formula.to_string(), "SUM(A1:A10)+Data!B7+B5");
+        formula.shift_rows("Sheet1", true, 1, -1);
+        assert_eq!(formula.to_string(), "SUM(A1:A9)+Data!B7+B4");
+        formula.shift_columns("Data", false, 1, -2);
+        assert_eq!(formula.to_string(), "SUM(A1:A9)+Data!#REF!+B4");
+        // the references pushed past the last row or column are deleted, the ranges are cut there
+        let mut formula = ParsedFormula::parse("SUM(B1:B1048576)+SUM(2:1048576)+XFD1+A1048576");
+        formula.shift_rows("Sheet1", true, 1, 1);
+        formula.shift_columns("Sheet1", true, 1, i32::MAX);
+        assert_eq!(formula.to_string(), "SUM(#REF!)+SUM(3:1048576)+#REF!+#REF!");
+    }
+
+    #[test]
+    fn test_from_formulas() -> WorkbookResult<()> {
+        for file in ["accounting", "business-budget", "paycheck-calculator", "world-meeting-planner", "yearly-calendar", "shift-schedule"] {
+            let workbook = Workbook::from_path(format!("tests/xlsx/{file}.xlsx"))?;
+            for worksheet in workbook.worksheets() {
+                for (_, cells) in worksheet.rows() {
+                    for (_, cell) in cells {
+                        if let Some(formula) = cell.formula().filter(|formula| !formula.is_empty()) {
+                            let parsed = ParsedFormula::parse(formula);
+                            assert_eq!(parsed.to_string(), formula);
+                            assert!(parsed.to_expression().is_ok(), "{formula}");
+                        }
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,86 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use crate::common::read_part;
+    use edit_xlsx::{FormatColor, HeaderFooter, Workbook, WorkbookResult};
+
+    #[test]
+    fn test_codes() {
+        let header = HeaderFooter::new()
+            .text("Q&A")
+            .center().bold().font_size(14).text("2024 Report").bold()
+            .right().font("Arial", "Italic").font_color(&FormatColor::RGB(255, 0, 0)).text("Page ").page_number().text(" of ").page_count();
+        assert_eq!(header.to_codes(), "&LQ&&A&C&B&14 2024 Report&B&R&\"Arial,Italic\"&KFF0000Page &P of &N");
+        let footer = HeaderFooter::new().right().sheet_name().text(" ").date().font_color(&FormatColor::Theme(1, 0.5));
+        assert_eq!(footer.to_codes(), "&R&A &D&K01+050");
+        assert_eq!(HeaderFooter::from_codes("&CConfidential").to_codes(), "&CConfidential");
+    }
+
+    #[test]
+    fn test_header_footer() -> WorkbookResult<()> {
+        let mut workbook = Workbook::new();
+        let worksheet = workbook.get_worksheet_mut(1)?;
+        worksheet.set_header(&HeaderFooter::new().center().text("Sales & Costs"))?;
+        worksheet.set_footer(&HeaderFooter::new().right().text("Page ").page_number())?;
+        worksheet.set_first_header(&HeaderFooter::new().center().text("Title"))?;
+        worksheet.set_even_footer(&HeaderFooter::new().left().page_number())?;
+        worksheet.different_odd_even(false);
+        // the header and the footer are limited to 255 characters
+        assert!(worksheet.set_header(&HeaderFooter::new().text(&"a".repeat(254))).is_err());
+        assert!(worksheet.set_header(&HeaderFooter::new().image("examples/pics/rust.png").image("examples/pics/ferris.png")).is_err());
+        workbook.save_as("tests/output/header_footer_test_header_footer.xlsx")?;
+        let xml = read_part("tests/output/header_footer_test_header_footer.xlsx", "xl/worksheets/sheet1.xml");
+        assert!(xml.contains("<headerFooter differentFirst=\"1\"><oddHeader>&amp;CSales &amp;&amp; Costs</oddHeader><oddFooter>&amp;RPage &amp;P</oddFooter>\
+            <evenFooter>&amp;L&amp;P</evenFooter><firstHeader>&amp;CTitle</firstHeader></headerFooter>"));
+
+        let workbook = Workbook::from_path("tests/output/header_footer_test_header_footer.xlsx")?;
+        let worksheet = workbook.get_worksheet(1)?;
+        assert_eq!(worksheet.get_header().as_deref(), Some("&CSales && Costs"));
+        assert_eq!(worksheet.get_footer().as_deref(), Some("&RPage &P"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_header_image() -> WorkbookResult<()> {
+        let mut workbook = Workbook::new();
+        let worksheet = workbook.get_worksheet_mut(1)?;
+        worksheet.set_header(&HeaderFooter::new().center().image("examples/pics/rust.png").right().text("Logo"))?;
+        workbook.save_as("tests/output/header_footer_test_header_image.xlsx")?;
+        let path = "tests/output/header_footer_test_header_image.xlsx";
+        let xml = read_part(path, "xl/worksheets/sheet1.xml");
+        assert!(xml.contains("<oddHeader>&amp;C&amp;G&amp;RLogo</oddHeader>"));
+        assert!(xml.contains("<legacyDrawingHF r:id=\"rId1\"/>"));
+        let rels = read_part(path, "xl/worksheets/_rels/sheet1.xml.rels");
+        assert!(rels.contains("Target=\"../drawings/vmlDrawing1.vml\""));
+        let vml = read_part(path, "xl/drawings/vmlDrawing1.vml");
+        assert!(vml.contains("<v:shape id=\"CH\""));
+        assert!(vml.contains("<v:imagedata o:relid=\"rId1\" o:title=\"rust\"/>"));
+        let rels = read_part(path, "xl/drawings/_rels/vmlDrawing1.vml.rels");
+        assert!(rels.contains("Target=\"../media/image1.png\""));
+
+        // the image is replaced by the one of the new header
+        let mut workbook = Workbook::from_path(path)?;
+        let worksheet = workbook.get_worksheet_mut(1)?;
+        worksheet.set_header(&HeaderFooter::new().left().image("examples/pics/ferris.png"))?;
+        workbook.save_as("tests/output/header_footer_test_replace_image.xlsx")?;
+        let vml = read_part("tests/output/header_footer_test_replace_image.xlsx", "xl/drawings/vmlDrawing1.vml");
+        assert!(vml.contains("<v:shape id=\"LH\""));
+        assert!(vml.contains("width:284.25pt;height:99.75pt"));
+        assert!(!vml.contains("id=\"CH\""));
+        Ok(())
+    }
+
+    #[test]
+    fn test_read_header_footer() -> WorkbookResult<()> {
+        let mut workbook = Workbook::from_path("tests/xlsx/checkbook-register.xlsx")?;
+        let worksheet = workbook.get_worksheet_mut(1)?;
+        assert!(worksheet.get_header().is_none());
+        assert_eq!(worksheet.get_footer().as_deref(), Some("&L&8&K01+049Checkbook Register Template by Vertex42.com&R&8&K01+049© 2008-2017 Vertex42 LLC"));
+        worksheet.set_header(&HeaderFooter::new().center().sheet_name())?;
+        workbook.save_as("tests/output/header_footer_test_read_header_footer.xlsx")?;
+        let xml = read_part("tests/output/header_footer_test_read_header_footer.xlsx", "xl/worksheets/sheet1.xml");
+        assert!(xml.contains("<headerFooter><oddHeader>&amp;C&amp;A</oddHeader><oddFooter>&amp;L&amp;8&amp;K01+049Checkbook"));
+        Ok(())
+    }
+}
@@ -0,0 +1,105 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use crate::common::read_part;
+    use edit_xlsx::{Format, Read, Value, Workbook, WorkbookResult, Write};
+
+    #[test]
+    fn test_import_styles_and_strings() -> WorkbookResult<()> {
+        let mut source = Workbook::new();
+        let worksheet = source.get_worksheet_mut(1)?;
+        worksheet.write_with_format("A1", "Hello", &Format::default().set_bold())?;
+        worksheet.write_with_format("A2", 1234.5, &Format::default().set_num_format("0.000"))?;
+        worksheet.write("A3", "World")?;
+        worksheet.write_formula("A4", "Sheet1!A2*2")?;
+        let mut workbook = Workbook::new();
+        let worksheet = workbook.get_worksheet_mut(1)?;
+        worksheet.write("A1", "World")?;
+        worksheet.write_with_format("A2", 1, &Format::default().set_num_format("0.0"))?;
+        // the name is taken, so the imported worksheet and its formulas are renamed
+        let imported = workbook.import_worksheet(&source, 1)?;
+        assert_eq!(imported.get_name(), "Sheet1 (2)");
+        let path = "tests/output/import_sheet_test_import_styles_and_strings.xlsx";
+        workbook.save_as(path)?;
+        let xml = read_part(path, "xl/worksheets/sheet2.xml");
+        assert!(xml.contains(">&apos;Sheet1 (2)&apos;!A2*2</f>"));
+
+        let workbook = Workbook::from_path(path)?;
+        let worksheet = workbook.get_worksheet_by_name("Sheet1 (2)")?;
+        assert_eq!(worksheet.read_value("A1")?, Value::String("Hello".to_string()));
+        assert_eq!(worksheet.read_value("A3")?, Value::String("World".to_string()));
+        assert!(worksheet.read_cell("A1")?.format.unwrap().is_bold());
+        let format = worksheet.read_cell("A2")?.format.unwrap();
+        assert_eq!((format.get_num_format(), format.get_num_format_index()), ("0.000", Some(165)));
+        // the strings are shared with the destination workbook
+        let xml = read_part(path, "xl/sharedStrings.xml");
+        assert_eq!(xml.matches("<t>World</t>").count(), 1);
+        Ok(())
+    }
+
+    #[test]
+    fn test_import_parts() -> WorkbookResult<()> {
+        let source = Workbook::from_path("tests/xlsx/checkbook-register.xlsx")?;
+        let mut workbook = Workbook::from_path("tests/xlsx/business-budget.xlsx")?;
+        workbook.import_worksheet(&source, 1)?;
+        workbook.import_worksheet(&source, 1)?;
+        let path = "tests/output/import_sheet_test_import_parts.xlsx";
+        workbook.save_as(path)?;
+        let xml = read_part(path, "xl/workbook.xml");
+        assert!(xml.contains("<sheet name=\"Register\" sheetId=\"5\""));
+        assert!(xml.contains("<sheet name=\"Register (2)\" sheetId=\"6\""));
+        assert!(xml.contains("<definedName name=\"numList\" localSheetId=\"4\">&apos;Register (2)&apos;!$L$1:$L$17</definedName>"));
+        // every copied part gets a fresh name
+        let xml = read_part(path, "xl/worksheets/_rels/sheet5.xml.rels");
+        for target in ["../tables/table2.xml", "../comments2.xml", "../drawings/vmlDrawing2.vml", "../drawings/drawing5.xml"] {
+            assert!(xml.contains(&format!("Target=\"{target}\"")), "{target} is missing");
+        }
+        assert!(xml.contains("Target=\"https://www.vertex42.com/ExcelTemplates/excel-checkbook.html\" TargetMode=\"External\""));
+        let xml = read_part(path, "xl/drawings/_rels/drawing5.xml.rels");
+        assert!(xml.contains("Target=\"../media/image5.png\""));
+        let mut archive = zip::ZipArchive::new(std::fs::File::open(path).unwrap()).unwrap();
+        assert!(archive.by_name("xl/media/image5.png").is_ok());
+        let xml = read_part(path, "[Content_Types].xml");
+        assert!(xml.contains("<Override PartName=\"/xl/tables/table2.xml\""));
+        assert!(xml.contains("<Override PartName=\"/xl/comments2.xml\""));
+        // the table names stay unique in the workbook
+        let xml = read_part(path, "xl/tables/table1.xml");
+        assert!(xml.contains("id=\"1\" name=\"Table1\" displayName=\"Table1\""));
+        let xml = read_part(path, "xl/tables/table2.xml");
+        assert!(xml.contains("id=\"2\" name=\"Table2\" displayName=\"Table2\""));
+        // with the structured references of the worksheet to its renamed table
+        let xml = read_part(path, "xl/worksheets/sheet4.xml");
+        assert!(xml.contains("<f>VLOOKUP(9E+100,Table1[Balance],1)</f>"));
+        let xml = read_part(path, "xl/worksheets/sheet5.xml");
+        assert!(xml.contains("<f>VLOOKUP(9E+100,Table2[Balance],1)</f>"));
+
+        let workbook = Workbook::from_path(path)?;
+        let imported = workbook.get_worksheet_by_name("Register (2)")?;
+        let worksheet = source.get_worksheet(1)?;
+        assert_eq!(imported.read_range("A1:H20"), worksheet.read_range("A1:H20"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_import_chart() -> WorkbookResult<()> {
+        let source = Workbook::from_path("tests/xlsx/paycheck-calculator.xlsx")?;
+        let mut workbook = Workbook::new();
+        workbook.import_worksheet_by_name(&source, "NEW W-4")?;
+        let path = "tests/output/import_sheet_test_import_chart.xlsx";
+        workbook.save_as(path)?;
+        let xml = read_part(path, "xl/drawings/_rels/drawing1.xml.rels");
+        assert!(xml.contains("Target=\"../charts/chart1.xml\""));
+        let xml = read_part(path, "xl/charts/chart1.xml");
+        assert!(xml.contains("<c:f>'NEW W-4'!$B$37:$B$46</c:f>"));
+        let xml = read_part(path, "[Content_Types].xml");
+        assert!(xml.contains("<Override PartName=\"/xl/charts/chart1.xml\""));
+
+        let workbook = Workbook::from_path(path)?;
+        let imported = workbook.get_worksheet_by_name("NEW W-4")?;
+        let worksheet = source.get_worksheet_by_name("NEW W-4")?;
+        // column C refers to a worksheet that is not imported
+        assert_eq!(imported.read_range("A1:B58"), worksheet.read_range("A1:B58"));
+        Ok(())
+    }
+}
@@ -0,0 +1,52 @@
+#[cfg(test)]
+mod tests {
+    use std::fs::File;
+    use std::io::Cursor;
+    use edit_xlsx::{Read, Workbook, WorkbookResult, Write};
+
+    #[test]
+    fn test_to_bytes() -> WorkbookResult<()> {
+        let mut workbook = Workbook::new();
+        let worksheet = workbook.get_worksheet_mut(1)?;
+        worksheet.write("A1", "In memory")?;
+        worksheet.write("B1", 42)?;
+        let bytes = workbook.to_bytes()?;
+        // the bytes are a complete xlsx package
+        let workbook = Workbook::from_bytes(&bytes)?;
+        let worksheet = workbook.get_worksheet(1)?;
+        assert_eq!(worksheet.read_cell("B1")?.text, Some("42".to_string()));
+        Ok(())
+    }
+
+    #[test]
+    fn test_from_reader() -> WorkbookResult<()> {
+        let file = File::open("tests/xlsx/accounting.xlsx")?;
+        let mut workbook = Workbook::from_reader(file)?;
+        let worksheet = workbook.get_worksheet_mut(1)?;
+        worksheet.write("A1", "Read from reader")?;
+        let mut cursor = Cursor::new(Vec::new());
+        workbook.save_to_writer(&mut cursor)?;
+        let workbook = Workbook::from_reader(cursor)?;
+        assert_eq!(workbook.worksheets().count(), 1);
+        workbook.save_as("tests/output/in_memory_test_from_reader.xlsx")?;
+        Ok(())
+    }
+
+    #[test]
+    fn test_save_without_path() -> WorkbookResult<()> {
+        let bytes = std::fs::read("tests/xlsx/accounting.xlsx")?;
+        let mut workbook = Workbook::from_bytes(&bytes)?;
+        assert!(workbook.save().is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_new_has_no_file_path() -> WorkbookResult<()> {
+        // the blank template is built in, saving must not overwrite any file
+        let mut workbook = Workbook::new();
+        assert!(workbook.save().is_err());
+        let workbook = Workbook::from_bytes(&workbook.to_bytes()?)?;
+        assert_eq!(workbook.get_worksheet(1)?.get_name(), "Sheet1");
+        Ok(())
+    }
+}
@@ -0,0 +1,135 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use crate::common::{read_part, part_names};
+    use edit_xlsx::{Read, TableOptions, Workbook, WorkbookResult, Write};
+
+    #[test]
+    fn test_remove_worksheet() -> WorkbookResult<()> {
+        let mut workbook = Workbook::from_path("tests/xlsx/business-budget.xlsx")?;
+        workbook.remove_worksheet_by_name("Goods")?;
+        assert!(workbook.remove_worksheet(2).is_err());
+        let path = "tests/output/move_sheet_test_remove_worksheet.xlsx";
+        workbook.save_as(path)?;
+        let parts = part_names(path);
+        for part in ["xl/worksheets/sheet2.xml", "xl/worksheets/_rels/sheet2.xml.rels", "xl/drawings/drawing2.xml",
+            "xl/drawings/_rels/drawing2.xml.rels", "xl/printerSettings/printerSettings2.bin"] {
+            assert!(!parts.iter().any(|name| name == part), "{part} is not removed");
+        }
+        // the media may be shared with the other drawings
+        assert!(parts.iter().any(|name| name == "xl/media/image2.png"));
+        assert!(parts.iter().any(|name| name == "xl/drawings/drawing3.xml"));
+        let content_types = read_part(path, "[Content_Types].xml");
+        assert!(!content_types.contains("/xl/worksheets/sheet2.xml"));
+        assert!(!content_types.contains("/xl/drawings/drawing2.xml"));
+        assert!(content_types.contains("/xl/drawings/drawing3.xml"));
+        let rels = read_part(path, "xl/_rels/workbook.xml.rels");
+        assert!(!rels.contains("worksheets/sheet2.xml"));
+        let xml = read_part(path, "xl/workbook.xml");
+        assert!(xml.contains("<sheets><sheet name=\"Services\" sheetId=\"1\" r:id=\"rId1\"/><sheet name=\"©\" sheetId=\"4\" r:id=\"rId3\"/></sheets>"));
+        assert!(xml.contains("<definedName name=\"_xlnm.Print_Area\" localSheetId=\"0\">Services!$B$1:$F$57</definedName>"));
+        assert!(!xml.contains("Goods"));
+
+        let mut workbook = Workbook::from_path(path)?;
+        let names = workbook.worksheets().map(|sheet| sheet.get_name().to_string()).collect::<Vec<String>>();
+        assert_eq!(names, vec!["Services", "©"]);
+        // a new worksheet takes the place of the removed one in the package
+        let worksheet = workbook.add_worksheet_by_name("Goods")?;
+        worksheet.write("A1", "Goods")?;
+        workbook.remove_worksheet_by_name("©")?;
+        workbook.remove_worksheet_by_name("Services")?;
+        assert!(workbook.remove_worksheet_by_name("Goods").is_err());
+        workbook.save_as("tests/output/move_sheet_test_remove_all.xlsx")?;
+        let workbook = Workbook::from_path("tests/output/move_sheet_test_remove_all.xlsx")?;
+        assert_eq!(workbook.worksheets().count(), 1);
+        assert_eq!(workbook.get_worksheet_by_name("Goods")?.read_value("A1")?.to_string(), "Goods");
+        Ok(())
+    }
+
+    #[test]
+    fn test_remove_new_parts() -> WorkbookResult<()> {
+        let mut workbook = Workbook::from_path("tests/xlsx/checkbook-register.xlsx")?;
+        let worksheet = workbook.add_worksheet_by_name("Report")?;
+        worksheet.write_row("A1", &["Date", "Amount"])?;
+        worksheet.add_table("A1:B3", &TableOptions::new())?;
+        worksheet.write_comment("A1", "Checked", "Bob")?;
+        workbook.remove_worksheet_by_name("Report")?;
+        // the parts still used by a copy are kept
+        workbook.duplicate_worksheet_by_name("Register")?;
+        workbook.remove_worksheet_by_name("Register")?;
+        let path = "tests/output/move_sheet_test_remove_new_parts.xlsx";
+        workbook.save_as(path)?;
+        let content_types = read_part(path, "[Content_Types].xml");
+        for part in ["/xl/tables/table1.xml", "/xl/tables/table2.xml", "/xl/comments1.xml", "/xl/drawings/drawing1.xml"] {
+            assert!(!content_types.contains(part), "{part} is not removed");
+        }
+        let parts = part_names(path);
+        assert!(!parts.iter().any(|name| name == "xl/tables/table1.xml"));
+        let xml = read_part(path, "xl/worksheets/_rels/sheet5.xml.rels");
+        assert!(xml.contains("Target=\"../printerSettings/printerSettings1.bin\""));
+        assert!(parts.iter().any(|name| name == "xl/printerSettings/printerSettings1.bin"));
+        let xml = read_part(path, "xl/drawings/_rels/drawing4.xml.rels");
+        assert!(xml.contains("Target=\"../media/image1.png\""));
+        assert!(parts.iter().any(|name| name == "xl/media/image1.png"));
+
+        let workbook = Workbook::from_path(path)?;
+        let worksheet = workbook.get_worksheet_by_name("Register Duplicated")?;
+        assert_eq!(worksheet.tables()[0].name, "Table1_2");
+        Ok(())
+    }
+
+    #[test]
+    fn test_remove_referenced_worksheet() -> WorkbookResult<()> {
+        let mut workbook = Workbook::new();
+        let data = workbook.add_worksheet_by_name("Data")?;
+        data.write_column("A1", &[1, 2, 3])?;
+        let data_id = data.id();
+        workbook.define_name("Rate", "Data!$B$2")?;
+        workbook.define_name("Total", "Sheet1!$B$1")?;
+        workbook.define_local_name("Local", "Data!$A$1", data_id)?;
+        let worksheet = workbook.get_worksheet_mut(1)?;
+        worksheet.write("A1", 10)?;
+        worksheet.write_formula("B1", "Data!A1+SUM('data'!A1:A3)+A1")?;
+        worksheet.write_formula("B2", "Data!Local*Rate")?;
+        workbook.remove_worksheet(data_id)?;
+        let worksheet = workbook.get_worksheet(1)?;
+        assert_eq!(worksheet.read_cell("B1")?.formula(), Some("#REF!+SUM(#REF!)+A1"));
+        assert_eq!(worksheet.read_cell("B2")?.formula(), Some("#REF!*Rate"));
+        // the local names follow the position of their worksheet
+        workbook.add_worksheet_by_name("Summary")?;
+        workbook.move_worksheet_by_name("Summary", 0)?;
+        workbook.define_local_name("Local", "Sheet1!$A$1", 1)?;
+        let path = "tests/output/move_sheet_test_remove_referenced_worksheet.xlsx";
+        workbook.save_as(path)?;
+        let xml = read_part(path, "xl/workbook.xml");
+        assert!(xml.contains("<definedName name=\"Rate\">#REF!</definedName>"));
+        assert!(xml.contains("<definedName name=\"Total\">Sheet1!$B$1</definedName>"));
+        assert!(xml.contains("<definedName name=\"Local\" localSheetId=\"1\">Sheet1!$A$1</definedName>"));
+        assert!(!xml.contains("Data"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_move_worksheet() -> WorkbookResult<()> {
+        let mut workbook = Workbook::new();
+        workbook.add_worksheet_by_name("Summary")?;
+        let worksheet = workbook.add_worksheet_by_name("Details")?;
+        worksheet.set_print_area("A1:D20");
+        worksheet.activate();
+        workbook.get_worksheet_mut(1)?.set_print_area("A1:B2");
+        workbook.move_worksheet_by_name("Details", 0)?;
+        workbook.move_worksheet(1, 10)?;
+        let names = workbook.worksheets().map(|sheet| sheet.get_name().to_string()).collect::<Vec<String>>();
+        assert_eq!(names, vec!["Details", "Summary", "Sheet1"]);
+        let path = "tests/output/move_sheet_test_move_worksheet.xlsx";
+        workbook.save_as(path)?;
+        let xml = read_part(path, "xl/workbook.xml");
+        assert!(xml.contains("<sheet name=\"Details\" sheetId=\"3\" r:id=\"rId5\"/><sheet name=\"Summary\" sheetId=\"2\" r:id=\"rId4\"/><sheet name=\"Sheet1\""));
+        assert!(xml.contains("<definedName name=\"_xlnm.Print_Area\" localSheetId=\"0\">Details!$A$1:$D$20</definedName>"));
+        assert!(xml.contains("<definedName name=\"_xlnm.Print_Area\" localSheetId=\"2\">Sheet1!$A$1:$B$2</definedName>"));
+        // the active worksheet is now the first one
+        assert!(!xml.contains("activeTab"));
+        Ok(())
+    }
+}
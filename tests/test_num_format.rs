@@ -0,0 +1,62 @@
+#[cfg(test)]
+mod tests {
+    use edit_xlsx::{ExcelDateTime, Format, Read, Value, Workbook, WorkbookResult, Write};
+
+    #[test]
+    fn test_num_format() -> WorkbookResult<()> {
+        let mut workbook = Workbook::new();
+        let worksheet = workbook.get_worksheet_mut(1)?;
+        worksheet.write_with_format("A1", 1234.5, &Format::default().set_num_format("#,##0.00"))?;
+        worksheet.write_with_format("A2", 0.25, &Format::default().set_num_format_index(9))?;
+        worksheet.write_with_format("A3", 12.5, &Format::default().set_num_format("0.000"))?;
+        worksheet.write_with_format("A4", 42, &Format::default().set_num_format("0.000").set_bold())?;
+        worksheet.write_with_format("A5", 7, &Format::default().set_num_format("\"$\"#,##0"))?;
+        worksheet.write("A6", 1)?;
+        workbook.save_as("tests/output/num_format_test_num_format.xlsx")?;
+        let workbook = Workbook::from_path("tests/output/num_format_test_num_format.xlsx")?;
+        let worksheet = workbook.get_worksheet(1)?;
+        let format = worksheet.read_cell("A1")?.format.unwrap();
+        // built-in codes use the built-in index
+        assert_eq!((format.get_num_format(), format.get_num_format_index()), ("#,##0.00", Some(4)));
+        let format = worksheet.read_cell("A2")?.format.unwrap();
+        assert_eq!((format.get_num_format(), format.get_num_format_index()), ("0%", Some(9)));
+        // custom codes are numbered from 164 and deduplicated
+        let format = worksheet.read_cell("A3")?.format.unwrap();
+        assert_eq!((format.get_num_format(), format.get_num_format_index()), ("0.000", Some(164)));
+        let format = worksheet.read_cell("A4")?.format.unwrap();
+        assert_eq!((format.get_num_format(), format.get_num_format_index()), ("0.000", Some(164)));
+        assert!(format.is_bold());
+        let format = worksheet.read_cell("A5")?.format.unwrap();
+        assert_eq!((format.get_num_format(), format.get_num_format_index()), ("\"$\"#,##0", Some(165)));
+        assert!(worksheet.read_cell("A6")?.format.is_none());
+        Ok(())
+    }
+
+    #[test]
+    fn test_date_num_format() -> WorkbookResult<()> {
+        let date = ExcelDateTime::from_ymd_hms(2024, 5, 1, 0, 0, 0.0).unwrap();
+        let mut workbook = Workbook::new();
+        let worksheet = workbook.get_worksheet_mut(1)?;
+        worksheet.write_with_format("A1", date, &Format::default().set_num_format("yyyy-mm-dd"))?;
+        worksheet.write_with_format("A2", 45413, &Format::default().set_num_format("dd/mm/yyyy"))?;
+        let workbook = Workbook::from_bytes(&workbook.to_bytes()?)?;
+        let worksheet = workbook.get_worksheet(1)?;
+        // the date format of the cell is kept
+        assert_eq!(worksheet.read_cell("A1")?.format.unwrap().get_num_format(), "yyyy-mm-dd");
+        assert_eq!(worksheet.read_value("A1")?, Value::DateTime(date));
+        assert_eq!(worksheet.read_value("A2")?, Value::DateTime(date));
+        Ok(())
+    }
+
+    #[test]
+    fn test_from_num_format() -> WorkbookResult<()> {
+        let mut workbook = Workbook::from_path("tests/xlsx/accounting.xlsx")?;
+        let worksheet = workbook.get_worksheet_mut(1)?;
+        worksheet.write_with_format("H1", 0.5, &Format::default().set_num_format("0.0%"))?;
+        workbook.save_as("tests/output/num_format_test_from_num_format.xlsx")?;
+        let workbook = Workbook::from_path("tests/output/num_format_test_from_num_format.xlsx")?;
+        let worksheet = workbook.get_worksheet(1)?;
+        assert_eq!(worksheet.read_cell("H1")?.format.unwrap().get_num_format(), "0.0%");
+        Ok(())
+    }
+}
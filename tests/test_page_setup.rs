@@ -0,0 +1,98 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use crate::common::read_part;
+    use edit_xlsx::{PaperSize, Workbook, WorkbookResult, Write};
+
+    #[test]
+    fn test_page_setup() -> WorkbookResult<()> {
+        let mut workbook = Workbook::new();
+        let worksheet = workbook.get_worksheet_mut(1)?;
+        worksheet.write("A1", "Report")?;
+        worksheet.set_landscape();
+        worksheet.set_paper(PaperSize::A4);
+        worksheet.fit_to_pages(1, 0);
+        worksheet.set_margins(0.5, 0.5, 1.0, 1.0, 0.4, 0.4);
+        worksheet.center_horizontally(true);
+        worksheet.center_vertically(true);
+        worksheet.center_vertically(false);
+        worksheet.print_gridlines(true);
+        worksheet.print_headings(true);
+        workbook.save_as("tests/output/page_setup_test_page_setup.xlsx")?;
+        let xml = read_part("tests/output/page_setup_test_page_setup.xlsx", "xl/worksheets/sheet1.xml");
+        assert!(xml.contains("<sheetPr><pageSetUpPr fitToPage=\"1\"/></sheetPr>"));
+        assert!(xml.contains("<printOptions horizontalCentered=\"1\" headings=\"1\" gridLines=\"1\"/>"));
+        assert!(xml.contains("<pageMargins left=\"0.5\" right=\"0.5\" top=\"1\" bottom=\"1\" header=\"0.4\" footer=\"0.4\"/>"));
+        assert!(xml.contains("<pageSetup paperSize=\"9\" fitToHeight=\"0\" orientation=\"landscape\"/>"));
+        assert!(xml.find("<printOptions").unwrap() < xml.find("<pageMargins").unwrap());
+        assert!(xml.find("<pageMargins").unwrap() < xml.find("<pageSetup").unwrap());
+
+        let mut workbook = Workbook::from_path("tests/output/page_setup_test_page_setup.xlsx")?;
+        let worksheet = workbook.get_worksheet_mut(1)?;
+        assert_eq!(worksheet.get_paper(), Some(PaperSize::A4));
+        // a scale replaces the fitting to pages
+        worksheet.set_print_scale(500);
+        worksheet.set_paper(PaperSize::Other(70));
+        workbook.save_as("tests/output/page_setup_test_print_scale.xlsx")?;
+        let xml = read_part("tests/output/page_setup_test_print_scale.xlsx", "xl/worksheets/sheet1.xml");
+        assert!(!xml.contains("fitToPage"));
+        assert!(xml.contains("<pageSetup paperSize=\"70\" scale=\"400\" fitToHeight=\"0\" orientation=\"landscape\"/>"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_print_area_and_titles() -> WorkbookResult<()> {
+        let mut workbook = Workbook::new();
+        workbook.add_worksheet_by_name("Sales Report")?;
+        let worksheet = workbook.get_worksheet_mut_by_name("Sales Report")?;
+        worksheet.set_print_area("A1:F40");
+        worksheet.set_print_area((1, 1, 50, 8));
+        worksheet.repeat_rows("1:2");
+        worksheet.repeat_columns("A:A");
+        worksheet.repeat_rows("1:3");
+        let worksheet = workbook.get_worksheet_mut(1)?;
+        worksheet.repeat_columns("B:C");
+        workbook.save_as("tests/output/page_setup_test_print_area_and_titles.xlsx")?;
+        let xml = read_part("tests/output/page_setup_test_print_area_and_titles.xlsx", "xl/workbook.xml");
+        assert!(xml.contains("<definedName name=\"_xlnm.Print_Area\" localSheetId=\"1\">&apos;Sales Report&apos;!$A$1:$H$50</definedName>"));
+        assert!(xml.contains("<definedName name=\"_xlnm.Print_Titles\" localSheetId=\"1\">&apos;Sales Report&apos;!$A:$A,&apos;Sales Report&apos;!$1:$3</definedName>"));
+        assert!(xml.contains("<definedName name=\"_xlnm.Print_Titles\" localSheetId=\"0\">Sheet1!$B:$C</definedName>"));
+        assert_eq!(xml.matches("<definedName ").count(), 3);
+        Ok(())
+    }
+
+    #[test]
+    fn test_page_breaks() -> WorkbookResult<()> {
+        let mut workbook = Workbook::new();
+        let worksheet = workbook.get_worksheet_mut(1)?;
+        worksheet.write("A1", "Invoice")?;
+        worksheet.set_row_breaks(&[40, 20, 20, 0]);
+        worksheet.set_column_breaks(&[5]);
+        worksheet.set_landscape();
+        assert_eq!(worksheet.get_row_breaks(), vec![20, 40]);
+        workbook.save_as("tests/output/page_setup_test_page_breaks.xlsx")?;
+        let xml = read_part("tests/output/page_setup_test_page_breaks.xlsx", "xl/worksheets/sheet1.xml");
+        assert!(xml.contains("<rowBreaks count=\"2\" manualBreakCount=\"2\"><brk id=\"20\" max=\"16383\" man=\"1\"/><brk id=\"40\" max=\"16383\" man=\"1\"/></rowBreaks>\
+            <colBreaks count=\"1\" manualBreakCount=\"1\"><brk id=\"5\" max=\"1048575\" man=\"1\"/></colBreaks>"));
+        assert!(xml.find("<pageSetup").unwrap() < xml.find("<rowBreaks").unwrap());
+
+        // the breaks move with their rows
+        let mut workbook = Workbook::from_path("tests/output/page_setup_test_page_breaks.xlsx")?;
+        assert_eq!(workbook.get_worksheet(1)?.get_column_breaks(), vec![5]);
+        workbook.insert_rows(1, 10, 2)?;
+        workbook.delete_rows(1, 42, 1)?;
+        let worksheet = workbook.get_worksheet_mut(1)?;
+        assert_eq!(worksheet.get_row_breaks(), vec![22]);
+        assert_eq!(worksheet.get_column_breaks(), vec![5]);
+        worksheet.set_column_breaks(&[]);
+        workbook.save_as("tests/output/page_setup_test_page_breaks_shift.xlsx")?;
+        let xml = read_part("tests/output/page_setup_test_page_breaks_shift.xlsx", "xl/worksheets/sheet1.xml");
+        assert!(xml.contains("<rowBreaks count=\"1\" manualBreakCount=\"1\"><brk id=\"22\" max=\"16383\" man=\"1\"/></rowBreaks>"));
+        assert!(!xml.contains("colBreaks"));
+
+        let workbook = Workbook::from_path("tests/xlsx/wedding-budget.xlsx")?;
+        assert_eq!(workbook.get_worksheet(1)?.get_row_breaks(), vec![57]);
+        Ok(())
+    }
+}
@@ -0,0 +1,149 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use crate::common::read_part;
+    use std::io::Read as _;
+    use edit_xlsx::{Format, ProtectionOptions, Read, Workbook, WorkbookResult, Write};
+
+    fn replace_in_parts(path: &str, dest: &str, replaces: &[(&str, &str, &str)]) {
+        let mut archive = zip::ZipArchive::new(std::fs::File::open(path).unwrap()).unwrap();
+        let mut writer = zip::ZipWriter::new(std::fs::File::create(dest).unwrap());
+        for i in 0..archive.len() {
+            let mut file = archive.by_index(i).unwrap();
+            let mut buf = vec![];
+            file.read_to_end(&mut buf).unwrap();
+            let name = file.name().to_string();
+            for (part, from, to) in replaces.iter().filter(|(part, _, _)| *part == name) {
+                buf = String::from_utf8(buf).unwrap().replace(from, to).into_bytes();
+                assert!(buf.windows(to.len()).any(|w| w == to.as_bytes()), "{from} is not in {part}");
+            }
+            writer.start_file(name, zip::write::FileOptions::default()).unwrap();
+            std::io::Write::write_all(&mut writer, &buf).unwrap();
+        }
+        writer.finish().unwrap();
+    }
+
+    #[test]
+    fn test_protect_worksheet() -> WorkbookResult<()> {
+        let mut workbook = Workbook::new();
+        let worksheet = workbook.get_worksheet_mut(1)?;
+        worksheet.write("A1", "Name")?;
+        worksheet.write_with_format("B1", "", &Format::default().set_locked(false))?;
+        worksheet.write_with_format("B2", "hidden", &Format::default().set_hidden(true))?;
+        worksheet.protect(&ProtectionOptions::new()
+            .set_password("secret")
+            .allow_format_cells(true)
+            .allow_sort(true)
+            .allow_select_locked_cells(false));
+        worksheet.add_protected_range("Inputs", "C1:C10", Some("inputs"));
+        worksheet.add_protected_range("Notes", "D1:D10", None);
+        assert!(worksheet.is_protected());
+        workbook.save_as("tests/output/protection_test_protect_worksheet.xlsx")?;
+
+        let xml = read_part("tests/output/protection_test_protect_worksheet.xlsx", "xl/worksheets/sheet1.xml");
+        let protection = &xml[xml.find("<sheetProtection ").unwrap()..];
+        let protection = &protection[..protection.find("/>").unwrap()];
+        assert!(protection.contains("algorithmName=\"SHA-512\""));
+        assert!(protection.contains("spinCount=\"100000\""));
+        assert!(protection.contains("sheet=\"1\" objects=\"1\" scenarios=\"1\" formatCells=\"0\""));
+        assert!(protection.contains("selectLockedCells=\"1\" sort=\"0\""));
+        assert!(!protection.contains("formatRows"));
+        assert!(!protection.contains("selectUnlockedCells"));
+        assert!(xml.contains("</sheetData><sheetProtection "));
+        assert!(xml.contains("<protectedRange sqref=\"D1:D10\" name=\"Notes\"/>"));
+
+        let workbook = Workbook::from_path("tests/output/protection_test_protect_worksheet.xlsx")?;
+        let worksheet = workbook.get_worksheet(1)?;
+        assert!(worksheet.is_protected());
+        assert_eq!(worksheet.protected_ranges(), vec![
+            ("Inputs".to_string(), "C1:C10".to_string()),
+            ("Notes".to_string(), "D1:D10".to_string()),
+        ]);
+        let format = worksheet.read_cell("B1")?.format.unwrap();
+        assert!(!format.is_locked());
+        assert!(!format.is_hidden());
+        let format = worksheet.read_cell("B2")?.format.unwrap();
+        assert!(format.is_locked());
+        assert!(format.is_hidden());
+        Ok(())
+    }
+
+    #[test]
+    fn test_legacy_password() -> WorkbookResult<()> {
+        let mut workbook = Workbook::new();
+        let worksheet = workbook.get_worksheet_mut(1)?;
+        worksheet.protect(&ProtectionOptions::new().set_legacy_password("password"));
+        worksheet.add_protected_range("Inputs", "A1:B2", None);
+        workbook.insert_rows(1, 1, 2)?;
+        workbook.save_as("tests/output/protection_test_legacy_password.xlsx")?;
+        let xml = read_part("tests/output/protection_test_legacy_password.xlsx", "xl/worksheets/sheet1.xml");
+        assert!(xml.contains("<sheetProtection password=\"83AF\" sheet=\"1\" objects=\"1\" scenarios=\"1\"/>"));
+        assert!(xml.contains("<protectedRange sqref=\"A3:B4\" name=\"Inputs\"/>"));
+
+        let mut workbook = Workbook::from_path("tests/output/protection_test_legacy_password.xlsx")?;
+        workbook.get_worksheet_mut(1)?.unprotect();
+        workbook.save_as("tests/output/protection_test_unprotect.xlsx")?;
+        let xml = read_part("tests/output/protection_test_unprotect.xlsx", "xl/worksheets/sheet1.xml");
+        assert!(!xml.contains("sheetProtection"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_protect_workbook() -> WorkbookResult<()> {
+        let mut workbook = Workbook::from_path("tests/xlsx/appraisal_score.xlsx")?;
+        assert!(!workbook.get_worksheet_by_name("Details")?.is_protected());
+        assert!(workbook.get_worksheet_by_name("Advanced Project Plan Template")?.is_protected());
+        workbook.protect_structure(Some("secret"))?;
+        workbook.protect_windows(None)?;
+        workbook.save_as("tests/output/protection_test_protect_workbook.xlsx")?;
+        let xml = read_part("tests/output/protection_test_protect_workbook.xlsx", "xl/workbook.xml");
+        let protection = &xml[xml.find("<workbookProtection ").unwrap()..];
+        let protection = &protection[..protection.find("/>").unwrap()];
+        assert!(protection.contains("lockStructure=\"1\" lockWindows=\"1\""));
+        assert!(protection.contains("workbookAlgorithmName=\"SHA-512\""));
+        assert!(protection.contains("workbookSpinCount=\"100000\""));
+        assert!(xml.find("<workbookProtection ").unwrap() < xml.find("<bookViews").unwrap());
+        // the protection of the worksheets loaded is kept
+        let xml = read_part("tests/output/protection_test_protect_workbook.xlsx", "xl/worksheets/sheet3.xml");
+        assert!(xml.contains("<sheetProtection sheet=\"1\" objects=\"1\" scenarios=\"1\"/>"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_boolean_spellings() -> WorkbookResult<()> {
+        let mut workbook = Workbook::new();
+        let worksheet = workbook.get_worksheet_mut(1)?;
+        worksheet.write("A1", "Name")?;
+        worksheet.write_with_format("B1", "", &Format::default().set_locked(false).set_hidden(true))?;
+        worksheet.protect(&ProtectionOptions::new().allow_format_cells(true));
+        workbook.protect_structure(None)?;
+        let path = "tests/output/protection_test_boolean_spellings.xlsx";
+        workbook.save_as(path)?;
+        let xml = read_part(path, "xl/styles.xml");
+        assert!(xml.contains("<protection locked=\"0\" hidden=\"1\"/>"));
+        for sheet in ["true", "false"] {
+            let dest = format!("tests/output/protection_test_boolean_spellings_{sheet}.xlsx");
+            replace_in_parts(path, &dest, &[
+                ("xl/worksheets/sheet1.xml", "sheet=\"1\"", &format!("sheet=\"{sheet}\"")),
+                ("xl/worksheets/sheet1.xml", "formatCells=\"0\"", "formatCells=\"false\""),
+                ("xl/styles.xml", "<protection locked=\"0\" hidden=\"1\"/>", "<protection locked=\"false\" hidden=\"true\"/>"),
+                ("xl/workbook.xml", "lockStructure=\"1\"", "lockStructure=\"true\""),
+            ]);
+            let workbook = Workbook::from_path(&dest)?;
+            let worksheet = workbook.get_worksheet(1)?;
+            assert_eq!(worksheet.is_protected(), sheet == "true");
+            assert_eq!(worksheet.read_cell("A1")?.text, Some("Name".to_string()));
+            let format = worksheet.read_cell("B1")?.format.unwrap();
+            assert!(!format.is_locked());
+            assert!(format.is_hidden());
+            // the attributes are written back the way Excel does
+            workbook.save_as(&dest)?;
+            let xml = read_part(&dest, "xl/styles.xml");
+            assert!(xml.contains("<protection locked=\"0\" hidden=\"1\"/>"));
+            let xml = read_part(&dest, "xl/workbook.xml");
+            assert!(xml.contains("lockStructure=\"1\""));
+        }
+        Ok(())
+    }
+}
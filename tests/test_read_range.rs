@@ -0,0 +1,102 @@
+#[cfg(test)]
+mod tests {
+    use edit_xlsx::{Format, Read, Value, Workbook, WorkbookResult, Write};
+
+    #[test]
+    fn test_rows() -> WorkbookResult<()> {
+        let mut workbook = Workbook::new();
+        let worksheet = workbook.get_worksheet_mut(1)?;
+        worksheet.write("B2", "Name")?;
+        worksheet.write("C2", "Score")?;
+        worksheet.write("B4", "Alice")?;
+        worksheet.write_with_format("C4", 90, &Format::default().set_bold())?;
+        // formats are only read when asked for
+        let (_, cells) = worksheet.rows().last().unwrap();
+        let formats: Vec<_> = cells.map(|(_, cell)| cell.format).collect();
+        assert_eq!(formats, vec![None, None]);
+        let (_, cells) = worksheet.rows_with_format().last().unwrap();
+        let bold: Vec<_> = cells.map(|(_, cell)| cell.format.is_some_and(|format| format.is_bold())).collect();
+        assert_eq!(bold, vec![false, true]);
+        let workbook = Workbook::from_bytes(&workbook.to_bytes()?)?;
+        let worksheet = workbook.get_worksheet(1)?;
+        let rows: Vec<_> = worksheet.rows()
+            .map(|(row, cells)| (row, cells.map(|(col, cell)| (col, cell.text)).collect::<Vec<_>>()))
+            .collect();
+        assert_eq!(rows, vec![
+            (2, vec![(2, Some("Name".to_string())), (3, Some("Score".to_string()))]),
+            (4, vec![(2, Some("Alice".to_string())), (3, Some("90".to_string()))]),
+        ]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_used_range() -> WorkbookResult<()> {
+        let mut workbook = Workbook::new();
+        let worksheet = workbook.get_worksheet_mut(1)?;
+        assert_eq!(worksheet.used_range(), None);
+        worksheet.write("C3", 1)?;
+        worksheet.write("B5", 2)?;
+        worksheet.write("E4", 3)?;
+        assert_eq!(worksheet.used_range(), Some((3, 2, 5, 5)));
+        Ok(())
+    }
+
+    #[test]
+    fn test_read_range() -> WorkbookResult<()> {
+        let mut workbook = Workbook::new();
+        let worksheet = workbook.get_worksheet_mut(1)?;
+        worksheet.write("A1", "Item")?;
+        worksheet.write("B1", "Cost")?;
+        worksheet.write("A2", "Rent")?;
+        worksheet.write("B2", 800)?;
+        worksheet.write("B3", 25.5)?;
+        worksheet.write("C9", "Outside")?;
+        let workbook = Workbook::from_bytes(&workbook.to_bytes()?)?;
+        let worksheet = workbook.get_worksheet(1)?;
+        let values = worksheet.read_range("A1:B3");
+        assert_eq!(values, vec![
+            vec![Value::String("Item".to_string()), Value::String("Cost".to_string())],
+            vec![Value::String("Rent".to_string()), Value::Number(800.0)],
+            vec![Value::Empty, Value::Number(25.5)],
+        ]);
+        assert_eq!(worksheet.read_range((2, 2, 2, 3)), vec![vec![Value::Number(800.0), Value::Empty]]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_read_whole_columns() -> WorkbookResult<()> {
+        let mut workbook = Workbook::new();
+        let worksheet = workbook.get_worksheet_mut(1)?;
+        worksheet.write("A1", "Item")?;
+        worksheet.write("B2", 800)?;
+        worksheet.write("C9", "Outside")?;
+        // the ranges stop at the last row and column used
+        let values = worksheet.read_range("A:XFD");
+        assert_eq!((values.len(), values[0].len()), (9, 3));
+        assert_eq!(values[8][2], Value::String("Outside".to_string()));
+        assert_eq!(worksheet.read_range((1, 1, 1048576, 16384)), values);
+        let values = worksheet.read_range("B:B");
+        assert_eq!(values.len(), 9);
+        assert_eq!(values[1], vec![Value::Number(800.0)]);
+        assert_eq!(worksheet.read_range("2:2"), vec![vec![Value::Empty, Value::Number(800.0), Value::Empty]]);
+        assert_eq!(Workbook::new().get_worksheet(1)?.read_range("A:C"), Vec::<Vec<Value>>::new());
+        Ok(())
+    }
+
+    #[test]
+    fn test_read_from_range() -> WorkbookResult<()> {
+        let workbook = Workbook::from_path("tests/xlsx/accounting.xlsx")?;
+        let worksheet = workbook.get_worksheet(1)?;
+        let used_range = worksheet.used_range().unwrap();
+        let values = worksheet.read_range(used_range);
+        assert_eq!(values.len() as u32, used_range.2 - used_range.0 + 1);
+        // the range agrees with reading the cells one by one
+        for (row, cells) in worksheet.rows() {
+            for (col, _) in cells {
+                let value = &values[(row - used_range.0) as usize][(col - used_range.1) as usize];
+                assert_eq!(*value, worksheet.read_value((row, col))?);
+            }
+        }
+        Ok(())
+    }
+}
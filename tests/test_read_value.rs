@@ -0,0 +1,52 @@
+#[cfg(test)]
+mod tests {
+    use edit_xlsx::{ExcelDateTime, Read, Value, Workbook, WorkbookResult, Write};
+
+    #[test]
+    fn test_read_value() -> WorkbookResult<()> {
+        let mut workbook = Workbook::new();
+        let worksheet = workbook.get_worksheet_mut(1)?;
+        worksheet.write("A1", "Text")?;
+        worksheet.write("A2", 1)?;
+        worksheet.write("A3", 2.5)?;
+        worksheet.write("A4", true)?;
+        worksheet.write("A5", "1")?;
+        worksheet.write_old_formula("A6", "A2+A3")?;
+        let workbook = Workbook::from_bytes(&workbook.to_bytes()?)?;
+        let worksheet = workbook.get_worksheet(1)?;
+        assert_eq!(worksheet.read_value("A1")?, Value::String("Text".to_string()));
+        assert_eq!(worksheet.read_value("A2")?, Value::Number(1.0));
+        assert_eq!(worksheet.read_value("A3")?, Value::Number(2.5));
+        assert_eq!(worksheet.read_value("A4")?, Value::Bool(true));
+        assert_eq!(worksheet.read_value("A5")?, Value::String("1".to_string()));
+        assert_eq!(worksheet.read_value("A7")?, Value::Empty);
+        assert_eq!(worksheet.read_cell("A6")?.formula(), Some("A2+A3"));
+        assert_eq!(worksheet.read_cell("A1")?.formula(), None);
+        Ok(())
+    }
+
+    #[test]
+    fn test_read_from_value() -> WorkbookResult<()> {
+        let workbook = Workbook::from_path("tests/xlsx/appraisal_score.xlsx")?;
+        let worksheet = workbook.get_worksheet_by_name("Details")?;
+        match worksheet.read_value("F12")? {
+            Value::RichText(rich_text) => {
+                assert!(rich_text.text().starts_with("Please check our Premium Templates Bundle"));
+                assert!(rich_text.runs.iter().any(|run| run.format.as_ref().is_some_and(|f| f.is_bold())));
+            }
+            value => panic!("F12 should be rich text, but it is {value:?}"),
+        }
+        let worksheet = workbook.get_worksheet_by_name("Template")?;
+        assert_eq!(worksheet.read_value("G11")?, Value::String("Accept".to_string()));
+        assert_eq!(worksheet.read_cell("G11")?.formula(), Some("IF(H7<0,\"Reject\",\"Accept\")"));
+        assert!(matches!(worksheet.read_value("H7")?, Value::Number(_)));
+        Ok(())
+    }
+
+    #[test]
+    fn test_date_time() {
+        let date_time = ExcelDateTime::from_ymd_hms(2024, 3, 1, 6, 30, 0.0).unwrap();
+        assert_eq!(date_time.serial(), 45352.270833333336);
+        assert_eq!(date_time.to_ymd_hms(), (2024, 3, 1, 6, 30, 0.0));
+    }
+}
@@ -0,0 +1,103 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use crate::common::read_part;
+    use edit_xlsx::{Chart, ChartSeries, ChartType, DataValidation, Read, Workbook, WorkbookResult, Write};
+
+    #[test]
+    fn test_rename_worksheet() -> WorkbookResult<()> {
+        let mut workbook = Workbook::new();
+        let worksheet = workbook.get_worksheet_mut(1)?;
+        worksheet.write_column("A1", &[1, 2, 3])?;
+        worksheet.write_formula("B1", "SUM(Sheet1!A1:A3)")?;
+        let summary = workbook.add_worksheet_by_name("Summary")?;
+        summary.write_formula("A1", "Sheet1!A1+sheet1!A2+Other!A1")?;
+        summary.add_data_validation("B1", &DataValidation::list_source("=Sheet1!$A$1:$A$3"))?;
+        summary.insert_chart((2, 1, 16, 8), &Chart::new(ChartType::Column)
+            .add_series(ChartSeries::new("Sheet1!$A$1:$A$3").set_categories("Summary!$A$1:$A$3")))?;
+        workbook.define_name("Values", "Sheet1!$A$1:$A$3")?;
+        workbook.rename_worksheet(1, "Q1 Report")?;
+        let path = "tests/output/rename_sheet_test_rename_worksheet.xlsx";
+        workbook.save_as(path)?;
+        let xml = read_part(path, "xl/workbook.xml");
+        assert!(xml.contains("<sheet name=\"Q1 Report\" sheetId=\"1\""));
+        assert!(xml.contains("<definedName name=\"Values\">&apos;Q1 Report&apos;!$A$1:$A$3</definedName>"));
+        let xml = read_part(path, "xl/worksheets/sheet1.xml");
+        assert!(xml.contains(">SUM(&apos;Q1 Report&apos;!A1:A3)</f>"));
+        let xml = read_part(path, "xl/worksheets/sheet2.xml");
+        assert!(xml.contains(">&apos;Q1 Report&apos;!A1+&apos;Q1 Report&apos;!A2+Other!A1</f>"));
+        assert!(xml.contains("<formula1>&apos;Q1 Report&apos;!$A$1:$A$3</formula1>"));
+        let xml = read_part(path, "xl/charts/chart1.xml");
+        assert!(xml.contains("<c:f>&apos;Q1 Report&apos;!$A$1:$A$3</c:f>"));
+        assert!(xml.contains("<c:f>Summary!$A$1:$A$3</c:f>"));
+
+        // the quotes are no longer needed, but a name like a cell reference is still quoted
+        let mut workbook = Workbook::from_path(path)?;
+        workbook.rename_worksheet_by_name("Q1 Report", "Data")?;
+        workbook.save_as("tests/output/rename_sheet_test_rename_again.xlsx")?;
+        let xml = read_part("tests/output/rename_sheet_test_rename_again.xlsx", "xl/worksheets/sheet2.xml");
+        assert!(xml.contains(">Data!A1+Data!A2+Other!A1</f>"));
+        let mut workbook = Workbook::from_path("tests/output/rename_sheet_test_rename_again.xlsx")?;
+        workbook.rename_worksheet_by_name("Data", "W4")?;
+        workbook.save_as("tests/output/rename_sheet_test_rename_cell_like.xlsx")?;
+        let xml = read_part("tests/output/rename_sheet_test_rename_cell_like.xlsx", "xl/worksheets/sheet2.xml");
+        assert!(xml.contains(">&apos;W4&apos;!A1+&apos;W4&apos;!A2+Other!A1</f>"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_rename_loaded_chart() -> WorkbookResult<()> {
+        let mut workbook = Workbook::from_path("tests/xlsx/paycheck-calculator.xlsx")?;
+        workbook.rename_worksheet_by_name("NEW W-4", "Withholding")?;
+        let path = "tests/output/rename_sheet_test_rename_loaded_chart.xlsx";
+        workbook.save_as(path)?;
+        let xml = read_part(path, "xl/charts/chart1.xml");
+        assert!(xml.contains("<c:f>Withholding!$B$37:$B$46</c:f>"));
+        assert!(!xml.contains("NEW W-4"));
+        let xml = read_part(path, "xl/workbook.xml");
+        assert!(xml.contains("<sheet name=\"Withholding\""));
+        assert!(!xml.contains("NEW W-4"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_invalid_sheet_name() -> WorkbookResult<()> {
+        let mut workbook = Workbook::new();
+        workbook.add_worksheet_by_name("Data")?;
+        for name in ["", "History", "history", "a/b", "[1]", "'quoted'", &"a".repeat(32)] {
+            assert!(workbook.rename_worksheet(1, name).is_err(), "{name} is accepted");
+            assert!(workbook.add_worksheet_by_name(name).is_err(), "{name} is accepted");
+        }
+        // the names of the worksheets are compared regardless of the case
+        assert!(workbook.rename_worksheet(1, "DATA").is_err());
+        assert!(workbook.add_worksheet_by_name("data").is_err());
+        workbook.add_worksheet_by_name("sheet4")?;
+        assert!(workbook.add_worksheet().is_err());
+        workbook.add_worksheet_by_name("data duplicated")?;
+        assert!(workbook.duplicate_worksheet_by_name("Data").is_err());
+        workbook.rename_worksheet(2, "DATA")?;
+        workbook.rename_worksheet(1, &"a".repeat(31))?;
+        // the name of a copy is cut to keep it at 31 characters
+        let copy = workbook.duplicate_worksheet_by_name(&"a".repeat(31))?;
+        assert_eq!(copy.get_name(), format!("{} Duplicated", "a".repeat(20)));
+        // the worksheets refused leave nothing in the package
+        let path = "tests/output/rename_sheet_test_invalid_sheet_name.xlsx";
+        workbook.save_as(path)?;
+        let xml = read_part(path, "xl/_rels/workbook.xml.rels");
+        assert_eq!(xml.matches("/worksheet\"").count(), 5);
+        Ok(())
+    }
+
+    #[test]
+    fn test_rename_unicode_sheet() -> WorkbookResult<()> {
+        let mut workbook = Workbook::new();
+        let id = workbook.add_worksheet_by_name("Überblick")?.id();
+        assert!(workbook.add_worksheet_by_name("ÜBERBLICK").is_err());
+        workbook.get_worksheet_mut(1)?.write_formula("A1", "ÜBERBLICK!A1*2")?;
+        workbook.get_worksheet_mut(id)?.set_name("überblick")?;
+        workbook.rename_worksheet(id, "Übersicht")?;
+        assert_eq!(workbook.get_worksheet(1)?.read_cell("A1")?.formula(), Some("Übersicht!A1*2"));
+        Ok(())
+    }
+}
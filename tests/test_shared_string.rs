@@ -0,0 +1,62 @@
+#[cfg(test)]
+mod tests {
+    use std::io::{Cursor, Read as _, Write as _};
+    use edit_xlsx::{Read, Workbook, WorkbookResult, Write};
+
+    #[test]
+    fn test_write_shared_string() -> WorkbookResult<()> {
+        let mut workbook = Workbook::new();
+        let worksheet = workbook.get_worksheet_mut(1)?;
+        let labels = vec!["Label"; 100];
+        worksheet.write_column("A1", &labels)?;
+        worksheet.write("B1", " padded ")?;
+        worksheet.write("C1", 100)?;
+        workbook.save_as("tests/output/shared_string_test_write_shared_string.xlsx")?;
+        let workbook = Workbook::from_path("tests/output/shared_string_test_write_shared_string.xlsx")?;
+        let worksheet = workbook.get_worksheet(1)?;
+        assert_eq!(worksheet.read_cell("A100")?.text, Some("Label".to_string()));
+        assert_eq!(worksheet.read_cell("B1")?.text, Some(" padded ".to_string()));
+        assert_eq!(worksheet.read_cell("C1")?.text, Some("100".to_string()));
+        Ok(())
+    }
+
+    #[test]
+    fn test_from_write_shared_string() -> WorkbookResult<()> {
+        let reading_book = Workbook::from_path("tests/xlsx/appraisal_score.xlsx")?;
+        let reading_sheet = reading_book.get_worksheet_by_name("Template")?;
+        let mut workbook = Workbook::from_path("tests/xlsx/random-group-generator.xlsx")?;
+        let worksheet = workbook.worksheets_mut().next().unwrap();
+        let cell = reading_sheet.read_cell("B2")?;
+        worksheet.write_cell("Z1", &cell)?;
+        worksheet.write("Z2", "Text")?;
+        let bytes = workbook.to_bytes()?;
+        let workbook = Workbook::from_bytes(&bytes)?;
+        let worksheet = workbook.worksheets().next().unwrap();
+        assert_eq!(worksheet.read_cell("Z1")?.text, cell.text);
+        assert_eq!(worksheet.read_cell("Z2")?.text, Some("Text".to_string()));
+        Ok(())
+    }
+
+    #[test]
+    fn test_read_broken_shared_string() -> WorkbookResult<()> {
+        let mut workbook = Workbook::new();
+        workbook.get_worksheet_mut(1)?.write("A1", "Label")?;
+        let bytes = workbook.to_bytes()?;
+        let mut archive = zip::ZipArchive::new(Cursor::new(bytes)).unwrap();
+        let mut writer = zip::ZipWriter::new(Cursor::new(vec![]));
+        for i in 0..archive.len() {
+            let mut file = archive.by_index(i).unwrap();
+            let mut buf = vec![];
+            file.read_to_end(&mut buf).unwrap();
+            if file.name() == "xl/sharedStrings.xml" {
+                buf = b"<sst><si><t>Label</si>".to_vec();
+            }
+            writer.start_file(file.name(), zip::write::FileOptions::default()).unwrap();
+            writer.write_all(&buf).unwrap();
+        }
+        let bytes = writer.finish().unwrap().into_inner();
+        // the strings of the cells are not lost silently
+        assert!(Workbook::from_bytes(&bytes).is_err());
+        Ok(())
+    }
+}
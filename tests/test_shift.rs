@@ -0,0 +1,161 @@
+#[cfg(test)]
+mod tests {
+    use edit_xlsx::{Chart, ChartSeries, ChartType, Comment, DataValidation, Read, TableOptions, Value, Workbook, WorkbookResult, Write};
+
+    #[test]
+    fn test_insert_rows() -> WorkbookResult<()> {
+        let mut workbook = Workbook::new();
+        let worksheet = workbook.get_worksheet_mut(1)?;
+        worksheet.write_column("A1", &[1, 2, 3])?;
+        worksheet.write_formula("B1", "SUM(A1:A3)")?;
+        worksheet.write_formula("B2", "A3*2+$A$3")?;
+        worksheet.write_url("C3", "https://github.com")?;
+        worksheet.write_comment("D3", "Moved", "Author")?;
+        worksheet.merge_range("E2:F3", "Merged")?;
+        worksheet.add_data_validation("A1:A3", &DataValidation::custom("ISNUMBER(A1)"))?;
+        workbook.insert_rows(1, 2, 2)?;
+        let worksheet = workbook.get_worksheet(1)?;
+        assert_eq!(worksheet.read_value("A1")?, Value::Number(1.0));
+        assert_eq!(worksheet.read_value("A4")?, Value::Number(2.0));
+        assert_eq!(worksheet.read_value("A5")?, Value::Number(3.0));
+        assert_eq!(worksheet.read_cell("B1")?.formula(), Some("SUM(A1:A5)"));
+        assert_eq!(worksheet.read_cell("B4")?.formula(), Some("A5*2+$A$5"));
+        workbook.save_as("tests/output/shift_test_insert_rows.xlsx")?;
+
+        let workbook = Workbook::from_path("tests/output/shift_test_insert_rows.xlsx")?;
+        let worksheet = workbook.get_worksheet(1)?;
+        assert_eq!(worksheet.read_comment("D5").map(|comment| comment.text), Some("Moved".to_string()));
+        assert_eq!(worksheet.read_comment("D3"), None);
+        assert_eq!(worksheet.data_validations(), vec![("A1:A5".to_string(), DataValidation::custom("ISNUMBER(A1)"))]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_delete_columns() -> WorkbookResult<()> {
+        let mut workbook = Workbook::new();
+        let worksheet = workbook.get_worksheet_mut(1)?;
+        worksheet.write_row("A1", &[1, 2, 3, 4])?;
+        worksheet.write_formula("A2", "SUM(A1:D1)")?;
+        worksheet.write_formula("D2", "B1+C1")?;
+        worksheet.write_formula("E2", "D1")?;
+        worksheet.write_comment_with_options("B3", &Comment::new("Deleted"))?;
+        workbook.delete_columns(1, 2, 1)?;
+        let worksheet = workbook.get_worksheet(1)?;
+        assert_eq!(worksheet.read_value("B1")?, Value::Number(3.0));
+        assert_eq!(worksheet.read_value("C1")?, Value::Number(4.0));
+        assert_eq!(worksheet.read_cell("A2")?.formula(), Some("SUM(A1:C1)"));
+        assert_eq!(worksheet.read_cell("C2")?.formula(), Some("#REF!+B1"));
+        assert_eq!(worksheet.read_cell("D2")?.formula(), Some("C1"));
+        assert_eq!(worksheet.read_comment("B3"), None);
+        assert_eq!(worksheet.used_range(), Some((1, 1, 2, 4)));
+        workbook.save_as("tests/output/shift_test_delete_columns.xlsx")?;
+        Ok(())
+    }
+
+    #[test]
+    fn test_shift_other_sheets() -> WorkbookResult<()> {
+        let mut workbook = Workbook::new();
+        workbook.get_worksheet_mut(1)?.write_column("A1", &[1, 2, 3])?;
+        let other = workbook.add_worksheet_by_name("Other sheet")?;
+        other.write_formula("A1", "Sheet1!A3+'Sheet1'!$A$2:A3")?;
+        other.write_formula("A2", "A3")?;
+        other.insert_chart((2, 3, 16, 10), &Chart::new(ChartType::Line).add_series(ChartSeries::new("Sheet1!$A$1:$A$3")))?;
+        workbook.define_name("Values", "Sheet1!$A$1:$A$3")?;
+        workbook.delete_rows(1, 2, 1)?;
+        workbook.insert_columns(1, 1, 1)?;
+        let other = workbook.get_worksheet_by_name("Other sheet")?;
+        assert_eq!(other.read_cell("A1")?.formula(), Some("Sheet1!B2+'Sheet1'!$B$2:B2"));
+        assert_eq!(other.read_cell("A2")?.formula(), Some("A3"));
+        assert_eq!(other.charts()[0].series[0].values, "Sheet1!$B$1:$B$2");
+        workbook.save_as("tests/output/shift_test_shift_other_sheets.xlsx")?;
+        Ok(())
+    }
+
+    #[test]
+    fn test_shift_table() -> WorkbookResult<()> {
+        let mut workbook = Workbook::new();
+        let worksheet = workbook.get_worksheet_mut(1)?;
+        worksheet.add_table("B2:D5", &TableOptions::default())?;
+        assert!(workbook.insert_columns(1, 3, 1).is_err());
+        assert!(workbook.delete_rows(1, 2, 1).is_err());
+        assert!(workbook.insert_rows(1, 0, 1).is_err());
+        assert!(workbook.insert_rows(2, 1, 1).is_err());
+        // nothing is inserted or deleted
+        workbook.insert_rows(1, 2, 0)?;
+        workbook.delete_columns(1, 1, 0)?;
+        workbook.insert_rows(1, 1, 2)?;
+        workbook.delete_rows(1, 6, 1)?;
+        workbook.insert_columns(1, 1, 1)?;
+        assert_eq!(workbook.get_worksheet(1)?.tables()[0].range, (4, 3, 6, 5));
+        workbook.save_as("tests/output/shift_test_shift_table.xlsx")?;
+
+        let workbook = Workbook::from_path("tests/output/shift_test_shift_table.xlsx")?;
+        assert_eq!(workbook.get_worksheet(1)?.tables()[0].range, (4, 3, 6, 5));
+        Ok(())
+    }
+
+    #[test]
+    fn test_shift_past_last_row() -> WorkbookResult<()> {
+        let mut workbook = Workbook::new();
+        let worksheet = workbook.get_worksheet_mut(1)?;
+        worksheet.write_formula("A1", "SUM(B1:B1048576)+SUM(2:1048576)+XFD1")?;
+        workbook.insert_rows(1, 1, 1)?;
+        workbook.insert_columns(1, 1, 1)?;
+        let worksheet = workbook.get_worksheet_mut(1)?;
+        assert_eq!(worksheet.read_cell("B2")?.formula(), Some("SUM(C2:C1048576)+SUM(3:1048576)+#REF!"));
+        // the cells with values are not pushed past the last row or column
+        worksheet.write("C1048576", 1)?;
+        assert!(workbook.insert_rows(1, 1, 1).is_err());
+        assert!(workbook.insert_rows(1, 1, u32::MAX).is_err());
+        assert!(workbook.insert_columns(1, 1, 16381).is_ok());
+        assert!(workbook.insert_columns(1, 1, 1).is_err());
+        assert_eq!(workbook.get_worksheet(1)?.read_value("XFD1048576")?, Value::Number(1.0));
+        Ok(())
+    }
+
+    #[test]
+    fn test_shift_shared_formula() -> WorkbookResult<()> {
+        // H18 holds the formula shared by H18:H53
+        let mut workbook = Workbook::from_path("tests/xlsx/checkbook-register.xlsx")?;
+        workbook.delete_rows(1, 18, 1)?;
+        workbook.insert_rows(1, 20, 2)?;
+        let worksheet = workbook.get_worksheet(1)?;
+        assert_eq!(worksheet.read_cell("H18")?.formula(), Some("IF(ISBLANK(A18),\" - \",SUM(OFFSET(H18,-1,0),G18,-F18))"));
+        assert_eq!(worksheet.read_cell("H22")?.formula(), Some("IF(ISBLANK(A22),\" - \",SUM(OFFSET(H22,-1,0),G22,-F22))"));
+        workbook.save_as("tests/output/shift_test_shift_shared_formula.xlsx")?;
+
+        let workbook = Workbook::from_path("tests/output/shift_test_shift_shared_formula.xlsx")?;
+        let worksheet = workbook.get_worksheet(1)?;
+        assert_eq!(worksheet.read_cell("H19")?.formula(), Some("IF(ISBLANK(A19),\" - \",SUM(OFFSET(H19,-1,0),G19,-F19))"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_shift_lowercase_references() -> WorkbookResult<()> {
+        let mut workbook = Workbook::new();
+        let worksheet = workbook.get_worksheet_mut(1)?;
+        worksheet.write_column("A1", &[1, 2, 3, 4, 5])?;
+        worksheet.write_formula("B1", "SUM(a1:a3)+A5")?;
+        workbook.add_worksheet_by_name("Other")?.write_formula("A1", "sheet1!$a$2*2")?;
+        workbook.insert_rows(1, 2, 3)?;
+        assert_eq!(workbook.get_worksheet(1)?.read_cell("B1")?.formula(), Some("SUM(A1:A6)+A8"));
+        assert_eq!(workbook.get_worksheet_by_name("Other")?.read_cell("A1")?.formula(), Some("sheet1!$A$5*2"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_from_shift() -> WorkbookResult<()> {
+        let mut workbook = Workbook::from_path("tests/xlsx/paycheck-calculator.xlsx")?;
+        let worksheet = workbook.get_worksheet_by_name("NEW W-4")?;
+        let (id, formula) = (worksheet.id(), worksheet.read_cell("C37")?.formula().map(str::to_string));
+        let charts = worksheet.charts().len();
+        workbook.insert_rows(id, 1, 3)?;
+        assert_eq!(workbook.get_worksheet(id)?.read_cell("C40")?.formula().map(str::to_string).is_some(), formula.is_some());
+        workbook.delete_columns(id, 20, 2)?;
+        workbook.save_as("tests/output/shift_test_from_shift.xlsx")?;
+
+        let workbook = Workbook::from_path("tests/output/shift_test_from_shift.xlsx")?;
+        assert_eq!(workbook.get_worksheet_by_name("NEW W-4")?.charts().len(), charts);
+        Ok(())
+    }
+}
@@ -0,0 +1,102 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use crate::common::read_part;
+    use edit_xlsx::{Read as XlsxRead, TableColumn, TableFunction, TableOptions, Workbook, WorkbookResult, Write};
+
+    #[test]
+    fn test_add_table() -> WorkbookResult<()> {
+        let mut workbook = Workbook::new();
+        let worksheet = workbook.get_worksheet_mut(1)?;
+        worksheet.write_row("B2", &["Product", "Quarter 1", "Quarter 2"])?;
+        worksheet.write_row("B3", &["Apples", "10000", "5000"])?;
+        worksheet.write_row("B4", &["Pears", "2000", "3000"])?;
+        let options = TableOptions::new()
+            .set_name("Sales")
+            .set_style("TableStyleLight9")
+            .set_total_row(true)
+            .set_columns(&[
+                TableColumn::new("").set_total_label("Total"),
+                TableColumn::new("Quarter 1").set_total_function(TableFunction::Sum),
+                TableColumn::new("Quarter 2").set_total_function(TableFunction::Average),
+            ]);
+        worksheet.add_table("B2:D5", &options)?;
+        worksheet.add_table("F2:G4", &TableOptions::new())?;
+        workbook.save_as("tests/output/table_test_add_table.xlsx")?;
+
+        let workbook = Workbook::from_path("tests/output/table_test_add_table.xlsx")?;
+        let worksheet = workbook.get_worksheet(1)?;
+        let tables = worksheet.tables();
+        assert_eq!(tables.len(), 2);
+        assert_eq!(tables[0].name, "Sales");
+        assert_eq!(tables[0].range, (2, 2, 5, 4));
+        assert_eq!(tables[0].style.as_deref(), Some("TableStyleLight9"));
+        assert!(tables[0].header_row && tables[0].total_row);
+        assert_eq!(tables[0].columns, vec![
+            TableColumn::new("Product").set_total_label("Total"),
+            TableColumn::new("Quarter 1").set_total_function(TableFunction::Sum),
+            TableColumn::new("Quarter 2").set_total_function(TableFunction::Average),
+        ]);
+        assert_eq!(tables[1].name, "Table2");
+        assert_eq!(tables[1].columns, vec![TableColumn::new("Column1"), TableColumn::new("Column2")]);
+        assert_eq!(worksheet.read_value("B5")?.to_string(), "Total");
+        assert_eq!(worksheet.read_value("F2")?.to_string(), "Column1");
+
+        let path = "tests/output/table_test_add_table.xlsx";
+        let sheet = read_part(path, "xl/worksheets/sheet1.xml");
+        assert!(sheet.contains("<tableParts count=\"2\">"));
+        assert!(sheet.contains("SUBTOTAL(109,[Quarter 1])"));
+        let table = read_part(path, "xl/tables/table1.xml");
+        assert!(table.contains("ref=\"B2:D5\""));
+        assert!(table.contains("<autoFilter ref=\"B2:D4\"/>"));
+        assert!(table.contains("totalsRowFunction=\"sum\""));
+        assert!(read_part(path, "xl/worksheets/_rels/sheet1.xml.rels").contains("../tables/table2.xml"));
+        assert!(read_part(path, "[Content_Types].xml").contains("/xl/tables/table2.xml"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_table_error() -> WorkbookResult<()> {
+        let mut workbook = Workbook::new();
+        let worksheet = workbook.get_worksheet_mut(1)?;
+        worksheet.add_table("A1:C3", &TableOptions::new().set_name("Data"))?;
+        let result = worksheet.add_table("C3:D4", &TableOptions::new());
+        assert!(result.is_err());
+        let result = worksheet.add_table("E1:F3", &TableOptions::new().set_name("data"));
+        assert!(result.is_err());
+        let result = worksheet.add_table("E1:F1", &TableOptions::new().set_total_row(true));
+        assert!(result.is_err());
+        assert_eq!(worksheet.tables().len(), 1);
+        // the names are unique in the workbook, and can't be read as cells
+        workbook.define_name("Rates", "Sheet1!$A$1")?;
+        let worksheet = workbook.add_worksheet()?;
+        for name in ["DATA", "rates", "A1", "R1C1", "my table", "2024"] {
+            assert!(worksheet.add_table("A1:B3", &TableOptions::new().set_name(name)).is_err(), "{name}");
+        }
+        worksheet.add_table("A1:B3", &TableOptions::new().set_name("Sales_2024"))?;
+        assert_eq!(worksheet.tables().len(), 1);
+        Ok(())
+    }
+
+    #[test]
+    fn test_from_table() -> WorkbookResult<()> {
+        let mut workbook = Workbook::from_path("tests/xlsx/checkbook-register.xlsx")?;
+        let worksheet = workbook.get_worksheet_mut(1)?;
+        let tables = worksheet.tables();
+        assert_eq!(tables.len(), 1);
+        assert_eq!(tables[0].name, "Table1");
+        assert_eq!(tables[0].range, (17, 1, 53, 8));
+        assert_eq!(tables[0].style.as_deref(), Some("TableStyleMedium2"));
+        assert_eq!(tables[0].columns[0].header, "Date");
+        worksheet.add_table("J17:K20", &TableOptions::new())?;
+        workbook.save_as("tests/output/table_test_from_table.xlsx")?;
+
+        let workbook = Workbook::from_path("tests/output/table_test_from_table.xlsx")?;
+        let tables = workbook.get_worksheet(1)?.tables();
+        assert_eq!(tables.iter().map(|table| table.name.as_str()).collect::<Vec<&str>>(), vec!["Table1", "Table2"]);
+        let table = read_part("tests/output/table_test_from_table.xlsx", "xl/tables/table2.xml");
+        assert!(table.contains("id=\"2\""));
+        Ok(())
+    }
+}